@@ -115,7 +115,7 @@ fn sanitize_linux_snap_environment() {
 
 #[cfg(all(not(target_arch = "wasm32"), target_os = "linux"))]
 fn load_rendering_settings_for_startup() -> RenderingSettings {
-    match Settings::load_for_network_sync(kaspa_ng_core::network::Network::Mainnet) {
+    match Settings::load_for_network_sync(kaspa_ng_core::settings::latest_settings_network()) {
         Ok(settings) => settings.rendering,
         Err(err) => {
             log_warn!("Unable to load rendering settings for startup: {err}");
@@ -204,8 +204,9 @@ cfg_if! {
             #[cfg(target_os = "linux")]
             sanitize_linux_ld_library_path();
 
+            // headless mode does not create windows or embedded WebViews
             #[cfg(target_os = "linux")]
-            {
+            if !std::env::args().any(|arg| arg == "--headless") {
                 let rendering = load_rendering_settings_for_startup();
                 configure_linux_webkit_runtime(&rendering);
             }
@@ -0,0 +1,199 @@
+//! Wallet address book, kept in the encrypted `<wallet>.contacts` file
+//! next to the wallet file.
+
+use crate::imports::*;
+use crate::wallet_file::{EncryptedWalletFile, WalletFileData};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Contact {
+    pub name: String,
+    pub address: String,
+    /// Network the address belongs to; contacts are only offered and
+    /// matched on this network.
+    pub network: Network,
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl Contact {
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::custom(i18n("Please enter a contact name")));
+        }
+        try_user_string_to_address(self.address.trim(), &self.network.into())?;
+        Ok(())
+    }
+
+    /// Case-insensitive match against the name, the address and the tags.
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.trim().to_lowercase();
+        text.is_empty()
+            || self.name.to_lowercase().contains(&text)
+            || self.address.to_lowercase().contains(&text)
+            || self
+                .tags
+                .iter()
+                .any(|tag| tag.to_lowercase().contains(&text))
+    }
+}
+
+/// Parses a comma-separated tag list, dropping empty and duplicate tags.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if !tags
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(tag))
+        {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Contacts of the wallet address book.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AddressBookData {
+    contacts: Vec<Contact>,
+}
+
+impl WalletFileData for AddressBookData {
+    const EXTENSION: &'static str = "contacts";
+    const VERSION: u32 = 1;
+    const NAME: &'static str = "address book";
+
+    fn loaded(&mut self) {
+        self.sort();
+    }
+}
+
+pub type AddressBook = EncryptedWalletFile<AddressBookData>;
+
+impl AddressBookData {
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    /// Contacts of the given network along with their index in the book.
+    pub fn contacts_for_network(
+        &self,
+        network: Network,
+    ) -> impl Iterator<Item = (usize, &Contact)> {
+        self.contacts
+            .iter()
+            .enumerate()
+            .filter(move |(_, contact)| contact.network == network)
+    }
+
+    pub fn find(&self, network: Network, address: &str) -> Option<&Contact> {
+        self.contacts
+            .iter()
+            .find(|contact| contact.network == network && contact.address == address)
+    }
+
+    pub fn search(&self, network: Network, text: &str) -> Vec<&Contact> {
+        self.contacts_for_network(network)
+            .map(|(_, contact)| contact)
+            .filter(|contact| contact.matches(text))
+            .collect()
+    }
+
+    pub fn insert(&mut self, contact: Contact) -> Result<()> {
+        let contact = self.check(None, contact)?;
+        self.contacts.push(contact);
+        self.sort();
+        Ok(())
+    }
+
+    pub fn update(&mut self, index: usize, contact: Contact) -> Result<()> {
+        if index >= self.contacts.len() {
+            return Err(Error::custom(i18n("Contact not found")));
+        }
+        let contact = self.check(Some(index), contact)?;
+        self.contacts[index] = contact;
+        self.sort();
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Contact> {
+        (index < self.contacts.len()).then(|| self.contacts.remove(index))
+    }
+
+    fn check(&self, index: Option<usize>, mut contact: Contact) -> Result<Contact> {
+        contact.name = contact.name.trim().to_string();
+        contact.address = contact.address.trim().to_string();
+        contact.note = contact.note.trim().to_string();
+        contact.validate()?;
+
+        let duplicate = self.contacts.iter().enumerate().any(|(idx, existing)| {
+            Some(idx) != index
+                && existing.network == contact.network
+                && existing.address == contact.address
+        });
+        if duplicate {
+            return Err(Error::custom(i18n(
+                "This address is already in the address book",
+            )));
+        }
+
+        Ok(contact)
+    }
+
+    fn sort(&mut self) {
+        self.contacts
+            .sort_by_key(|contact| (contact.network, contact.name.to_lowercase()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::{Prefix, Version};
+
+    fn contact(name: &str, network: Network, payload: u8) -> Contact {
+        let address = Address::new(Prefix::from(&network), Version::PubKey, &[payload; 32]);
+        Contact {
+            name: name.to_string(),
+            address: address.to_string(),
+            network,
+            note: String::new(),
+            tags: parse_tags("shop, Shop, ,friends"),
+        }
+    }
+
+    #[test]
+    fn contacts_are_validated_per_network() {
+        let key = WalletFileKey::new(&Secret::from("secret"));
+        let mut address_book = AddressBook::new("test", key);
+
+        let alice = contact("Alice", Network::Mainnet, 1);
+        assert_eq!(alice.tags, vec!["shop".to_string(), "friends".to_string()]);
+        address_book.insert(alice.clone()).unwrap();
+        address_book
+            .insert(contact("Bob", Network::Testnet10, 2))
+            .unwrap();
+
+        // duplicate address on the same network
+        assert!(address_book.insert(alice.clone()).is_err());
+        // mainnet address stored as a testnet contact
+        let mut mismatch = alice.clone();
+        mismatch.network = Network::Testnet10;
+        assert!(address_book.insert(mismatch).is_err());
+
+        assert_eq!(
+            address_book
+                .find(Network::Mainnet, &alice.address)
+                .map(|contact| contact.name.as_str()),
+            Some("Alice")
+        );
+        assert!(
+            address_book
+                .find(Network::Testnet10, &alice.address)
+                .is_none()
+        );
+        assert_eq!(address_book.search(Network::Mainnet, "FRIEND").len(), 1);
+        assert_eq!(address_book.search(Network::Testnet10, "alice").len(), 0);
+    }
+}
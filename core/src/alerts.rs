@@ -0,0 +1,582 @@
+//!
+//! User-defined alert rules evaluated against node metrics, service health
+//! and wallet events, delivered as in-app toasts, OS notifications and an
+//! optional local webhook.
+//!
+
+use crate::imports::*;
+use crate::runtime::services::LoaderPhase;
+use kaspa_metrics_core::Metric;
+use kaspa_wallet_core::storage::TransactionKind;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub message: String,
+}
+
+impl Alert {
+    fn new(kind: AlertKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+/// Node metrics the alert rules are evaluated against.
+#[derive(Debug, Default, Clone, Copy)]
+struct NodeSample {
+    peers: f64,
+    daa_score: f64,
+    mempool: f64,
+}
+
+impl From<&MetricsSnapshot> for NodeSample {
+    fn from(snapshot: &MetricsSnapshot) -> Self {
+        Self {
+            peers: snapshot.get(&Metric::NodeActivePeers),
+            daa_score: snapshot.get(&Metric::NetworkVirtualDaaScore),
+            mempool: snapshot.get(&Metric::NetworkMempoolSize),
+        }
+    }
+}
+
+/// Service health sampled alongside each metrics snapshot. `None` means
+/// the service is disabled and its rules are not evaluated.
+#[derive(Debug, Default, Clone)]
+pub struct ServiceHealth {
+    pub loader_phase: Option<LoaderPhase>,
+    pub bridge_running: Option<bool>,
+}
+
+impl ServiceHealth {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn sample() -> Self {
+        let runtime = runtime();
+        let loader_phase = Some(runtime.self_hosted_loader_service().status_snapshot().phase_id)
+            .filter(|phase| *phase != LoaderPhase::Disabled);
+        let bridge = runtime.stratum_bridge_service();
+        let bridge_running = bridge
+            .is_enabled()
+            .then(|| bridge.process_status().iter().any(|status| status.running));
+
+        Self {
+            loader_phase,
+            bridge_running,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn sample() -> Self {
+        Self::default()
+    }
+}
+
+/// Evaluates alert rules and tracks which conditions are currently raised,
+/// so that each alert is delivered once when its condition starts to hold.
+#[derive(Default)]
+pub struct AlertMonitor {
+    raised: HashSet<AlertKind>,
+    /// Last observed virtual DAA score and the time it was first seen.
+    daa_score: Option<(f64, f64)>,
+    /// Last observed loader phase and the time it was entered.
+    loader_phase: Option<(LoaderPhase, f64)>,
+    /// Time the stratum bridge was first seen down.
+    bridge_down_since: Option<f64>,
+}
+
+impl AlertMonitor {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Evaluates the metric and service health rules, returning the alerts
+    /// whose condition has just been raised.
+    pub fn evaluate(
+        &mut self,
+        settings: &AlertSettings,
+        snapshot: &MetricsSnapshot,
+        health: &ServiceHealth,
+    ) -> Vec<Alert> {
+        self.evaluate_sample(
+            settings,
+            snapshot.unixtime_millis,
+            NodeSample::from(snapshot),
+            health,
+        )
+    }
+
+    /// `now` is in milliseconds.
+    fn evaluate_sample(
+        &mut self,
+        settings: &AlertSettings,
+        now: f64,
+        sample: NodeSample,
+        health: &ServiceHealth,
+    ) -> Vec<Alert> {
+        let mut alerts = vec![];
+
+        let peers = sample.peers;
+        let condition = settings
+            .rule(AlertKind::PeersBelow)
+            .filter(|rule| peers < rule.threshold);
+        self.update(&mut alerts, AlertKind::PeersBelow, condition, || {
+            i18n_args(
+                "Active peers dropped to {peers}",
+                &[("peers", peers.to_string())],
+            )
+        });
+
+        let daa_score = sample.daa_score;
+        let since = match self.daa_score {
+            Some((score, since)) if score == daa_score => since,
+            _ => {
+                self.daa_score = Some((daa_score, now));
+                now
+            }
+        };
+        let minutes = (now - since) / 60_000.0;
+        let condition = settings
+            .rule(AlertKind::SyncStalled)
+            .filter(|rule| minutes >= rule.threshold);
+        self.update(&mut alerts, AlertKind::SyncStalled, condition, || {
+            i18n_args(
+                "The virtual DAA score has not advanced for {minutes} minutes",
+                &[("minutes", format!("{minutes:.0}"))],
+            )
+        });
+
+        let mempool = sample.mempool;
+        let condition = settings
+            .rule(AlertKind::MempoolAbove)
+            .filter(|rule| mempool > rule.threshold);
+        self.update(&mut alerts, AlertKind::MempoolAbove, condition, || {
+            i18n_args(
+                "Mempool size reached {size} transactions",
+                &[("size", mempool.to_string())],
+            )
+        });
+
+        let condition = health.loader_phase.and_then(|phase| {
+            let since = match self.loader_phase {
+                Some((current, since)) if current == phase => since,
+                _ => {
+                    self.loader_phase = Some((phase, now));
+                    now
+                }
+            };
+            let minutes = (now - since) / 60_000.0;
+            settings
+                .rule(AlertKind::LoaderStuck)
+                .filter(|rule| phase.is_pending() && minutes >= rule.threshold)
+                .map(|_| (phase.name().to_string(), minutes))
+        });
+        if health.loader_phase.is_none() {
+            self.loader_phase = None;
+        }
+        self.update(
+            &mut alerts,
+            AlertKind::LoaderStuck,
+            condition.as_ref(),
+            || {
+                let (phase, minutes) = condition.clone().unwrap_or_default();
+                i18n_args(
+                    "The database loader has been in phase \"{phase}\" for {minutes} minutes",
+                    &[("phase", phase), ("minutes", format!("{minutes:.0}"))],
+                )
+            },
+        );
+
+        let down = health.bridge_running == Some(false);
+        let since = if down {
+            *self.bridge_down_since.get_or_insert(now)
+        } else {
+            self.bridge_down_since = None;
+            now
+        };
+        let seconds = (now - since) / 1000.0;
+        let condition = settings
+            .rule(AlertKind::StratumBridgeDown)
+            .filter(|rule| down && seconds >= rule.threshold);
+        self.update(&mut alerts, AlertKind::StratumBridgeDown, condition, || {
+            i18n("The stratum bridge is not running").to_string()
+        });
+
+        alerts
+    }
+
+    /// Returns an alert for an incoming wallet transaction that meets the
+    /// configured minimum amount.
+    pub fn incoming_transaction(
+        &self,
+        settings: &AlertSettings,
+        record: &TransactionRecord,
+        network_type: &NetworkType,
+    ) -> Option<Alert> {
+        let rule = settings.rule(AlertKind::IncomingTransaction)?;
+        if record.is_change()
+            || record.kind() != TransactionKind::Incoming
+            || sompi_to_kaspa(record.value()) < rule.threshold
+        {
+            return None;
+        }
+
+        Some(Alert::new(
+            AlertKind::IncomingTransaction,
+            i18n_args(
+                "Incoming transaction of {amount}",
+                &[(
+                    "amount",
+                    sompi_to_kaspa_string_with_suffix(record.value(), network_type),
+                )],
+            ),
+        ))
+    }
+
+    fn update<T>(
+        &mut self,
+        alerts: &mut Vec<Alert>,
+        kind: AlertKind,
+        condition: Option<T>,
+        message: impl FnOnce() -> String,
+    ) {
+        if condition.is_some() {
+            if self.raised.insert(kind) {
+                alerts.push(Alert::new(kind, message()));
+            }
+        } else {
+            self.raised.remove(&kind);
+        }
+    }
+}
+
+/// Returns the reason the webhook URL would be rejected, if any.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn webhook_url_error(url: &str) -> Option<String> {
+    native::parse_webhook_url(url.trim())
+        .err()
+        .map(|err| err.to_string())
+}
+
+/// Delivers an alert through the channels enabled in the settings.
+pub fn deliver(settings: &AlertSettings, network: Network, alert: Alert) {
+    log_warn!("Alert ({:?}): {}", alert.kind, alert.message);
+    runtime()
+        .toast(UserNotification::warning(alert.message.as_str()).duration(Duration::from_secs(10)));
+
+    cfg_if! {
+        if #[cfg(not(target_arch = "wasm32"))] {
+            if settings.os_notifications {
+                native::notify(alert.message.as_str());
+            }
+
+            if settings.webhook_enabled && settings.webhook_url.trim().is_not_empty() {
+                let url = settings.webhook_url.trim().to_string();
+                spawn(async move {
+                    if let Err(err) = native::post_webhook(url.as_str(), network, &alert).await {
+                        log_warn!("Alert webhook failed: {err}");
+                    }
+                    Ok(())
+                });
+            }
+        } else {
+            let _ = network;
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use super::*;
+    use std::net::IpAddr;
+    use std::process::{Command, Stdio};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio::time::timeout;
+
+    const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Shows a desktop notification using the notification tool of the
+    /// platform. Failures are logged and otherwise ignored.
+    pub fn notify(message: &str) {
+        let title = "Kaspa NG";
+
+        #[cfg(target_os = "linux")]
+        let mut cmd = {
+            let mut cmd = Command::new("notify-send");
+            cmd.args(["--app-name", title, title, message]);
+            cmd
+        };
+
+        #[cfg(target_os = "macos")]
+        let mut cmd = {
+            let mut cmd = Command::new("osascript");
+            cmd.args([
+                "-e",
+                &format!(
+                    "display notification {} with title {}",
+                    applescript_string(message),
+                    applescript_string(title)
+                ),
+            ]);
+            cmd
+        };
+
+        #[cfg(windows)]
+        let mut cmd = {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+            let script = format!(
+                "Add-Type -AssemblyName System.Windows.Forms; \
+                 $n = New-Object System.Windows.Forms.NotifyIcon; \
+                 $n.Icon = [System.Drawing.SystemIcons]::Information; \
+                 $n.Visible = $true; \
+                 $n.ShowBalloonTip(10000, '{}', '{}', 'Warning'); \
+                 Start-Sleep -Seconds 10; $n.Dispose()",
+                title.replace('\'', "''"),
+                message.replace('\'', "''")
+            );
+            let mut cmd = Command::new("powershell");
+            cmd.args(["-NoProfile", "-NonInteractive", "-Command", &script])
+                .creation_flags(CREATE_NO_WINDOW);
+            cmd
+        };
+
+        #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+        {
+            let _ = (title, message);
+            return;
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+        if let Err(err) = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            log_warn!("Unable to show desktop notification: {err}");
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn applescript_string(text: &str) -> String {
+        format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    /// `true` for `localhost` and loopback, private and link-local IP
+    /// addresses. Other host names are rejected as they may resolve to
+    /// public addresses.
+    fn is_local_host(host: &str) -> bool {
+        if host.eq_ignore_ascii_case("localhost") {
+            return true;
+        }
+
+        match host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+        {
+            Ok(IpAddr::V4(ip)) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+            Ok(IpAddr::V6(ip)) => {
+                let segment = ip.segments()[0];
+                ip.is_loopback()
+                    // unique local (fc00::/7) and link-local (fe80::/10)
+                    || (segment & 0xfe00) == 0xfc00
+                    || (segment & 0xffc0) == 0xfe80
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Splits an `http://host[:port][/path]` URL into host, port and path.
+    /// The host must be on the local network.
+    pub(super) fn parse_webhook_url(url: &str) -> Result<(String, u16, String)> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| {
+            Error::custom("The alert webhook must be a plain http:// URL on the local network")
+        })?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => (
+                host,
+                port.parse::<u16>()
+                    .map_err(|_| Error::custom(format!("Invalid webhook port: {port}")))?,
+            ),
+            _ => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(Error::custom("The alert webhook URL has no host"));
+        }
+        if !is_local_host(host) {
+            return Err(Error::custom(
+                "The alert webhook must be on the local network (localhost or a private IP address)",
+            ));
+        }
+
+        Ok((host.to_string(), port, path.to_string()))
+    }
+
+    /// Posts the alert as JSON to the configured webhook.
+    pub async fn post_webhook(url: &str, network: Network, alert: &Alert) -> Result<()> {
+        let (host, port, path) = parse_webhook_url(url)?;
+        let body = serde_json::json!({
+            "kind": alert.kind,
+            "message": alert.message,
+            "network": network.to_string(),
+            "timestamp": unixtime_as_millis_f64() as u64,
+        })
+        .to_string();
+        let request = format!(
+            "POST {path} HTTP/1.1\r\nHost: {host}:{port}\r\nUser-Agent: kaspa-ng\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        let exchange = async {
+            let address = format!(
+                "{}:{port}",
+                host.trim_start_matches('[').trim_end_matches(']')
+            );
+            let mut stream = TcpStream::connect(address).await?;
+            stream.write_all(request.as_bytes()).await?;
+            let mut response = vec![0u8; 256];
+            let read = stream.read(&mut response).await?;
+            Ok::<_, std::io::Error>(String::from_utf8_lossy(&response[..read]).to_string())
+        };
+        let response = timeout(WEBHOOK_TIMEOUT, exchange)
+            .await
+            .map_err(|_| Error::custom("Timed out"))?
+            .map_err(|err| Error::custom(err.to_string()))?;
+
+        let status = response.split_whitespace().nth(1).unwrap_or_default();
+        if !status.starts_with('2') {
+            return Err(Error::custom(format!(
+                "Unexpected response status: {status}"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(peers: f64, daa_score: f64, mempool: f64) -> NodeSample {
+        NodeSample {
+            peers,
+            daa_score,
+            mempool,
+        }
+    }
+
+    fn settings() -> AlertSettings {
+        let mut settings = AlertSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        settings
+            .rules
+            .iter_mut()
+            .for_each(|rule| rule.enabled = true);
+        settings
+    }
+
+    fn kinds(alerts: Vec<Alert>) -> Vec<AlertKind> {
+        alerts.into_iter().map(|alert| alert.kind).collect()
+    }
+
+    #[test]
+    fn alerts_are_raised_once_per_condition() {
+        let settings = settings();
+        let health = ServiceHealth::default();
+        let mut monitor = AlertMonitor::default();
+
+        let alerts = monitor.evaluate_sample(&settings, 0.0, snapshot(1.0, 100.0, 0.0), &health);
+        assert_eq!(kinds(alerts), vec![AlertKind::PeersBelow]);
+        assert!(
+            monitor
+                .evaluate_sample(&settings, 1000.0, snapshot(1.0, 101.0, 0.0), &health)
+                .is_empty()
+        );
+
+        // recovered and dropped again
+        assert!(
+            monitor
+                .evaluate_sample(&settings, 2000.0, snapshot(8.0, 102.0, 0.0), &health)
+                .is_empty()
+        );
+        let alerts =
+            monitor.evaluate_sample(&settings, 3000.0, snapshot(2.0, 103.0, 20_000.0), &health);
+        assert_eq!(
+            kinds(alerts),
+            vec![AlertKind::PeersBelow, AlertKind::MempoolAbove]
+        );
+
+        // the DAA score stops advancing for five minutes
+        let alerts = monitor.evaluate_sample(
+            &settings,
+            3000.0 + 300_000.0,
+            snapshot(2.0, 103.0, 20_000.0),
+            &health,
+        );
+        assert_eq!(kinds(alerts), vec![AlertKind::SyncStalled]);
+    }
+
+    #[test]
+    fn service_alerts_require_a_sustained_condition() {
+        let settings = settings();
+        let mut monitor = AlertMonitor::default();
+        let sample = snapshot(8.0, 100.0, 0.0);
+        let health = ServiceHealth {
+            loader_phase: Some(LoaderPhase::WaitingForNodeSync),
+            bridge_running: Some(false),
+        };
+
+        assert!(
+            monitor
+                .evaluate_sample(&settings, 0.0, sample, &health)
+                .is_empty()
+        );
+        let alerts =
+            monitor.evaluate_sample(&settings, 30_000.0, snapshot(8.0, 101.0, 0.0), &health);
+        assert_eq!(kinds(alerts), vec![AlertKind::StratumBridgeDown]);
+        let alerts =
+            monitor.evaluate_sample(&settings, 900_000.0, snapshot(8.0, 102.0, 0.0), &health);
+        assert_eq!(kinds(alerts), vec![AlertKind::LoaderStuck]);
+
+        let disabled = AlertSettings::default();
+        monitor.reset();
+        assert!(
+            monitor
+                .evaluate_sample(&disabled, 0.0, snapshot(0.0, 0.0, 1e9), &health)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn webhook_urls_are_parsed() {
+        assert_eq!(
+            native::parse_webhook_url("http://127.0.0.1:8080/alerts").unwrap(),
+            ("127.0.0.1".to_string(), 8080, "/alerts".to_string())
+        );
+        assert_eq!(
+            native::parse_webhook_url("http://localhost").unwrap(),
+            ("localhost".to_string(), 80, "/".to_string())
+        );
+        assert_eq!(
+            native::parse_webhook_url("http://[fd00::1]:9000").unwrap(),
+            ("[fd00::1]".to_string(), 9000, "/".to_string())
+        );
+        assert!(native::parse_webhook_url("http://192.168.1.10/hook").is_ok());
+        assert!(native::parse_webhook_url("https://example.com").is_err());
+        assert!(native::parse_webhook_url("http://example.com/hook").is_err());
+        assert!(native::parse_webhook_url("http://8.8.8.8/hook").is_err());
+    }
+}
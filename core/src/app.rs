@@ -51,6 +51,8 @@ impl ApplicationContext {
 
 cfg_if! {
     if #[cfg(not(target_arch = "wasm32"))] {
+        use crate::events::Events;
+        use crate::utils::PaymentUri;
         use kaspad_lib::daemon::{
             create_core,
             // DESIRED_DAEMON_SOFT_FD_LIMIT,
@@ -81,6 +83,11 @@ cfg_if! {
             Kng {
                 reset_settings : bool,
                 disable : bool,
+                payment_uri : Option<std::result::Result<PaymentUri, String>>,
+            },
+            Headless {
+                reset_settings : bool,
+                disable : bool,
             },
             Kaspad { args : Box<NodeArgs> },
         }
@@ -113,12 +120,18 @@ cfg_if! {
                     .arg(arg!(--disable "Disable node services when starting"))
                     .arg(arg!(--daemon "Run as Rusty Kaspa p2p daemon"))
                     .arg(arg!(--cli "Run as Rusty Kaspa Cli Wallet"))
+                    .arg(arg!(--headless "Run kaspa-ng services without the user interface"))
                     .arg(
                         Arg::new("reset-settings")
                         .long("reset-settings")
                         .action(ArgAction::SetTrue)
                         .help("Reset kaspa-ng settings")
                     )
+                    .arg(
+                        Arg::new("uri")
+                        .value_name("URI")
+                        .help("Open a kaspa: payment URI in the wallet send form")
+                    )
                     .subcommand(
                         Command::new("i18n").hide(true)
                         .about("kaspa-ng i18n user interface translation")
@@ -166,11 +179,67 @@ cfg_if! {
                         let disable = matches.get_one::<bool>("disable").cloned().unwrap_or(false);
                         let reset_settings = matches.get_one::<bool>("reset-settings").cloned().unwrap_or(false);
 
-                        Args::Kng { reset_settings, disable }
+                        if matches.get_one::<bool>("headless").cloned().unwrap_or(false) {
+                            Args::Headless { reset_settings, disable }
+                        } else {
+                            // an invalid URI is reported once the user interface is up
+                            let payment_uri = matches.get_one::<String>("uri").map(|uri| {
+                                uri.parse::<PaymentUri>().map_err(|err| format!("'{uri}': {err}"))
+                            });
+                            Args::Kng { reset_settings, disable, payment_uri }
+                        }
                     }
             }
         }
 
+        async fn init_kng(reset_settings : bool, disable : bool) -> Result<Settings> {
+            init_graceful_panic_handler();
+
+            workflow_log::set_colors_enabled(true);
+
+            let display_version = kaspa_version();
+            println!(
+                "kaspa-ng v{}-{GIT_DESCRIBE} (Rusty Kaspa v{})",
+                display_version, display_version
+            );
+
+            // Log to stderr (if you run with `RUST_LOG=debug`).
+            env_logger::init();
+
+            set_log_level(LevelFilter::Info);
+
+            let mut settings = if reset_settings {
+                println!("Resetting kaspa-ng settings on user request...");
+                Settings::default().store_sync()?.clone()
+            } else {
+                Settings::load().await.unwrap_or_else(|err| {
+                    log_error!("Unable to load settings: {err}");
+                    Settings::default()
+                })
+            };
+
+            // println!("settings: {:#?}", settings);
+
+            let i18n_json_file = i18n_storage_file()?;
+            let i18n_json_file_load = i18n_json_file.clone();
+            let i18n_json_file_store = i18n_json_file.clone();
+            i18n::Builder::new(settings.language_code.as_str(), "en")
+                .with_static_json_data(I18N_EMBEDDED)
+                .with_string_json_data(i18n_json_file.exists().then(move ||{
+                    fs::read_to_string(i18n_json_file_load)
+                }).transpose()?)
+                .with_store(move |json_data: &str| {
+                    Ok(fs::write(&i18n_json_file_store, json_data)?)
+                })
+                .try_init()?;
+
+            if disable {
+                settings.node.node_kind = kaspa_ng_core::settings::KaspadNodeKind::Disable;
+            }
+
+            Ok(settings)
+        }
+
         fn run_build_command() -> Result<()> {
             let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
             let status = std::process::Command::new(&cargo)
@@ -242,50 +311,13 @@ cfg_if! {
                     manage_i18n(op)?;
                 }
 
-                Args::Kng { reset_settings, disable } => {
-                    init_graceful_panic_handler();
-
-                    workflow_log::set_colors_enabled(true);
-
-                    let display_version = kaspa_version();
-                    println!(
-                        "kaspa-ng v{}-{GIT_DESCRIBE} (Rusty Kaspa v{})",
-                        display_version, display_version
-                    );
-
-                    // Log to stderr (if you run with `RUST_LOG=debug`).
-                    env_logger::init();
-
-                    set_log_level(LevelFilter::Info);
-
-                    let mut settings = if reset_settings {
-                        println!("Resetting kaspa-ng settings on user request...");
-                        Settings::default().store_sync()?.clone()
-                    } else {
-                        Settings::load().await.unwrap_or_else(|err| {
-                            log_error!("Unable to load settings: {err}");
-                            Settings::default()
-                        })
-                    };
+                Args::Headless { reset_settings, disable } => {
+                    let settings = init_kng(reset_settings, disable).await?;
+                    runtime::headless::run(settings, wallet_api, application_events).await?;
+                }
 
-                    // println!("settings: {:#?}", settings);
-
-                    let i18n_json_file = i18n_storage_file()?;
-                    let i18n_json_file_load = i18n_json_file.clone();
-                    let i18n_json_file_store = i18n_json_file.clone();
-                    i18n::Builder::new(settings.language_code.as_str(), "en")
-                        .with_static_json_data(I18N_EMBEDDED)
-                        .with_string_json_data(i18n_json_file.exists().then(move ||{
-                            fs::read_to_string(i18n_json_file_load)
-                        }).transpose()?)
-                        .with_store(move |json_data: &str| {
-                            Ok(fs::write(&i18n_json_file_store, json_data)?)
-                        })
-                        .try_init()?;
-
-                    if disable {
-                        settings.node.node_kind = kaspa_ng_core::settings::KaspadNodeKind::Disable;
-                    }
+                Args::Kng { reset_settings, disable, payment_uri } => {
+                    let settings = init_kng(reset_settings, disable).await?;
 
                     let runtime: Arc<Mutex<Option<runtime::Runtime>>> = Arc::new(Mutex::new(None));
                     let delegate = runtime.clone();
@@ -349,6 +381,17 @@ cfg_if! {
                             runtime::signals::Signals::bind(&runtime);
                             runtime.start();
 
+                            match payment_uri {
+                                Some(Ok(payment_uri)) => {
+                                    runtime.application_events().sender.try_send(Events::PaymentUri(Box::new(payment_uri))).ok();
+                                }
+                                Some(Err(err)) => {
+                                    log_error!("Invalid payment URI {err}");
+                                    runtime.error(format!("{} {err}", i18n("Invalid payment URI:")));
+                                }
+                                None => {}
+                            }
+
                             Ok(Box::new(kaspa_ng_core::Core::new(cc, runtime, settings, window_frame)))
                         }),
                     )?;
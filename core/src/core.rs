@@ -2,6 +2,7 @@ use crate::frame::window_frame;
 use crate::imports::*;
 use crate::market::*;
 use crate::mobile::MobileMenu;
+use crate::wallet_file::{EncryptedWalletFile, WalletFileData};
 use egui::load::Bytes;
 use egui_notify::Toasts;
 use kaspa_wallet_core::events::Events as CoreWallet;
@@ -14,6 +15,7 @@ use workflow_i18n::*;
 use workflow_wasm::callback::CallbackMap;
 pub const TRANSACTION_PAGE_SIZE: u64 = 20;
 pub const MAINNET_EXPLORER: &str = "https://explorer.kaspa.org";
+pub const TESTNET10_EXPLORER: &str = "https://explorer-tn10.kaspa.org";
 
 pub enum Exception {
     #[allow(dead_code)]
@@ -48,6 +50,10 @@ pub struct Core {
     pub wallet_list: Vec<WalletDescriptor>,
     pub prv_key_data_map: Option<HashMap<PrvKeyDataId, Arc<PrvKeyDataInfo>>>,
     pub account_collection: Option<AccountCollection>,
+    pub address_book: Option<AddressBook>,
+    pub transaction_notes: Option<TransactionNotes>,
+    pub watch_list: Option<WatchList>,
+    wallet_file_key: Option<WalletFileKey>,
     pub release: Option<Release>,
 
     pub device: Device,
@@ -164,8 +170,6 @@ impl Core {
             settings.store_sync().unwrap();
         }
 
-        settings.node.network = Network::Mainnet;
-
         let module = modules.get(&module_typeid).unwrap().clone();
         // let mut module = modules
         //     .get(&TypeId::of::<modules::Overview>())
@@ -203,6 +207,10 @@ impl Core {
             wallet_list: Vec::new(),
             prv_key_data_map: None,
             account_collection: None,
+            address_book: None,
+            transaction_notes: None,
+            watch_list: None,
+            wallet_file_key: None,
             state: Default::default(),
             hint: None,
             discard_hint: false,
@@ -276,6 +284,14 @@ impl Core {
                     type_id == TypeId::of::<modules::RkBridgeLogs>(),
                     Ordering::Relaxed,
                 );
+                crate::runtime::services::cpu_miner::update_logs_flag().store(
+                    type_id == TypeId::of::<modules::CpuMinerLogs>(),
+                    Ordering::Relaxed,
+                );
+                crate::runtime::services::rothschild::update_logs_flag().store(
+                    type_id == TypeId::of::<modules::RothschildLogs>(),
+                    Ordering::Relaxed,
+                );
             }
         }
     }
@@ -350,6 +366,147 @@ impl Core {
         &self.prv_key_data_map
     }
 
+    pub fn address_book(&self) -> &Option<AddressBook> {
+        &self.address_book
+    }
+
+    /// Applies a change to the address book of the open wallet and stores it.
+    pub fn update_address_book(
+        &mut self,
+        update: impl FnOnce(&mut AddressBook) -> Result<()>,
+    ) -> Result<()> {
+        Self::update_wallet_file(
+            &mut self.address_book,
+            i18n("The address book is not available"),
+            update,
+        )
+    }
+
+    pub fn transaction_notes(&self) -> &Option<TransactionNotes> {
+        &self.transaction_notes
+    }
+
+    /// Applies a change to the transaction notes of the open wallet and stores them.
+    pub fn update_transaction_notes(
+        &mut self,
+        update: impl FnOnce(&mut TransactionNotes) -> Result<()>,
+    ) -> Result<()> {
+        Self::update_wallet_file(
+            &mut self.transaction_notes,
+            i18n("Transaction notes are not available"),
+            update,
+        )
+    }
+
+    pub fn watch_list(&self) -> &Option<WatchList> {
+        &self.watch_list
+    }
+
+    /// Applies a change to the watch-only address lists of the open wallet and stores them.
+    pub fn update_watch_list(
+        &mut self,
+        update: impl FnOnce(&mut WatchList) -> Result<()>,
+    ) -> Result<()> {
+        Self::update_wallet_file(
+            &mut self.watch_list,
+            i18n("Watch-only address lists are not available"),
+            update,
+        )
+    }
+
+    fn update_wallet_file<T: WalletFileData>(
+        file: &mut Option<EncryptedWalletFile<T>>,
+        unavailable: &str,
+        update: impl FnOnce(&mut EncryptedWalletFile<T>) -> Result<()>,
+    ) -> Result<()> {
+        let file = file.as_mut().ok_or_else(|| Error::custom(unavailable))?;
+        update(file)?;
+        spawn(file.store());
+        Ok(())
+    }
+
+    /// Loads the wallet files (address book, transaction notes and watch
+    /// lists) once both the wallet descriptor and the wallet file key are
+    /// known.
+    fn load_wallet_data(&mut self) {
+        let (Some(key), Some(wallet_descriptor)) = (
+            self.wallet_file_key.clone(),
+            self.wallet_descriptor.as_ref(),
+        ) else {
+            return;
+        };
+        let wallet_filename = wallet_descriptor.filename.clone();
+        let sender = self.application_events_channel.sender.clone();
+
+        Self::load_wallet_file(
+            &mut self.address_book,
+            &wallet_filename,
+            &key,
+            &sender,
+            Events::AddressBook,
+        );
+        Self::load_wallet_file(
+            &mut self.transaction_notes,
+            &wallet_filename,
+            &key,
+            &sender,
+            Events::TransactionNotes,
+        );
+        Self::load_wallet_file(
+            &mut self.watch_list,
+            &wallet_filename,
+            &key,
+            &sender,
+            Events::WatchList,
+        );
+    }
+
+    /// Wallet files stay `None` until they have been loaded, so that a
+    /// pending or failed load can never overwrite the stored data. If the
+    /// file of the open wallet is already loaded and the key has changed
+    /// (wallet secret change), it is re-encrypted with the new key.
+    fn load_wallet_file<T: WalletFileData>(
+        file: &mut Option<EncryptedWalletFile<T>>,
+        wallet_filename: &str,
+        key: &WalletFileKey,
+        sender: &crate::runtime::channel::Sender<Events>,
+        loaded: fn(Box<EncryptedWalletFile<T>>) -> Events,
+    ) {
+        if let Some(file) = file.as_mut() {
+            if file.wallet_filename() == wallet_filename {
+                if !file.has_key(key) {
+                    file.set_key(key.clone());
+                    spawn(file.store());
+                }
+                return;
+            }
+        }
+
+        *file = None;
+        let sender = sender.clone();
+        let wallet_filename = wallet_filename.to_string();
+        let key = key.clone();
+        spawn(async move {
+            match EncryptedWalletFile::<T>::load(&wallet_filename, key).await {
+                Ok(file) => {
+                    sender.try_send(loaded(Box::new(file))).ok();
+                }
+                Err(err) => {
+                    log_error!("Unable to load the {}: {err}", T::NAME);
+                    runtime().error(err.to_string());
+                }
+            }
+            Ok(())
+        });
+    }
+
+    /// Whether data loaded for `wallet_filename` belongs to the open wallet.
+    fn is_open_wallet(&self, wallet_filename: &str) -> bool {
+        self.wallet_descriptor
+            .as_ref()
+            .is_some_and(|descriptor| descriptor.filename == wallet_filename)
+    }
+
     pub fn modules(&self) -> &HashMap<TypeId, Module> {
         &self.modules
     }
@@ -398,8 +555,7 @@ impl Core {
         })
     }
 
-    pub fn change_current_network(&mut self, _network: Network) {
-        let network = Network::Mainnet;
+    pub fn change_current_network(&mut self, network: Network) {
         if self.settings.node.network != network {
             let initialized = self.settings.initialized;
             let mut next_settings = crate::settings::Settings::load_for_network_sync(network)
@@ -427,6 +583,20 @@ impl Core {
             self.runtime
                 .stratum_bridge_service()
                 .enable(bridge_enabled, &self.settings.node);
+            self.runtime
+                .cpu_miner_service()
+                .enable(self.settings.node.cpu_miner_enabled, &self.settings.node);
+            let rothschild_enabled = self.settings.node.rothschild_enabled
+                && crate::runtime::services::RothschildService::is_network_allowed(network);
+            self.runtime
+                .rothschild_service()
+                .enable(rothschild_enabled, &self.settings.node);
+            self.runtime
+                .peer_monitor_service()
+                .update_node_settings(&self.settings.node);
+            self.runtime
+                .alert_monitor_service()
+                .update_settings(self.settings.alerts.clone());
             let settings_snapshot = self.settings.clone();
             self.get_mut::<modules::Settings>().load(settings_snapshot);
             #[cfg(not(target_arch = "wasm32"))]
@@ -437,6 +607,10 @@ impl Core {
                 self.runtime
                     .self_hosted_loader_service()
                     .update_node_settings(self.settings.node.clone());
+                self.runtime
+                    .control_api_service()
+                    .update_settings(&self.settings.control_api, network);
+                crate::runtime::services::log_hub().configure(&self.settings.logs);
             }
             self.store_settings();
             self.runtime
@@ -881,6 +1055,30 @@ impl Core {
             Events::NodeInfo { node_info } => {
                 self.node_info = node_info;
             }
+            Events::WalletFileKey(key) => {
+                self.wallet_file_key = Some(key);
+                self.load_wallet_data();
+            }
+            Events::AddressBook(address_book) => {
+                if self.is_open_wallet(address_book.wallet_filename()) {
+                    self.address_book.get_or_insert(*address_book);
+                }
+            }
+            Events::TransactionNotes(transaction_notes) => {
+                if self.is_open_wallet(transaction_notes.wallet_filename()) {
+                    self.transaction_notes.get_or_insert(*transaction_notes);
+                }
+            }
+            Events::WatchList(watch_list) => {
+                if self.is_open_wallet(watch_list.wallet_filename()) {
+                    self.watch_list.get_or_insert(*watch_list);
+                }
+            }
+            Events::PaymentUri(payment_uri) => {
+                self.get_mut::<modules::AccountManager>()
+                    .open_payment_uri(*payment_uri);
+                self.select::<modules::AccountManager>();
+            }
             Events::Close => {}
             Events::UnlockSuccess => {}
             Events::UnlockFailure { .. } => {}
@@ -1040,6 +1238,7 @@ impl Core {
                         let account_descriptors =
                             account_descriptors.ok_or(Error::WalletOpenAccountDescriptors)?;
                         self.load_accounts(network_id, account_descriptors)?;
+                        self.load_wallet_data();
                     }
                     CoreWallet::WalletOpen {
                         wallet_descriptor,
@@ -1055,6 +1254,7 @@ impl Core {
                         let account_descriptors =
                             account_descriptors.ok_or(Error::WalletOpenAccountDescriptors)?;
                         self.load_accounts(network_id, account_descriptors)?;
+                        self.load_wallet_data();
                     }
                     CoreWallet::WalletCreate {
                         wallet_descriptor,
@@ -1064,6 +1264,7 @@ impl Core {
                         self.wallet_descriptor = Some(wallet_descriptor);
                         self.account_collection = Some(AccountCollection::default());
                         self.state.is_open = true;
+                        self.load_wallet_data();
                     }
                     CoreWallet::PrvKeyDataCreate { prv_key_data_info } => {
                         if let Some(prv_key_data_map) = self.prv_key_data_map.as_mut() {
@@ -1096,6 +1297,10 @@ impl Core {
                         self.account_collection = None;
                         self.wallet_descriptor = None;
                         self.prv_key_data_map = None;
+                        self.address_book = None;
+                        self.transaction_notes = None;
+                        self.watch_list = None;
+                        self.wallet_file_key = None;
 
                         self.modules.clone().into_iter().for_each(|(_, module)| {
                             module.reset(self);
@@ -339,35 +339,41 @@ impl LayoutJobBuilder {
         }
     }
 
+    /// Renders `text` as a link to `url`, or as plain text if there is no `url`.
     pub fn hyperlink_with_clipboard_icon(
         self,
         ui: &mut Ui,
         text: &str,
-        url: &str,
+        url: Option<&str>,
         color: Color32,
         clipboard_text: Option<String>,
     ) {
         ui.horizontal(|ui| {
             Self::render_label(ui, self.job, self.heading);
-            ui.hyperlink_to_tab(
-                RichText::new(text)
-                    .font(self.font_id.unwrap_or_default())
-                    .color(color),
-                url,
-            );
+            let text = RichText::new(text)
+                .font(self.font_id.unwrap_or_default())
+                .color(color);
+            match url {
+                Some(url) => {
+                    ui.hyperlink_to_tab(text, url);
+                }
+                None => {
+                    ui.label(text);
+                }
+            }
             if let Some(text) = clipboard_text {
                 Self::clipboard_icon(ui, text);
             }
         });
     }
     pub fn hyperlink(self, ui: &mut Ui, text: &str, url: &str, color: Color32) {
-        self.hyperlink_with_clipboard_icon(ui, text, url, color, None)
+        self.hyperlink_with_clipboard_icon(ui, text, Some(url), color, None)
     }
     pub fn transaction_id(
         self,
         ui: &mut Ui,
         txid: &str,
-        url: &str,
+        url: Option<&str>,
         color: Color32,
         range: Option<usize>,
     ) {
@@ -396,7 +402,7 @@ impl LayoutJobBuilder {
         self,
         ui: &mut Ui,
         address: &str,
-        url: &str,
+        url: Option<&str>,
         color: Color32,
         range: Option<usize>,
     ) {
@@ -0,0 +1,321 @@
+use crate::imports::*;
+use crate::runtime::services::{LogFilter, LogLevel, LogRecord, LogSource, log_hub};
+use egui_phosphor::light::{CLIPBOARD_TEXT, EXPORT, PAUSE, PLAY};
+use regex::{Regex, RegexBuilder};
+
+/// Maximum number of records displayed at once.
+const LOG_VIEWER_LIMIT: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportWindow {
+    Minutes15,
+    Hour,
+    Day,
+    All,
+}
+
+impl ExportWindow {
+    const ALL: [ExportWindow; 4] = [
+        ExportWindow::Minutes15,
+        ExportWindow::Hour,
+        ExportWindow::Day,
+        ExportWindow::All,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            ExportWindow::Minutes15 => i18n("Last 15 minutes"),
+            ExportWindow::Hour => i18n("Last hour"),
+            ExportWindow::Day => i18n("Last 24 hours"),
+            ExportWindow::All => i18n("All"),
+        }
+    }
+
+    fn duration_msec(&self) -> Option<f64> {
+        match self {
+            ExportWindow::Minutes15 => Some(15.0 * 60.0 * 1000.0),
+            ExportWindow::Hour => Some(60.0 * 60.0 * 1000.0),
+            ExportWindow::Day => Some(24.0 * 60.0 * 60.0 * 1000.0),
+            ExportWindow::All => None,
+        }
+    }
+}
+
+/// Selection the cached records were filtered with.
+#[derive(Debug, Clone, PartialEq)]
+struct CacheKey {
+    last_seq: u64,
+    selected: Vec<LogSource>,
+    min_level: LogLevel,
+    search: Option<String>,
+    exclude: Option<String>,
+    paused_at: Option<u64>,
+}
+
+/// Log viewer over the shared log hub with source and level filters,
+/// regex search, pause/follow and export of a time window.
+pub struct LogViewer {
+    id: &'static str,
+    sources: Vec<LogSource>,
+    selected: Vec<LogSource>,
+    min_level: LogLevel,
+    search: String,
+    regex: Option<std::result::Result<Regex, String>>,
+    exclude: Option<Regex>,
+    paused_at: Option<u64>,
+    follow: bool,
+    export_window: ExportWindow,
+    cache: Option<(CacheKey, Arc<Vec<LogRecord>>)>,
+}
+
+impl LogViewer {
+    /// Creates a viewer of the given sources, all of them selected.
+    pub fn new(id: &'static str, sources: &[LogSource]) -> Self {
+        Self {
+            id,
+            sources: sources.to_vec(),
+            selected: sources.to_vec(),
+            min_level: LogLevel::Trace,
+            search: String::new(),
+            regex: None,
+            exclude: None,
+            paused_at: None,
+            follow: true,
+            export_window: ExportWindow::Hour,
+            cache: None,
+        }
+    }
+
+    /// Limits the initially selected sources.
+    pub fn with_selected(mut self, selected: &[LogSource]) -> Self {
+        self.selected = selected.to_vec();
+        self
+    }
+
+    /// Hides records whose message matches `exclude`.
+    pub fn set_exclude(&mut self, exclude: Option<Regex>) {
+        self.exclude = exclude;
+    }
+
+    fn filter(&self) -> LogFilter {
+        LogFilter {
+            sources: self.selected.clone(),
+            min_level: Some(self.min_level),
+            search: self
+                .regex
+                .as_ref()
+                .and_then(|regex| regex.as_ref().ok().cloned()),
+            exclude: self.exclude.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn update_search(&mut self) {
+        self.regex = self.search.is_not_empty().then(|| {
+            RegexBuilder::new(&self.search)
+                .case_insensitive(true)
+                .build()
+                .map_err(|err| err.to_string())
+        });
+    }
+
+    /// Returns the records currently shown, filtering them again only
+    /// when new records have arrived or the selection has changed.
+    fn records(&mut self) -> Arc<Vec<LogRecord>> {
+        let key = CacheKey {
+            last_seq: log_hub().last_seq(),
+            selected: self.selected.clone(),
+            min_level: self.min_level,
+            search: self
+                .regex
+                .as_ref()
+                .and_then(|regex| regex.as_ref().ok())
+                .map(|regex| regex.as_str().to_string()),
+            exclude: self
+                .exclude
+                .as_ref()
+                .map(|regex| regex.as_str().to_string()),
+            paused_at: self.paused_at,
+        };
+
+        if let Some((cached, records)) = self.cache.as_ref() {
+            if *cached == key {
+                return records.clone();
+            }
+        }
+
+        let filter = LogFilter {
+            until_seq: self.paused_at,
+            ..self.filter()
+        };
+        let records = Arc::new(log_hub().records(&filter, LOG_VIEWER_LIMIT));
+        self.cache = Some((key, records.clone()));
+        records
+    }
+
+    /// Returns the records currently shown, as plain text.
+    pub fn text(&mut self) -> String {
+        self.records()
+            .iter()
+            .map(LogRecord::format)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn export(&self) {
+        let filter = LogFilter {
+            from: self
+                .export_window
+                .duration_msec()
+                .map(|duration| unixtime_as_millis_f64() - duration),
+            ..self.filter()
+        };
+
+        let filename = format!(
+            "kaspa-ng-{}-{}.log",
+            self.id,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(filename)
+            .add_filter("Log", &["log", "txt"])
+            .save_file()
+        else {
+            return;
+        };
+
+        match log_hub().export(&path, &filter) {
+            Ok(count) => runtime().notify(UserNotification::success(i18n_args(
+                "Exported {count} log lines to {path}",
+                &[
+                    ("count", count.to_string()),
+                    ("path", path.to_string_lossy().to_string()),
+                ],
+            ))),
+            Err(err) => runtime().error(err.to_string()),
+        }
+    }
+
+    fn render_toolbar(&mut self, ui: &mut Ui) {
+        ui.horizontal_wrapped(|ui| {
+            if self.sources.len() > 1 {
+                for source in self.sources.clone() {
+                    let selected = self.selected.contains(&source);
+                    if ui.selectable_label(selected, source.name()).clicked() {
+                        if selected {
+                            self.selected.retain(|item| *item != source);
+                        } else {
+                            self.selected.push(source);
+                        }
+                    }
+                }
+                ui.separator();
+            }
+
+            ComboBox::from_id_salt(format!("{}_level", self.id))
+                .selected_text(self.min_level.name())
+                .show_ui(ui, |ui| {
+                    for level in LogLevel::iter() {
+                        ui.selectable_value(&mut self.min_level, level, level.name());
+                    }
+                });
+
+            let response = ui.add(
+                TextEdit::singleline(&mut self.search)
+                    .hint_text(i18n("Search (regex)"))
+                    .desired_width(180.0),
+            );
+            if response.changed() {
+                self.update_search();
+            }
+
+            ui.separator();
+
+            if self.paused_at.is_some() {
+                if ui.button(format!("{PLAY} {}", i18n("Resume"))).clicked() {
+                    self.paused_at = None;
+                }
+            } else if ui.button(format!("{PAUSE} {}", i18n("Pause"))).clicked() {
+                self.paused_at = Some(log_hub().last_seq());
+            }
+            ui.checkbox(&mut self.follow, i18n("Follow"));
+
+            ui.separator();
+
+            ComboBox::from_id_salt(format!("{}_export_window", self.id))
+                .selected_text(self.export_window.label())
+                .show_ui(ui, |ui| {
+                    for window in ExportWindow::ALL {
+                        ui.selectable_value(&mut self.export_window, window, window.label());
+                    }
+                });
+            if ui
+                .button(format!("{EXPORT} {}", i18n("Export")))
+                .on_hover_text(i18n("Save the filtered logs of the selected time window"))
+                .clicked()
+            {
+                self.export();
+            }
+            if ui
+                .button(CLIPBOARD_TEXT)
+                .on_hover_text(i18n("Copy logs to clipboard"))
+                .clicked()
+            {
+                ui.ctx().copy_text(self.text());
+                runtime().notify_clipboard(i18n("Copied to clipboard"));
+            }
+        });
+
+        if let Some(Err(err)) = self.regex.as_ref() {
+            ui.colored_label(theme_color().warning_color, err);
+        }
+    }
+
+    pub fn render(&mut self, ui: &mut Ui) {
+        self.render_toolbar(ui);
+        ui.add_space(4.0);
+
+        // not all sources request a repaint when new lines arrive
+        if self.paused_at.is_none() {
+            ui.ctx().request_repaint_after(Duration::from_millis(500));
+        }
+
+        let records = self.records();
+
+        if records.is_empty() {
+            ui.colored_label(theme_color().warning_color, i18n("No logs yet."));
+            return;
+        }
+
+        let font_id = FontId::monospace(theme_style().node_log_font_size);
+        let row_height = ui.fonts(|fonts| fonts.row_height(&font_id));
+        let show_source = self.selected.len() != 1;
+
+        ScrollArea::both()
+            .id_salt(self.id)
+            .auto_shrink([false; 2])
+            .stick_to_bottom(self.follow && self.paused_at.is_none())
+            .show_rows(ui, row_height, records.len(), |ui, range| {
+                for record in &records[range] {
+                    let text = if show_source {
+                        format!(
+                            "{} [{}] {}",
+                            record.time(),
+                            record.source.slug(),
+                            record.message
+                        )
+                    } else {
+                        format!("{} {}", record.time(), record.message)
+                    };
+                    ui.add(
+                        Label::new(
+                            RichText::new(text)
+                                .font(font_id.clone())
+                                .color(record.level.color()),
+                        )
+                        .extend(),
+                    );
+                }
+            });
+    }
+}
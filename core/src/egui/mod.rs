@@ -4,6 +4,8 @@ mod composite_icon;
 mod easy_mark;
 mod extensions;
 mod icon;
+#[cfg(not(target_arch = "wasm32"))]
+mod log_viewer;
 mod mnemonic;
 mod network;
 mod pagination;
@@ -18,6 +20,8 @@ pub use composite_icon::CompositeIcon;
 pub use easy_mark::easy_mark;
 pub use extensions::*;
 pub use icon::IconSize;
+#[cfg(not(target_arch = "wasm32"))]
+pub use log_viewer::LogViewer;
 pub use mnemonic::*;
 pub use network::NetworkInterfaceEditor;
 pub use pagination::*;
@@ -35,6 +35,10 @@ pub enum Error {
     #[error(transparent)]
     WrpcClientError(#[from] kaspa_wrpc_client::error::Error),
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error(transparent)]
+    GrpcClientError(#[from] kaspa_grpc_client::error::Error),
+
     #[error(transparent)]
     WorkflowStorage(#[from] workflow_store::error::Error),
 
@@ -48,6 +48,13 @@ pub enum Events {
     NodeInfo {
         node_info: Option<Box<String>>,
     },
+    /// Wallet file key derived from the wallet secret on unlock.
+    WalletFileKey(WalletFileKey),
+    AddressBook(Box<AddressBook>),
+    TransactionNotes(Box<TransactionNotes>),
+    WatchList(Box<WatchList>),
+    /// Opens the send form pre-filled from a `kaspa:` payment URI.
+    PaymentUri(Box<PaymentUri>),
     Close,
     Exit,
 }
@@ -64,9 +64,10 @@ pub use egui::epaint::{
 pub use egui::*;
 pub use egui_plot::{PlotPoint, PlotPoints};
 
+pub use crate::address_book::{AddressBook, Contact};
 pub use crate::collection::Collection;
 pub use crate::core::Core;
-pub use crate::core::MAINNET_EXPLORER;
+pub use crate::core::{MAINNET_EXPLORER, TESTNET10_EXPLORER};
 pub use crate::device::{Device, Orientation};
 pub use crate::egui::*;
 pub use crate::error::Error;
@@ -87,14 +88,18 @@ pub use crate::primitives::{
 pub use crate::result::Result;
 pub use crate::runtime::{Payload, Runtime, Service, runtime, spawn, spawn_with_result};
 pub use crate::settings::{
-    EstimatorMode, EstimatorSettings, ExplorerDataSource, ExplorerEndpoint,
-    ExplorerNetworkProfiles, ExplorerSettings, KaspadNodeKind, NetworkInterfaceConfig,
-    NetworkInterfaceKind, NodeConnectionConfigKind, NodeMemoryScale, NodeSettings,
-    RenderingSettings, RpcConfig, RpcOptions, SelfHostedSettings, Settings, StratumBridgeSettings,
+    AlertKind, AlertRule, AlertSettings, ControlApiSettings, CpuMinerSettings, EstimatorMode, EstimatorSettings,
+    ExplorerDataSource, ExplorerEndpoint,
+    ExplorerNetworkProfiles, ExplorerSettings, KaspadNodeKind, LogSettings, NetworkInterfaceConfig,
+    NetworkInterfaceKind, NodeConnectionConfigKind, NodeMemoryScale, NodeSettings, PeerSettings,
+    RenderingSettings, RothschildSettings, RpcConfig, RpcOptions, SelfHostedSettings, Settings, StratumBridgeSettings,
     UserInterfaceSettings,
 };
 pub use crate::state::State;
 pub use crate::status::Status;
 pub use crate::storage::{Storage, StorageUpdateOptions};
+pub use crate::transaction_notes::{TRANSACTION_NOTE_MAX_LENGTH, TransactionNotes, TransactionNotesData};
 pub use crate::utils::spawn;
 pub use crate::utils::*;
+pub use crate::wallet_file::WalletFileKey;
+pub use crate::watch_list::{WatchList, WatchedAddresses};
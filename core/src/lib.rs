@@ -13,6 +13,8 @@ extern crate self as kaspa_ng_core;
 mod core;
 pub use core::Core;
 
+pub mod address_book;
+pub mod alerts;
 pub mod app;
 pub mod collection;
 pub mod device;
@@ -38,7 +40,10 @@ pub mod state;
 pub mod status;
 pub mod storage;
 pub mod sync;
+pub mod transaction_notes;
 pub mod utils;
+pub mod wallet_file;
+pub mod watch_list;
 
 #[cfg(test)]
 mod tests;
@@ -40,3 +40,34 @@ pub enum MarketUpdate {
     Price(Arc<MarketDataMap>),
     Ohlc(Arc<OhlcMap>),
 }
+
+/// Historical KAS prices in a single currency as `(unixtime_msec, price)`
+/// samples sorted by time.
+#[derive(Default, Clone, Debug)]
+pub struct PriceHistory {
+    pub currency: String,
+    pub prices: Vec<(u64, f64)>,
+}
+
+impl PriceHistory {
+    /// Samples older than this are not used to price a transaction.
+    pub const MAX_SAMPLE_AGE_MSEC: u64 = 36 * 60 * 60 * 1000;
+
+    pub fn new(currency: &str, mut prices: Vec<(u64, f64)>) -> Self {
+        prices.sort_by_key(|(unixtime_msec, _)| *unixtime_msec);
+        Self {
+            currency: currency.to_lowercase(),
+            prices,
+        }
+    }
+
+    /// Price in effect at the given time, i.e. the last sample taken at
+    /// or before it. Returns `None` if there is no sufficiently recent sample.
+    pub fn price_at(&self, unixtime_msec: u64) -> Option<f64> {
+        let index = self
+            .prices
+            .partition_point(|(sample_msec, _)| *sample_msec <= unixtime_msec);
+        let (sample_msec, price) = self.prices.get(index.checked_sub(1)?)?;
+        (unixtime_msec - sample_msec <= Self::MAX_SAMPLE_AGE_MSEC).then_some(*price)
+    }
+}
@@ -464,6 +464,19 @@ impl<'core> Menu<'core> {
 
         ui.separator();
 
+        if self
+            .menu_tab_button(
+                ui,
+                i18n("Check Balance"),
+                active == TypeId::of::<modules::CheckBalance>(),
+            )
+            .clicked()
+        {
+            self.select::<modules::CheckBalance>();
+            ui.close_menu();
+        }
+        ui.separator();
+
         if self
             .menu_tab_button(
                 ui,
@@ -554,7 +567,7 @@ impl<'core> Menu<'core> {
                 if self
                     .menu_tab_button(
                         ui,
-                        i18n("Rusty Kaspa"),
+                        i18n("Logs"),
                         active == TypeId::of::<modules::Logs>(),
                     )
                     .clicked()
@@ -580,6 +593,40 @@ impl<'core> Menu<'core> {
                     }
                 }
             }
+
+            if self.core.settings.node.cpu_miner_enabled {
+                ui.separator();
+                if self
+                    .menu_tab_button(
+                        ui,
+                        i18n("CPU Miner"),
+                        active == TypeId::of::<modules::CpuMinerLogs>(),
+                    )
+                    .clicked()
+                {
+                    self.select::<modules::CpuMinerLogs>();
+                    ui.close_menu();
+                }
+            }
+
+            if self.core.settings.node.rothschild_enabled
+                && crate::runtime::services::RothschildService::is_network_allowed(
+                    self.core.settings.node.network,
+                )
+            {
+                ui.separator();
+                if self
+                    .menu_tab_button(
+                        ui,
+                        i18n("Rothschild"),
+                        active == TypeId::of::<modules::RothschildLogs>(),
+                    )
+                    .clicked()
+                {
+                    self.select::<modules::RothschildLogs>();
+                    ui.close_menu();
+                }
+            }
         }
     }
 
@@ -4,6 +4,9 @@ use modules::wallet_create::WalletCreate;
 use crate::imports::*;
 use kaspa_wallet_core::storage::AssocPrvKeyDataIds;
 use kaspa_wallet_core::deterministic::bip32::BIP32_ACCOUNT_KIND;
+use kaspa_wallet_core::account::MULTISIG_ACCOUNT_KIND;
+use kaspa_wallet_core::wallet::AccountCreateArgsBip32Watch;
+use crate::watch_list::parse_addresses;
 
 #[derive(Clone)]
 pub enum CreateAccountKind {
@@ -12,6 +15,7 @@ pub enum CreateAccountKind {
     Legacy,
     MultiSig,
     Keypair,
+    WatchOnly,
     // Keypair,
     // MultiSig,
 }
@@ -26,6 +30,11 @@ pub enum State {
     ImportMnemonicWithEditor,
     PrivateKeyCreate,
     PrivateKeyConfirm,
+    MultisigKeys,
+    MultisigCosigners,
+    CosignerXpub,
+    PresentCosignerXpub(Arc<String>),
+    WatchOnlyKey,
     AccountName,
     WalletSecret,
     PaymentSecret,
@@ -62,6 +71,9 @@ enum Focus {
     WalletSecret,
     PaymentSecret,
     WalletMnemonic,
+    CosignerXpubs,
+    WatchOnlyKey,
+    WatchListName,
 }
 
 #[derive(Clone, Default)]
@@ -81,6 +93,63 @@ struct Context {
     import_with_bip39_passphrase: bool,
     import_private_key_mnemonic: String,
     prv_keys: Vec<Arc<PrvKeyDataInfo>>,
+    multisig_prv_key_data_ids: Vec<PrvKeyDataId>,
+    cosigner_xpubs: String,
+    minimum_signatures: u16,
+    export_cosigner_xpub: bool,
+    watch_only_xpub: String,
+    watch_addresses: bool,
+    watch_list_name: String,
+    watch_list_addresses: String,
+    watch_list_error: Option<String>,
+}
+
+impl Context {
+    fn is_multisig(&self) -> bool {
+        matches!(self.account_kind, Some(CreateAccountKind::MultiSig))
+    }
+
+    fn is_watch_only(&self) -> bool {
+        matches!(self.account_kind, Some(CreateAccountKind::WatchOnly))
+    }
+
+    fn requires_bip39_passphrase(&self) -> bool {
+        if self.export_cosigner_xpub {
+            self.prv_key_data_info.as_ref().map(|info| info.requires_bip39_passphrase()).unwrap_or(false)
+        } else if self.is_watch_only() {
+            false
+        } else if self.is_multisig() {
+            self.prv_keys.iter().any(|info| {
+                self.multisig_prv_key_data_ids.contains(info.id()) && info.requires_bip39_passphrase()
+            })
+        } else {
+            self.import_with_bip39_passphrase || self.prv_key_data_info.as_ref().map(|info| info.requires_bip39_passphrase()).unwrap_or(false)
+        }
+    }
+
+    fn secrets_complete_state(&self) -> State {
+        if self.export_cosigner_xpub {
+            State::CosignerXpub
+        } else {
+            State::AddAccount
+        }
+    }
+}
+
+/// Parses extended public keys (xpub, kpub) entered one per line.
+fn parse_xpubs(text: &str) -> std::result::Result<Vec<String>, String> {
+    let mut xpubs = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        match kaspa_bip32::ExtendedKey::from_str(line) {
+            Ok(key) if key.prefix.is_public() => {},
+            _ => return Err(i18n_args("Invalid extended public key: {key}", &[("key", format_partial_string(line, Some(12)))])),
+        }
+        if xpubs.iter().any(|xpub| xpub == line) {
+            return Err(i18n("Duplicate extended public key").to_string());
+        }
+        xpubs.push(line.to_string());
+    }
+    Ok(xpubs)
 }
 
 impl Zeroize for Context {
@@ -89,6 +158,15 @@ impl Zeroize for Context {
         self.wallet_secret.zeroize();
         self.payment_secret.zeroize();
         self.import_private_key_mnemonic.zeroize();
+        self.multisig_prv_key_data_ids.clear();
+        self.cosigner_xpubs.zeroize();
+        self.minimum_signatures = 0;
+        self.export_cosigner_xpub = false;
+        self.watch_only_xpub.zeroize();
+        self.watch_addresses = false;
+        self.watch_list_name.zeroize();
+        self.watch_list_addresses.zeroize();
+        self.watch_list_error = None;
     }
 }
 
@@ -175,6 +253,27 @@ impl ModuleT for AccountCreate {
 
                         ui.add(ui.create_separator(Some(32.0), 0.5, Some(true)));
 
+                        if ui.large_button_enabled(!no_keys, i18n("Multisig Account")).clicked(){
+                            this.context.account_kind = Some(CreateAccountKind::MultiSig);
+                            this.context.import_mnemonic = false;
+                            this.context.multisig_prv_key_data_ids.clear();
+                            this.state = State::MultisigKeys;
+                        }
+                        ui.label(i18n("Create an M-of-N account from local private keys and cosigner xpubs."));
+
+                        ui.add(ui.create_separator(Some(32.0), 0.5, Some(true)));
+
+                        if ui.large_button(i18n("Watch-only Account")).clicked(){
+                            this.context.account_kind = Some(CreateAccountKind::WatchOnly);
+                            this.context.import_mnemonic = false;
+                            this.context.prv_key_data_info = None;
+                            this.state = State::WatchOnlyKey;
+                            this.focus.next(Focus::WatchOnlyKey);
+                        }
+                        ui.label(i18n("Monitor an account using its extended public key (xpub or kpub) or a list of addresses."));
+
+                        ui.add(ui.create_separator(Some(32.0), 0.5, Some(true)));
+
 
                         if ui.large_button(i18n("Import Account")).clicked(){
                             this.state = State::StartImport;
@@ -216,6 +315,7 @@ impl ModuleT for AccountCreate {
                                 .clicked() {
                                     this.context.prv_key_data_info = Some(prv_key_data_info.clone());
                                     this.context.account_kind = Some(CreateAccountKind::Bip44);
+                                    this.context.export_cosigner_xpub = false;
                                     this.state = State::AccountName;
                                     this.focus.next(Focus::AccountName);
                                 }
@@ -234,6 +334,7 @@ impl ModuleT for AccountCreate {
             }
             State::StartImport => {
                 self.context.import_mnemonic = true;
+                self.context.account_kind = None;
                 self.context.import_private_key_mnemonic.zeroize();
                 self.context.wallet_secret.zeroize();
                 self.context.payment_secret.zeroize();
@@ -280,12 +381,301 @@ impl ModuleT for AccountCreate {
 
             }
 
+            State::MultisigKeys => {
+                Panel::new(self)
+                    .with_caption(i18n("Multisig Account"))
+                    .with_back(|this| {
+                        this.state = State::Start;
+                    })
+                    .with_close_enabled(false, |_|{
+                    })
+                    .with_header(|_ctx,ui| {
+                        ui.label(i18n("Select the local private keys that will co-sign for this account."));
+                        ui.label(i18n("Each cosigner must share the extended public key (xpub) of their key."));
+                        ui.label(" ");
+                    })
+                    .with_body(|this,ui|{
+                        for prv_key_data_info in this.context.prv_keys.clone() {
+                            let id = *prv_key_data_info.id();
+                            let mut selected = this.context.multisig_prv_key_data_ids.contains(&id);
+                            ui.horizontal(|ui| {
+                                if ui.checkbox(&mut selected, prv_key_data_info.name_or_id()).changed() {
+                                    if selected {
+                                        this.context.multisig_prv_key_data_ids.push(id);
+                                    } else {
+                                        this.context.multisig_prv_key_data_ids.retain(|selected_id| selected_id != &id);
+                                    }
+                                }
+                                if ui.button(i18n("Export xpub")).clicked() {
+                                    this.context.prv_key_data_info = Some(prv_key_data_info.clone());
+                                    this.context.export_cosigner_xpub = true;
+                                    this.state = State::WalletSecret;
+                                    this.focus.next(Focus::WalletSecret);
+                                }
+                            });
+                            ui.add_space(8.);
+                        }
+                    })
+                    .with_footer(|this,ui| {
+                        let enabled = !this.context.multisig_prv_key_data_ids.is_empty();
+                        if ui.large_button_enabled(enabled, i18n("Continue")).clicked() {
+                            this.state = State::MultisigCosigners;
+                            this.focus.next(Focus::CosignerXpubs);
+                        }
+                    })
+                    .render(ui);
+            }
+
+            State::MultisigCosigners => {
+                let cosigner_xpubs = parse_xpubs(self.context.cosigner_xpubs.as_str());
+                let total_signers = self.context.multisig_prv_key_data_ids.len() + cosigner_xpubs.as_ref().map(|xpubs| xpubs.len()).unwrap_or_default();
+                let total_signers = total_signers.min(u16::MAX as usize) as u16;
+                if self.context.minimum_signatures == 0 || self.context.minimum_signatures > total_signers {
+                    self.context.minimum_signatures = total_signers;
+                }
+
+                Panel::new(self)
+                    .with_caption(i18n("Cosigners"))
+                    .with_back(|this| {
+                        this.state = State::MultisigKeys;
+                    })
+                    .with_close_enabled(false, |_|{
+                    })
+                    .with_header(|_ctx,ui| {
+                        ui.label(i18n("Enter the extended public keys (xpubs) of the external cosigners, one per line."));
+                        ui.label(" ");
+                    })
+                    .with_body(|this,ui| {
+                        TextEditor::new(
+                            &mut this.context.cosigner_xpubs,
+                            &mut this.focus,
+                            Focus::CosignerXpubs,
+                            |ui, text| {
+                                ui.add_sized([ui.available_width(), 120.], TextEdit::multiline(text)
+                                    .font(egui::TextStyle::Monospace))
+                            },
+                        )
+                        .build(ui);
+
+                        ui.add_space(8.);
+                        if let Err(err) = cosigner_xpubs.as_ref() {
+                            ui.colored_label(theme_color().error_color, err);
+                        }
+
+                        ui.add_space(16.);
+                        ui.horizontal(|ui| {
+                            ui.label(i18n("Required signatures:"));
+                            ui.add(egui::DragValue::new(&mut this.context.minimum_signatures).range(1..=total_signers.max(1)));
+                            ui.label(i18n_args("of {total}", &[("total", total_signers.to_string())]));
+                        });
+                    })
+                    .with_footer(|this,ui| {
+                        let enabled = cosigner_xpubs.is_ok() && total_signers > 1 && this.context.minimum_signatures > 0;
+                        if ui.large_button_enabled(enabled, i18n("Continue")).clicked() {
+                            this.state = State::AccountName;
+                            this.focus.next(Focus::AccountName);
+                        }
+                    })
+                    .render(ui);
+            }
+
+            State::WatchOnlyKey => {
+                let xpub = parse_xpubs(self.context.watch_only_xpub.as_str())
+                    .and_then(|xpubs| if xpubs.len() == 1 { Ok(xpubs) } else { Err(i18n("Please enter a single extended public key").to_string()) });
+
+                let save_watch_list = Rc::new(RefCell::new(false));
+
+                Panel::new(self)
+                    .with_caption(i18n("Watch-only Account"))
+                    .with_back(|this| {
+                        this.state = State::Start;
+                    })
+                    .with_close_enabled(false, |_|{
+                    })
+                    .with_header(|this,ui| {
+                        ui.horizontal(|ui| {
+                            if ui.selectable_label(!this.context.watch_addresses, i18n("Extended public key")).clicked() {
+                                this.context.watch_addresses = false;
+                                this.focus.next(Focus::WatchOnlyKey);
+                            }
+                            if ui.selectable_label(this.context.watch_addresses, i18n("Address list")).clicked() {
+                                this.context.watch_addresses = true;
+                                this.focus.next(Focus::WatchListName);
+                            }
+                        });
+                        ui.label(" ");
+                        if this.context.watch_addresses {
+                            ui.label(i18n("Enter a name and the addresses you want to monitor, one per line."));
+                            ui.label(i18n("Address lists are stored with the wallet and can be checked from the Check Balance tool."));
+                        } else {
+                            ui.label(i18n("Enter the extended public key (xpub or kpub) of the account you want to monitor."));
+                            ui.label(i18n("Watch-only accounts show balances and transactions but cannot sign transactions."));
+                        }
+                        ui.label(" ");
+                    })
+                    .with_body(|this,ui| {
+                        if this.context.watch_addresses {
+                            TextEditor::new(
+                                &mut this.context.watch_list_name,
+                                &mut this.focus,
+                                Focus::WatchListName,
+                                |ui, text| {
+                                    ui.label(RichText::new(i18n("Enter a name for the address list")).size(12.).raised());
+                                    ui.add_sized(theme_style().panel_editor_size, TextEdit::singleline(text)
+                                        .vertical_align(Align::Center))
+                                },
+                            )
+                            .build(ui);
+
+                            ui.add_space(8.);
+                            ui.label(RichText::new(i18n("Addresses (one per line)")).size(12.).raised());
+                            ui.add_sized([ui.available_width(), 96.], TextEdit::multiline(&mut this.context.watch_list_addresses)
+                                .font(egui::TextStyle::Monospace));
+
+                            if let Some(error) = this.context.watch_list_error.as_ref() {
+                                ui.add_space(8.);
+                                ui.colored_label(theme_color().error_color, error);
+                            }
+                            return;
+                        }
+
+                        TextEditor::new(
+                            &mut this.context.watch_only_xpub,
+                            &mut this.focus,
+                            Focus::WatchOnlyKey,
+                            |ui, text| {
+                                ui.label(RichText::new(i18n("Enter extended public key")).size(12.).raised());
+                                ui.add_sized(theme_style().panel_editor_size, TextEdit::singleline(text)
+                                    .vertical_align(Align::Center))
+                            },
+                        ).submit(|text,focus| {
+                            if parse_xpubs(text).map(|xpubs| xpubs.len() == 1).unwrap_or(false) {
+                                this.state = State::AccountName;
+                                focus.next(Focus::AccountName);
+                            }
+                        })
+                        .build(ui);
+
+                        if let (Err(err), false) = (xpub.as_ref(), this.context.watch_only_xpub.trim().is_empty()) {
+                            ui.add_space(8.);
+                            ui.colored_label(theme_color().error_color, err);
+                        }
+                    })
+                    .with_footer(|this,ui| {
+                        if this.context.watch_addresses {
+                            let ready = this.context.watch_list_name.trim().is_not_empty() && this.context.watch_list_addresses.trim().is_not_empty();
+                            if ui.large_button_enabled(ready, i18n("Save")).clicked() {
+                                *save_watch_list.borrow_mut() = true;
+                            }
+                        } else if ui.large_button_enabled(xpub.is_ok(), i18n("Continue")).clicked() {
+                            this.state = State::AccountName;
+                            this.focus.next(Focus::AccountName);
+                        }
+                    })
+                    .render(ui);
+
+                if *save_watch_list.borrow() {
+                    let network = core.network();
+                    let network_type : NetworkType = network.into();
+                    let list = parse_addresses(self.context.watch_list_addresses.as_str(), &network_type)
+                        .map(|addresses| WatchedAddresses {
+                            name : self.context.watch_list_name.trim().to_string(),
+                            network,
+                            addresses : addresses.iter().map(|address| address.to_string()).collect(),
+                        })
+                        .and_then(|list| core.update_watch_list(|watch_list| watch_list.insert(list.clone())).map(|_| list));
+
+                    match list {
+                        Ok(list) => {
+                            runtime().notify(UserNotification::success(i18n_args("Address list '{name}' saved", &[("name", list.name.clone())])));
+                            self.context.zeroize();
+                            self.state = State::Start;
+                            core.get_mut::<modules::CheckBalance>().watch(&list);
+                            core.select::<modules::CheckBalance>();
+                        }
+                        Err(err) => {
+                            self.context.watch_list_error = Some(err.to_string());
+                        }
+                    }
+                }
+            }
+
+            State::CosignerXpub => {
+                Panel::new(self)
+                    .with_caption(i18n("Cosigner xpub"))
+                    .with_header(|_, ui|{
+                        ui.label(" ");
+                        ui.label(i18n("Please wait..."));
+                        ui.label(" ");
+                        ui.add_space(64.);
+                        ui.add(egui::Spinner::new().size(92.));
+                    })
+                    .render(ui);
+
+                let cosigner_xpub_result = Payload::<Result<String>>::new("cosigner_xpub_result");
+                if !cosigner_xpub_result.is_pending() {
+                    let wallet = self.runtime.wallet().clone();
+                    let prv_key_data_info = self.context.prv_key_data_info.clone().expect("cosigner private key");
+                    let wallet_secret = Secret::from(self.context.wallet_secret.as_str());
+                    let payment_secret = prv_key_data_info.requires_bip39_passphrase().then(|| Secret::from(self.context.payment_secret.as_str()));
+                    self.context.wallet_secret.zeroize();
+                    self.context.payment_secret.zeroize();
+
+                    spawn_with_result(&cosigner_xpub_result, async move {
+                        let prv_key_data = wallet.prv_key_data_get(*prv_key_data_info.id(), wallet_secret).await?;
+                        let xpub = prv_key_data.create_xpub(payment_secret.as_ref(), MULTISIG_ACCOUNT_KIND.into(), 0).await?;
+                        Ok(xpub.to_string(Some(kaspa_bip32::Prefix::XPUB)))
+                    });
+                }
+
+                if let Some(result) = cosigner_xpub_result.take() {
+                    self.context.export_cosigner_xpub = false;
+                    match result {
+                        Ok(xpub) => {
+                            self.state = State::PresentCosignerXpub(Arc::new(xpub));
+                        }
+                        Err(err) => {
+                            self.state = State::AccountError(Arc::new(err));
+                        }
+                    }
+                }
+            }
+
+            State::PresentCosignerXpub(xpub) => {
+                Panel::new(self)
+                    .with_caption(i18n("Cosigner xpub"))
+                    .with_close_enabled(false, |_|{
+                    })
+                    .with_header(|_ctx,ui| {
+                        ui.label(i18n("Share this extended public key with the other cosigners."));
+                        ui.label(" ");
+                    })
+                    .with_body(|_this,ui| {
+                        ui.add(Label::new(RichText::new(xpub.as_str()).monospace()).wrap());
+                        ui.add_space(16.);
+                        if ui.medium_button(i18n("Copy")).clicked() {
+                            ui.ctx().copy_text(xpub.to_string());
+                            runtime().notify_clipboard(i18n("Copied to clipboard"));
+                        }
+                    })
+                    .with_footer(|this,ui| {
+                        if ui.large_button(i18n("Continue")).clicked() {
+                            this.state = State::MultisigKeys;
+                        }
+                    })
+                    .render(ui);
+            }
+
             State::AccountName => {
 
                 Panel::new(self)
                     .with_caption(i18n("Account Name"))
                     .with_back(|this| {
-                        if this.context.import_mnemonic{
+                        if this.context.is_multisig() {
+                            this.state = State::MultisigCosigners;
+                        } else if this.context.is_watch_only() {
+                            this.state = State::WatchOnlyKey;
+                        } else if this.context.import_mnemonic{
                             this.state = State::StartImport;
                         }else{
                             this.state = State::Start;
@@ -333,7 +723,12 @@ impl ModuleT for AccountCreate {
                 Panel::new(self)
                     .with_caption(i18n("Wallet Secret"))
                     .with_back(|this| {
-                        this.state = State::AccountName;
+                        if this.context.export_cosigner_xpub {
+                            this.context.export_cosigner_xpub = false;
+                            this.state = State::MultisigKeys;
+                        } else {
+                            this.state = State::AccountName;
+                        }
                     })
                     .with_close_enabled(false, |_|{
                     })
@@ -367,11 +762,11 @@ impl ModuleT for AccountCreate {
                     .render(ui);
 
                 if *submit.borrow() {
-                    if self.context.import_with_bip39_passphrase || self.context.prv_key_data_info.as_ref().map(|info| info.requires_bip39_passphrase()).unwrap_or(false) {
+                    if self.context.requires_bip39_passphrase() {
                         self.state = State::PaymentSecret;
                         self.focus.next(Focus::PaymentSecret);
                     } else {
-                        self.state = State::AddAccount;
+                        self.state = self.context.secrets_complete_state();
                     }
                 }
             }
@@ -400,7 +795,7 @@ impl ModuleT for AccountCreate {
                             },
                         ).submit(|text,focus| {
                             if !text.is_empty() {
-                                this.state = State::AddAccount;
+                                this.state = this.context.secrets_complete_state();
                                 focus.clear()
                             }
                         })
@@ -409,7 +804,7 @@ impl ModuleT for AccountCreate {
                     .with_footer(|this,ui| {
                         let enabled = !this.context.payment_secret.is_empty();
                         if ui.large_button_enabled(enabled,i18n("Continue")).clicked() {
-                            this.state = State::AddAccount;
+                            this.state = this.context.secrets_complete_state();
                         }
                     })
                     .render(ui);
@@ -449,6 +844,34 @@ impl ModuleT for AccountCreate {
                         let account_name = account_name.is_not_empty().then_some(account_name.to_string());
                         let wallet_secret = Secret::from(args.wallet_secret);
 
+                        if args.is_watch_only() {
+                            let xpub_keys = parse_xpubs(args.watch_only_xpub.as_str()).map_err(Error::custom)?;
+                            let account_create_args = AccountCreateArgs::Bip32Watch {
+                                account_args: AccountCreateArgsBip32Watch {
+                                    account_name,
+                                    xpub_keys,
+                                },
+                            };
+                            return Ok(wallet.accounts_create(wallet_secret, account_create_args).await?);
+                        }
+
+                        if args.is_multisig() {
+                            let payment_secret = args.payment_secret.is_not_empty().then(|| Secret::from(args.payment_secret.as_str()));
+                            let prv_key_data_args = args.prv_keys
+                                .iter()
+                                .filter(|info| args.multisig_prv_key_data_ids.contains(info.id()))
+                                .map(|info| PrvKeyDataArgs::new(*info.id(), info.requires_bip39_passphrase().then(|| payment_secret.clone()).flatten()))
+                                .collect::<Vec<_>>();
+                            let additional_xpub_keys = parse_xpubs(args.cosigner_xpubs.as_str()).map_err(Error::custom)?;
+                            let account_create_args = AccountCreateArgs::Multisig {
+                                prv_key_data_args,
+                                additional_xpub_keys,
+                                name: account_name,
+                                minimum_signatures: args.minimum_signatures,
+                            };
+                            return Ok(wallet.accounts_create(wallet_secret, account_create_args).await?);
+                        }
+
                         let payment_secret: Option<Secret>;
                         let entered_mnemonic =
                             sanitize_mnemonic(args.import_private_key_mnemonic.as_str());
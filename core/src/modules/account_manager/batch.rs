@@ -0,0 +1,425 @@
+use crate::imports::*;
+use super::*;
+
+/// Maximum number of recipients placed in a single batch transaction.
+/// Larger batches are split into several transactions that are
+/// estimated and submitted one after another.
+pub const BATCH_OUTPUTS_PER_TRANSACTION: usize = 50;
+
+#[derive(Default, Clone)]
+pub struct BatchRow {
+    pub address : String,
+    pub amount : String,
+    pub label : String,
+}
+
+impl BatchRow {
+    fn validate(&self, network_type : &NetworkType) -> std::result::Result<PaymentOutput, String> {
+        let address = try_user_string_to_address(self.address.trim(), network_type).map_err(|err| err.to_string())?;
+        let amount = match try_kaspa_str_to_sompi(self.amount.trim()) {
+            Ok(Some(amount)) if amount > 0 => amount,
+            Ok(_) => return Err(i18n("Please enter an amount").to_string()),
+            Err(err) => return Err(format!("{} {err}", i18n("Invalid amount:"))),
+        };
+        Ok(PaymentOutput { address, amount })
+    }
+}
+
+/// Recipients of a batch payment entered in the send form or
+/// imported from a CSV file.
+#[derive(Default, Clone)]
+pub struct BatchPayments {
+    pub rows : Vec<BatchRow>,
+    /// Number of leading rows paid by the batch being submitted.
+    pub paid : Arc<AtomicUsize>,
+}
+
+impl BatchPayments {
+    pub fn new() -> Self {
+        Self { rows : vec![BatchRow::default()], ..Default::default() }
+    }
+
+    /// Returns a batch of the rows left unpaid by a partially failed
+    /// submission, so that paid recipients can not be paid twice.
+    pub fn unpaid(&self) -> Self {
+        let paid = self.paid.load(Ordering::SeqCst).min(self.rows.len());
+        Self { rows : self.rows[paid..].to_vec(), ..Default::default() }
+    }
+
+    /// Validates every row, returning the payment outputs or the
+    /// errors of the invalid rows keyed by row index.
+    pub fn validate(&self, network_type : &NetworkType) -> std::result::Result<Vec<PaymentOutput>, Vec<(usize, String)>> {
+        let mut outputs = Vec::with_capacity(self.rows.len());
+        let mut errors = Vec::new();
+        for (index, row) in self.rows.iter().enumerate() {
+            match row.validate(network_type) {
+                Ok(output) => outputs.push(output),
+                Err(err) => errors.push((index, err)),
+            }
+        }
+
+        if errors.is_empty() && outputs.is_empty() {
+            errors.push((0, i18n("Please add at least one recipient").to_string()));
+        }
+
+        if errors.is_empty() { Ok(outputs) } else { Err(errors) }
+    }
+
+    /// Appends rows from a CSV document, replacing the initial empty row.
+    pub fn import_csv(&mut self, text : &str, network_type : &NetworkType) -> Result<usize> {
+        let rows = parse_batch_csv(text, network_type)?;
+        let count = rows.len();
+        self.rows.retain(|row| row.address.trim().is_not_empty() || row.amount.trim().is_not_empty());
+        self.rows.extend(rows);
+        if self.rows.is_empty() {
+            self.rows.push(BatchRow::default());
+        }
+        Ok(count)
+    }
+}
+
+/// Splits the batch into payment destinations of at most
+/// [`BATCH_OUTPUTS_PER_TRANSACTION`] outputs.
+pub fn batch_destinations(outputs : &[PaymentOutput]) -> Vec<PaymentDestination> {
+    outputs
+        .chunks(BATCH_OUTPUTS_PER_TRANSACTION)
+        .map(|chunk| PaymentOutputs { outputs : chunk.to_vec() }.into())
+        .collect()
+}
+
+/// Combines the summaries of the transactions generated for each
+/// chunk of a batch into a single summary for display.
+pub fn merge_generator_summaries(network_id : NetworkId, summaries : Vec<GeneratorSummary>) -> GeneratorSummary {
+    let mut merged = GeneratorSummary::new(network_id);
+    for summary in summaries {
+        merged.aggregated_utxos += summary.aggregated_utxos;
+        merged.aggregate_fees += summary.aggregate_fees;
+        merged.aggregate_mass += summary.aggregate_mass;
+        merged.number_of_generated_transactions += summary.number_of_generated_transactions;
+        merged.number_of_generated_stages += summary.number_of_generated_stages;
+        merged.final_transaction_amount = match (merged.final_transaction_amount, summary.final_transaction_amount) {
+            (Some(total), Some(amount)) => Some(total + amount),
+            (total, amount) => total.or(amount),
+        };
+    }
+    merged
+}
+
+/// Converts the priority fee entered for the whole batch into a feerate
+/// against the combined zero-fee mass of all batch transactions.
+pub async fn batch_fee_rate(account_id : AccountId, destinations : &[PaymentDestination], priority_fee_sompi : u64) -> f64 {
+    let mut base_mass = 0;
+    for destination in destinations {
+        let request = AccountsEstimateRequest {
+            account_id,
+            destination: destination.clone(),
+            priority_fee_sompi: Fees::SenderPays(0),
+            fee_rate: Some(0.0),
+            payload: None,
+        };
+        if let Ok(response) = runtime().wallet().accounts_estimate_call(request).await {
+            base_mass += response.generator_summary.aggregate_mass;
+        }
+    }
+
+    if base_mass == 0 {
+        1.0
+    } else {
+        priority_fee_sompi as f64 / base_mass as f64
+    }
+}
+
+/// Estimates every transaction of the batch. Each chunk is estimated
+/// against the full UTXO set, so the aggregated UTXO count is an upper bound.
+pub async fn estimate_batch(network_id : NetworkId, account_id : AccountId, outputs : Vec<PaymentOutput>, priority_fee_sompi : u64) -> Result<GeneratorSummary> {
+    let destinations = batch_destinations(&outputs);
+    let fee_rate = batch_fee_rate(account_id, &destinations, priority_fee_sompi).await;
+
+    let mut summaries = Vec::with_capacity(destinations.len());
+    for destination in destinations {
+        let request = AccountsEstimateRequest {
+            account_id,
+            destination,
+            priority_fee_sompi: Fees::SenderPays(0),
+            fee_rate: Some(fee_rate),
+            payload: None,
+        };
+        summaries.push(runtime().wallet().accounts_estimate_call(request).await?.generator_summary);
+    }
+
+    Ok(merge_generator_summaries(network_id, summaries))
+}
+
+/// Submits the batch transactions one after another, adding the number
+/// of outputs of each submitted transaction to `paid`. If a transaction
+/// fails, the error reports how many recipients have already been paid.
+pub async fn submit_batch(network_id : NetworkId, account_id : AccountId, outputs : Vec<PaymentOutput>, priority_fee_sompi : u64, wallet_secret : Secret, payment_secret : Option<Secret>, paid : Arc<AtomicUsize>) -> Result<GeneratorSummary> {
+    let destinations = batch_destinations(&outputs);
+    let fee_rate = batch_fee_rate(account_id, &destinations, priority_fee_sompi).await;
+    let total = destinations.len();
+
+    let mut summaries = Vec::with_capacity(total);
+    let counts = outputs.chunks(BATCH_OUTPUTS_PER_TRANSACTION).map(|chunk| chunk.len());
+    for (index, (destination, count)) in destinations.into_iter().zip(counts).enumerate() {
+        let request = AccountsSendRequest {
+            account_id,
+            destination,
+            wallet_secret: wallet_secret.clone(),
+            payment_secret: payment_secret.clone(),
+            fee_rate: Some(fee_rate),
+            priority_fee_sompi: Fees::SenderPays(0),
+            payload: None,
+        };
+
+        match runtime().wallet().accounts_send_call(request).await {
+            Ok(response) => {
+                paid.fetch_add(count, Ordering::SeqCst);
+                summaries.push(response.generator_summary);
+            }
+            Err(err) => {
+                return Err(Error::custom(i18n_args("Batch transaction {index} of {total} failed: {err}. {paid} recipients have been paid and were removed from the batch.", &[
+                    ("index", (index + 1).to_string()),
+                    ("total", total.to_string()),
+                    ("paid", paid.load(Ordering::SeqCst).to_string()),
+                    ("err", err.to_string()),
+                ])));
+            }
+        }
+    }
+
+    Ok(merge_generator_summaries(network_id, summaries))
+}
+
+/// Parses `address,amount[,label]` lines. Empty lines and lines starting
+/// with `#` are ignored, as is a header on the first line whose address
+/// and amount columns do not parse. Addresses may omit the network prefix.
+/// Fields may be enclosed in double quotes.
+pub fn parse_batch_csv(text : &str, network_type : &NetworkType) -> Result<Vec<BatchRow>> {
+    let mut rows = Vec::new();
+    let mut invalid = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields = split_csv_line(line);
+        if !(2..=3).contains(&fields.len()) {
+            return Err(Error::custom(i18n_args("Line {line}: expected address,amount[,label]", &[("line", (index + 1).to_string())])));
+        }
+
+        let address = normalize_address(&fields[0], network_type);
+        let is_address = try_user_string_to_address(&address, network_type).is_ok();
+        if index == 0 && !is_address && try_kaspa_str_to_sompi(&fields[1]).is_err() {
+            // header line
+            continue;
+        }
+
+        if !is_address {
+            invalid.push((index + 1).to_string());
+        }
+
+        rows.push(BatchRow {
+            address,
+            amount : fields[1].clone(),
+            label : fields.get(2).cloned().unwrap_or_default(),
+        });
+    }
+
+    if !invalid.is_empty() {
+        return Err(Error::custom(i18n_args("Invalid address on line {lines}", &[("lines", invalid.join(", "))])));
+    }
+
+    Ok(rows)
+}
+
+/// Prepends the network prefix to an address entered without one.
+fn normalize_address(address : &str, network_type : &NetworkType) -> String {
+    if address.is_empty() || address.contains(':') {
+        address.to_string()
+    } else {
+        format!("{}:{address}", kaspa_addresses::Prefix::from(*network_type))
+    }
+}
+
+fn split_csv_line(line : &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// Editor for the recipients of a batch payment.
+pub struct BatchEditor<'context> {
+    context : &'context mut ManagerContext,
+}
+
+impl<'context> BatchEditor<'context> {
+    pub fn new(context : &'context mut ManagerContext) -> Self {
+        Self { context }
+    }
+
+    /// Renders the recipient table, returning `true` if the rows have changed.
+    pub fn render(&mut self, ui : &mut Ui, rc : &RenderContext) -> bool {
+        use egui_phosphor::light::{PLUS, TRASH};
+
+        let RenderContext { network_type, .. } = rc;
+        let mut changed = false;
+
+        // the table always keeps at least one row to type into
+        if self.context.batch.rows.is_empty() {
+            self.context.batch.rows.push(BatchRow::default());
+        }
+        let mut remove = None;
+
+        let errors = match self.context.batch.validate(network_type) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors,
+        };
+
+        ui.add_space(8.);
+        egui::ScrollArea::vertical()
+            .id_salt("batch_payments")
+            .max_height(240.)
+            .show(ui, |ui| {
+                egui::Grid::new("batch_payments_grid")
+                    .num_columns(4)
+                    .spacing([8.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(i18n("Address")).size(12.).raised());
+                        ui.label(RichText::new(i18n("Amount")).size(12.).raised());
+                        ui.label(RichText::new(i18n("Label")).size(12.).raised());
+                        ui.label("");
+                        ui.end_row();
+
+                        let row_count = self.context.batch.rows.len();
+                        for (index, row) in self.context.batch.rows.iter_mut().enumerate() {
+                            let has_error = errors.iter().any(|(row_index, _)| *row_index == index);
+                            let address = TextEdit::singleline(&mut row.address).desired_width(260.).hint_text("kaspa:...");
+                            let address = if has_error { address.text_color(theme_color().error_color) } else { address };
+                            changed |= ui.add(address).changed();
+                            changed |= ui.add(TextEdit::singleline(&mut row.amount).desired_width(80.)).changed();
+                            changed |= ui.add(TextEdit::singleline(&mut row.label).desired_width(100.)).changed();
+                            if ui.add_enabled(row_count > 1, Button::new(TRASH)).clicked() {
+                                remove = Some(index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if let Some(index) = remove {
+            self.context.batch.rows.remove(index);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button(format!("{PLUS} {}", i18n("Add recipient"))).clicked() {
+                self.context.batch.rows.push(BatchRow::default());
+                changed = true;
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui.button(format!("{} {}", egui_phosphor::light::FILE_CSV, i18n("Import CSV"))).clicked() {
+                if let Some(path) = rfd::FileDialog::new().add_filter("CSV", &["csv", "txt"]).pick_file() {
+                    let result = std::fs::read_to_string(&path)
+                        .map_err(Error::from)
+                        .and_then(|text| self.context.batch.import_csv(&text, network_type));
+                    match result {
+                        Ok(count) => {
+                            runtime().notify(UserNotification::success(i18n_args("Imported {count} recipients", &[("count", count.to_string())])));
+                            changed = true;
+                        }
+                        Err(err) => runtime().error(err.to_string()),
+                    }
+                }
+            }
+        });
+
+        if let Some((index, error)) = errors.first() {
+            let rows = &self.context.batch.rows;
+            if rows.len() > 1 || rows.first().is_some_and(|row| row.address.is_not_empty()) {
+                ui.label(RichText::new(i18n_args("Row {row}: {error}", &[("row", (index + 1).to_string()), ("error", error.clone())])).color(theme_color().error_color));
+            }
+        }
+
+        let total = self.context.batch_outputs.iter().map(|output| output.amount).sum::<u64>();
+        ui.label(i18n_args("{count} recipients, total {amount}", &[
+            ("count", self.context.batch.rows.len().to_string()),
+            ("amount", sompi_to_kaspa_string_with_suffix(total, network_type)),
+        ]));
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rows_with_header_and_quotes() {
+        let csv = "address,amount,label\n\
+            kaspa:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzthw5j,1.5,\"Alice, payroll\"\n\
+            \n\
+            # comment\n\
+            qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzthw5j, 20 \n";
+        let rows = parse_batch_csv(csv, &NetworkType::Mainnet).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].amount, "1.5");
+        assert_eq!(rows[0].label, "Alice, payroll");
+        assert_eq!(rows[1].address, "kaspa:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzthw5j");
+        assert_eq!(rows[1].amount, "20");
+        assert_eq!(rows[1].label, "");
+
+        // a first row with an invalid amount is data, not a header
+        let rows = parse_batch_csv("kaspa:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzthw5j,abc", &NetworkType::Mainnet).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert!(parse_batch_csv("address,amount\n", &NetworkType::Mainnet).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_rows_with_invalid_addresses() {
+        // a mistyped first recipient is not mistaken for a header
+        assert!(parse_batch_csv("kaspa:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzth,1", &NetworkType::Mainnet).is_err());
+        // nor is a bad row following the header
+        let csv = "address,amount\nkaspa:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzth,1\n";
+        assert!(parse_batch_csv(csv, &NetworkType::Mainnet).is_err());
+        // a header is only recognized on the first line
+        let csv = "kaspa:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzthw5j,1\naddress,amount\n";
+        assert!(parse_batch_csv(csv, &NetworkType::Mainnet).is_err());
+    }
+
+    #[test]
+    fn unpaid_rows_exclude_paid_recipients() {
+        let mut batch = BatchPayments::new();
+        batch.import_csv("address,amount\n", &NetworkType::Mainnet).unwrap();
+        assert_eq!(batch.rows.len(), 1);
+
+        batch.rows = (0..3).map(|n| BatchRow { amount : n.to_string(), ..Default::default() }).collect();
+        batch.paid.store(2, Ordering::SeqCst);
+        let unpaid = batch.unpaid();
+        assert_eq!(unpaid.rows.len(), 1);
+        assert_eq!(unpaid.rows[0].amount, "2");
+        assert_eq!(unpaid.paid.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn rejects_rows_with_missing_columns() {
+        assert!(parse_batch_csv("kaspa:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzthw5j", &NetworkType::Mainnet).is_err());
+        assert_eq!(split_csv_line("a,\"b \"\"c\"\"\",d"), vec!["a", "b \"c\"", "d"]);
+    }
+}
@@ -10,9 +10,60 @@ impl<'context> Destination<'context> {
         Self { context }
     }
 
-    pub fn render(&mut self, _core: &mut Core, ui : &mut Ui, rc : &RenderContext) {
+    fn update_address_status(&mut self, network_type : &NetworkType) {
+        match Address::try_from(self.context.destination_address_string.as_str()) {
+            Ok(address) => {
+                let address_network_type = NetworkType::try_from(address.prefix).expect("prefix to network type");
+                if address_network_type != *network_type {
+                    self.context.address_status = AddressStatus::NetworkMismatch(address_network_type);
+                } else {
+                    self.context.address_status = AddressStatus::Valid;
+                }
+            }
+            Err(err) => {
+                self.context.address_status = AddressStatus::Invalid(err.to_string());
+            }
+        }
+    }
+
+    /// Shows the contact name of a known address, suggests address book
+    /// contacts matching the entered text and offers to save new addresses.
+    fn render_contacts(&mut self, core : &mut Core, ui : &mut Ui, network_type : &NetworkType) {
+        let network = core.settings.node.network;
+        let text = self.context.destination_address_string.trim().to_string();
+        let mut selected = None;
+        let mut save = false;
+
+        if let Some(address_book) = core.address_book().as_ref() {
+            if let Some(contact) = address_book.find(network, text.as_str()) {
+                ui.label(i18n_args("Contact: {name}", &[("name", contact.name.as_str())]));
+            } else if self.context.address_status == AddressStatus::Valid {
+                save = ui.add(Label::new(RichText::new(i18n("Save to address book")).small()).sense(Sense::click())).clicked();
+            } else if text.is_not_empty() {
+                for contact in address_book.search(network, text.as_str()).into_iter().take(5) {
+                    let suggestion = format!("{} - {}", contact.name, format_address_string(contact.address.as_str(), Some(8)));
+                    if ui.add(Label::new(suggestion).sense(Sense::click())).clicked() {
+                        selected = Some(contact.address.clone());
+                    }
+                }
+            }
+        }
+
+        if let Some(address) = selected {
+            self.context.destination_address_string = address;
+            self.update_address_status(network_type);
+            self.context.focus.next(Focus::Amount);
+        } else if save {
+            core.get_mut::<modules::Contacts>().add(Some(text));
+            core.select::<modules::Contacts>();
+        }
+    }
+
+    pub fn render(&mut self, core: &mut Core, ui : &mut Ui, rc : &RenderContext) {
         let RenderContext { network_type, .. } = rc;
 
+        let mut address_change = None;
+
         TextEditor::new(
             &mut self.context.destination_address_string,
             // None,
@@ -20,31 +71,42 @@ impl<'context> Destination<'context> {
             Focus::Address,
             |ui, text| {
                 ui.add_space(8.);
-                ui.label(RichText::new(i18n("Enter destination address")).size(12.).raised());
+                ui.label(RichText::new(i18n("Enter destination address or payment URI")).size(12.).raised());
                 ui.add_sized(Overview::editor_size(ui), TextEdit::singleline(text)
                     .vertical_align(Align::Center))
             },
         )
         .change(|address| {
-            match Address::try_from(address) {
-                Ok(address) => {
-                    let address_network_type = NetworkType::try_from(address.prefix).expect("prefix to network type");
-                    if address_network_type != *network_type {
-                        self.context.address_status = AddressStatus::NetworkMismatch(address_network_type);
-                    } else {
-                        self.context.address_status = AddressStatus::Valid;
-                    }
-                }
-                Err(err) => {
-                    self.context.address_status = AddressStatus::Invalid(err.to_string());
-                }
-            }
+            address_change = Some(address.to_string());
         })
         .submit(|_, focus|{
             // *focus = Some(Focus::Amount);
             focus.next(Focus::Amount);
         })
         .build(ui);
+
+        match address_change {
+            Some(text) if PaymentUri::has_query(text.as_str()) => {
+                match text.parse::<PaymentUri>() {
+                    Ok(payment_uri) => {
+                        self.context.apply_payment_uri(&payment_uri);
+                        self.update_address_status(network_type);
+                    }
+                    Err(err) => {
+                        self.context.address_status = AddressStatus::Invalid(err.to_string());
+                    }
+                }
+            }
+            Some(_) => {
+                self.update_address_status(network_type);
+            }
+            None => {
+                // the form can be pre-filled from a payment URI opened externally
+                if self.context.address_status == AddressStatus::None && self.context.destination_address_string.is_not_empty() {
+                    self.update_address_status(network_type);
+                }
+            }
+        }
         
         match &self.context.address_status {
             AddressStatus::Valid => {},
@@ -57,6 +119,32 @@ impl<'context> Destination<'context> {
             }
         }
 
+        self.render_contacts(core, ui, network_type);
+
+        // multisig sends end in a partially signed transaction that is
+        // broadcast elsewhere, so there is no transaction to attach a note to
+        if rc.account.is_multisig() {
+            return;
+        }
+
+        TextEditor::new(
+            &mut self.context.payment_note,
+            &mut self.context.focus,
+            Focus::Note,
+            |ui, text| {
+                ui.add_space(8.);
+                ui.label(RichText::new(i18n("Note (optional)")).size(12.).raised());
+                ui.add_sized(Overview::editor_size(ui), TextEdit::singleline(text)
+                    .hint_text(i18n("Stored in this wallet, not sent to the recipient"))
+                    .char_limit(TRANSACTION_NOTE_MAX_LENGTH)
+                    .vertical_align(Align::Center))
+            },
+        )
+        .submit(|_, focus|{
+            focus.next(Focus::Amount);
+        })
+        .build(ui);
+
 
     }
 }
\ No newline at end of file
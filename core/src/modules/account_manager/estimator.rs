@@ -24,18 +24,55 @@ impl<'context> Estimator<'context> {
         let mut request_send = false;
         let mut request_estimate = self.context.request_estimate.take().unwrap_or_default();
 
-        match self.context.transaction_kind.as_ref().unwrap() {
+        let transaction_kind = *self.context.transaction_kind.as_ref().unwrap();
+
+        if let Some(selection) = self.context.utxo_selection.as_ref() {
+            selection.render(ui, network_type);
+        }
+
+        match transaction_kind {
             TransactionKind::Send => {
                 Destination::new(self.context).render(core, ui, rc);
             }
             TransactionKind::Transfer => {
                 Transfer::new(self.context).render(core, ui, rc);
             }
+            TransactionKind::Consolidate => {
+                ui.label(i18n("Selected UTXOs will be consolidated into a single output sent to the account change address."));
+            }
+            TransactionKind::Batch => {
+                if BatchEditor::new(self.context).render(ui, rc) {
+                    request_estimate = true;
+                }
+            }
+            TransactionKind::Split => {
+                TextEditor::new(
+                    &mut self.context.split_outputs_text,
+                    &mut self.context.focus,
+                    Focus::Outputs,
+                    |ui, text| {
+                        ui.add_space(8.);
+                        ui.label(RichText::new(i18n("Enter the number of outputs")).size(12.).raised());
+                        ui.add_sized(Overview::editor_size(ui), TextEdit::singleline(text)
+                            .vertical_align(Align::Center))
+                    },
+                )
+                .change(|_| {
+                    request_estimate = true;
+                })
+                .submit(|_, focus| {
+                    focus.next(Focus::Amount);
+                })
+                .build(ui);
+            }
         }
 
         let (ready_to_send, actual_estimate, error) = match &*self.context.estimate.lock().unwrap() {
             EstimatorStatus::GeneratorSummary(actual_estimate) => {                
-                let ready_to_send = self.context.address_status == AddressStatus::Valid || (self.context.transaction_kind == Some(TransactionKind::Transfer) && self.context.transfer_to_account.is_some());
+                let ready_to_send = match transaction_kind {
+                    TransactionKind::Consolidate | TransactionKind::Split | TransactionKind::Batch => true,
+                    _ => self.context.address_status == AddressStatus::Valid || (self.context.transaction_kind == Some(TransactionKind::Transfer) && self.context.transfer_to_account.is_some()),
+                };
                 (ready_to_send, actual_estimate.clone(), None)
             }
             EstimatorStatus::Error(error) => {
@@ -48,25 +85,40 @@ impl<'context> Estimator<'context> {
             }
         };
 
+        // the note is stored once the transaction has been broadcast,
+        // so an invalid note has to be caught before sending
+        let note_error = TransactionNotesData::validate(self.context.payment_note.as_str()).err()
+            .map(|err| RichText::new(err.to_string()).color(theme_color().error_color));
+        let ready_to_send = ready_to_send && note_error.is_none();
+        let error = note_error.or(error);
 
-        let response = TextEditor::new(
-            &mut self.context.send_amount_text,
-            &mut self.context.focus,
-            Focus::Amount,
-            |ui, text| {
-                ui.add_space(8.);
-                ui.label(RichText::new(i18n_args("Enter {suffix} amount to send", &[("suffix", kaspa_suffix(&network_type))])).size(12.).raised());
-                ui.add_sized(Overview::editor_size(ui), TextEdit::singleline(text)
-                    .vertical_align(Align::Center))
-            },
-        )
-        .change(|_| {
-            request_estimate = true;
-        })
-        .build(ui);
 
-        if response.text_edit_submit(ui) {
-            self.context.focus.next(Focus::Fees);
+        if !matches!(transaction_kind, TransactionKind::Consolidate | TransactionKind::Batch) {
+            let amount_caption = if transaction_kind == TransactionKind::Split {
+                i18n_args("Enter {suffix} amount per output", &[("suffix", kaspa_suffix(&network_type))])
+            } else {
+                i18n_args("Enter {suffix} amount to send", &[("suffix", kaspa_suffix(&network_type))])
+            };
+
+            let response = TextEditor::new(
+                &mut self.context.send_amount_text,
+                &mut self.context.focus,
+                Focus::Amount,
+                |ui, text| {
+                    ui.add_space(8.);
+                    ui.label(RichText::new(amount_caption).size(12.).raised());
+                    ui.add_sized(Overview::editor_size(ui), TextEdit::singleline(text)
+                        .vertical_align(Align::Center))
+                },
+            )
+            .change(|_| {
+                request_estimate = true;
+            })
+            .build(ui);
+
+            if response.text_edit_submit(ui) {
+                self.context.focus.next(Focus::Fees);
+            }
         }
 
         // ui.add_space(8.);
@@ -134,7 +186,7 @@ impl<'context> Estimator<'context> {
         ui.add_space(8.);
         ui.heading(i18n("Priority Fee Estimator"));
 
-        let is_send_amount_zero = self.context.send_amount_sompi == 0;
+        let is_send_amount_zero = self.context.send_amount_sompi == 0 && transaction_kind != TransactionKind::Consolidate;
 
         let mut fee_selection = SelectionPanels::new(
             120.0,
@@ -203,7 +255,7 @@ impl<'context> Estimator<'context> {
         if let Some(error) = error {
             ui.label(error);
             ui.add_space(16.);
-        } else if !network_below_capacity && self.context.priority_fees_sompi == 0 && self.context.send_amount_sompi != 0 {
+        } else if !network_below_capacity && self.context.priority_fees_sompi == 0 && !is_send_amount_zero {
             ui.add_space(16.);
             ui.label(RichText::new(i18n("The network is currently congested.")));
             ui.label(RichText::new(i18n("Sending funds without priority fees will result in long transaction wait times.")));
@@ -242,7 +294,7 @@ impl<'context> Estimator<'context> {
 
         ui.add_space(16.);
 
-        self.update_user_args() 
+        self.update_user_args(&network_type) 
             && request_estimate 
             && matches!(self.context.action,Action::Estimating)
 
@@ -250,20 +302,49 @@ impl<'context> Estimator<'context> {
 
 
 
-    fn update_user_args(&mut self) -> bool {
+    fn update_user_args(&mut self, network_type : &NetworkType) -> bool {
         let mut valid = true;
 
-        match try_kaspa_str_to_sompi(self.context.send_amount_text.as_str()) {
-            Ok(Some(sompi)) => {
-                self.context.send_amount_sompi = sompi;
+        if self.context.transaction_kind == Some(TransactionKind::Split) {
+            match self.context.split_outputs_text.trim().parse::<usize>() {
+                Ok(outputs) if (2..=MAX_SPLIT_OUTPUTS).contains(&outputs) => {
+                    self.context.split_outputs = outputs;
+                }
+                _ => {
+                    self.user_error(i18n_args("Please enter between 2 and {max} outputs", &[("max", MAX_SPLIT_OUTPUTS.to_string())]));
+                    valid = false;
+                }
             }
-            Ok(None) => {
-                self.user_error(i18n("Please enter an amount").to_string());
-                valid = false;
+        }
+
+        if self.context.transaction_kind == Some(TransactionKind::Consolidate) {
+            self.context.send_amount_sompi = 0;
+        } else if self.context.transaction_kind == Some(TransactionKind::Batch) {
+            match self.context.batch.validate(network_type) {
+                Ok(outputs) => {
+                    self.context.send_amount_sompi = outputs.iter().map(|output| output.amount).sum();
+                    self.context.batch_outputs = outputs;
+                }
+                Err(errors) => {
+                    let (index, error) = errors.into_iter().next().unwrap_or_default();
+                    self.user_error(i18n_args("Row {row}: {error}", &[("row", (index + 1).to_string()), ("error", error)]));
+                    self.context.batch_outputs.clear();
+                    valid = false;
+                }
             }
-            Err(err) => {
-                self.user_error(format!("{} {err}", i18n("Invalid amount:")));
-                valid = false;
+        } else {
+            match try_kaspa_str_to_sompi(self.context.send_amount_text.as_str()) {
+                Ok(Some(sompi)) => {
+                    self.context.send_amount_sompi = sompi;
+                }
+                Ok(None) => {
+                    self.user_error(i18n("Please enter an amount").to_string());
+                    valid = false;
+                }
+                Err(err) => {
+                    self.user_error(format!("{} {err}", i18n("Invalid amount:")));
+                    valid = false;
+                }
             }
         }
 
@@ -0,0 +1,465 @@
+use crate::imports::*;
+use crate::market::PriceHistory;
+use kaspa_txscript::standard::extract_script_pub_key_address;
+use kaspa_wallet_core::api::TransactionsDataGetResponse;
+use kaspa_wallet_core::storage::{TransactionKind, transaction::TransactionData};
+use std::borrow::Cow;
+
+/// Number of transaction records requested from the wallet at a time
+/// when loading the complete history of an account.
+const HISTORY_CHUNK_SIZE : u64 = 500;
+
+const MSEC_PER_DAY : u64 = 24 * 60 * 60 * 1000;
+
+pub const TRANSACTION_KINDS : [TransactionKind; 9] = [
+    TransactionKind::Incoming,
+    TransactionKind::Outgoing,
+    TransactionKind::External,
+    TransactionKind::Batch,
+    TransactionKind::TransferIncoming,
+    TransactionKind::TransferOutgoing,
+    TransactionKind::Change,
+    TransactionKind::Reorg,
+    TransactionKind::Stasis,
+];
+
+pub fn transaction_kind_name(kind : &TransactionKind) -> &'static str {
+    match kind {
+        TransactionKind::Incoming => "incoming",
+        TransactionKind::Outgoing => "outgoing",
+        TransactionKind::External => "external",
+        TransactionKind::Reorg => "reorg",
+        TransactionKind::Batch => "batch",
+        TransactionKind::Stasis => "stasis",
+        TransactionKind::TransferIncoming => "transfer-incoming",
+        TransactionKind::TransferOutgoing => "transfer-outgoing",
+        TransactionKind::Change => "change",
+    }
+}
+
+/// Transaction history filter as entered by the user. Dates are
+/// `YYYY-MM-DD` in UTC and both ends of the date range are inclusive.
+#[derive(Default, Clone, PartialEq)]
+pub struct HistoryFilter {
+    pub from_date : String,
+    pub to_date : String,
+    pub kind : Option<TransactionKind>,
+    pub min_amount : String,
+    pub max_amount : String,
+    pub address : String,
+}
+
+impl HistoryFilter {
+    pub fn is_empty(&self) -> bool {
+        self.from_date.trim().is_empty()
+            && self.to_date.trim().is_empty()
+            && self.kind.is_none()
+            && self.min_amount.trim().is_empty()
+            && self.max_amount.trim().is_empty()
+            && self.address.trim().is_empty()
+    }
+
+    pub fn query(&self) -> Result<HistoryQuery> {
+        let from_msec = parse_date(&self.from_date)?.map(|date| date_to_msec(&date));
+        let to_msec = parse_date(&self.to_date)?
+            .map(|date| date_to_msec(&date) + MSEC_PER_DAY);
+        if matches!((from_msec, to_msec), (Some(from), Some(to)) if from >= to) {
+            return Err(Error::custom(i18n("The start date must not be after the end date")));
+        }
+
+        let min_sompi = try_kaspa_str_to_sompi(self.min_amount.trim())?;
+        let max_sompi = try_kaspa_str_to_sompi(self.max_amount.trim())?;
+        if matches!((min_sompi, max_sompi), (Some(min), Some(max)) if min > max) {
+            return Err(Error::custom(i18n("The minimum amount must not exceed the maximum amount")));
+        }
+
+        let address = self.address.trim().to_lowercase();
+
+        Ok(HistoryQuery {
+            from_msec,
+            to_msec,
+            kind : self.kind.as_ref().map(transaction_kind_name),
+            min_sompi,
+            max_sompi,
+            address : address.is_not_empty().then_some(address),
+        })
+    }
+}
+
+fn parse_date(text : &str) -> Result<Option<chrono::NaiveDate>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| Error::custom(i18n_args("Invalid date '{date}', expected YYYY-MM-DD", &[("date", text.to_string())])))
+}
+
+fn date_to_msec(date : &chrono::NaiveDate) -> u64 {
+    date.and_time(chrono::NaiveTime::MIN).and_utc().timestamp_millis().max(0) as u64
+}
+
+/// Parsed [`HistoryFilter`]. Transactions without a timestamp never
+/// match a date range.
+#[derive(Default, Clone, Debug)]
+pub struct HistoryQuery {
+    from_msec : Option<u64>,
+    to_msec : Option<u64>,
+    kind : Option<&'static str>,
+    min_sompi : Option<u64>,
+    max_sompi : Option<u64>,
+    address : Option<String>,
+}
+
+impl HistoryQuery {
+    pub fn matches(&self, entry : &HistoryEntry) -> bool {
+        if self.from_msec.is_some() || self.to_msec.is_some() {
+            let Some(unixtime_msec) = entry.unixtime_msec else {
+                return false;
+            };
+            if self.from_msec.is_some_and(|from| unixtime_msec < from)
+                || self.to_msec.is_some_and(|to| unixtime_msec >= to) {
+                return false;
+            }
+        }
+
+        self.kind.is_none_or(|kind| entry.kind == kind)
+            && self.min_sompi.is_none_or(|min| entry.amount_sompi >= min)
+            && self.max_sompi.is_none_or(|max| entry.amount_sompi <= max)
+            && self.address.as_ref().is_none_or(|address| {
+                entry.addresses.iter().any(|entry_address| entry_address.to_lowercase().contains(address))
+            })
+    }
+}
+
+/// Value of a transaction in fiat currency at the time of the transaction.
+#[derive(Clone, Debug, Serialize)]
+pub struct FiatValue {
+    pub currency : String,
+    pub price : f64,
+    pub amount : f64,
+    pub fee : f64,
+}
+
+/// Flattened transaction record used for filtering and export.
+///
+/// The amount is the value received for incoming transactions and the
+/// value paid to the recipient (excluding fees) for outgoing ones.
+#[derive(Clone, Debug, Serialize)]
+pub struct HistoryEntry {
+    pub transaction_id : String,
+    pub kind : &'static str,
+    pub timestamp : Option<String>,
+    pub unixtime_msec : Option<u64>,
+    pub block_daa_score : u64,
+    pub amount_sompi : u64,
+    pub fee_sompi : u64,
+    pub addresses : Vec<String>,
+    pub note : Option<String>,
+    pub fiat : Option<FiatValue>,
+}
+
+impl HistoryEntry {
+    pub fn new(record : &TransactionRecord, network : Network) -> Self {
+        let address_prefix : kaspa_addresses::Prefix = network.into();
+
+        let (amount_sompi, fee_sompi, mut addresses) : (u64, u64, Vec<String>) = match record.transaction_data() {
+            TransactionData::Reorg { utxo_entries, .. }
+            | TransactionData::Stasis { utxo_entries, .. }
+            | TransactionData::Incoming { utxo_entries, .. }
+            | TransactionData::TransferIncoming { utxo_entries, .. }
+            | TransactionData::External { utxo_entries, .. } => {
+                let addresses = utxo_entries
+                    .iter()
+                    .filter_map(|utxo_entry| utxo_entry.address.as_ref().map(|address| address.to_string()))
+                    .collect();
+                (record.value(), 0, addresses)
+            }
+            TransactionData::Outgoing { fees, transaction, payment_value, .. }
+            | TransactionData::TransferOutgoing { fees, transaction, payment_value, .. } => {
+                let addresses = transaction
+                    .outputs
+                    .iter()
+                    .filter_map(|output| extract_script_pub_key_address(&output.script_public_key, address_prefix).ok())
+                    .map(|address| address.to_string())
+                    .collect();
+                (payment_value.unwrap_or_default(), *fees, addresses)
+            }
+            TransactionData::Batch { fees, .. } => (0, *fees, vec![]),
+            TransactionData::Change { .. } => (record.value(), 0, vec![]),
+        };
+
+        let timestamp = record.unixtime_msec.and_then(|unixtime_msec| {
+            chrono::DateTime::<chrono::Utc>::from_timestamp_millis(unixtime_msec as i64)
+                .map(|datetime| datetime.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        });
+
+        addresses.dedup();
+
+        Self {
+            transaction_id : record.id().to_string(),
+            kind : transaction_kind_name(&record.kind()),
+            timestamp,
+            unixtime_msec : record.unixtime_msec,
+            block_daa_score : record.block_daa_score(),
+            amount_sompi,
+            fee_sompi,
+            addresses,
+            note : None,
+            fiat : None,
+        }
+    }
+
+    pub fn apply_price(&mut self, prices : &PriceHistory) {
+        self.fiat = self.unixtime_msec
+            .and_then(|unixtime_msec| prices.price_at(unixtime_msec))
+            .map(|price| FiatValue {
+                currency : prices.currency.clone(),
+                price,
+                amount : sompi_to_kaspa(self.amount_sompi) * price,
+                fee : sompi_to_kaspa(self.fee_sompi) * price,
+            });
+    }
+}
+
+/// Complete transaction history of an account, loaded for filtering.
+pub struct TransactionHistory {
+    pub account_id : AccountId,
+    /// Transaction count of the account at the time the history was
+    /// requested, used to detect that the history needs to be reloaded.
+    pub transaction_count : u64,
+    records : Vec<Arc<TransactionRecord>>,
+    entries : Vec<HistoryEntry>,
+}
+
+impl TransactionHistory {
+    pub fn new(account_id : AccountId, transaction_count : u64, records : Vec<Arc<TransactionRecord>>, network : Network) -> Self {
+        let entries = records.iter().map(|record| HistoryEntry::new(record, network)).collect();
+        Self { account_id, transaction_count, records, entries }
+    }
+
+    /// Indexes of the records matching the query.
+    pub fn filter(&self, query : &HistoryQuery) -> Vec<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| query.matches(entry).then_some(index))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn record(&self, index : usize) -> Option<&Arc<TransactionRecord>> {
+        self.records.get(index)
+    }
+}
+
+/// Loads all transaction records of the account, newest first.
+pub async fn load_transaction_history(account_id : AccountId, network_id : NetworkId) -> Result<Vec<Arc<TransactionRecord>>> {
+    let wallet = runtime().wallet();
+    let mut records = Vec::new();
+    loop {
+        let start = records.len() as u64;
+        let TransactionsDataGetResponse { transactions, total, .. } = wallet
+            .transactions_data_get_range(account_id, network_id, start..start + HISTORY_CHUNK_SIZE)
+            .await?;
+        let done = transactions.is_empty() || start + transactions.len() as u64 >= total;
+        records.extend(transactions);
+        if done {
+            break;
+        }
+    }
+    records.sort_by_key(|record| std::cmp::Reverse(record.block_daa_score()));
+    Ok(records)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub fn serialize(&self, entries : &[HistoryEntry]) -> Result<String> {
+        match self {
+            ExportFormat::Csv => Ok(history_to_csv(entries)),
+            ExportFormat::Json => Ok(serde_json::to_string_pretty(entries)?),
+        }
+    }
+}
+
+/// Outcome of a transaction history export.
+pub struct HistoryExport {
+    pub transactions : usize,
+    /// Fiat currency the transactions were to be valued in.
+    pub currency : Option<String>,
+    /// Dated transactions left without a fiat value because no price
+    /// was available for their time.
+    pub missing_fiat : usize,
+}
+
+/// Loads the history of the account, values it in `currency` (if any),
+/// attaches the transaction notes and writes the entries matching the
+/// query to a file.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn export_transaction_history(
+    path : std::path::PathBuf,
+    format : ExportFormat,
+    account_id : AccountId,
+    network : Network,
+    query : HistoryQuery,
+    currency : Option<String>,
+    notes : Option<TransactionNotes>,
+) -> Result<HistoryExport> {
+    let records = load_transaction_history(account_id, network.into()).await?;
+    let mut entries = records
+        .iter()
+        .map(|record| {
+            let mut entry = HistoryEntry::new(record, network);
+            entry.note = notes.as_ref().and_then(|notes| notes.get(record.id())).map(String::from);
+            entry
+        })
+        .filter(|entry| query.matches(entry))
+        .collect::<Vec<_>>();
+
+    if let Some(currency) = currency.as_ref() {
+        let timestamps = entries.iter().filter_map(|entry| entry.unixtime_msec);
+        if let (Some(from), Some(to)) = (timestamps.clone().min(), timestamps.max()) {
+            let prices = runtime()
+                .market_monitor_service()
+                .fetch_price_history(currency, from.saturating_sub(MSEC_PER_DAY), to)
+                .await;
+            match prices {
+                Ok(prices) => entries.iter_mut().for_each(|entry| entry.apply_price(&prices)),
+                // the export remains useful without fiat values
+                Err(err) => log_warn!("Unable to fetch {currency} price history: {err}"),
+            }
+        }
+    }
+
+    std::fs::write(&path, format.serialize(&entries)?)?;
+
+    let missing_fiat = if currency.is_some() {
+        entries.iter().filter(|entry| entry.unixtime_msec.is_some() && entry.fiat.is_none()).count()
+    } else {
+        0
+    };
+    Ok(HistoryExport { transactions : entries.len(), currency, missing_fiat })
+}
+
+/// Serializes history entries as CSV with a header line. Amounts are
+/// exact decimal KAS values; addresses are separated by spaces.
+pub fn history_to_csv(entries : &[HistoryEntry]) -> String {
+    let mut csv = String::from("transaction_id,kind,timestamp,block_daa_score,amount,fee,addresses,note,fiat_currency,fiat_price,fiat_amount,fiat_fee\n");
+    for entry in entries {
+        let (fiat_currency, fiat_price, fiat_amount, fiat_fee) = match entry.fiat.as_ref() {
+            Some(fiat) => (fiat.currency.clone(), fiat.price.to_string(), fiat.amount.to_string(), fiat.fee.to_string()),
+            None => Default::default(),
+        };
+        let fields = [
+            entry.transaction_id.clone(),
+            entry.kind.to_string(),
+            entry.timestamp.clone().unwrap_or_default(),
+            entry.block_daa_score.to_string(),
+            format_kaspa_amount(entry.amount_sompi),
+            format_kaspa_amount(entry.fee_sompi),
+            entry.addresses.join(" "),
+            entry.note.clone().unwrap_or_default(),
+            fiat_currency,
+            fiat_price,
+            fiat_amount,
+            fiat_fee,
+        ];
+        csv.push_str(&fields.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_field(text : &str) -> Cow<'_, str> {
+    if text.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind : &'static str, unixtime_msec : Option<u64>, amount_sompi : u64) -> HistoryEntry {
+        HistoryEntry {
+            transaction_id : "00".repeat(32),
+            kind,
+            timestamp : None,
+            unixtime_msec,
+            block_daa_score : 1,
+            amount_sompi,
+            fee_sompi : 2_000,
+            addresses : vec!["kaspa:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzthw5j".to_string()],
+            note : None,
+            fiat : None,
+        }
+    }
+
+    #[test]
+    fn filters_by_date_kind_amount_and_address() {
+        // 2024-03-01T12:00:00Z
+        let noon = 1_709_294_400_000;
+        let incoming = entry("incoming", Some(noon), 5 * SOMPI_PER_KASPA);
+
+        let filter = HistoryFilter { from_date : "2024-03-01".into(), to_date : "2024-03-01".into(), ..Default::default() };
+        assert!(filter.query().unwrap().matches(&incoming));
+        assert!(!filter.query().unwrap().matches(&entry("incoming", None, 1)));
+        let filter = HistoryFilter { from_date : "2024-03-02".into(), ..Default::default() };
+        assert!(!filter.query().unwrap().matches(&incoming));
+
+        let filter = HistoryFilter { kind : Some(TransactionKind::Outgoing), ..Default::default() };
+        assert!(!filter.query().unwrap().matches(&incoming));
+
+        let filter = HistoryFilter { min_amount : "1".into(), max_amount : "5".into(), address : "QPAUQ".into(), ..Default::default() };
+        assert!(filter.query().unwrap().matches(&incoming));
+        let filter = HistoryFilter { min_amount : "5.1".into(), ..Default::default() };
+        assert!(!filter.query().unwrap().matches(&incoming));
+
+        assert!(HistoryFilter { from_date : "01/03/2024".into(), ..Default::default() }.query().is_err());
+        assert!(HistoryFilter { min_amount : "2".into(), max_amount : "1".into(), ..Default::default() }.query().is_err());
+        assert!(HistoryFilter::default().is_empty());
+    }
+
+    #[test]
+    fn exports_csv_with_fiat_value() {
+        let noon = 1_709_294_400_000;
+        let prices = PriceHistory::new("USD", vec![(noon - 1_000, 0.25), (noon - MSEC_PER_DAY, 0.1)]);
+        assert_eq!(prices.price_at(noon), Some(0.25));
+        assert_eq!(prices.price_at(noon - MSEC_PER_DAY - 1), None);
+        assert_eq!(prices.price_at(noon + 2 * MSEC_PER_DAY), None);
+
+        let mut priced = entry("outgoing", Some(noon), 2 * SOMPI_PER_KASPA);
+        priced.addresses.push("label, \"quoted\"".to_string());
+        priced.note = Some("invoice #123".to_string());
+        priced.apply_price(&prices);
+        let csv = history_to_csv(&[priced, entry("change", None, 1)]);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].contains(",outgoing,,1,2,0.00002,\"kaspa:"));
+        assert!(lines[1].contains(" label, \"\"quoted\"\"\",invoice #123,usd,0.25,0.5,"));
+        assert!(lines[2].ends_with(",change,,1,0.00000001,0.00002,kaspa:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzthw5j,,,,,"));
+    }
+}
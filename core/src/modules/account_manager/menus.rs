@@ -251,7 +251,7 @@ impl ToolsMenu {
     pub fn new() -> Self {
         Self { }
     }
-    pub fn render(&mut self, core: &mut Core, ui : &mut Ui, _account_manager : &mut AccountManager, _rc : &RenderContext, max_height: f32) {
+    pub fn render(&mut self, core: &mut Core, ui : &mut Ui, _account_manager : &mut AccountManager, rc : &RenderContext, max_height: f32) {
 
         PopupPanel::new(PopupPanel::id(ui,"tools_popup"),|ui|{ ui.add(Label::new(format!("{} ⏷", i18n("Tools"))).sense(Sense::click())) }, |ui, _| {
 
@@ -268,6 +268,17 @@ impl ToolsMenu {
                     if ui.large_button(i18n("Address derivation scan")).clicked() {
                         core.select::<modules::Scanner>();
                     }
+                    if ui.large_button(i18n("Address Book")).clicked() {
+                        core.select::<modules::Contacts>();
+                    }
+                    if rc.account.is_multisig() && ui.large_button(i18n("Co-sign Transaction")).clicked() {
+                        core.get_mut::<modules::CoSign>().select(&rc.account, None);
+                        core.select::<modules::CoSign>();
+                    }
+                    if !rc.account.is_multisig() && !rc.account.is_watch_only() && ui.large_button(i18n("Sign / Verify Message")).clicked() {
+                        core.get_mut::<modules::SignMessage>().select(&rc.account);
+                        core.select::<modules::SignMessage>();
+                    }
                 });
 
         })
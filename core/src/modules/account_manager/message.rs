@@ -0,0 +1,90 @@
+use crate::imports::*;
+use super::*;
+use kaspa_addresses::Version;
+use kaspa_wallet_core::account::DerivationCapableAccount;
+use kaspa_wallet_core::message::{PersonalMessage, SignMessageOptions};
+use secp256k1::XOnlyPublicKey;
+
+/// Locates an address of the account in its receive and change chains,
+/// returning `(change, index)`.
+fn find_address(account : &Arc<dyn DerivationCapableAccount>, address : &Address) -> Result<(bool, u32)> {
+    let derivation = account.derivation();
+    for (change, manager) in [(false, derivation.receive_address_manager()), (true, derivation.change_address_manager())] {
+        let addresses = manager.get_range(0..manager.index() + 1)?;
+        if let Some(index) = addresses.iter().position(|candidate| candidate == address) {
+            return Ok((change, index as u32));
+        }
+    }
+
+    Err(Error::custom(i18n("The address does not belong to this account")))
+}
+
+/// Signs a personal message with the private key of one of the account's
+/// addresses, returning the hex-encoded Schnorr signature.
+pub async fn sign_message(account_id : AccountId, address : Address, message : String, wallet_secret : Secret, payment_secret : Option<Secret>) -> Result<String> {
+    with_account(account_id, |account| async move {
+        let account = account.as_derivation_capable()?;
+        let (change, index) = find_address(&account, &address)?;
+        let keydata = account.prv_key_data(wallet_secret).await?;
+
+        let indexes = [(&address, index)];
+        let (receive, change) = if change { (&[][..], &indexes[..]) } else { (&indexes[..], &[][..]) };
+        let keys = account.create_private_keys(&keydata, &payment_secret, receive, change)?;
+        let (_, key) = keys.first().ok_or_else(|| Error::custom(i18n("Unable to derive the address key")))?;
+
+        let mut private_key = key.secret_bytes();
+        let signature = kaspa_wallet_core::message::sign_message(
+            &PersonalMessage(message.as_str()),
+            &private_key,
+            &SignMessageOptions { no_aux_rand : false },
+        );
+        private_key.zeroize();
+
+        Ok(signature?.to_hex())
+    }).await
+}
+
+/// Verifies a hex-encoded signature of a personal message against the public
+/// key of a Schnorr (`kaspa:q...`) address. Returns `Ok(false)` if the
+/// signature does not match and an error if the input can not be parsed.
+pub fn verify_message(address : &Address, message : &str, signature : &str) -> Result<bool> {
+    if address.version != Version::PubKey {
+        return Err(Error::custom(i18n("Only Schnorr (P2PK) addresses are supported")));
+    }
+
+    let public_key = XOnlyPublicKey::from_slice(address.payload.as_slice())
+        .map_err(|err| Error::custom(format!("Invalid address public key: {err}")))?;
+    let signature = Vec::<u8>::from_hex(signature.trim())
+        .map_err(|_| Error::custom(i18n("The signature must be a hex string")))?;
+    if signature.len() != 64 {
+        return Err(Error::custom(i18n("The signature must be 64 bytes long")));
+    }
+
+    Ok(kaspa_wallet_core::message::verify_message(&PersonalMessage(message), &signature, &public_key).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::Prefix;
+
+    #[test]
+    fn signatures_are_verified_against_the_address_key() {
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let (public_key, _) = secret_key.x_only_public_key(secp256k1::SECP256K1);
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &public_key.serialize());
+
+        let signature = kaspa_wallet_core::message::sign_message(
+            &PersonalMessage("hello kaspa"),
+            &secret_key.secret_bytes(),
+            &SignMessageOptions { no_aux_rand : true },
+        ).unwrap().to_hex();
+
+        assert!(verify_message(&address, "hello kaspa", &signature).unwrap());
+        assert!(!verify_message(&address, "hello kaspa!", &signature).unwrap());
+        assert!(verify_message(&address, "hello kaspa", "00").is_err());
+
+        let ecdsa = Address::new(Prefix::Mainnet, Version::PubKeyECDSA, &[2u8; 33]);
+        assert!(verify_message(&ecdsa, "hello kaspa", &signature).is_err());
+    }
+}
@@ -2,20 +2,24 @@ use crate::imports::*;
 use crate::primitives::account;
 use std::borrow::Cow;
 use egui_phosphor::thin::{CLOUD_ARROW_DOWN, CLOUD_SLASH};
-use kaspa_wallet_core::tx::{GeneratorSummary, PaymentOutput, Fees};
+use kaspa_wallet_core::tx::{GeneratorSummary, PaymentDestination, PaymentOutput, PaymentOutputs, Fees};
 use kaspa_wallet_core::api::*;
 use workflow_core::runtime;
 use crate::primitives::descriptor::*;
 
 mod address;
 mod balance;
+mod batch;
 mod destination;
 mod details;
 mod estimator;
+mod history;
 pub mod menus;
+pub mod message;
 mod network;
 mod overview;
 mod processor;
+pub mod pskb;
 mod qr;
 mod secret;
 mod transactions;
@@ -24,18 +28,20 @@ mod utxo;
 
 use address::*;
 use balance::*;
+use batch::*;
 use destination::*;
 use details::*;
 use estimator::*;
+use history::*;
 use menus::*;
 use network::*;
 use overview::*;
 use processor::*;
+use pskb::*;
 use qr::*;
 use secret::*;
 use transactions::*;
 use transfer::*;
-#[allow(unused_imports)]
 use utxo::*;
 
 
@@ -53,7 +59,7 @@ pub enum AccountManagerSection {
     Overview,
     Transactions,
     Details,
-    // UtxoManager
+    UtxoManager
 }
 
 // #[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
@@ -66,6 +72,9 @@ enum Action {
     Sending,
     // Reset,
     Processing,
+    /// Serialized PSKB created by a multisig account,
+    /// awaiting signatures from the other cosigners.
+    PartiallySigned(Arc<String>),
     Error(Arc<Error>),
 }
 
@@ -80,6 +89,9 @@ enum Action {
 enum TransactionKind {
     Send,
     Transfer,
+    Consolidate,
+    Split,
+    Batch,
 }
 
 
@@ -91,6 +103,8 @@ enum Focus {
     Address,
     Amount,
     Fees,
+    Note,
+    Outputs,
     WalletSecret,
     PaymentSecret,
 }
@@ -171,6 +185,7 @@ pub struct ManagerContext {
     transfer_to_account : Option<Account>,
     destination_address_string : String,
     send_amount_text: String,
+    payment_note : String,
     send_amount_sompi : u64,
     enable_priority_fees : bool,
     priority_fees_text : String,
@@ -185,7 +200,15 @@ pub struct ManagerContext {
     wallet_secret : String,
     payment_secret : String,
     loading : bool,
-    fee_mode : FeeMode
+    fee_mode : FeeMode,
+    utxo_selection : Option<UtxoSelection>,
+    /// Set once a UTXO-constrained transaction has been submitted so
+    /// that the [`UtxoManager`] drops its stale selection and list.
+    utxos_spent : bool,
+    split_outputs_text : String,
+    split_outputs : usize,
+    batch : BatchPayments,
+    batch_outputs : Vec<PaymentOutput>,
 }
 
 impl ManagerContext {
@@ -200,6 +223,17 @@ impl ManagerContext {
 
         self.zeroize()
     }
+
+    /// Pre-fills the send form from a `kaspa:` payment URI.
+    fn apply_payment_uri(&mut self, payment_uri : &PaymentUri) {
+        self.destination_address_string = payment_uri.address.to_string();
+        self.address_status = AddressStatus::None;
+        if let Some(amount_sompi) = payment_uri.amount_sompi {
+            self.send_amount_text = format_kaspa_amount(amount_sompi);
+            self.request_estimate = Some(true);
+        }
+        self.payment_note = payment_uri.note().unwrap_or_default();
+    }
 }
 
 impl Zeroize for ManagerContext {
@@ -208,6 +242,7 @@ impl Zeroize for ManagerContext {
         self.transfer_to_account = None;
         self.destination_address_string = String::default();
         self.send_amount_text = String::default();
+        self.payment_note = String::default();
         self.send_amount_sompi = 0;
         self.enable_priority_fees = false;
         self.priority_fees_text = String::default();
@@ -216,6 +251,11 @@ impl Zeroize for ManagerContext {
         *self.estimate.lock().unwrap() = EstimatorStatus::None;
         self.address_status = AddressStatus::None;
         self.transaction_kind = None;
+        self.utxo_selection = None;
+        self.split_outputs_text = String::default();
+        self.split_outputs = 0;
+        self.batch = BatchPayments::default();
+        self.batch_outputs.clear();
         self.focus.clear();
         self.wallet_secret.zeroize();
         self.payment_secret.zeroize();    
@@ -254,6 +294,8 @@ pub struct AccountManager {
     state: AccountManagerState,
     section: AccountManagerSection,
     context : ManagerContext,
+    transactions : Transactions,
+    utxo_manager : UtxoManager,
 }
 
 impl AccountManager {
@@ -263,6 +305,8 @@ impl AccountManager {
             state: AccountManagerState::Select,
             section: AccountManagerSection::Overview,
             context : ManagerContext::default(),
+            transactions : Transactions::new(),
+            utxo_manager : UtxoManager::new(),
         }
     }
 }
@@ -271,6 +315,8 @@ impl ModuleT for AccountManager {
 
     fn reset(&mut self, _core: &mut Core) {
         self.context = ManagerContext::default();
+        self.transactions = Transactions::new();
+        self.utxo_manager = UtxoManager::new();
         self.state = AccountManagerState::Select;
     }
 
@@ -304,6 +350,16 @@ impl AccountManager {
         self.context.request_estimate = Some(true);
     }
 
+    /// Opens the send form pre-filled from a payment URI. If no account
+    /// is selected yet, the form is shown once the user selects one.
+    pub fn open_payment_uri(&mut self, payment_uri : PaymentUri) {
+        self.context.reset_send_state();
+        self.context.apply_payment_uri(&payment_uri);
+        self.context.action = Action::Estimating;
+        self.context.transaction_kind = Some(TransactionKind::Send);
+        self.context.focus.next(Focus::Amount);
+    }
+
     pub fn select(&mut self, wallet : Arc<dyn WalletApi>, account: Option<Account>, device : Device, notify : bool) {
 
         if let Some(account) = account {
@@ -508,12 +564,12 @@ impl AccountManager {
                             self.section = AccountManagerSection::Details;
                         }
 
-                        // if core.device().desktop() {
-                        //     ui.separator();
-                        //     if ui.add(Label::new(i18n("UTXOs")).sense(Sense::click())).clicked() {
-                        //         self.section = AccountManagerSection::UtxoManager;
-                        //     }
-                        // }
+                        if core.device().desktop() {
+                            ui.separator();
+                            if ui.add(Label::new(i18n("UTXOs")).sense(Sense::click())).clicked() {
+                                self.section = AccountManagerSection::UtxoManager;
+                            }
+                        }
 
                     });
 
@@ -537,12 +593,12 @@ impl AccountManager {
                     ui.separator();
                     ToolsMenu::new().render(core,ui,self, rc, screen_rect_height * 0.8);
 
-                    // if core.device().desktop() {
-                    //     ui.separator();
-                    //     if ui.add(Label::new(i18n("UTXOs")).sense(Sense::click())).clicked() {
-                    //         self.section = AccountManagerSection::UtxoManager;
-                    //     }
-                    // }
+                    if core.device().desktop() {
+                        ui.separator();
+                        if ui.add(Label::new(i18n("UTXOs")).sense(Sense::click())).clicked() {
+                            self.section = AccountManagerSection::UtxoManager;
+                        }
+                    }
 
                     ui.separator();
                     if ui.add(Label::new(i18n("Details")).sense(Sense::click())).clicked() {
@@ -592,14 +648,14 @@ impl AccountManager {
                         Overview::new(&mut self.context).render(core,ui,rc);
                     }
                     AccountManagerSection::Transactions => {
-                        Transactions::new().render(ui,core,rc);
+                        self.transactions.render(ui,core,rc);
                     }
                     AccountManagerSection::Details => {
                         Details::new().render(core,ui,rc);
                     }
-                    // AccountManagerSection::UtxoManager => {
-                    //     UtxoManager::new().render(core,ui,rc);
-                    // }
+                    AccountManagerSection::UtxoManager => {
+                        self.utxo_manager.render(core,ui,rc,&mut self.context);
+                    }
                 }
             });
 
@@ -613,14 +669,16 @@ impl AccountManager {
                 Overview::new(&mut self.context).render(core,ui,rc);
             }
             AccountManagerSection::Transactions => {
-                Transactions::new().render(ui,core,rc);
+                self.transactions.render(ui,core,rc);
             }
             AccountManagerSection::Details => {
                 Details::new().render(core,ui,rc);
             }
-            // AccountManagerSection::UtxoManager => {
-            //     UtxoManager::new().render(core,ui,rc);
-            // }
+            AccountManagerSection::UtxoManager => {
+                if self.utxo_manager.render(core,ui,rc,&mut self.context) {
+                    self.section = AccountManagerSection::Overview;
+                }
+            }
         }
 
     }
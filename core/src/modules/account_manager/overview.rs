@@ -15,7 +15,7 @@ impl<'manager> Overview<'manager> {
     }
 
     pub fn render(&mut self, core: &mut Core, ui : &mut Ui, rc : &RenderContext) {
-        use egui_phosphor::light::{ARROW_CIRCLE_UP,ARROWS_DOWN_UP,QR_CODE};
+        use egui_phosphor::light::{ARROW_CIRCLE_UP,ARROWS_DOWN_UP,LIST_BULLETS,QR_CODE};
 
         core.apply_mobile_style(ui);
 
@@ -41,6 +41,37 @@ impl<'manager> Overview<'manager> {
                             Processor::new(self.context).render(core, ui, rc);
                             // self.render_send_ui(core, ui, rc);
                         }
+                        Action::PartiallySigned(pskb) => {
+                            ui.vertical_centered(|ui|{
+
+                                ui.add_space(16.);
+                                ui.label(RichText::new(i18n("Partially signed transaction created")).strong());
+                                ui.add_space(8.);
+                                ui.label(i18n("Share the PSKB below with the other cosigners. Once enough signatures have been collected, it can be broadcast from the Co-sign tool."));
+                                ui.add_space(8.);
+                                ui.separator();
+                                ui.add_space(8.);
+                                ui.label(RichText::new(format_partial_string(pskb.as_str(), Some(24))).monospace());
+                                ui.add_space(8.);
+                                ui.separator();
+                                ui.add_space(16.);
+
+                                CenterLayoutBuilder::new()
+                                    .add(Button::new(i18n("Copy")).min_size(theme_style().medium_button_size()), |_ : &mut (&mut Overview<'_>, &mut Core)| {
+                                        runtime().egui_ctx().copy_text(pskb.to_string());
+                                        runtime().notify_clipboard(i18n("Copied to clipboard"));
+                                    })
+                                    .add(Button::new(i18n("Co-sign")).min_size(theme_style().medium_button_size()), |(this, core)| {
+                                        core.get_mut::<modules::CoSign>().select(&rc.account, Some(pskb.to_string()));
+                                        core.select::<modules::CoSign>();
+                                        this.context.action = Action::None;
+                                    })
+                                    .add(Button::new(i18n("Continue")).min_size(theme_style().medium_button_size()), |(this, _)| {
+                                        this.context.action = Action::None;
+                                    })
+                                    .build(ui, &mut (self, core));
+                            });
+                        }
                         Action::Error(error) => {
                             ui.vertical_centered(|ui|{
 
@@ -55,7 +86,14 @@ impl<'manager> Overview<'manager> {
                                 ui.add_space(16.);
                                 // TODO - copy to clipboard?
                                 if ui.medium_button(i18n("Continue")).clicked() {
-                                    self.context.action = Action::None;
+                                    if self.context.batch.rows.is_empty() {
+                                        self.context.action = Action::None;
+                                    } else {
+                                        // return to the unpaid rows of a failed batch
+                                        self.context.action = Action::Estimating;
+                                        self.context.transaction_kind = Some(TransactionKind::Batch);
+                                        self.context.request_estimate = Some(true);
+                                    }
                                 }
                             });
                         }
@@ -69,14 +107,21 @@ impl<'manager> Overview<'manager> {
                                 ui.horizontal(|ui| {
 
                                     let mut layout = CenterLayoutBuilder::new();
+                                    let watch_only = rc.account.is_watch_only();
                                     
-                                    layout = layout.add(Button::new(i18n_args("{arrowCircleUpIcon} Send", &[("arrowCircleUpIcon", ARROW_CIRCLE_UP)])).min_size(theme_style().medium_button_size()), |(this, _):&mut (&mut Overview<'_>, &mut Core)| {
+                                    layout = layout.add_enabled(!watch_only, Button::new(i18n_args("{arrowCircleUpIcon} Send", &[("arrowCircleUpIcon", ARROW_CIRCLE_UP)])).min_size(theme_style().medium_button_size()), |(this, _):&mut (&mut Overview<'_>, &mut Core)| {
                                         this.context.action = Action::Estimating;
                                         this.context.transaction_kind = Some(TransactionKind::Send);
                                         this.context.focus.next(Focus::Address);
                                     });
 
-                                    if core.account_collection().as_ref().map(|collection|collection.len()).unwrap_or(0) > 1 {
+                                    layout = layout.add_enabled(!watch_only, Button::new(i18n_args("{listBulletsIcon} Batch", &[("listBulletsIcon", LIST_BULLETS)])).min_size(theme_style().medium_button_size()), |(this, _)| {
+                                        this.context.action = Action::Estimating;
+                                        this.context.transaction_kind = Some(TransactionKind::Batch);
+                                        this.context.batch = BatchPayments::new();
+                                    });
+
+                                    if !watch_only && core.account_collection().as_ref().map(|collection|collection.len()).unwrap_or(0) > 1 {
                                         layout = layout.add(Button::new(i18n_args("{arrowsDownUpIcon} Transfer", &[("arrowsDownUpIcon", ARROWS_DOWN_UP)])).min_size(theme_style().medium_button_size()), |(this,_)| {
                                             this.context.action = Action::Estimating;
                                             this.context.transaction_kind = Some(TransactionKind::Transfer);
@@ -91,6 +136,11 @@ impl<'manager> Overview<'manager> {
 
                                     layout.build(ui,&mut (self,core));
                                 });
+
+                                if rc.account.is_watch_only() {
+                                    ui.add_space(8.);
+                                    ui.label(RichText::new(i18n("This is a watch-only account. Sending is disabled because the account has no private keys.")).color(theme_color().warning_color));
+                                }
                             });
                         }
                     }
@@ -24,22 +24,75 @@ impl<'context> Processor<'context> {
             TransactionKind::Transfer => {
                 // ui.label("Transferring funds");
             }
+            TransactionKind::Consolidate => {
+                ui.label(i18n("Consolidating UTXOs"));
+                ui.add_space(8.);
+            }
+            TransactionKind::Split => {
+                ui.label(i18n("Splitting UTXOs"));
+                ui.add_space(8.);
+            }
+            TransactionKind::Batch => {
+                ui.label(i18n("Batch payment"));
+                ui.add_space(8.);
+            }
         }
 
+        let network_id = NetworkId::from(core.network());
+
         let send_result = Payload::<Result<GeneratorSummary>>::new("send_result");
+        let pskb_result = Payload::<Result<String>>::new("pskb_result");
 
         match &self.context.action {
             Action::Estimating => {
 
                 let request_estimate = Estimator::new(self.context).render(core, ui, rc);
 
-                if request_estimate {
+                let utxo_selection = self.context.utxo_selection.clone().filter(|_| request_estimate);
 
-                    let address = match network_type {
-                        NetworkType::Testnet => Address::try_from("kaspatest:qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqhqrxplya").unwrap(),
-                        NetworkType::Mainnet => Address::try_from("kaspa:qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqkx9awp4e").unwrap(),
-                        _ => panic!("Unsupported network"),
-                    };
+                if let Some(selection) = utxo_selection {
+
+                    let account_id = account.id();
+                    let destination = utxo_destination(self.context, rc);
+                    let priority_fee_sompi = self.context.priority_fees_sompi;
+
+                    let status = self.context.estimate.clone();
+                    spawn(async move {
+                        match estimate_utxo_transaction(account_id, selection, destination, priority_fee_sompi).await {
+                            Ok(generator_summary) => {
+                                *status.lock().unwrap() = EstimatorStatus::GeneratorSummary(generator_summary);
+                            }
+                            Err(error) => {
+                                *status.lock().unwrap() = EstimatorStatus::Error(error.to_string());
+                            }
+                        }
+
+                        runtime().egui_ctx().request_repaint();
+                        Ok(())
+                    });
+                } else if request_estimate && self.context.transaction_kind == Some(TransactionKind::Batch) {
+
+                    let account_id = account.id();
+                    let outputs = self.context.batch_outputs.clone();
+                    let priority_fee_sompi = self.context.priority_fees_sompi;
+
+                    let status = self.context.estimate.clone();
+                    spawn(async move {
+                        match estimate_batch(network_id, account_id, outputs, priority_fee_sompi).await {
+                            Ok(generator_summary) => {
+                                *status.lock().unwrap() = EstimatorStatus::GeneratorSummary(generator_summary);
+                            }
+                            Err(error) => {
+                                *status.lock().unwrap() = EstimatorStatus::Error(error.to_string());
+                            }
+                        }
+
+                        runtime().egui_ctx().request_repaint();
+                        Ok(())
+                    });
+                } else if request_estimate {
+
+                    let address = estimation_address(network_type);
 
                     let account_id = account.id();
 
@@ -99,61 +152,117 @@ impl<'context> Processor<'context> {
                     let wallet_secret = Secret::from(self.context.wallet_secret.clone());
                     let payment_secret = account.requires_bip39_passphrase(core).then_some(Secret::from(self.context.payment_secret.clone()));
 
-                    match self.context.transaction_kind.unwrap() {
-                        TransactionKind::Send => {
+                    if let Some(selection) = self.context.utxo_selection.clone() {
 
-                            let address = Address::try_from(self.context.destination_address_string.as_str()).expect("invalid address");
-                            let account_id = account.id();
-                            let send_amount_sompi = self.context.send_amount_sompi;
-                            let payment_output = PaymentOutput {
-                                address,
-                                amount: send_amount_sompi,
-                            };
-        
-                            spawn_with_result(&send_result, async move {
-
-                                let fee_rate = calculate_fee_rate(network_type, account_id, send_amount_sompi, priority_fee_sompi).await;
-
-                                let request = AccountsSendRequest {
-                                    account_id,
-                                    destination: payment_output.into(),
-                                    wallet_secret,
-                                    payment_secret,
-                                    fee_rate: Some(fee_rate),
-                                    priority_fee_sompi: Fees::SenderPays(0),
-                                    payload: None,
+                        let account_id = account.id();
+                        let destination = utxo_destination(self.context, rc);
+
+                        spawn_with_result(&send_result, async move {
+                            let generator_summary = submit_utxo_transaction(account_id, selection, destination, priority_fee_sompi, wallet_secret, payment_secret).await?;
+                            runtime().request_repaint();
+                            Ok(generator_summary)
+                        });
+
+                    } else {
+
+                        match self.context.transaction_kind.unwrap() {
+                            TransactionKind::Send => {
+
+                                let address = Address::try_from(self.context.destination_address_string.as_str()).expect("invalid address");
+                                let account_id = account.id();
+                                let send_amount_sompi = self.context.send_amount_sompi;
+                                let payment_output = PaymentOutput {
+                                    address,
+                                    amount: send_amount_sompi,
                                 };
+
+                                if account.is_multisig() {
+                                    // multisig spends are assembled into a PSKB that
+                                    // the remaining cosigners sign in the CoSign module
+                                    spawn_with_result(&pskb_result, async move {
+                                        let fee_rate = calculate_fee_rate(network_type, account_id, send_amount_sompi, priority_fee_sompi).await;
+                                        let pskb = create_pskb(account_id, payment_output.into(), Some(fee_rate), wallet_secret, payment_secret).await?;
+                                        runtime().request_repaint();
+                                        Ok(pskb)
+                                    });
+
+                                    self.context.action = Action::Processing;
+                                    return;
+                                }
+        
+                                spawn_with_result(&send_result, async move {
+
+                                    let fee_rate = calculate_fee_rate(network_type, account_id, send_amount_sompi, priority_fee_sompi).await;
+
+                                    let request = AccountsSendRequest {
+                                        account_id,
+                                        destination: payment_output.into(),
+                                        wallet_secret,
+                                        payment_secret,
+                                        fee_rate: Some(fee_rate),
+                                        priority_fee_sompi: Fees::SenderPays(0),
+                                        payload: None,
+                                    };
         
-                                let generator_summary = runtime().wallet().accounts_send_call(request).await?.generator_summary;
-                                runtime().request_repaint();
-                                Ok(generator_summary)
-                            });
+                                    let generator_summary = runtime().wallet().accounts_send_call(request).await?.generator_summary;
+                                    runtime().request_repaint();
+                                    Ok(generator_summary)
+                                });
 
-                        }
+                            }
 
-                        TransactionKind::Transfer => {
-                            let destination_account_id = self.context.transfer_to_account.as_ref().expect("transfer destination account").id();
-                            let source_account_id = account.id();
-                            let transfer_amount_sompi = self.context.send_amount_sompi;
-
-                            spawn_with_result(&send_result, async move {
-                                let fee_rate = calculate_fee_rate(network_type, source_account_id, transfer_amount_sompi, priority_fee_sompi).await;
-
-                                let request = AccountsTransferRequest {
-                                    source_account_id,
-                                    destination_account_id,
-                                    wallet_secret,
-                                    payment_secret,
-                                    fee_rate: Some(fee_rate),
-                                    priority_fee_sompi: Some(Fees::SenderPays(0)),
-                                    transfer_amount_sompi,
-                                };
+                            TransactionKind::Transfer => {
+                                let destination_account_id = self.context.transfer_to_account.as_ref().expect("transfer destination account").id();
+                                let source_account_id = account.id();
+                                let transfer_amount_sompi = self.context.send_amount_sompi;
+
+                                spawn_with_result(&send_result, async move {
+                                    let fee_rate = calculate_fee_rate(network_type, source_account_id, transfer_amount_sompi, priority_fee_sompi).await;
+
+                                    let request = AccountsTransferRequest {
+                                        source_account_id,
+                                        destination_account_id,
+                                        wallet_secret,
+                                        payment_secret,
+                                        fee_rate: Some(fee_rate),
+                                        priority_fee_sompi: Some(Fees::SenderPays(0)),
+                                        transfer_amount_sompi,
+                                    };
         
-                                let generator_summary = runtime().wallet().accounts_transfer_call(request).await?.generator_summary;
-                                runtime().request_repaint();
-                                Ok(generator_summary)
-                            });
+                                    let generator_summary = runtime().wallet().accounts_transfer_call(request).await?.generator_summary;
+                                    runtime().request_repaint();
+                                    Ok(generator_summary)
+                                });
+                            }
+
+                            TransactionKind::Batch => {
+                                let account_id = account.id();
+                                let outputs = self.context.batch_outputs.clone();
+
+                                if account.is_multisig() {
+                                    spawn_with_result(&pskb_result, async move {
+                                        let destination : PaymentDestination = PaymentOutputs { outputs }.into();
+                                        let fee_rate = batch_fee_rate(account_id, std::slice::from_ref(&destination), priority_fee_sompi).await;
+                                        let pskb = create_pskb(account_id, destination, Some(fee_rate), wallet_secret, payment_secret).await?;
+                                        runtime().request_repaint();
+                                        Ok(pskb)
+                                    });
+                                } else {
+                                    let paid = self.context.batch.paid.clone();
+                                    paid.store(0, Ordering::SeqCst);
+                                    spawn_with_result(&send_result, async move {
+                                        let generator_summary = submit_batch(network_id, account_id, outputs, priority_fee_sompi, wallet_secret, payment_secret, paid).await?;
+                                        runtime().request_repaint();
+                                        Ok(generator_summary)
+                                    });
+                                }
+                            }
+
+                            TransactionKind::Consolidate | TransactionKind::Split => {
+                                unreachable!("UTXO consolidation and split require a UTXO selection");
+                            }
                         }
+
                     }
             
                     self.context.action = Action::Processing;
@@ -164,17 +273,48 @@ impl<'context> Processor<'context> {
                 ui.add_space(16.);
                 ui.add(egui::Spinner::new().size(92.));
 
+                if let Some(result) = pskb_result.take() {
+                    self.context.reset_send_state();
+                    match result {
+                        Ok(pskb) => {
+                            self.context.action = Action::PartiallySigned(Arc::new(pskb));
+                        }
+                        Err(error) => {
+                            self.context.action = Action::Error(Arc::new(error));
+                        }
+                    }
+                }
+
                 if let Some(result) = send_result.take() {
                     match result {
-                        Ok(_) => {
+                        Ok(generator_summary) => {
+                            // the note entered in the send form is kept
+                            // locally, keyed by the id of the final transaction
+                            let note = std::mem::take(&mut self.context.payment_note);
+                            let is_send = self.context.transaction_kind == Some(TransactionKind::Send);
+                            if let Some(transaction_id) = generator_summary.final_transaction_id.filter(|_| is_send) {
+                                if note.trim().is_not_empty() {
+                                    if let Err(err) = core.update_transaction_notes(|notes| notes.set(transaction_id, &note)) {
+                                        runtime().error(err.to_string());
+                                    }
+                                }
+                            }
+                            let utxos_spent = self.context.utxo_selection.is_some();
                             self.context.reset_send_state();
+                            self.context.utxos_spent = utxos_spent;
                             self.context.action = Action::None;
                         }
                         Err(error) => {
                             println!();
                             println!("Transaction error: {error}");
                             println!();
+                            // recipients of a partially submitted batch that have
+                            // not been paid are kept for another attempt
+                            let unpaid = (self.context.transaction_kind == Some(TransactionKind::Batch)).then(|| self.context.batch.unpaid());
                             self.context.reset_send_state();
+                            if let Some(unpaid) = unpaid {
+                                self.context.batch = unpaid;
+                            }
                             self.context.action = Action::Error(Arc::new(error));
                         }
                     }
@@ -186,13 +326,15 @@ impl<'context> Processor<'context> {
     }
 }
 
+/// Placeholder destination used to estimate fees before the actual
+/// destination address is known.
+fn estimation_address(network_type : NetworkType) -> Address {
+    Address::new(network_type.into(), kaspa_addresses::Version::PubKey, &[0u8; 32])
+}
+
 async fn calculate_fee_rate(network_type : NetworkType, account_id : AccountId, send_amount_sompi : u64, priority_fee_sompi : u64) -> f64 {
 
-    let address = match network_type {
-        NetworkType::Testnet => Address::try_from("kaspatest:qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqhqrxplya").unwrap(),
-        NetworkType::Mainnet => Address::try_from("kaspa:qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqkx9awp4e").unwrap(),
-        _ => panic!("Unsupported network"),
-    };
+    let address = estimation_address(network_type);
 
     let payment_output = PaymentOutput {
         address,
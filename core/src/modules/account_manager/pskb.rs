@@ -0,0 +1,87 @@
+use crate::imports::*;
+use super::*;
+use kaspa_txscript::extract_script_pub_key_address;
+use kaspa_wallet_pskt::bundle::Bundle;
+
+/// Overview of a partially signed transaction bundle (PSKB) presented
+/// to a cosigner before the bundle is signed or broadcast.
+#[derive(Clone, Debug, Default)]
+pub struct PskbSummary {
+    pub transactions : usize,
+    /// Number of signatures collected for each input.
+    pub signatures : Vec<usize>,
+    pub input_amount : u64,
+    pub outputs : Vec<(String, u64)>,
+    /// Fee of each transaction, computed from its own inputs and outputs,
+    /// as the outputs of one transaction may be spent by the next.
+    pub fees : Vec<u64>,
+}
+
+impl PskbSummary {
+    pub fn try_new(pskb : &str, network_type : NetworkType) -> Result<Self> {
+        let bundle = deserialize(pskb)?;
+
+        let mut summary = PskbSummary::default();
+        for inner in bundle.0.iter() {
+            summary.transactions += 1;
+            let mut input_amount = 0;
+            for input in inner.inputs.iter() {
+                summary.signatures.push(input.partial_sigs.len());
+                input_amount += input.utxo_entry.as_ref().map(|entry| entry.amount).unwrap_or_default();
+            }
+            let mut output_amount = 0;
+            for output in inner.outputs.iter() {
+                let address = extract_script_pub_key_address(&output.script_public_key, network_type.into())
+                    .map(|address| address.to_string())
+                    .unwrap_or_else(|_| i18n("Non-standard script").to_string());
+                summary.outputs.push((address, output.amount));
+                output_amount += output.amount;
+            }
+            summary.input_amount += input_amount;
+            summary.fees.push(input_amount.saturating_sub(output_amount));
+        }
+
+        Ok(summary)
+    }
+
+    pub fn total_fees(&self) -> u64 {
+        self.fees.iter().sum()
+    }
+}
+
+fn deserialize(pskb : &str) -> Result<Bundle> {
+    Bundle::deserialize(pskb.trim()).map_err(|err| Error::custom(format!("{}: {err}", i18n("Invalid PSKB"))))
+}
+
+fn serialize(bundle : &Bundle) -> Result<String> {
+    bundle.serialize().map_err(|err| Error::custom(err.to_string()))
+}
+
+/// Creates a bundle spending from a multisig account. The bundle carries
+/// the signatures of the local keys and must be completed by the other
+/// cosigners before it can be broadcast.
+pub async fn create_pskb(account_id : AccountId, destination : PaymentDestination, fee_rate : Option<f64>, wallet_secret : Secret, payment_secret : Option<Secret>) -> Result<String> {
+    with_account(account_id, |account| async move {
+        let bundle = account.pskb_from_send_generator(destination, fee_rate, Fees::SenderPays(0), None, wallet_secret, payment_secret, &Abortable::new()).await?;
+        serialize(&bundle)
+    }).await
+}
+
+/// Adds the signatures of the local keys of the account to the bundle.
+pub async fn sign_pskb(account_id : AccountId, pskb : String, wallet_secret : Secret, payment_secret : Option<Secret>) -> Result<String> {
+    let bundle = deserialize(&pskb)?;
+    with_account(account_id, |account| async move {
+        let bundle = account.pskb_sign(&bundle, wallet_secret, payment_secret, None).await?;
+        serialize(&bundle)
+    }).await
+}
+
+/// Finalizes and submits a fully signed bundle, returning the ids of the
+/// submitted transactions.
+pub async fn broadcast_pskb(account_id : AccountId, pskb : String) -> Result<Vec<String>> {
+    let bundle = deserialize(&pskb)?;
+    with_account(account_id, |account| async move {
+        let transaction_ids = account.pskb_broadcast(&bundle).await?;
+        Ok(transaction_ids.into_iter().map(|id| id.to_string()).collect())
+    }).await
+}
@@ -1,17 +1,191 @@
 use crate::imports::*;
 use super::*;
 use crate::core::TRANSACTION_PAGE_SIZE;
+use egui_phosphor::light::{EXPORT, FUNNEL};
 
-pub struct Transactions { }
+/// Account transaction list. Without a filter, the list pages through
+/// the transactions loaded into the account; with a filter, the complete
+/// history is loaded and filtered locally.
+pub struct Transactions {
+    filter : HistoryFilter,
+    show_filter : bool,
+    history : Option<TransactionHistory>,
+    history_error : Option<String>,
+    filtered : Option<(HistoryFilter, Vec<usize>)>,
+    start : u64,
+    fiat_currency : String,
+    editing_note : Option<(TransactionId, String)>,
+}
 
 impl Transactions {
     pub fn new() -> Self {
-        Self { }
+        Self {
+            filter : HistoryFilter::default(),
+            show_filter : false,
+            history : None,
+            history_error : None,
+            filtered : None,
+            start : 0,
+            fiat_currency : "usd".to_string(),
+            editing_note : None,
+        }
     }
 
     pub fn render(&mut self, ui: &mut Ui, core : &mut Core, rc : &RenderContext) {
-        let RenderContext { account, network_type, current_daa_score, .. } = rc;
-        let max_height = ui.available_height() - (ui.fonts(|fonts|RichText::new("YWgy").font_height(fonts, ui.style())).at_least(ui.spacing().interact_size.y) * 2.0 + 5.0);
+        self.render_filter(ui, core, rc);
+
+        if self.filter.is_empty() {
+            self.render_account_transactions(ui, core, rc);
+        } else {
+            match self.filter.query() {
+                Ok(query) => self.render_history(ui, core, rc, &query),
+                Err(err) => {
+                    ui.colored_label(error_color(), err.to_string());
+                }
+            }
+        }
+    }
+
+    fn list_height(ui : &Ui) -> f32 {
+        ui.available_height() - (ui.fonts(|fonts|RichText::new("YWgy").font_height(fonts, ui.style())).at_least(ui.spacing().interact_size.y) * 2.0 + 5.0)
+    }
+
+    /// Fiat currency used to value exported transactions, available if
+    /// the market monitor is enabled for the current network.
+    fn fiat_currency(&self, core : &Core) -> Option<String> {
+        let network = core.settings.node.network;
+        (core.settings.market_monitor && (network == Network::Mainnet || core.settings.developer.market_monitor_on_testnet))
+            .then(|| self.fiat_currency.clone())
+    }
+
+    fn render_filter(&mut self, ui: &mut Ui, core : &mut Core, rc : &RenderContext) {
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut export = None;
+
+        ui.horizontal(|ui| {
+            let caption = if self.filter.is_empty() { i18n("Filter") } else { i18n("Filter (active)") };
+            if ui.selectable_label(self.show_filter, format!("{FUNNEL} {caption}")).clicked() {
+                self.show_filter = !self.show_filter;
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if ui.button(format!("{EXPORT} JSON")).on_hover_text(i18n("Export transactions as JSON")).clicked() {
+                    export = Some(ExportFormat::Json);
+                }
+                if ui.button(format!("{EXPORT} CSV")).on_hover_text(i18n("Export transactions as CSV")).clicked() {
+                    export = Some(ExportFormat::Csv);
+                }
+            });
+        });
+
+        if self.show_filter {
+            let date_hint = i18n("YYYY-MM-DD");
+            Grid::new("transaction_history_filter").num_columns(4).spacing([8.,4.]).show(ui, |ui| {
+                ui.label(i18n("From"));
+                ui.add(TextEdit::singleline(&mut self.filter.from_date).hint_text(date_hint).desired_width(100.));
+                ui.label(i18n("To"));
+                ui.add(TextEdit::singleline(&mut self.filter.to_date).hint_text(date_hint).desired_width(100.));
+                ui.end_row();
+
+                ui.label(i18n("Min amount"));
+                ui.add(TextEdit::singleline(&mut self.filter.min_amount).desired_width(100.));
+                ui.label(i18n("Max amount"));
+                ui.add(TextEdit::singleline(&mut self.filter.max_amount).desired_width(100.));
+                ui.end_row();
+
+                ui.label(i18n("Type"));
+                ComboBox::from_id_salt("transaction_history_kind")
+                    .selected_text(self.filter.kind.as_ref().map(transaction_kind_name).unwrap_or(i18n("All")))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.filter.kind, None, i18n("All"));
+                        for kind in TRANSACTION_KINDS {
+                            let text = transaction_kind_name(&kind);
+                            ui.selectable_value(&mut self.filter.kind, Some(kind), text);
+                        }
+                    });
+                ui.label(i18n("Address"));
+                ui.add(TextEdit::singleline(&mut self.filter.address).desired_width(100.));
+                ui.end_row();
+            });
+
+            ui.horizontal(|ui| {
+                if ui.button(i18n("Clear")).clicked() {
+                    self.filter = HistoryFilter::default();
+                }
+
+                let currencies = self.fiat_currency(core).and_then(|_| runtime().market_monitor_service().currencies());
+                if let Some(currencies) = currencies {
+                    ui.separator();
+                    ui.label(i18n("Export fiat currency"));
+                    ComboBox::from_id_salt("transaction_history_fiat")
+                        .selected_text(self.fiat_currency.to_uppercase())
+                        .show_ui(ui, |ui| {
+                            for currency in currencies {
+                                let text = currency.to_uppercase();
+                                ui.selectable_value(&mut self.fiat_currency, currency, text);
+                            }
+                        });
+                }
+            });
+            ui.separator();
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(format) = export {
+            self.export(core, rc, format);
+        }
+    }
+
+    /// Exports the transactions matching the current filter
+    /// (or the complete history if no filter is set).
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export(&self, core : &Core, rc : &RenderContext, format : ExportFormat) {
+        let query = match self.filter.query() {
+            Ok(query) => query,
+            Err(err) => {
+                runtime().error(err.to_string());
+                return;
+            }
+        };
+
+        let file_name = format!("kaspa-transactions.{}", format.extension());
+        let Some(path) = rfd::FileDialog::new().set_file_name(file_name).add_filter(format.extension().to_uppercase(), &[format.extension()]).save_file() else {
+            return;
+        };
+
+        let account_id = rc.account.id();
+        let network = rc.account.network();
+        let currency = self.fiat_currency(core);
+        let notes = core.transaction_notes().clone();
+        spawn(async move {
+            match export_transaction_history(path, format, account_id, network, query, currency, notes).await {
+                Ok(export) => {
+                    let count = export.transactions.to_string();
+                    match export.currency {
+                        Some(currency) if export.missing_fiat > 0 => {
+                            runtime().notify(UserNotification::warning(i18n_args("Exported {count} transactions, {missing} of them without a {currency} value because no price history was available", &[
+                                ("count", count),
+                                ("missing", export.missing_fiat.to_string()),
+                                ("currency", currency.to_uppercase()),
+                            ])));
+                        }
+                        _ => {
+                            runtime().notify(UserNotification::success(i18n_args("Exported {count} transactions", &[("count", count)])));
+                        }
+                    }
+                }
+                Err(err) => {
+                    runtime().error(format!("{} {err}", i18n("Unable to export transactions:")));
+                }
+            }
+            Ok(())
+        });
+    }
+
+    fn render_account_transactions(&mut self, ui: &mut Ui, core : &mut Core, rc : &RenderContext) {
+        let RenderContext { account, .. } = rc;
+        let max_height = Self::list_height(ui);
         egui::ScrollArea::vertical().max_height(max_height).auto_shrink([false,false]).show(ui, |ui| {
             let transactions = account.transactions();
             if transactions.is_empty() {
@@ -22,7 +196,7 @@ impl Transactions {
             } else {
                 let total: u64 = transactions.iter().map(|transaction|transaction.aggregate_input_value()).sum();
                 transactions.iter().for_each(|transaction| {
-                    transaction.render(ui, *network_type, account.network(), *current_daa_score, true, Some(total));
+                    Self::render_transaction(ui, core, rc, transaction, total, &mut self.editing_note);
                 });
             }
         });
@@ -39,4 +213,126 @@ impl Transactions {
             runtime().request_repaint();
         }
     }
+
+    /// Renders a transaction along with its note and the note editor
+    /// if the user is editing the note of this transaction.
+    fn render_transaction(ui : &mut Ui, core : &mut Core, rc : &RenderContext, transaction : &Transaction, largest : u64, editing_note : &mut Option<(TransactionId, String)>) {
+        let RenderContext { account, network_type, current_daa_score, .. } = rc;
+        let transaction_id = transaction.id();
+        let note = core.transaction_notes().as_ref().and_then(|notes| notes.get(&transaction_id)).map(String::from);
+
+        if transaction.render(ui, *network_type, account.network(), *current_daa_score, true, Some(largest), core.address_book().as_ref(), note.as_deref()) {
+            *editing_note = Some((transaction_id, note.unwrap_or_default()));
+        }
+
+        let Some((_, text)) = editing_note.as_mut().filter(|(id, _)| *id == transaction_id) else {
+            return;
+        };
+
+        let mut save = false;
+        let mut cancel = false;
+        ui.horizontal(|ui| {
+            let response = ui.add(TextEdit::singleline(text)
+                .hint_text(i18n("Note"))
+                .char_limit(TRANSACTION_NOTE_MAX_LENGTH)
+                .desired_width(240.));
+            save = response.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter));
+            save |= ui.button(i18n("Save")).clicked();
+            cancel = ui.button(i18n("Cancel")).clicked();
+        });
+
+        if save {
+            if let Some((transaction_id, text)) = editing_note.take() {
+                if let Err(err) = core.update_transaction_notes(|notes| notes.set(transaction_id, &text)) {
+                    runtime().error(err.to_string());
+                }
+            }
+        } else if cancel {
+            *editing_note = None;
+        }
+    }
+
+    fn render_history(&mut self, ui: &mut Ui, core : &mut Core, rc : &RenderContext, query : &HistoryQuery) {
+        let RenderContext { account, .. } = rc;
+        let account_id = account.id();
+        let network = account.network();
+        let transaction_count = account.transaction_count();
+
+        // reload if the account has changed or received new transactions
+        if self.history.as_ref().is_some_and(|history| history.account_id != account_id || history.transaction_count != transaction_count) {
+            self.history = None;
+            self.filtered = None;
+        }
+
+        let history_result = Payload::<Result<(AccountId, u64, Vec<Arc<TransactionRecord>>)>>::new("account_transaction_history");
+        if let Some(result) = history_result.take() {
+            match result {
+                Ok((id, count, records)) => {
+                    if id == account_id {
+                        self.history = Some(TransactionHistory::new(id, count, records, network));
+                        self.filtered = None;
+                    }
+                }
+                Err(err) => {
+                    self.history_error = Some(err.to_string());
+                }
+            }
+        }
+
+        let Some(history) = self.history.as_ref() else {
+            ui.vertical_centered(|ui| {
+                if let Some(err) = self.history_error.as_ref() {
+                    ui.label("");
+                    ui.colored_label(error_color(), err);
+                    if ui.button(i18n("Retry")).clicked() {
+                        self.history_error = None;
+                    }
+                } else {
+                    if !history_result.is_pending() {
+                        let network_id = NetworkId::from(network);
+                        spawn_with_result(&history_result, async move {
+                            let records = load_transaction_history(account_id, network_id).await?;
+                            Ok((account_id, transaction_count, records))
+                        });
+                    }
+                    ui.label("");
+                    ui.label(i18n("Loading transaction history..."));
+                    ui.add_space(8.);
+                    ui.add(egui::Spinner::new().size(32.));
+                }
+            });
+            return;
+        };
+
+        if self.filtered.as_ref().is_none_or(|(filter, _)| filter != &self.filter) {
+            self.filtered = Some((self.filter.clone(), history.filter(query)));
+            self.start = 0;
+        }
+        let Some((_, filtered)) = self.filtered.as_ref() else { return; };
+
+        ui.label(i18n_args("Showing {count} of {total} transactions", &[("count", filtered.len().to_string()), ("total", history.len().to_string())]));
+
+        let max_height = Self::list_height(ui);
+        let page = filtered.iter().skip(self.start as usize).take(TRANSACTION_PAGE_SIZE as usize).filter_map(|index| history.record(*index)).collect::<Vec<_>>();
+        egui::ScrollArea::vertical().max_height(max_height).auto_shrink([false,false]).show(ui, |ui| {
+            if page.is_empty() {
+                ui.vertical_centered(|ui| {
+                    ui.label("");
+                    ui.label(RichText::new(i18n("No matching transactions")).size(16.));
+                });
+            } else {
+                let total: u64 = page.iter().map(|record|record.aggregate_input_value()).sum();
+                page.iter().for_each(|record| {
+                    Self::render_transaction(ui, core, rc, &Transaction::from((*record).clone()), total, &mut self.editing_note);
+                });
+            }
+        });
+
+        ui.add_space(4.);
+        let pagination = Pagination::new(filtered.len() as u64, Some(self.start), Some(TRANSACTION_PAGE_SIZE), Some(5));
+        if let Some(start) = pagination.render(ui){
+            self.start = start;
+            runtime().request_repaint();
+        }
+    }
 }
@@ -1,27 +1,499 @@
-#![allow(dead_code)]
-
 use crate::imports::*;
 use super::*;
+use futures::TryStreamExt;
+use kaspa_wallet_core::account::MULTISIG_ACCOUNT_KIND;
+use kaspa_wallet_core::tx::{Generator, GeneratorSettings, PaymentDestination, PaymentOutputs, Signer};
+use kaspa_wallet_core::utxo::{Maturity, NetworkParams, UtxoEntryId, UtxoEntryReference, UtxoIterator};
+use kaspa_wallet_core::wallet::Wallet;
+
+const UTXO_PAGE_SIZE: u64 = 100;
+pub const MAX_SPLIT_OUTPUTS: usize = 100;
+
+#[derive(Clone)]
+pub struct UtxoRecord {
+    pub id: UtxoEntryId,
+    pub reference: UtxoEntryReference,
+}
+
+impl UtxoRecord {
+    fn amount(&self) -> u64 {
+        self.reference.amount()
+    }
+
+    fn block_daa_score(&self) -> u64 {
+        self.reference.block_daa_score()
+    }
+
+    fn is_coinbase(&self) -> bool {
+        self.reference.is_coinbase()
+    }
+
+    fn maturity(&self, params : &NetworkParams, current_daa_score : u64) -> Maturity {
+        self.reference.maturity(params, current_daa_score)
+    }
+
+    fn outpoint(&self) -> String {
+        format!("{}:{}", self.id.transaction_id(), self.id.index())
+    }
+}
+
+/// UTXO subset selected in the [`UtxoManager`] that is handed
+/// over to the estimator/processor as the only funding source.
+#[derive(Clone)]
+pub struct UtxoSelection {
+    pub entries: Arc<Vec<UtxoEntryReference>>,
+    pub total_sompi: u64,
+}
+
+impl UtxoSelection {
+    fn new(entries: Vec<UtxoEntryReference>) -> Self {
+        let total_sompi = entries.iter().map(|entry| entry.amount()).sum();
+        Self { entries: Arc::new(entries), total_sompi }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn render(&self, ui : &mut Ui, network_type : NetworkType) {
+        ui.add_space(8.);
+        ui.label(i18n_args("Spending {count} selected UTXOs ({amount})", &[
+            ("count", self.len().separated_string()),
+            ("amount", sompi_to_kaspa_string_with_suffix(self.total_sompi, &network_type)),
+        ]));
+    }
+}
+
+#[derive(Default, Clone)]
+enum UtxoStatus {
+    #[default]
+    None,
+    Loading,
+    Ready(Arc<Vec<UtxoRecord>>),
+    Error(String),
+}
 
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
+enum UtxoSort {
+    #[default]
+    Amount,
+    DaaScore,
+    Outpoint,
+}
+
+impl UtxoSort {
+    fn iter() -> impl Iterator<Item = &'static UtxoSort> {
+        [UtxoSort::Amount, UtxoSort::DaaScore, UtxoSort::Outpoint].iter()
+    }
+}
+
+impl std::fmt::Display for UtxoSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UtxoSort::Amount => write!(f, "{}", i18n("Amount")),
+            UtxoSort::DaaScore => write!(f, "{}", i18n("DAA Score")),
+            UtxoSort::Outpoint => write!(f, "{}", i18n("Outpoint")),
+        }
+    }
+}
+
+#[derive(Default)]
 pub struct UtxoManager {
+    account_id : Option<AccountId>,
+    status : Arc<Mutex<UtxoStatus>>,
+    sort : UtxoSort,
+    descending : bool,
+    min_amount_text : String,
+    max_amount_text : String,
+    hide_coinbase : bool,
+    selected : AHashSet<UtxoEntryId>,
+    page_start : u64,
 }
 
 impl UtxoManager {
     pub fn new() -> Self {
-        Self { }
+        Self { descending : true, ..Default::default() }
+    }
+
+    /// Renders the UTXO list. Returns `true` if a transaction
+    /// has been staged in the [`ManagerContext`] and the caller
+    /// should switch to the overview (estimator) pane.
+    pub fn render(&mut self, core: &mut Core, ui : &mut Ui, rc : &RenderContext, context : &mut ManagerContext) -> bool {
+        use egui_phosphor::light::{ARROWS_CLOCKWISE, ARROWS_IN_SIMPLE, ARROWS_OUT_SIMPLE, ARROW_CIRCLE_UP};
+
+        let RenderContext { account, network_type, current_daa_score, .. } = rc;
+        let network_type = *network_type;
+        let network_params : &NetworkParams = core.settings.node.network.into();
+
+        if self.account_id != Some(account.id()) {
+            self.account_id = Some(account.id());
+            self.selected.clear();
+            self.page_start = 0;
+            self.reload(account);
+        }
+
+        // the submitted UTXOs are spent; drop the selection so they
+        // can not be staged again and refresh the list
+        if std::mem::take(&mut context.utxos_spent) {
+            self.selected.clear();
+            self.reload(account);
+        }
+
+        let status = self.status.lock().unwrap().clone();
+        let records = match status {
+            UtxoStatus::None | UtxoStatus::Loading => {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(32.);
+                    ui.add(egui::Spinner::new().size(48.));
+                });
+                return false;
+            }
+            UtxoStatus::Error(err) => {
+                ui.label(RichText::new(err).color(theme_color().error_color));
+                if ui.medium_button(i18n("Retry")).clicked() {
+                    self.reload(account);
+                }
+                return false;
+            }
+            UtxoStatus::Ready(records) => records,
+        };
+
+        let min_amount = try_kaspa_str_to_sompi(self.min_amount_text.as_str()).ok().flatten();
+        let max_amount = try_kaspa_str_to_sompi(self.max_amount_text.as_str()).ok().flatten();
+
+        let mut filtered = records.iter().filter(|record| {
+            min_amount.map(|min| record.amount() >= min).unwrap_or(true)
+                && max_amount.map(|max| record.amount() <= max).unwrap_or(true)
+                && !(self.hide_coinbase && record.is_coinbase())
+        }).collect::<Vec<_>>();
+
+        filtered.sort_by(|a, b| {
+            let ordering = match self.sort {
+                UtxoSort::Amount => a.amount().cmp(&b.amount()),
+                UtxoSort::DaaScore => a.block_daa_score().cmp(&b.block_daa_score()),
+                UtxoSort::Outpoint => a.outpoint().cmp(&b.outpoint()),
+            };
+            if self.descending { ordering.reverse() } else { ordering }
+        });
+
+        let selected_records = records.iter().filter(|record| self.selected.contains(&record.id)).collect::<Vec<_>>();
+        let selected_total : u64 = selected_records.iter().map(|record| record.amount()).sum();
+        let total : u64 = records.iter().map(|record| record.amount()).sum();
+
+        let mut staged = false;
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(i18n_args("UTXOs: {count}", &[("count", records.len().separated_string())]));
+            ui.separator();
+            ui.label(i18n_args("Total: {amount}", &[("amount", sompi_to_kaspa_string_with_suffix(total, &network_type))]));
+            ui.separator();
+            ui.label(RichText::new(i18n_args("Selected: {count} ({amount})", &[
+                ("count", selected_records.len().separated_string()),
+                ("amount", sompi_to_kaspa_string_with_suffix(selected_total, &network_type)),
+            ])).strong());
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add(Label::new(RichText::new(ARROWS_CLOCKWISE).size(16.)).sense(Sense::click())).on_hover_text(i18n("Refresh")).clicked() {
+                    self.reload(account);
+                }
+            });
+        });
+
+        ui.add_space(4.);
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(i18n("Min:"));
+            ui.add(TextEdit::singleline(&mut self.min_amount_text).desired_width(72.));
+            ui.label(i18n("Max:"));
+            ui.add(TextEdit::singleline(&mut self.max_amount_text).desired_width(72.));
+            ui.checkbox(&mut self.hide_coinbase, i18n("Hide coinbase"));
+        });
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(i18n("Sort by:"));
+            ComboBox::from_id_salt("utxo_manager_sort")
+                .selected_text(self.sort.to_string())
+                .show_ui(ui, |ui| {
+                    UtxoSort::iter().for_each(|sort| {
+                        ui.selectable_value(&mut self.sort, *sort, sort.to_string());
+                    });
+                });
+            ui.checkbox(&mut self.descending, i18n("Descending"));
+
+            ui.separator();
+
+            if ui.button(i18n("Select filtered")).clicked() {
+                filtered.iter().for_each(|record| { self.selected.insert(record.id.clone()); });
+            }
+            if ui.button(i18n("Clear selection")).clicked() {
+                self.selected.clear();
+            }
+        });
+
+        ui.add_space(4.);
+
+        let can_stage = !selected_records.is_empty() && matches!(context.action, Action::None)
+            && !account.is_watch_only() && !account.is_multisig() && core.state().is_connected() && core.state().is_synced();
+
+        ui.horizontal_wrapped(|ui| {
+            let selection = || UtxoSelection::new(selected_records.iter().map(|record| record.reference.clone()).collect());
+
+            if ui.add_enabled(can_stage && selected_records.len() > 1, Button::new(format!("{ARROWS_IN_SIMPLE} {}", i18n("Consolidate")))).clicked() {
+                context.reset_send_state();
+                context.utxo_selection = Some(selection());
+                context.transaction_kind = Some(TransactionKind::Consolidate);
+                context.action = Action::Estimating;
+                context.request_estimate = Some(true);
+                staged = true;
+            }
+
+            if ui.add_enabled(can_stage, Button::new(format!("{ARROWS_OUT_SIMPLE} {}", i18n("Split")))).clicked() {
+                context.reset_send_state();
+                context.utxo_selection = Some(selection());
+                context.transaction_kind = Some(TransactionKind::Split);
+                context.action = Action::Estimating;
+                context.split_outputs_text = "2".to_string();
+                context.focus.next(Focus::Outputs);
+                staged = true;
+            }
+
+            if ui.add_enabled(can_stage, Button::new(format!("{ARROW_CIRCLE_UP} {}", i18n("Send from selected")))).clicked() {
+                context.reset_send_state();
+                context.utxo_selection = Some(selection());
+                context.transaction_kind = Some(TransactionKind::Send);
+                context.action = Action::Estimating;
+                context.focus.next(Focus::Address);
+                staged = true;
+            }
+        });
+
+        if !matches!(context.action, Action::None) && context.utxo_selection.is_none() {
+            ui.label(RichText::new(i18n("Please complete or cancel the pending transaction first.")).color(theme_color().warning_color));
+        }
+
+        ui.add_space(4.);
+        ui.separator();
+
+        let max_height = ui.available_height() - (ui.fonts(|fonts|RichText::new("YWgy").font_height(fonts, ui.style())).at_least(ui.spacing().interact_size.y) * 2.0 + 5.0);
+        let page_start = self.page_start.min(filtered.len() as u64 / UTXO_PAGE_SIZE * UTXO_PAGE_SIZE);
+
+        egui::ScrollArea::vertical().id_salt("utxo_manager_list").max_height(max_height).auto_shrink([false,false]).show(ui, |ui| {
+            if filtered.is_empty() {
+                ui.vertical_centered(|ui| {
+                    ui.label("");
+                    ui.label(RichText::new(i18n("No UTXOs")).size(16.));
+                });
+                return;
+            }
+
+            Grid::new("utxo_manager_grid")
+                .striped(true)
+                .num_columns(6)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.label(RichText::new(i18n("Amount")).strong());
+                    ui.label(RichText::new(i18n("Outpoint")).strong());
+                    ui.label(RichText::new(i18n("DAA Score")).strong());
+                    ui.label(RichText::new(i18n("Confirmations")).strong());
+                    ui.label(RichText::new(i18n("Maturity")).strong());
+                    ui.end_row();
+
+                    filtered.iter().skip(page_start as usize).take(UTXO_PAGE_SIZE as usize).for_each(|record| {
+                        let mut is_selected = self.selected.contains(&record.id);
+                        if ui.checkbox(&mut is_selected, "").changed() {
+                            if is_selected {
+                                self.selected.insert(record.id.clone());
+                            } else {
+                                self.selected.remove(&record.id);
+                            }
+                        }
+
+                        ui.label(RichText::new(sompi_to_kaspa_string_with_suffix(record.amount(), &network_type)).color(theme_color().balance_color));
+
+                        let outpoint = record.outpoint();
+                        let response = ui.add(Label::new(RichText::new(format_partial_string(&outpoint, Some(12))).monospace()).sense(Sense::click()));
+                        if response.on_hover_text(&outpoint).clicked() {
+                            ui.ctx().copy_text(outpoint.clone());
+                            runtime().notify_clipboard(i18n("Copied to clipboard"));
+                        }
+
+                        ui.label(record.block_daa_score().separated_string());
+
+                        let confirmations = current_daa_score.map(|daa_score| daa_score.saturating_sub(record.block_daa_score()).separated_string()).unwrap_or_else(|| "---".to_string());
+                        if record.is_coinbase() {
+                            ui.label(format!("{confirmations} ({})", i18n("coinbase")));
+                        } else {
+                            ui.label(confirmations);
+                        }
+
+                        match current_daa_score.map(|daa_score| record.maturity(network_params, daa_score)) {
+                            Some(Maturity::Confirmed) => ui.label(RichText::new(i18n("Mature")).color(theme_color().ack_color)),
+                            Some(Maturity::Pending) => ui.label(RichText::new(i18n("Pending")).color(theme_color().warning_color)),
+                            Some(Maturity::Stasis) => ui.label(RichText::new(i18n("Stasis")).color(theme_color().transaction_stasis)),
+                            None => ui.label("---"),
+                        };
+                        ui.end_row();
+                    });
+                });
+        });
+
+        ui.add_space(4.);
+        let pagination = Pagination::new(filtered.len() as u64, Some(page_start), Some(UTXO_PAGE_SIZE), Some(5));
+        if let Some(start) = pagination.render(ui) {
+            self.page_start = start;
+        }
+
+        staged
     }
 
-    pub fn render(&mut self, _core: &mut Core, ui : &mut Ui, rc : &RenderContext) {
-        let RenderContext { account: _, .. } = rc;
+    fn reload(&mut self, account : &Account) {
+        let status = self.status.clone();
+        *status.lock().unwrap() = UtxoStatus::Loading;
+        let account_id = account.id();
 
-        egui::ScrollArea::vertical().auto_shrink([false,false]).show(ui, |ui| {
-            ui.label(i18n("UTXO Manager"));
+        let wallet = runtime().wallet().downcast_arc::<Wallet>().ok();
+        spawn(async move {
+            let Some(wallet) = wallet else {
+                *status.lock().unwrap() = UtxoStatus::Error(i18n("Unable to access the wallet subsystem").to_string());
+                return Ok(());
+            };
 
-            ui.label("");
-            ui.label("UTXO management is not implemented in this beta release.");
-            ui.label("");
+            let binding = wallet.guard();
+            let guard = binding.lock().await;
+            if let Some(account) = wallet.get_account_by_id(&account_id, &guard).await? {
+                let records = UtxoIterator::new(account.utxo_context())
+                    .map(|reference| UtxoRecord { id: reference.id(), reference })
+                    .collect::<Vec<_>>();
+                *status.lock().unwrap() = UtxoStatus::Ready(Arc::new(records));
+            } else {
+                *status.lock().unwrap() = UtxoStatus::Error(i18n("Account not found").to_string());
+            }
 
+            runtime().request_repaint();
+            Ok(())
         });
+    }
+}
 
+/// Builds generator settings that draw only from the selected UTXOs.
+/// Consolidation sweeps the selection into the account change address,
+/// while split and send produce explicit payment outputs.
+fn generator_settings(account : &Arc<dyn CoreAccount>, selection : &UtxoSelection, destination : PaymentDestination, fee_rate : Option<f64>) -> Result<GeneratorSettings> {
+    let network_id = account.wallet().network_id()?;
+    let priority_fee_sompi = if matches!(destination, PaymentDestination::Change) { Fees::None } else { Fees::SenderPays(0) };
+    let entries = selection.entries.as_ref().clone();
+
+    Ok(GeneratorSettings::try_new_with_iterator(
+        network_id,
+        Box::new(entries.into_iter()),
+        None,
+        account.change_address()?,
+        account.sig_op_count(),
+        account.minimum_signatures(),
+        destination,
+        fee_rate,
+        priority_fee_sompi,
+        None,
+        Some(account.wallet().multiplexer().clone()),
+    )?)
+}
+
+async fn estimate_with_settings(settings : GeneratorSettings) -> Result<GeneratorSummary> {
+    let generator = Generator::try_new(settings, None, None)?;
+    let mut stream = generator.stream();
+    while stream.try_next().await?.is_some() {
+        yield_executor().await;
     }
-}
\ No newline at end of file
+    Ok(generator.summary())
+}
+
+pub async fn with_account<F, Fut, R>(account_id : AccountId, f : F) -> Result<R>
+where
+    F: FnOnce(Arc<dyn CoreAccount>) -> Fut,
+    Fut: Future<Output = Result<R>>,
+{
+    let wallet = runtime().wallet().downcast_arc::<Wallet>().map_err(|_| Error::WalletIsNotLocal)?;
+    let account = {
+        let binding = wallet.guard();
+        let guard = binding.lock().await;
+        wallet.get_account_by_id(&account_id, &guard).await?
+    };
+    let account = account.ok_or_else(|| Error::custom(i18n("Account not found")))?;
+    f(account).await
+}
+
+/// Mirrors `calculate_fee_rate()` in the processor: the priority fee entered
+/// by the user is converted into a feerate against the zero-fee mass of the
+/// same UTXO-constrained transaction set.
+async fn utxo_fee_rate(account : &Arc<dyn CoreAccount>, selection : &UtxoSelection, destination : PaymentDestination, priority_fee_sompi : u64) -> f64 {
+    let base_mass = match generator_settings(account, selection, destination, Some(0.0)) {
+        Ok(settings) => estimate_with_settings(settings).await.map(|summary| summary.aggregate_mass).unwrap_or_default(),
+        Err(_) => 0,
+    };
+
+    if base_mass == 0 {
+        1.0
+    } else {
+        priority_fee_sompi as f64 / base_mass as f64
+    }
+}
+
+pub async fn estimate_utxo_transaction(account_id : AccountId, selection : UtxoSelection, destination : PaymentDestination, priority_fee_sompi : u64) -> Result<GeneratorSummary> {
+    with_account(account_id, |account| async move {
+        let fee_rate = utxo_fee_rate(&account, &selection, destination.clone(), priority_fee_sompi).await;
+        let settings = generator_settings(&account, &selection, destination, Some(fee_rate))?;
+        estimate_with_settings(settings).await
+    }).await
+}
+
+pub async fn submit_utxo_transaction(account_id : AccountId, selection : UtxoSelection, destination : PaymentDestination, priority_fee_sompi : u64, wallet_secret : Secret, payment_secret : Option<Secret>) -> Result<GeneratorSummary> {
+    with_account(account_id, |account| async move {
+        // multisig transactions are signed by the co-signers through a PSKB
+        if account.account_kind().as_ref() == MULTISIG_ACCOUNT_KIND {
+            return Err(Error::custom(i18n("UTXO selection is not supported for multisig accounts")));
+        }
+
+        let fee_rate = utxo_fee_rate(&account, &selection, destination.clone(), priority_fee_sompi).await;
+        let settings = generator_settings(&account, &selection, destination, Some(fee_rate))?;
+
+        let keydata = account.prv_key_data(wallet_secret).await?;
+        let signer = Arc::new(Signer::new(account.clone(), keydata, payment_secret));
+        let generator = Generator::try_new(settings, Some(signer), None)?;
+        let rpc_api = account.wallet().rpc_api().clone();
+
+        let mut stream = generator.stream();
+        while let Some(transaction) = stream.try_next().await? {
+            transaction.try_sign()?;
+            transaction.try_submit(&rpc_api).await?;
+            yield_executor().await;
+        }
+
+        Ok(generator.summary())
+    }).await
+}
+
+/// Equal-value payment outputs to the account receive address
+/// used by the split operation.
+pub fn split_destination(address : &Address, outputs : usize, amount_per_output : u64) -> PaymentDestination {
+    PaymentOutputs {
+        outputs: (0..outputs).map(|_| PaymentOutput { address: address.clone(), amount: amount_per_output }).collect(),
+    }.into()
+}
+
+/// Resolves the payment destination for a UTXO-constrained transaction
+/// staged by the [`UtxoManager`]. While the destination address is still
+/// being entered, the account receive address stands in for the estimate.
+pub fn utxo_destination(context : &ManagerContext, rc : &RenderContext) -> PaymentDestination {
+    match context.transaction_kind {
+        Some(TransactionKind::Consolidate) => PaymentDestination::Change,
+        Some(TransactionKind::Split) => {
+            split_destination(rc.context.address(), context.split_outputs, context.send_amount_sompi)
+        }
+        _ => {
+            let address = Address::try_from(context.destination_address_string.as_str())
+                .unwrap_or_else(|_| rc.context.address().clone());
+            PaymentOutput { address, amount: context.send_amount_sompi }.into()
+        }
+    }
+}
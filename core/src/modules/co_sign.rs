@@ -0,0 +1,285 @@
+use crate::imports::*;
+use crate::modules::account_manager::pskb::*;
+use egui_phosphor::light::CLIPBOARD_TEXT;
+
+#[derive(Clone, Default)]
+enum State {
+    #[default]
+    Edit,
+    Secret,
+    Processing,
+    Broadcast(Arc<Vec<String>>),
+}
+
+enum CoSignResult {
+    Signed(String),
+    Broadcast(Vec<String>),
+}
+
+/// Imports, signs and broadcasts partially signed transaction bundles
+/// (PSKB) exchanged between the cosigners of a multisig account.
+pub struct CoSign {
+    #[allow(dead_code)]
+    runtime: Runtime,
+    account : Option<Account>,
+    state : State,
+    pskb : String,
+    summary : Option<(String, std::result::Result<PskbSummary, String>)>,
+    secret : SecretPrompt,
+    error : Option<String>,
+}
+
+impl CoSign {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            runtime,
+            account : None,
+            state : State::default(),
+            pskb : String::default(),
+            summary : None,
+            secret : SecretPrompt::default(),
+            error : None,
+        }
+    }
+
+    pub fn select(&mut self, account : &Account, pskb : Option<String>) {
+        self.account = Some(account.clone());
+        self.state = State::Edit;
+        self.pskb = pskb.unwrap_or_default();
+        self.summary = None;
+        self.error = None;
+    }
+
+    fn summary(&mut self, network_type : NetworkType) -> Option<std::result::Result<PskbSummary, String>> {
+        if self.pskb.trim().is_empty() {
+            return None;
+        }
+
+        let stale = self.summary.as_ref().map(|(pskb, _)| pskb != &self.pskb).unwrap_or(true);
+        if stale {
+            let summary = PskbSummary::try_new(self.pskb.as_str(), network_type).map_err(|err| err.to_string());
+            self.summary = Some((self.pskb.clone(), summary));
+        }
+
+        self.summary.as_ref().map(|(_, summary)| summary.clone())
+    }
+
+    fn render_summary(ui : &mut Ui, summary : &PskbSummary, network_type : &NetworkType) {
+        ui.label(i18n_args("Transactions: {count}", &[("count", summary.transactions.to_string())]));
+        ui.label(i18n_args("Inputs: {amount}", &[("amount", sompi_to_kaspa_string_with_suffix(summary.input_amount, network_type))]));
+
+        let signatures = summary.signatures.iter().map(|count| count.to_string()).collect::<Vec<_>>().join(", ");
+        ui.label(i18n_args("Signatures per input: {signatures}", &[("signatures", signatures)]));
+
+        ui.add_space(8.);
+        ui.label(RichText::new(i18n("Outputs")).strong());
+        for (address, amount) in summary.outputs.iter() {
+            ui.label(format!("{} {}", format_address_string(address.as_str(), Some(12)), sompi_to_kaspa_string_with_suffix(*amount, network_type)));
+        }
+
+        if summary.fees.len() > 1 {
+            for (index, fee) in summary.fees.iter().enumerate() {
+                ui.label(i18n_args("Transaction {index} fee: {amount}", &[("index", (index + 1).to_string()), ("amount", sompi_to_kaspa_string_with_suffix(*fee, network_type))]));
+            }
+        }
+        ui.label(i18n_args("Total fees: {amount}", &[("amount", sompi_to_kaspa_string_with_suffix(summary.total_fees(), network_type))]));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_file_operations(&mut self, ui : &mut Ui) {
+        if ui.button(i18n("Load from file")).clicked() {
+            if let Some(path) = rfd::FileDialog::new().add_filter("PSKB", &["pskb", "txt"]).pick_file() {
+                match std::fs::read_to_string(&path) {
+                    Ok(pskb) => self.pskb = pskb.trim().to_string(),
+                    Err(err) => runtime().error(err.to_string()),
+                }
+            }
+        }
+
+        if ui.add_enabled(self.pskb.is_not_empty(), Button::new(i18n("Save to file"))).clicked() {
+            if let Some(path) = rfd::FileDialog::new().set_file_name("transaction.pskb").add_filter("PSKB", &["pskb"]).save_file() {
+                match std::fs::write(&path, self.pskb.as_bytes()) {
+                    Ok(()) => runtime().notify(UserNotification::success(i18n_args("PSKB saved to {path}", &[("path", path.to_string_lossy())]))),
+                    Err(err) => runtime().error(err.to_string()),
+                }
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn render_file_operations(&mut self, _ui : &mut Ui) { }
+}
+
+impl ModuleT for CoSign {
+
+    fn style(&self) -> ModuleStyle {
+        ModuleStyle::Mobile
+    }
+
+    fn secure(&self) -> bool {
+        true
+    }
+
+    fn deactivate(&mut self, _core: &mut Core) {
+        self.secret.clear();
+    }
+
+    fn render(
+        &mut self,
+        core: &mut Core,
+        _ctx: &egui::Context,
+        _frame: &mut eframe::Frame,
+        ui: &mut egui::Ui,
+    ) {
+        let network_type : NetworkType = core.network().into();
+        let requires_bip39_passphrase = self.account.as_ref().map(|account| account.requires_bip39_passphrase(core)).unwrap_or(false);
+        let summary = self.summary(network_type);
+        let co_sign_result = Payload::<Result<CoSignResult>>::new("co_sign_result");
+
+        if let Some(result) = co_sign_result.take() {
+            match result {
+                Ok(CoSignResult::Signed(pskb)) => {
+                    self.pskb = pskb;
+                    self.state = State::Edit;
+                    runtime().notify(UserNotification::success(i18n("Signatures added to the PSKB")));
+                }
+                Ok(CoSignResult::Broadcast(transaction_ids)) => {
+                    self.state = State::Broadcast(Arc::new(transaction_ids));
+                }
+                Err(err) => {
+                    self.error = Some(err.to_string());
+                    self.state = State::Edit;
+                }
+            }
+        }
+
+        let close = Rc::new(RefCell::new(false));
+        let submit_sign = Rc::new(RefCell::new(false));
+        let submit_broadcast = Rc::new(RefCell::new(false));
+
+        Panel::new(self)
+            .with_caption(i18n("Co-sign Transaction"))
+            .with_back_enabled(core.has_stack(), |_|{
+                *close.borrow_mut() = true;
+            })
+            .with_header(|this, ui| {
+                if let Some(account) = this.account.as_ref() {
+                    ui.label(i18n_args("Multisig account: {account}", &[("account", account.name_or_id())]));
+                } else {
+                    ui.label(i18n("Please select a multisig account"));
+                }
+            })
+            .with_body(|this, ui| {
+
+                match this.state.clone() {
+                    State::Edit => {
+                        ui.label(i18n("Paste a partially signed transaction (PSKB) received from a cosigner."));
+                        ui.add_space(8.);
+                        ui.add_sized([ui.available_width(), 96.], TextEdit::multiline(&mut this.pskb)
+                            .font(egui::TextStyle::Monospace));
+
+                        ui.add_space(8.);
+                        ui.horizontal(|ui| {
+                            if ui.add_enabled(this.pskb.is_not_empty(), Button::new(format!("{CLIPBOARD_TEXT} {}", i18n("Copy")))).clicked() {
+                                ui.ctx().copy_text(this.pskb.clone());
+                                runtime().notify_clipboard(i18n("Copied to clipboard"));
+                            }
+                            this.render_file_operations(ui);
+                        });
+
+                        ui.add_space(8.);
+                        match summary.as_ref() {
+                            Some(Ok(summary)) => Self::render_summary(ui, summary, &network_type),
+                            Some(Err(err)) => { ui.colored_label(error_color(), err); }
+                            None => { }
+                        }
+
+                        if let Some(error) = this.error.as_ref() {
+                            ui.add_space(8.);
+                            ui.colored_label(error_color(), error);
+                        }
+                    }
+
+                    State::Secret => {
+                        if this.secret.render(ui, requires_bip39_passphrase) {
+                            *submit_sign.borrow_mut() = true;
+                        }
+                    }
+
+                    State::Processing => {
+                        ui.add_space(64.);
+                        ui.add(egui::Spinner::new().size(92.));
+                    }
+
+                    State::Broadcast(transaction_ids) => {
+                        ui.label(i18n("Transaction submitted"));
+                        ui.add_space(8.);
+                        for id in transaction_ids.iter() {
+                            ui.label(RichText::new(format_partial_string(id.as_str(), Some(16))).monospace());
+                        }
+                    }
+                }
+            })
+            .with_footer(|this, ui| {
+                let ready = this.account.is_some() && matches!(summary, Some(Ok(_)));
+                match this.state {
+                    State::Edit => {
+                        if ui.large_button_enabled(ready, i18n("Sign")).clicked() {
+                            this.error = None;
+                            this.state = State::Secret;
+                            this.secret.activate();
+                        }
+                        if ui.large_button_enabled(ready, i18n("Broadcast")).clicked() {
+                            this.error = None;
+                            *submit_broadcast.borrow_mut() = true;
+                        }
+                    }
+                    State::Secret => {
+                        match this.secret.render_footer(ui, i18n("Sign"), requires_bip39_passphrase) {
+                            SecretPromptAction::Submit => { *submit_sign.borrow_mut() = true; }
+                            SecretPromptAction::Cancel => { this.state = State::Edit; }
+                            SecretPromptAction::None => { }
+                        }
+                    }
+                    State::Processing => { }
+                    State::Broadcast(_) => {
+                        if ui.large_button(i18n("Close")).clicked() {
+                            *close.borrow_mut() = true;
+                        }
+                    }
+                }
+            })
+            .render(ui);
+
+        if let Some(account_id) = self.account.as_ref().map(|account| account.id()) {
+            if *submit_sign.borrow() {
+                let pskb = self.pskb.clone();
+                let (wallet_secret, payment_secret) = self.secret.secrets(requires_bip39_passphrase);
+                self.secret.clear();
+                self.state = State::Processing;
+
+                spawn_with_result(&co_sign_result, async move {
+                    let pskb = sign_pskb(account_id, pskb, wallet_secret, payment_secret).await?;
+                    runtime().request_repaint();
+                    Ok(CoSignResult::Signed(pskb))
+                });
+            } else if *submit_broadcast.borrow() {
+                let pskb = self.pskb.clone();
+                self.state = State::Processing;
+
+                spawn_with_result(&co_sign_result, async move {
+                    let transaction_ids = broadcast_pskb(account_id, pskb).await?;
+                    runtime().request_repaint();
+                    Ok(CoSignResult::Broadcast(transaction_ids))
+                });
+            }
+        }
+
+        if *close.borrow() {
+            self.state = State::Edit;
+            self.pskb.clear();
+            self.summary = None;
+            core.back();
+        }
+    }
+}
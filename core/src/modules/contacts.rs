@@ -0,0 +1,263 @@
+use crate::imports::*;
+use crate::address_book::parse_tags;
+use egui_phosphor::light::{ARROW_CIRCLE_UP, CLIPBOARD_TEXT, PENCIL_SIMPLE, PLUS};
+
+#[derive(Clone, Default)]
+enum State {
+    #[default]
+    List,
+    Edit { index : Option<usize> },
+}
+
+enum ContactAction {
+    Send(String),
+    Save(Option<usize>, Contact),
+    Remove(usize),
+}
+
+/// Address book of the open wallet; lists, searches and edits the
+/// contacts of the current network.
+pub struct Contacts {
+    #[allow(dead_code)]
+    runtime: Runtime,
+    state : State,
+    search : String,
+    name : String,
+    address : String,
+    note : String,
+    tags : String,
+    error : Option<String>,
+}
+
+impl Contacts {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            runtime,
+            state : State::default(),
+            search : String::default(),
+            name : String::default(),
+            address : String::default(),
+            note : String::default(),
+            tags : String::default(),
+            error : None,
+        }
+    }
+
+    /// Opens the editor for a new contact, optionally pre-filled with an address.
+    pub fn add(&mut self, address : Option<String>) {
+        self.edit(None, &Contact { address : address.unwrap_or_default(), ..Default::default() });
+    }
+
+    fn edit(&mut self, index : Option<usize>, contact : &Contact) {
+        self.name = contact.name.clone();
+        self.address = contact.address.clone();
+        self.note = contact.note.clone();
+        self.tags = contact.tags.join(", ");
+        self.error = None;
+        self.state = State::Edit { index };
+    }
+
+    fn editor(ui : &mut Ui, caption : &str, text : &mut String) {
+        ui.label(RichText::new(caption).size(12.).raised());
+        ui.add_sized(theme_style().panel_editor_size, TextEdit::singleline(text)
+            .vertical_align(Align::Center));
+        ui.add_space(8.);
+    }
+
+    fn render_contact(ui : &mut Ui, index : usize, contact : &Contact, action : &RefCell<Option<ContactAction>>, edit : &RefCell<Option<(usize, Contact)>>) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(contact.name.as_str()).strong());
+            if !contact.tags.is_empty() {
+                ui.label(RichText::new(contact.tags.join(", ")).small());
+            }
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if ui.button(PENCIL_SIMPLE).on_hover_text(i18n("Edit")).clicked() {
+                    edit.borrow_mut().replace((index, contact.clone()));
+                }
+                if ui.button(ARROW_CIRCLE_UP).on_hover_text(i18n("Send")).clicked() {
+                    action.borrow_mut().replace(ContactAction::Send(contact.address.clone()));
+                }
+                if ui.button(CLIPBOARD_TEXT).on_hover_text(i18n("Copy address")).clicked() {
+                    ui.ctx().copy_text(contact.address.clone());
+                    runtime().notify_clipboard(i18n("Address copied to clipboard"));
+                }
+            });
+        });
+        ui.label(RichText::new(format_address_string(contact.address.as_str(), Some(16))).monospace());
+        if contact.note.is_not_empty() {
+            ui.label(RichText::new(contact.note.as_str()).small());
+        }
+        ui.separator();
+    }
+}
+
+impl ModuleT for Contacts {
+
+    fn style(&self) -> ModuleStyle {
+        ModuleStyle::Mobile
+    }
+
+    fn secure(&self) -> bool {
+        true
+    }
+
+    fn deactivate(&mut self, _core: &mut Core) {
+        self.state = State::List;
+        self.error = None;
+    }
+
+    fn reset(&mut self, _core: &mut Core) {
+        self.state = State::List;
+        self.search.clear();
+        self.error = None;
+    }
+
+    fn render(
+        &mut self,
+        core: &mut Core,
+        _ctx: &egui::Context,
+        _frame: &mut eframe::Frame,
+        ui: &mut egui::Ui,
+    ) {
+        let network = core.settings.node.network;
+        let contacts = core.address_book().as_ref().map(|address_book| {
+            address_book
+                .contacts_for_network(network)
+                .filter(|(_, contact)| contact.matches(self.search.as_str()))
+                .map(|(index, contact)| (index, contact.clone()))
+                .collect::<Vec<_>>()
+        });
+
+        let close = Rc::new(RefCell::new(false));
+        let action = RefCell::new(None);
+        let edit = RefCell::new(None);
+
+        Panel::new(self)
+            .with_caption(i18n("Address Book"))
+            .with_back_enabled(core.has_stack(), |_|{
+                *close.borrow_mut() = true;
+            })
+            .with_header(|this, ui| {
+                match this.state {
+                    State::List => {
+                        ui.label(i18n_args("Contacts on {network}", &[("network", network.to_string())]));
+                        ui.add_space(4.);
+                        ui.add_sized(theme_style().panel_editor_size, TextEdit::singleline(&mut this.search)
+                            .hint_text(i18n("Search by name, address or tag"))
+                            .vertical_align(Align::Center));
+                    }
+                    State::Edit { index } => {
+                        ui.label(if index.is_some() { i18n("Edit contact") } else { i18n("New contact") });
+                    }
+                }
+            })
+            .with_body(|this, ui| {
+                match this.state.clone() {
+                    State::List => {
+                        match contacts.as_ref() {
+                            None => {
+                                ui.label(i18n("Please open a wallet to use the address book"));
+                            }
+                            Some(contacts) if contacts.is_empty() => {
+                                ui.label(i18n("No contacts"));
+                            }
+                            Some(contacts) => {
+                                for (index, contact) in contacts.iter() {
+                                    Self::render_contact(ui, *index, contact, &action, &edit);
+                                }
+                            }
+                        }
+                    }
+                    State::Edit { index } => {
+                        Self::editor(ui, i18n("Name"), &mut this.name);
+                        Self::editor(ui, i18n("Address"), &mut this.address);
+                        if this.address.is_not_empty() {
+                            if let Err(err) = try_user_string_to_address(this.address.trim(), &network.into()) {
+                                ui.colored_label(error_color(), err.to_string());
+                                ui.add_space(8.);
+                            }
+                        }
+                        Self::editor(ui, i18n("Note"), &mut this.note);
+                        Self::editor(ui, i18n("Tags (comma separated)"), &mut this.tags);
+
+                        if let Some(error) = this.error.as_ref() {
+                            ui.colored_label(error_color(), error);
+                        }
+
+                        if let Some(index) = index {
+                            ui.add_space(8.);
+                            if ui.button(i18n("Delete contact")).clicked() {
+                                action.borrow_mut().replace(ContactAction::Remove(index));
+                            }
+                        }
+                    }
+                }
+            })
+            .with_footer(|this, ui| {
+                match this.state {
+                    State::List => {
+                        if ui.large_button_enabled(contacts.is_some(), format!("{PLUS} {}", i18n("Add contact"))).clicked() {
+                            this.add(None);
+                        }
+                    }
+                    State::Edit { index } => {
+                        let enabled = this.name.trim().is_not_empty() && this.address.trim().is_not_empty();
+                        if ui.large_button_enabled(enabled, i18n("Save")).clicked() {
+                            let contact = Contact {
+                                name : this.name.clone(),
+                                address : this.address.clone(),
+                                network,
+                                note : this.note.clone(),
+                                tags : parse_tags(this.tags.as_str()),
+                            };
+                            action.borrow_mut().replace(ContactAction::Save(index, contact));
+                        }
+                        if ui.large_button(i18n("Cancel")).clicked() {
+                            this.state = State::List;
+                        }
+                    }
+                }
+            })
+            .render(ui);
+
+        if let Some((index, contact)) = edit.take() {
+            self.edit(Some(index), &contact);
+        }
+
+        match action.take() {
+            Some(ContactAction::Send(address)) => {
+                match Address::try_from(address.as_str()) {
+                    Ok(address) => {
+                        core.get_mut::<modules::AccountManager>().open_payment_uri(PaymentUri::new(address));
+                        core.select::<modules::AccountManager>();
+                    }
+                    Err(err) => runtime().error(err.to_string()),
+                }
+            }
+            Some(ContactAction::Save(index, contact)) => {
+                let result = core.update_address_book(|address_book| {
+                    match index {
+                        Some(index) => address_book.update(index, contact),
+                        None => address_book.insert(contact),
+                    }
+                });
+                match result {
+                    Ok(()) => self.state = State::List,
+                    Err(err) => self.error = Some(err.to_string()),
+                }
+            }
+            Some(ContactAction::Remove(index)) => {
+                match core.update_address_book(|address_book| { address_book.remove(index); Ok(()) }) {
+                    Ok(()) => self.state = State::List,
+                    Err(err) => self.error = Some(err.to_string()),
+                }
+            }
+            None => { }
+        }
+
+        if *close.borrow() {
+            self.state = State::List;
+            core.back();
+        }
+    }
+}
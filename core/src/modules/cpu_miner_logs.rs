@@ -1,12 +1,18 @@
 use crate::imports::*;
+use crate::runtime::services::LogSource;
 
 pub struct CpuMinerLogs {
+    #[allow(dead_code)]
     runtime: Runtime,
+    logs: LogViewer,
 }
 
 impl CpuMinerLogs {
     pub fn new(runtime: Runtime) -> Self {
-        Self { runtime }
+        Self {
+            runtime,
+            logs: LogViewer::new("cpu_miner_logs", &[LogSource::CpuMiner]),
+        }
     }
 }
 
@@ -22,10 +28,6 @@ impl ModuleT for CpuMinerLogs {
         _frame: &mut eframe::Frame,
         ui: &mut egui::Ui,
     ) {
-        use egui_phosphor::light::CLIPBOARD_TEXT;
-
-        let available_width = ui.available_width();
-
         if !core.settings.node.cpu_miner_enabled {
             ui.colored_label(theme_color().warning_color, i18n("CPU Miner is disabled in Settings."));
             ui.colored_label(
@@ -48,39 +50,6 @@ impl ModuleT for CpuMinerLogs {
             ui.add_space(8.);
         }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        egui::ScrollArea::vertical()
-            .id_salt("cpu_miner_logs")
-            .auto_shrink([false; 2])
-            .stick_to_bottom(true)
-            .show(ui, |ui| {
-                for log in self.runtime.cpu_miner_service().logs().iter() {
-                    ui.label(RichText::from(log));
-                }
-            });
-
-        let copy_to_clipboard = Button::new(RichText::new(format!(" {CLIPBOARD_TEXT} ")).size(20.));
-
-        let button_rect = Rect::from_min_size(
-            pos2(available_width - 48.0, core.device().top_offset() + 32.0),
-            vec2(38.0, 20.0),
-        );
-
-        if ui
-            .put(button_rect, copy_to_clipboard)
-            .on_hover_text_at_pointer(i18n("Copy logs to clipboard"))
-            .clicked()
-        {
-            let logs = self
-                .runtime
-                .cpu_miner_service()
-                .logs()
-                .iter()
-                .map(|log| log.to_string())
-                .collect::<Vec<String>>()
-                .join("\n");
-            ui.ctx().copy_text(logs);
-            runtime().notify_clipboard(i18n("Copied to clipboard"));
-        }
+        self.logs.render(ui);
     }
 }
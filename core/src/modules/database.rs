@@ -1,4 +1,5 @@
 use crate::imports::*;
+use crate::runtime::services::{ComponentState, LoaderStatusSnapshot, LogSource};
 use kaspa_metrics_core::data::as_data_size;
 
 #[derive(Clone, Default, Deserialize)]
@@ -9,32 +10,6 @@ struct TableStats {
     total_size_bytes: i64,
 }
 
-#[derive(Clone, Default, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct LogLine {
-    ts: String,
-    level: String,
-    message: String,
-}
-
-#[derive(Clone, Default, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct LogResponse {
-    lines: Vec<LogLine>,
-}
-
-#[derive(Clone, Default, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct LoaderStatus {
-    phase: String,
-    message: String,
-    connected: bool,
-    postgres_ready: bool,
-    indexers_ready: bool,
-    rest_ready: bool,
-    socket_ready: bool,
-}
-
 #[derive(Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct DatabaseStatus {
@@ -47,34 +22,13 @@ struct DatabaseStatus {
 #[derive(Default)]
 struct DatabaseState {
     status: Option<DatabaseStatus>,
-    loader_status: Option<LoaderStatus>,
+    loader_status: Option<LoaderStatusSnapshot>,
     last_error: Option<String>,
     last_updated: Option<Instant>,
     in_flight: bool,
     loader_last_error: Option<String>,
     loader_last_updated: Option<Instant>,
     loader_in_flight: bool,
-    logs_loader: Vec<LogLine>,
-    logs_postgres: Vec<LogLine>,
-    logs_indexer: Vec<LogLine>,
-    logs_k_indexer: Vec<LogLine>,
-    logs_kasia_indexer: Vec<LogLine>,
-    logs_rest: Vec<LogLine>,
-    logs_socket: Vec<LogLine>,
-    logs_last_error: Option<String>,
-    logs_last_updated: Option<Instant>,
-    logs_in_flight: bool,
-}
-
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum LogService {
-    Loader,
-    Postgres,
-    Indexer,
-    KIndexer,
-    KasiaIndexer,
-    Rest,
-    Socket,
 }
 
 pub struct Database {
@@ -83,9 +37,7 @@ pub struct Database {
     state: Arc<Mutex<DatabaseState>>,
     poll_interval: Duration,
     log_poll_interval: Duration,
-    log_service: LogService,
-    log_autoscroll: bool,
-    log_limit: usize,
+    logs: LogViewer,
 }
 
 impl Database {
@@ -97,9 +49,8 @@ impl Database {
             state: Arc::new(Mutex::new(DatabaseState::default())),
             poll_interval: Duration::from_secs(8),
             log_poll_interval: Duration::from_secs(2),
-            log_service: LogService::Loader,
-            log_autoscroll: true,
-            log_limit: 1000,
+            logs: LogViewer::new("database_logs", &LogSource::SELF_HOSTED)
+                .with_selected(&[LogSource::Loader]),
         }
     }
 
@@ -131,53 +82,6 @@ impl Database {
         )
     }
 
-    fn loader_log_lines_from_runtime(limit: usize) -> Vec<LogLine> {
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            runtime()
-                .self_hosted_loader_service()
-                .log_snapshot(limit)
-                .into_iter()
-                .map(|line| LogLine {
-                    ts: line.ts,
-                    level: line.level,
-                    message: line.message,
-                })
-                .collect()
-        }
-
-        #[cfg(target_arch = "wasm32")]
-        {
-            let _ = limit;
-            Vec::new()
-        }
-    }
-
-    fn logs_url(
-        settings: &SelfHostedSettings,
-        network: Network,
-        service: LogService,
-        limit: usize,
-    ) -> String {
-        let host = Self::resolve_api_host(&settings.api_bind);
-        let service = match service {
-            LogService::Loader => "loader",
-            LogService::Postgres => "postgres",
-            LogService::Indexer => "indexer",
-            LogService::KIndexer => "k-indexer",
-            LogService::KasiaIndexer => "kasia-indexer",
-            LogService::Rest => "rest",
-            LogService::Socket => "socket",
-        };
-        format!(
-            "http://{}:{}/api/logs/{}?limit={}",
-            host,
-            settings.effective_api_port(network),
-            service,
-            limit
-        )
-    }
-
     fn schedule_fetch(&self, settings: &SelfHostedSettings, network: Network) {
         let should_fetch = {
             let mut state = self.state.lock().unwrap();
@@ -330,15 +234,7 @@ impl Database {
             let mut guard = self.state.lock().unwrap();
             guard.loader_in_flight = false;
             guard.loader_last_updated = Some(Instant::now());
-            guard.loader_status = Some(LoaderStatus {
-                phase: snapshot.phase,
-                message: snapshot.message,
-                connected: snapshot.connected,
-                postgres_ready: snapshot.postgres_ready,
-                indexers_ready: snapshot.indexers_ready,
-                rest_ready: snapshot.rest_ready,
-                socket_ready: snapshot.socket_ready,
-            });
+            guard.loader_status = Some(snapshot);
             guard.loader_last_error = None;
         }
 
@@ -347,9 +243,9 @@ impl Database {
             let url = Self::loader_url(settings, network);
             let state = self.state.clone();
             spawn(async move {
-                let result: std::result::Result<LoaderStatus, String> = match tokio::time::timeout(
+                let result: std::result::Result<LoaderStatusSnapshot, String> = match tokio::time::timeout(
                     Self::API_TIMEOUT,
-                    http::get_json::<LoaderStatus>(&url),
+                    http::get_json::<LoaderStatusSnapshot>(&url),
                 )
                 .await
                 {
@@ -374,72 +270,6 @@ impl Database {
         }
     }
 
-    fn schedule_logs_fetch(&self, settings: &SelfHostedSettings, network: Network, service: LogService) {
-        if matches!(service, LogService::Loader) {
-            let lines = Self::loader_log_lines_from_runtime(self.log_limit);
-            let mut guard = self.state.lock().unwrap();
-            guard.logs_loader = lines;
-            guard.logs_last_error = None;
-            guard.logs_last_updated = Some(Instant::now());
-            guard.logs_in_flight = false;
-            return;
-        }
-
-        let should_fetch = {
-            let mut state = self.state.lock().unwrap();
-            if state.logs_in_flight {
-                false
-            } else {
-                let elapsed = state
-                    .logs_last_updated
-                    .map(|time| time.elapsed() >= self.log_poll_interval)
-                    .unwrap_or(true);
-                if elapsed {
-                    state.logs_in_flight = true;
-                    true
-                } else {
-                    false
-                }
-            }
-        };
-
-        if !should_fetch {
-            return;
-        }
-
-        let url = Self::logs_url(settings, network, service, self.log_limit);
-        let state = self.state.clone();
-        spawn(async move {
-            let result: std::result::Result<LogResponse, String> =
-                match tokio::time::timeout(Self::API_TIMEOUT, http::get_json::<LogResponse>(&url)).await {
-                Ok(inner) => inner.map_err(|err| err.to_string()),
-                Err(_) => Err("logs request timed out".to_string()),
-            };
-            let mut guard = state.lock().unwrap();
-            guard.logs_in_flight = false;
-            guard.logs_last_updated = Some(Instant::now());
-            match result {
-                Ok(response) => {
-                    match service {
-                        LogService::Loader => guard.logs_loader = response.lines,
-                        LogService::Postgres => guard.logs_postgres = response.lines,
-                        LogService::Indexer => guard.logs_indexer = response.lines,
-                        LogService::KIndexer => guard.logs_k_indexer = response.lines,
-                        LogService::KasiaIndexer => guard.logs_kasia_indexer = response.lines,
-                        LogService::Rest => guard.logs_rest = response.lines,
-                        LogService::Socket => guard.logs_socket = response.lines,
-                    }
-                    guard.logs_last_error = None;
-                }
-                Err(err) => {
-                    guard.logs_last_error = Some(err);
-                }
-            }
-            runtime().request_repaint();
-            Ok(())
-        });
-    }
-
     fn format_bytes(bytes: i64) -> String {
         if bytes <= 0 {
             return "0 B".to_string();
@@ -457,6 +287,77 @@ impl Database {
         }
     }
 
+    fn format_timestamp(timestamp: f64) -> String {
+        chrono::DateTime::<chrono::Utc>::from_timestamp_millis(timestamp as i64)
+            .unwrap_or_default()
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    }
+
+    fn component_state_color(state: ComponentState) -> Color32 {
+        match state {
+            ComponentState::Ready => theme_color().ack_color,
+            ComponentState::Degraded => theme_color().warning_color,
+            ComponentState::Waiting => theme_color().node_data_color,
+            ComponentState::Disabled => theme_color().default_color,
+        }
+    }
+
+    fn render_loader_timeline(loader: &LoaderStatusSnapshot, ui: &mut Ui) {
+        Grid::new("db_loader_components")
+            .num_columns(3)
+            .spacing([16.0, 4.0])
+            .show(ui, |ui| {
+                for status in &loader.components {
+                    ui.label(status.component.name());
+                    ui.colored_label(Self::component_state_color(status.state), status.state.name());
+                    if status.failures > 0 {
+                        ui.colored_label(
+                            theme_color().warning_color,
+                            i18n_args("{count} failed checks", &[("count", status.failures.to_string())]),
+                        );
+                    } else {
+                        ui.label("");
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(8.0);
+
+        if loader.history.is_empty() {
+            ui.label(i18n("No phase transitions recorded yet."));
+            return;
+        }
+
+        let now = unixtime_as_millis_f64();
+        Grid::new("db_loader_history")
+            .striped(true)
+            .num_columns(4)
+            .spacing([16.0, 4.0])
+            .show(ui, |ui| {
+                ui.label(RichText::new(i18n("Time")).strong());
+                ui.label(RichText::new(i18n("Phase")).strong());
+                ui.label(RichText::new(i18n("Duration")).strong());
+                ui.label(RichText::new(i18n("Reason")).strong());
+                ui.end_row();
+
+                // newest first; each phase lasted until the following transition
+                let mut until = now;
+                for transition in loader.history.iter().rev() {
+                    let duration = (until - transition.timestamp).max(0.0) as u64;
+                    until = transition.timestamp;
+
+                    ui.label(Self::format_timestamp(transition.timestamp));
+                    ui.label(format!("{} → {}", transition.from.name(), transition.to.name()));
+                    ui.label(format_duration(duration));
+                    ui.label(transition.reason.as_str());
+                    ui.end_row();
+                }
+            });
+    }
+
     fn render_disabled(&self, _core: &mut Core, ui: &mut Ui) {
         let fill = theme_color().kaspa_color.linear_multiply(0.08);
         let stroke = Stroke::new(1.0, theme_color().kaspa_color.linear_multiply(0.5));
@@ -509,7 +410,6 @@ impl ModuleT for Database {
         let network = core.settings.node.network;
         self.schedule_fetch(&core.settings.self_hosted, network);
         self.schedule_loader_fetch(&core.settings.self_hosted, network);
-        self.schedule_logs_fetch(&core.settings.self_hosted, network, self.log_service);
 
         let (status, error, mut loader_status, loader_error) = {
             let state = self.state.lock().unwrap();
@@ -523,16 +423,7 @@ impl ModuleT for Database {
 
         #[cfg(not(target_arch = "wasm32"))]
         if loader_status.is_none() {
-            let snapshot = runtime().self_hosted_loader_service().status_snapshot();
-            loader_status = Some(LoaderStatus {
-                phase: snapshot.phase,
-                message: snapshot.message,
-                connected: snapshot.connected,
-                postgres_ready: snapshot.postgres_ready,
-                indexers_ready: snapshot.indexers_ready,
-                rest_ready: snapshot.rest_ready,
-                socket_ready: snapshot.socket_ready,
-            });
+            loader_status = Some(runtime().self_hosted_loader_service().status_snapshot());
         }
 
         ScrollArea::vertical()
@@ -574,8 +465,8 @@ impl ModuleT for Database {
                                 .unwrap_or(false);
                             let loader_phase = loader_status
                                 .as_ref()
-                                .map(|value| value.phase.as_str())
-                                .unwrap_or("Initialisation");
+                                .map(|value| value.phase_id.name())
+                                .unwrap_or(i18n("Initialisation"));
                             let loader_message = loader_status
                                 .as_ref()
                                 .map(|value| value.message.clone())
@@ -646,6 +537,16 @@ impl ModuleT for Database {
 
             ui.add_space(8.0);
 
+            if let Some(loader) = &loader_status {
+                CollapsingHeader::new(i18n("Loader Timeline"))
+                    .default_open(!loader.connected)
+                    .show(ui, |ui| {
+                        Self::render_loader_timeline(loader, ui);
+                    });
+
+                ui.add_space(8.0);
+            }
+
             CollapsingHeader::new(i18n("Indexer Tables"))
                 .default_open(true)
                 .show(ui, |ui| {
@@ -678,133 +579,18 @@ impl ModuleT for Database {
             CollapsingHeader::new(i18n("Logs"))
                 .default_open(false)
                 .show(ui, |ui| {
-                    ui.horizontal(|ui| {
-                        ui.label(i18n("Service"));
-                        ComboBox::from_id_salt("db_logs_service")
-                            .selected_text(match self.log_service {
-                                LogService::Loader => i18n("Loader"),
-                                LogService::Postgres => i18n("Postgres"),
-                                LogService::Indexer => i18n("Indexer"),
-                                LogService::KIndexer => i18n("K-indexer"),
-                                LogService::KasiaIndexer => i18n("Kasia-indexer"),
-                                LogService::Rest => i18n("REST API"),
-                                LogService::Socket => i18n("Socket"),
-                            })
-                            .show_ui(ui, |ui| {
-                                ui.selectable_value(
-                                    &mut self.log_service,
-                                    LogService::Loader,
-                                    i18n("Loader"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.log_service,
-                                    LogService::Postgres,
-                                    i18n("Postgres"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.log_service,
-                                    LogService::Indexer,
-                                    i18n("Indexer"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.log_service,
-                                    LogService::KIndexer,
-                                    i18n("K-indexer"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.log_service,
-                                    LogService::KasiaIndexer,
-                                    i18n("Kasia-indexer"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.log_service,
-                                    LogService::Rest,
-                                    i18n("REST API"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.log_service,
-                                    LogService::Socket,
-                                    i18n("Socket"),
-                                );
-                            });
-                        ui.checkbox(&mut self.log_autoscroll, i18n("Autoscroll"));
-                        if ui.small_button(i18n("Copy Logs")).clicked() {
-                            let state = self.state.lock().unwrap();
-                            let lines = match self.log_service {
-                                LogService::Loader => &state.logs_loader,
-                                LogService::Postgres => &state.logs_postgres,
-                                LogService::Indexer => &state.logs_indexer,
-                                LogService::KIndexer => &state.logs_k_indexer,
-                                LogService::KasiaIndexer => &state.logs_kasia_indexer,
-                                LogService::Rest => &state.logs_rest,
-                                LogService::Socket => &state.logs_socket,
-                            };
-                            let mut out = String::new();
-                            for line in lines {
-                                out.push_str(&format!(
-                                    "{} [{}] {}\n",
-                                    line.ts, line.level, line.message
-                                ));
-                            }
-                            ui.ctx().copy_text(out);
-                            runtime().notify_clipboard(i18n("Copied to clipboard"));
-                        }
-                    });
-
-                    let (lines, log_error) = {
-                        let state = self.state.lock().unwrap();
-                        let lines = match self.log_service {
-                            LogService::Loader => state.logs_loader.clone(),
-                            LogService::Postgres => state.logs_postgres.clone(),
-                            LogService::Indexer => state.logs_indexer.clone(),
-                            LogService::KIndexer => state.logs_k_indexer.clone(),
-                            LogService::KasiaIndexer => state.logs_kasia_indexer.clone(),
-                            LogService::Rest => state.logs_rest.clone(),
-                            LogService::Socket => state.logs_socket.clone(),
-                        };
-                        (lines, state.logs_last_error.clone())
-                    };
-
-                    if let Some(log_error) = log_error {
-                        ui.label(
-                            RichText::new(log_error)
-                                .color(theme_color().warning_color)
-                                .size(12.0),
-                        );
-                    }
-
                     let row_height = ui.fonts(|fonts| {
                         let font_id = TextStyle::Monospace.resolve(ui.style());
                         fonts.row_height(&font_id)
                     });
                     let log_height =
-                        row_height * 20.0 + ui.spacing().item_spacing.y * 2.0 + 8.0;
+                        row_height * 24.0 + ui.spacing().item_spacing.y * 2.0 + 8.0;
 
                     ui.allocate_ui_with_layout(
                         vec2(ui.available_width(), log_height),
                         Layout::top_down(Align::Min),
                         |ui| {
-                        ScrollArea::vertical()
-                            .auto_shrink([false; 2])
-                            .stick_to_bottom(self.log_autoscroll)
-                            .show(ui, |ui| {
-                                if lines.is_empty() {
-                                    ui.label(i18n("No logs yet."));
-                                } else {
-                                    for line in lines {
-                                        let color = match line.level.as_str() {
-                                            "ERROR" => theme_color().nack_color,
-                                            "WARN" => theme_color().warning_color,
-                                            _ => theme_color().node_data_color,
-                                        };
-                                        let text = format!(
-                                            "{} [{}] {}",
-                                            line.ts, line.level, line.message
-                                        );
-                                        ui.label(RichText::new(text).monospace().color(color));
-                                    }
-                                }
-                            });
+                            self.logs.render(ui);
                         },
                     );
                 });
@@ -14,6 +14,17 @@ use egui_plot::{
 const METRICS_SAMPLES_START : isize = -(MAX_METRICS_SAMPLES as isize);
 const MIN_RANGE : isize = 15;
 
+cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+        use crate::runtime::services::MetricsTier;
+        /// Graph ranges reach back to the retention of the hourly tier of the
+        /// on-disk metrics history (one year).
+        const METRICS_RANGE_START : isize = -(365 * 24 * 60 * 60);
+    } else {
+        const METRICS_RANGE_START : isize = METRICS_SAMPLES_START;
+    }
+}
+
 pub struct Metrics {
     #[allow(dead_code)]
     runtime: Runtime,
@@ -47,11 +58,11 @@ impl ModuleT for Metrics {
         let mut graph_range_from = core.settings.user_interface.metrics.graph_range_from;
         let mut graph_range_to = core.settings.user_interface.metrics.graph_range_to;
 
-        if graph_range_from < METRICS_SAMPLES_START {
-            graph_range_from = METRICS_SAMPLES_START;
+        if graph_range_from < METRICS_RANGE_START {
+            graph_range_from = METRICS_RANGE_START;
         }
         
-        graph_range_to = graph_range_to.clamp(METRICS_SAMPLES_START, 0);
+        graph_range_to = graph_range_to.clamp(METRICS_RANGE_START, 0);
 
         ui.horizontal(|ui|{
             ui.heading(i18n("Metrics"));
@@ -127,8 +138,16 @@ impl ModuleT for Metrics {
 
                 ui.separator();
 
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    if ui.button(i18n("Export CSV")).clicked() {
+                        self.export_csv(core.settings.node.network, graph_range_from, graph_range_to);
+                    }
+                    ui.separator();
+                }
+
                 ui.add(
-                    Slider::new(&mut graph_range_to, (METRICS_SAMPLES_START+MIN_RANGE)..=0)
+                    Slider::new(&mut graph_range_to, (METRICS_RANGE_START+MIN_RANGE)..=0)
                         .logarithmic(true)
                         .orientation(SliderOrientation::Horizontal)
                         .show_value(false)
@@ -137,7 +156,7 @@ impl ModuleT for Metrics {
                         // })
                 );
                 ui.add(
-                    Slider::new(&mut graph_range_from, METRICS_SAMPLES_START..=-MIN_RANGE)
+                    Slider::new(&mut graph_range_from, METRICS_RANGE_START..=-MIN_RANGE)
                         .logarithmic(true)
                         .orientation(SliderOrientation::Horizontal)
                         .show_value(false)
@@ -273,20 +292,22 @@ impl Metrics {
                         });
 
                         // ---
-                        let graph_data = {
+                        let graph_data = if range.start < METRICS_SAMPLES_START {
+                            self.history_data(metric, &range, graph_width)
+                        } else {
                             let metrics_data = self.runtime.metrics_service().metrics_data();
                             let data = metrics_data.get(&metric).unwrap();
-                            let mut start = range.start.clamp(METRICS_SAMPLES_START, 0).unsigned_abs();
-                            let mut end = range.end.clamp(METRICS_SAMPLES_START, 0).unsigned_abs();
-                            if start > data.len() {
-                                start = data.len();
-                            }
-                            if end > data.len() {
-                                end = data.len();
-                            }
-                            data[data.len()-start..data.len()-end].to_vec()
+                            // select by timestamp, the buffer may begin with samples restored from disk
+                            let now = data.last().map(|point| point.x).unwrap_or_default();
+                            let from = now + range.start.clamp(METRICS_SAMPLES_START, 0) as f64 * 1000.0;
+                            let to = now + range.end.clamp(METRICS_SAMPLES_START, 0) as f64 * 1000.0;
+                            let start = data.partition_point(|point| point.x <= from);
+                            let end = data.partition_point(|point| point.x <= to);
+                            data[start..end.max(start)].to_vec()
                         };
 
+                        let time_format = if range.start.abs_diff(range.end) > 24 * 60 * 60 { "%m-%d %H:%M" } else { "%H:%M:%S" };
+
                         let mut plot = Plot::new(metric.as_str())
                         // .link_axis(id, true, false)
                         // .allow_boxed_zoom(true)
@@ -314,7 +335,7 @@ impl Metrics {
                                 DateTime::<chrono::Utc>::from_timestamp((grid.value / 1000.0) as i64, 0)
                                     .expect("could not parse timestamp")
                                     .with_timezone(&chrono::Local)
-                                    .format("%H:%M:%S")
+                                    .format(time_format)
                                     .to_string()
                             })
                             .x_grid_spacer(
@@ -331,7 +352,7 @@ impl Metrics {
                                 format!("{} @ {}", metric.format(*y, true, true), DateTime::<chrono::Utc>::from_timestamp((*x / 1000.0) as i64, 0)
                                     .expect("could not parse timestamp")
                                     .with_timezone(&chrono::Local)
-                                    .format("%H:%M:%S")
+                                    .format(time_format)
                                 )
                             })                                                    
                             .coordinates_formatter(Corner::LeftTop, CoordinatesFormatter::new(move |point,_| {
@@ -369,6 +390,63 @@ impl Metrics {
     }
 }
 
+impl Metrics {
+
+    /// Samples older than the in-memory buffer, read from the on-disk
+    /// history tier that covers the start of the range.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn history_data(&self, metric : Metric, range : &std::ops::Range<isize>, graph_width : f32) -> Vec<PlotPoint> {
+        let now = unixtime_as_millis_f64();
+        let from = now + range.start as f64 * 1000.0;
+        let to = now + range.end as f64 * 1000.0;
+        let tier = MetricsTier::for_age(now - from);
+        self.runtime.metrics_service().metrics_history().as_ref()
+            .map(|history| history.series(tier, metric, from, to, graph_width as usize * 2))
+            .unwrap_or_default()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn history_data(&self, _metric : Metric, _range : &std::ops::Range<isize>, _graph_width : f32) -> Vec<PlotPoint> {
+        Vec::new()
+    }
+
+    /// Exports all metrics of the selected range from the on-disk history.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_csv(&self, network : Network, range_from : isize, range_to : isize) {
+        let now = unixtime_as_millis_f64();
+        let from = now + range_from as f64 * 1000.0;
+        let to = now + range_to as f64 * 1000.0;
+        let tier = MetricsTier::for_age(now - from);
+
+        let filename = format!("metrics-{}-{}.csv", network, tier.name());
+        let Some(path) = rfd::FileDialog::new().set_file_name(filename).add_filter("CSV", &["csv"]).save_file() else {
+            return;
+        };
+
+        // copy the rows out, so that the history is not locked while writing
+        let Some(export) = self.runtime.metrics_service().metrics_history().as_ref().map(|history| history.export(tier, from, to)) else {
+            runtime().error(i18n("Metrics history is not available"));
+            return;
+        };
+
+        spawn(async move {
+            let result = tokio::task::spawn_blocking({
+                let path = path.clone();
+                move || export.write_csv(&path)
+            }).await.map_err(|err| Error::custom(err.to_string())).and_then(|result| result);
+
+            match result {
+                Ok(count) => runtime().notify(UserNotification::success(i18n_args("Exported {count} samples to {path}", &[
+                    ("count", count.to_string()),
+                    ("path", path.to_string_lossy().to_string()),
+                ]))),
+                Err(err) => runtime().error(err.to_string()),
+            }
+            Ok(())
+        });
+    }
+}
+
 fn calculate_grid_lines(base_step_size : f64) -> [f64; 3] {
     let mut small_grid = 15.*1000_f64;
     let mut medium_grid = 30.*1000_f64;
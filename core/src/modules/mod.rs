@@ -7,6 +7,8 @@ kaspa_ng_macros::register_modules!(
     [
         account_create,
         account_manager,
+        co_sign,
+        contacts,
         donations,
         export,
         import,
@@ -15,7 +17,9 @@ kaspa_ng_macros::register_modules!(
         request,
         scanner,
         settings,
+        sign_message,
         testing,
+        tools::check_balance,
         wallet_create,
         wallet_open,
         wallet_secret,
@@ -27,11 +31,13 @@ kaspa_ng_macros::register_modules!(
     register_native_modules,
     [
         changelog,
+        cpu_miner_logs,
         database,
         explorer,
         k_social,
         logs,
         rk_bridge_logs,
+        rothschild_logs,
         node,
         kasia,
         kasvault,
@@ -1,16 +1,250 @@
 use kaspa_rpc_core::RpcPeerInfo;
 
 use crate::imports::*;
+use crate::runtime::services::peer_monitor::PEER_PING_HISTORY_LENGTH;
 use crate::utils::format_duration;
 
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
+enum PeerColumn {
+    Address,
+    UserAgent,
+    Direction,
+    #[default]
+    Ping,
+    Uptime,
+    Ibd,
+}
+
+impl PeerColumn {
+    fn caption(&self) -> &'static str {
+        match self {
+            PeerColumn::Address => i18n("Address"),
+            PeerColumn::UserAgent => i18n("User Agent"),
+            PeerColumn::Direction => i18n("Direction"),
+            PeerColumn::Ping => i18n("Ping"),
+            PeerColumn::Uptime => i18n("Uptime"),
+            PeerColumn::Ibd => i18n("IBD"),
+        }
+    }
+
+    fn compare(&self, a : &RpcPeerInfo, b : &RpcPeerInfo) -> std::cmp::Ordering {
+        match self {
+            PeerColumn::Address => a.address.to_string().cmp(&b.address.to_string()),
+            PeerColumn::UserAgent => a.user_agent.cmp(&b.user_agent),
+            PeerColumn::Direction => a.is_outbound.cmp(&b.is_outbound),
+            PeerColumn::Ping => a.last_ping_duration.cmp(&b.last_ping_duration),
+            PeerColumn::Uptime => a.time_connected.cmp(&b.time_connected),
+            PeerColumn::Ibd => a.is_ibd_peer.cmp(&b.is_ibd_peer),
+        }
+    }
+}
+
+enum PeerAction {
+    Connect { address : String, remember : bool },
+    Ban(String),
+    Unban(String),
+    RemovePreferred(String),
+}
+
 pub struct Node {
     #[allow(dead_code)]
     runtime: Runtime,
+    sort_column : PeerColumn,
+    sort_ascending : bool,
+    peer_address : String,
+    remember_peer : bool,
+    ban_address : String,
+    error : Option<String>,
 }
 
 impl Node {
     pub fn new(runtime: Runtime) -> Self {
-        Self { runtime }
+        Self {
+            runtime,
+            sort_column : PeerColumn::default(),
+            sort_ascending : true,
+            peer_address : String::default(),
+            remember_peer : true,
+            ban_address : String::default(),
+            error : None,
+        }
+    }
+
+    fn render_peer_table(&mut self, ui : &mut Ui, peers : &[RpcPeerInfo], action : &mut Option<PeerAction>) {
+        let mut peers = peers.iter().collect::<Vec<_>>();
+        peers.sort_by(|a, b| {
+            let ordering = self.sort_column.compare(a, b);
+            if self.sort_ascending { ordering } else { ordering.reverse() }
+        });
+
+        let color = theme_color().node_data_color;
+        let peer_monitor = self.runtime.peer_monitor_service().clone();
+
+        Grid::new("peer_table")
+            .num_columns(8)
+            .spacing([16.0,4.0])
+            .striped(true)
+            .show(ui, |ui| {
+
+                for column in [PeerColumn::Address, PeerColumn::UserAgent, PeerColumn::Direction, PeerColumn::Ping] {
+                    self.render_column_header(ui, column);
+                }
+                ui.label(RichText::new(i18n("Ping History")).strong());
+                self.render_column_header(ui, PeerColumn::Uptime);
+                self.render_column_header(ui, PeerColumn::Ibd);
+                ui.label("");
+                ui.end_row();
+
+                for peer in peers {
+                    let ip = peer.address.ip.to_string();
+                    ui.colored_label(color, peer.address.to_string())
+                        .on_hover_text(format!("{}: {}\n{}: v{}\n{}: {}", i18n("Id"), peer.id, i18n("Protocol"), peer.advertised_protocol_version, i18n("Time Offset"), peer.time_offset));
+                    ui.colored_label(color, peer.user_agent.to_string());
+                    ui.label(if peer.is_outbound { i18n("Outbound") } else { i18n("Inbound") });
+                    ui.colored_label(color, format!("{} ms", peer.last_ping_duration));
+                    render_sparkline(ui, &peer_monitor.ping_history(&peer.id.to_string()), color);
+                    ui.colored_label(color, format_duration(peer.time_connected));
+                    ui.label(if peer.is_ibd_peer { i18n("Yes") } else { "" });
+                    ui.horizontal(|ui| {
+                        if ui.small_button(i18n("Ban")).on_hover_text(i18n("Disconnect and ban this IP")).clicked() {
+                            action.replace(PeerAction::Ban(ip.clone()));
+                        }
+                        if peer.is_outbound && ui.small_button(i18n("Prefer")).on_hover_text(i18n("Connect to this peer on every start")).clicked() {
+                            action.replace(PeerAction::Connect { address : peer.address.to_string(), remember : true });
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn render_column_header(&mut self, ui : &mut Ui, column : PeerColumn) {
+        let caption = if self.sort_column == column {
+            format!("{} {}", column.caption(), if self.sort_ascending { "⏶" } else { "⏷" })
+        } else {
+            column.caption().to_string()
+        };
+
+        if ui.add(Label::new(RichText::new(caption).strong()).sense(Sense::click())).clicked() {
+            if self.sort_column == column {
+                self.sort_ascending = !self.sort_ascending;
+            } else {
+                self.sort_column = column;
+                self.sort_ascending = true;
+            }
+        }
+    }
+
+    fn render_peer_management(&mut self, ui : &mut Ui, peers : &PeerSettings, action : &mut Option<PeerAction>) {
+        ui.horizontal(|ui| {
+            ui.label(i18n("Connect to peer:"));
+            ui.add(TextEdit::singleline(&mut self.peer_address).hint_text("ip[:port]").desired_width(200.));
+            ui.checkbox(&mut self.remember_peer, i18n("Remember"));
+            if ui.add_enabled(self.peer_address.trim().is_not_empty(), Button::new(i18n("Connect"))).clicked() {
+                action.replace(PeerAction::Connect { address : self.peer_address.trim().to_string(), remember : self.remember_peer });
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(i18n("Ban IP address:"));
+            ui.add(TextEdit::singleline(&mut self.ban_address).hint_text("ip").desired_width(200.));
+            if ui.add_enabled(self.ban_address.trim().is_not_empty(), Button::new(i18n("Ban"))).clicked() {
+                action.replace(PeerAction::Ban(self.ban_address.trim().to_string()));
+            }
+        });
+
+        if let Some(error) = self.error.as_ref() {
+            ui.colored_label(error_color(), error);
+        }
+
+        ui.add_space(8.);
+        CollapsingHeader::new(i18n_args("Preferred peers ({count})", &[("count", peers.preferred.len().to_string())]))
+            .default_open(false)
+            .show(ui, |ui| {
+                if peers.preferred.is_empty() {
+                    ui.label(i18n("No preferred peers"));
+                }
+                for address in peers.preferred.iter() {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(address.as_str()).monospace());
+                        if ui.small_button(i18n("Remove")).clicked() {
+                            action.replace(PeerAction::RemovePreferred(address.clone()));
+                        }
+                    });
+                }
+                ui.label(RichText::new(i18n("Preferred peers are added to the node when it starts.")).small());
+            });
+
+        CollapsingHeader::new(i18n_args("Banned peers ({count})", &[("count", peers.banned.len().to_string())]))
+            .default_open(false)
+            .show(ui, |ui| {
+                if peers.banned.is_empty() {
+                    ui.label(i18n("No banned peers"));
+                }
+                for ip in peers.banned.iter() {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(ip.as_str()).monospace());
+                        if ui.small_button(i18n("Unban")).clicked() {
+                            action.replace(PeerAction::Unban(ip.clone()));
+                        }
+                    });
+                }
+                ui.label(RichText::new(i18n("Banned peers are applied each time the node connects.")).small());
+            });
+    }
+
+    fn apply(&mut self, core : &mut Core, action : PeerAction) -> Result<()> {
+        let rpc_api = self.runtime.peer_monitor_service().rpc_api();
+
+        match action {
+            PeerAction::Connect { address, remember } => {
+                let peer = PeerSettings::parse_peer(&address)?;
+                if remember {
+                    core.settings.node.peers.add_preferred(&address)?;
+                }
+                if let Some(rpc_api) = rpc_api {
+                    let message = i18n_args("Connecting to {peer}", &[("peer", peer.to_string())]);
+                    self.runtime.spawn_task(async move {
+                        rpc_api.add_peer(peer, remember).await?;
+                        runtime().notify(UserNotification::success(message));
+                        Ok(())
+                    });
+                }
+                self.peer_address.clear();
+            }
+            PeerAction::Ban(ip) => {
+                let addr = PeerSettings::parse_ip(&ip)?;
+                core.settings.node.peers.ban(&ip)?;
+                if let Some(rpc_api) = rpc_api {
+                    self.runtime.spawn_task(async move {
+                        rpc_api.ban(addr.into()).await?;
+                        runtime().notify(UserNotification::success(i18n_args("Banned {ip}", &[("ip", addr.to_string())])));
+                        Ok(())
+                    });
+                }
+                self.ban_address.clear();
+            }
+            PeerAction::Unban(ip) => {
+                core.settings.node.peers.unban(&ip);
+                if let (Some(rpc_api), Ok(addr)) = (rpc_api, PeerSettings::parse_ip(&ip)) {
+                    self.runtime.spawn_task(async move {
+                        rpc_api.unban(addr.into()).await?;
+                        runtime().notify(UserNotification::success(i18n_args("Unbanned {ip}", &[("ip", addr.to_string())])));
+                        Ok(())
+                    });
+                }
+            }
+            PeerAction::RemovePreferred(address) => {
+                core.settings.node.peers.remove_preferred(&address);
+            }
+        }
+
+        core.settings.store_sync()?;
+        self.runtime.peer_monitor_service().update_node_settings(&core.settings.node);
+        let settings_snapshot = core.settings.clone();
+        core.get_mut::<modules::Settings>().load(settings_snapshot);
+
+        Ok(())
     }
 }
 
@@ -30,58 +264,61 @@ impl ModuleT for Node {
 
         ui.heading(i18n("Node Status"));
         ui.separator();
-        
+
         if !core.state().is_connected() {
             ui.label(i18n("Not connected"));
             return;
         }
 
+        let peer_settings = core.settings.node.peers.clone();
+        let mut action = None;
+
         egui::ScrollArea::vertical()
             .auto_shrink([false; 2])
 
             .show(ui, |ui| {
 
+                CollapsingHeader::new(i18n("Peer Management"))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        self.render_peer_management(ui, &peer_settings, &mut action);
+                    });
 
                 CollapsingHeader::new(i18n("Network Peers"))
                     .default_open(true)
                     .show(ui, |ui| {
 
-                        ui.vertical(|ui| {
-
-                            if let Some(peers) = self.runtime.peer_monitor_service().peer_info() {
-                                let (outbound, inbound) : (Vec<_>,Vec<_>) = peers.iter().partition(|peer| peer.is_outbound);
-
-                                CollapsingHeader::new(i18n("Inbound"))
-                                    .default_open(true)
+                        if let Some(peers) = self.runtime.peer_monitor_service().peer_info() {
+                            if peers.is_empty() {
+                                ui.colored_label(theme_color().warning_color, i18n("No peers"));
+                            } else {
+                                let outbound = peers.iter().filter(|peer| peer.is_outbound).count();
+                                ui.label(i18n_args("Inbound: {inbound} Outbound: {outbound}", &[
+                                    ("inbound", (peers.len() - outbound).to_string()),
+                                    ("outbound", outbound.to_string()),
+                                ]));
+                                ui.add_space(4.);
+                                egui::ScrollArea::horizontal()
+                                    .id_salt("peer_table_scroll")
                                     .show(ui, |ui| {
-
-                                        inbound.iter().for_each(|peer| {
-                                            render_peer(ui, peer);
-                                        });
+                                        self.render_peer_table(ui, &peers, &mut action);
                                     });
-
-                                CollapsingHeader::new(i18n("Outbound"))
-                                    .default_open(true)
-                                    .show(ui, |ui| {
-
-                                        outbound.iter().for_each(|peer| {
-                                            render_peer(ui, peer);
-                                        });
-                                    });
-                            } else if core.state().metrics().as_ref().map(|m| m.data.node_active_peers).unwrap_or_default() > 0 {
-                                ui.horizontal(|ui| {
-                                    ui.spinner();
-                                    ui.label(i18n("Updating..."));
-                                });
-                            } else {
-                                ui.colored_label(theme_color().warning_color, i18n("No peers"));
                             }
-
-                        });
+                        } else if core.state().metrics().as_ref().map(|m| m.data.node_active_peers).unwrap_or_default() > 0 {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(i18n("Updating..."));
+                            });
+                        } else {
+                            ui.colored_label(theme_color().warning_color, i18n("No peers"));
+                        }
 
                     });
                 });
-            
+
+        if let Some(action) = action {
+            self.error = self.apply(core, action).err().map(|err| err.to_string());
+        }
     }
 
     fn activate(&mut self, _core: &mut Core) {
@@ -94,45 +331,26 @@ impl ModuleT for Node {
 
 }
 
-fn render_peer(ui : &mut Ui, peer: &RpcPeerInfo) {
-
-    let color = theme_color().node_data_color;
-
-    CollapsingHeader::new(peer.id.to_string())
-        .default_open(true)
-        .show(ui, |ui| {
-
-            Grid::new("peer_info_grid")
-                .num_columns(2)
-                .spacing([16.0,4.0])
-                .show(ui, |ui| {
-
-                    ui.label(i18n("User Agent"));
-                    ui.colored_label(color, peer.user_agent.to_string());
-                    ui.end_row();
+/// Draws the ping history of a peer as a small line chart.
+fn render_sparkline(ui : &mut Ui, samples : &[u64], color : Color32) {
+    let (rect, response) = ui.allocate_exact_size(vec2(96., 16.), Sense::hover());
+    if samples.len() < 2 {
+        return;
+    }
 
-                    ui.label(i18n("Connection"));
-                    ui.horizontal(|ui| {
-                        ui.label(i18n("Address:"));
-                        ui.colored_label(color, peer.address.to_string());
-                        ui.label(i18n("Protocol:"));
-                        ui.colored_label(color, format!("v{}", peer.advertised_protocol_version));
-                        ui.label(i18n("IBD:"));
-                        ui.colored_label(color, peer.is_ibd_peer.to_string());
-                    });
-                    ui.end_row();
+    let min = samples.iter().copied().min().unwrap_or_default() as f32;
+    let max = samples.iter().copied().max().unwrap_or_default() as f32;
+    let range = (max - min).max(1.0);
+    let step = rect.width() / (PEER_PING_HISTORY_LENGTH - 1) as f32;
+    let offset = rect.width() - step * (samples.len() - 1) as f32;
 
-                    ui.label(i18n("Metrics"));
-                    ui.horizontal(|ui|{
-                        ui.label(i18n("Ping:"));
-                        ui.colored_label(color, format_duration(peer.last_ping_duration));
-                        ui.label(i18n("Time Offset:"));
-                        ui.colored_label(color, peer.time_offset.to_string());
-                        ui.label(i18n("Uptime:"));
-                        ui.colored_label(color, format_duration(peer.time_connected));
-                    });
-                    ui.end_row();
-                });
+    let points = samples.iter().enumerate().map(|(index, sample)| {
+        pos2(
+            rect.left() + offset + step * index as f32,
+            rect.bottom() - (*sample as f32 - min) / range * rect.height(),
+        )
+    }).collect::<Vec<_>>();
 
-        });
-}
\ No newline at end of file
+    ui.painter().add(egui::Shape::line(points, Stroke::new(1.0, color)));
+    response.on_hover_text(i18n_args("Min: {min} ms Max: {max} ms", &[("min", min.to_string()), ("max", max.to_string())]));
+}
@@ -220,28 +220,37 @@ impl Overview {
                                 self.render_fee_rate(core, ui);
                             });
                     }
-                    // Network::Testnet11 => {
-                    //     CollapsingHeader::new(i18n("Testnet 11"))
-                    //         .default_open(true)
-                    //         .show(ui, |ui| {
-                    //             CollapsingHeader::new(i18n("Resources"))
-                    //                 .default_open(true)
-                    //                 .show(ui, |ui| {
-                    //                     use egui_phosphor::light::{HAND_COINS,DATABASE};
+                    Network::Testnet10 => {
+                        let (explorer, faucet) = ("https://explorer-tn10.kaspa.org/", "https://faucet-tn10.kaspanet.io");
+                        CollapsingHeader::new(core.settings.node.network.name())
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                CollapsingHeader::new(i18n("Resources"))
+                                    .default_open(true)
+                                    .show(ui, |ui| {
+                                        use egui_phosphor::light::{HAND_COINS,DATABASE};
                 
-                    //                     ui.hyperlink_to_tab(
-                    //                         format!("• {DATABASE} {}",i18n("Explorer")),
-                    //                         "https://explorer-tn11.kaspa.org/",
-                    //                     );
-                    //                     ui.hyperlink_to_tab(
-                    //                         format!("• {HAND_COINS} {}",i18n("Faucet")),
-                    //                         "https://faucet-t11.kaspanet.io",
-                    //                     );
-                    //                 });
-                    //             self.render_network_info(core, ui);
-                    //             self.render_fee_rate(core, ui);
-                    //         });
-                    // }
+                                        ui.hyperlink_to_tab(
+                                            format!("• {DATABASE} {}",i18n("Explorer")),
+                                            explorer,
+                                        );
+                                        ui.hyperlink_to_tab(
+                                            format!("• {HAND_COINS} {}",i18n("Faucet")),
+                                            faucet,
+                                        );
+                                    });
+                                self.render_network_info(core, ui);
+                                self.render_fee_rate(core, ui);
+                            });
+                    }
+                    Network::Devnet => {
+                        CollapsingHeader::new(i18n("Devnet"))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                self.render_network_info(core, ui);
+                                self.render_fee_rate(core, ui);
+                            });
+                    }
                 }
 
                 CollapsingHeader::new(i18n("Developer Resources"))
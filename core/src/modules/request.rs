@@ -4,25 +4,6 @@ use egui_phosphor::light::CLIPBOARD_TEXT;
 use std::{borrow::Cow, collections::hash_map::Entry};
 pub use xxhash_rust::xxh3::xxh3_64;
 
-pub struct RequestUri {
-    pub address : String,
-    pub amount_sompi : Option<u64>,
-    pub label : Option<String>,
-}
-
-impl std::fmt::Display for RequestUri {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut uri = self.address.clone();
-        if let Some(amount_sompi) = self.amount_sompi {
-            uri = format!("{}?amount={}", uri, sompi_to_kaspa(amount_sompi));
-        }
-        if let Some(label) = self.label.as_ref() {
-            uri = format!("{}?label={}", uri, label);
-        }
-        write!(f, "{}", uri)
-    }
-}
-
 pub struct Request {
     #[allow(dead_code)]
     runtime: Runtime,
@@ -31,6 +12,7 @@ pub struct Request {
     amount : String,
     amount_sompi : Option<u64>,
     label : String,
+    message : String,
     error : Option<String>,
 }
 
@@ -43,19 +25,11 @@ impl Request {
             amount : String::default(),
             amount_sompi : None,
             label : String::default(),
+            message : String::default(),
             error : None,
         }
     }
 
-    fn create_request_uri(&self, address : impl Into<String>, amount_sompi : Option<u64>, label : Option<impl Into<String>>) -> RequestUri {
-
-        RequestUri {
-            address : address.into(),
-            amount_sompi,
-            label : label.map(|l|l.into()),
-        }
-    }
-
     fn qr(&mut self, request_uri : &str) -> (String,load::Bytes) {
 
         let hash = format!("{:x}",xxh3_64(format!("{request_uri}{}", theme_color().name).as_bytes()));
@@ -72,7 +46,7 @@ impl Request {
         (qr_uri.clone(),qr_bytes.clone())
     }
 
-    fn render_destination(&mut self, ui: &mut Ui, address : &str, request_uri : &RequestUri ) {
+    fn render_destination(&mut self, ui: &mut Ui, address : &str, request_uri : &PaymentUri ) {
 
         let request_uri = request_uri.to_string();
 
@@ -105,7 +79,7 @@ impl Request {
 
         if response.clicked() {
             //ui.output_mut(|o| address.clone_into(&mut o.copied_text));
-            ui.ctx().copy_text(address.to_string());
+            ui.ctx().copy_text(request_uri.clone());
             runtime().notify_clipboard(i18n("URI copied to clipboard"));
         }
 
@@ -169,11 +143,13 @@ impl ModuleT for Request {
 
 
                 if let Some(account) = this.account.as_ref() {
-                    let address = account.receive_address().to_string();
-                    let label = this.label.is_not_empty().then_some(this.label.clone());
-                    let request_uri = this.create_request_uri(address.clone(), this.amount_sompi, label);
+                    let address = account.receive_address();
+                    let request_uri = PaymentUri::new(address.clone())
+                        .with_amount(this.amount_sompi)
+                        .with_label(Some(this.label.trim().to_string()))
+                        .with_message(Some(this.message.trim().to_string()));
 
-                    this.render_destination(ui, address.as_str(), &request_uri);
+                    this.render_destination(ui, address.to_string().as_str(), &request_uri);
                 }
 
                 ui.label("");
@@ -204,6 +180,22 @@ impl ModuleT for Request {
                     }
                 }
 
+                ui.label("");
+                ui.label(i18n("Label (optional)"));
+                ui.add_sized(
+                    theme_style().panel_editor_size,
+                    TextEdit::singleline(&mut this.label)
+                        .vertical_align(Align::Center),
+                );
+
+                ui.label("");
+                ui.label(i18n("Message (optional)"));
+                ui.add_sized(
+                    theme_style().panel_editor_size,
+                    TextEdit::singleline(&mut this.message)
+                        .vertical_align(Align::Center),
+                );
+
                 if let Some(error) = this.error.as_ref() {
                     ui.label("");
                     ui.colored_label(error_color(), error);
@@ -1,13 +1,426 @@
 use crate::imports::*;
+use crate::runtime::services::LogSource;
+use crate::runtime::services::stratum_bridge::{
+    BlockReconciliation, BridgeStats, BridgeStatsHistory, WorkerStats, stats::format_hashrate,
+};
+use crate::settings::network_profile_slug;
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum BridgeView {
+    #[default]
+    Statistics,
+    Blocks,
+    Logs,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum WorkerSort {
+    Worker,
+    Wallet,
+    #[default]
+    Hashrate,
+    Difficulty,
+    Shares,
+    Stale,
+    Invalid,
+    Blocks,
+}
+
+impl WorkerSort {
+    fn label(&self) -> &'static str {
+        match self {
+            WorkerSort::Worker => i18n("Worker"),
+            WorkerSort::Wallet => i18n("Wallet"),
+            WorkerSort::Hashrate => i18n("Hashrate"),
+            WorkerSort::Difficulty => i18n("Difficulty"),
+            WorkerSort::Shares => i18n("Shares"),
+            WorkerSort::Stale => i18n("Stale"),
+            WorkerSort::Invalid => i18n("Invalid"),
+            WorkerSort::Blocks => i18n("Blocks"),
+        }
+    }
+
+    fn compare(&self, a: &WorkerStats, b: &WorkerStats) -> std::cmp::Ordering {
+        match self {
+            WorkerSort::Worker => a.worker.cmp(&b.worker),
+            WorkerSort::Wallet => a.wallet.cmp(&b.wallet),
+            WorkerSort::Hashrate => a.hashrate.total_cmp(&b.hashrate),
+            WorkerSort::Difficulty => a.difficulty.total_cmp(&b.difficulty),
+            WorkerSort::Shares => a.shares.cmp(&b.shares),
+            WorkerSort::Stale => a.stale.cmp(&b.stale),
+            WorkerSort::Invalid => (a.invalid + a.duplicate).cmp(&(b.invalid + b.duplicate)),
+            WorkerSort::Blocks => a.blocks.cmp(&b.blocks),
+        }
+    }
+}
+
+const WORKER_COLUMNS: [WorkerSort; 8] = [
+    WorkerSort::Worker,
+    WorkerSort::Wallet,
+    WorkerSort::Hashrate,
+    WorkerSort::Difficulty,
+    WorkerSort::Shares,
+    WorkerSort::Stale,
+    WorkerSort::Invalid,
+    WorkerSort::Blocks,
+];
 
 pub struct RkBridgeLogs {
     runtime: Runtime,
+    view: BridgeView,
+    sort: WorkerSort,
+    sort_ascending: bool,
+    logs: LogViewer,
 }
 
 impl RkBridgeLogs {
     pub fn new(runtime: Runtime) -> Self {
-        Self { runtime }
+        Self {
+            runtime,
+            view: BridgeView::default(),
+            sort: WorkerSort::default(),
+            sort_ascending: false,
+            logs: LogViewer::new("rk_bridge_logs", &[LogSource::Bridge]),
+        }
+    }
+
+    fn render_statistics(&mut self, ui: &mut egui::Ui) {
+        let stats = self.runtime.stratum_bridge_service().stats();
+        let Some(stats) = stats else {
+            ui.label(i18n("Waiting for worker statistics from the bridge..."));
+            return;
+        };
+
+        egui::ScrollArea::vertical()
+            .id_salt("rk_bridge_stats")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                self.render_summary(ui, &stats);
+                ui.add_space(8.);
+                self.render_workers(ui, &stats);
+                ui.add_space(12.);
+                let history = self.runtime.stratum_bridge_service().stats_history().clone();
+                render_history(ui, &history);
+            });
+    }
+
+    fn render_summary(&self, ui: &mut egui::Ui, stats: &BridgeStats) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label(i18n_args(
+                "Workers: {count}",
+                &[("count", stats.workers.len().to_string())],
+            ));
+            ui.separator();
+            ui.label(i18n_args(
+                "Hashrate: {hashrate}",
+                &[("hashrate", format_hashrate(stats.total_hashrate()))],
+            ));
+            ui.separator();
+            ui.label(i18n_args(
+                "Shares: {shares}",
+                &[("shares", stats.total_shares().to_string())],
+            ));
+            ui.separator();
+            ui.label(i18n_args(
+                "Rejected: {rejected}",
+                &[("rejected", stats.total_rejected().to_string())],
+            ));
+            ui.separator();
+            ui.label(i18n_args(
+                "Blocks: {blocks}",
+                &[("blocks", stats.total_blocks().to_string())],
+            ));
+            if let Some(difficulty) = stats.network_difficulty {
+                ui.separator();
+                ui.label(i18n_args(
+                    "Network difficulty: {difficulty}",
+                    &[("difficulty", format!("{difficulty:.3e}"))],
+                ));
+            }
+        });
+    }
+
+    fn render_workers(&mut self, ui: &mut egui::Ui, stats: &BridgeStats) {
+        if stats.workers.is_empty() {
+            ui.label(i18n("No workers connected"));
+            return;
+        }
+
+        let mut workers = stats.workers.iter().collect::<Vec<_>>();
+        workers.sort_by(|a, b| {
+            let ordering = self.sort.compare(a, b);
+            if self.sort_ascending { ordering } else { ordering.reverse() }
+        });
+
+        egui::Grid::new("rk_bridge_workers")
+            .num_columns(WORKER_COLUMNS.len())
+            .striped(true)
+            .spacing([16.0, 4.0])
+            .show(ui, |ui| {
+                for column in WORKER_COLUMNS {
+                    let mut text = column.label().to_string();
+                    if column == self.sort {
+                        text.push_str(if self.sort_ascending { " ▲" } else { " ▼" });
+                    }
+                    if ui
+                        .add(Label::new(RichText::new(text).strong()).sense(Sense::click()))
+                        .clicked()
+                    {
+                        if column == self.sort {
+                            self.sort_ascending = !self.sort_ascending;
+                        } else {
+                            self.sort = column;
+                            self.sort_ascending =
+                                matches!(column, WorkerSort::Worker | WorkerSort::Wallet);
+                        }
+                    }
+                }
+                ui.end_row();
+
+                for worker in workers {
+                    ui.label(if worker.worker.is_empty() { "-" } else { worker.worker.as_str() })
+                        .on_hover_text(format!("{} {}", worker.miner, worker.ip));
+                    if worker.wallet.contains(':') {
+                        ui.label(format_address_string(worker.wallet.as_str(), Some(4)))
+                            .on_hover_text(worker.wallet.as_str());
+                    } else {
+                        ui.label(format_partial_string(worker.wallet.as_str(), Some(4)));
+                    }
+                    ui.label(format_hashrate(worker.hashrate));
+                    ui.label(format!("{:.0}", worker.difficulty));
+                    ui.label(worker.shares.to_string());
+                    ui.label(worker.stale.to_string());
+                    ui.label((worker.invalid + worker.duplicate).to_string())
+                        .on_hover_text(i18n_args(
+                            "Invalid: {invalid}, duplicate: {duplicate}",
+                            &[
+                                ("invalid", worker.invalid.to_string()),
+                                ("duplicate", worker.duplicate.to_string()),
+                            ],
+                        ));
+                    ui.label(worker.blocks.to_string());
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn render_blocks(&self, ui: &mut egui::Ui) {
+        let service = self.runtime.stratum_bridge_service();
+
+        ui.horizontal(|ui| {
+            {
+                let ledger = service.ledger();
+                let blocks = ledger.blocks();
+                let count = |state: BlockReconciliation| {
+                    blocks
+                        .iter()
+                        .filter(|block| block.reconciliation == state)
+                        .count()
+                        .to_string()
+                };
+                ui.label(i18n_args(
+                    "Blocks: {total} (chain: {chain}, blue: {blue}, red: {red}, orphaned: {orphaned}, pending: {pending})",
+                    &[
+                        ("total", blocks.len().to_string()),
+                        ("chain", count(BlockReconciliation::Chain)),
+                        ("blue", count(BlockReconciliation::Blue)),
+                        ("red", count(BlockReconciliation::Red)),
+                        ("orphaned", count(BlockReconciliation::Orphaned)),
+                        ("pending", count(BlockReconciliation::Pending)),
+                    ],
+                ));
+            }
+
+            if ui.button(i18n("Export CSV")).clicked() {
+                let file_name = format!(
+                    "kaspa-ng-{}-blocks.csv",
+                    network_profile_slug(service.ledger().network())
+                );
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_file_name(file_name)
+                    .add_filter("CSV", &["csv"])
+                    .save_file()
+                {
+                    match service.export_ledger_csv(&path) {
+                        Ok(()) => runtime().notify(UserNotification::success(i18n_args(
+                            "Blocks exported to {path}",
+                            &[("path", path.to_string_lossy())],
+                        ))),
+                        Err(err) => runtime().error(err.to_string()),
+                    }
+                }
+            }
+        });
+        ui.separator();
+
+        let ledger = service.ledger();
+        if ledger.blocks().is_empty() {
+            ui.label(i18n("No blocks found yet"));
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .id_salt("rk_bridge_blocks")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                egui::Grid::new("rk_bridge_blocks_grid")
+                    .num_columns(6)
+                    .striped(true)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        for header in [
+                            i18n("Time"),
+                            i18n("Hash"),
+                            i18n("Worker"),
+                            i18n("Wallet"),
+                            i18n("Status"),
+                            i18n("Node"),
+                        ] {
+                            ui.label(RichText::new(header).strong());
+                        }
+                        ui.end_row();
+
+                        for block in ledger.blocks().iter().rev() {
+                            let time = block.timestamp.clone().unwrap_or_else(|| {
+                                chrono::DateTime::<chrono::Utc>::from_timestamp_millis(
+                                    block.recorded_at as i64,
+                                )
+                                .map(|time| {
+                                    time.with_timezone(&chrono::Local)
+                                        .format("%Y-%m-%d %H:%M:%S")
+                                        .to_string()
+                                })
+                                .unwrap_or_default()
+                            });
+                            ui.label(time);
+                            if ui
+                                .add(
+                                    Label::new(format_partial_string(block.hash.as_str(), Some(8)))
+                                        .sense(Sense::click()),
+                                )
+                                .on_hover_text(block.hash.as_str())
+                                .clicked()
+                            {
+                                ui.ctx().copy_text(block.hash.clone());
+                                runtime().notify_clipboard(i18n("Copied to clipboard"));
+                            }
+                            ui.label(block.worker.as_deref().unwrap_or("-"));
+                            match block.wallet.as_deref() {
+                                Some(wallet) if wallet.contains(':') => {
+                                    ui.label(format_address_string(wallet, Some(4)))
+                                        .on_hover_text(wallet);
+                                }
+                                Some(wallet) => {
+                                    ui.label(wallet);
+                                }
+                                None => {
+                                    ui.label("-");
+                                }
+                            }
+                            ui.label(block.status.as_str());
+                            let color = match block.reconciliation {
+                                BlockReconciliation::Chain | BlockReconciliation::Blue => {
+                                    theme_color().ack_color
+                                }
+                                BlockReconciliation::Red | BlockReconciliation::Orphaned => {
+                                    theme_color().error_color
+                                }
+                                BlockReconciliation::Pending => theme_color().warning_color,
+                            };
+                            ui.colored_label(color, block.reconciliation.as_str());
+                            ui.end_row();
+                        }
+                    });
+            });
     }
+
+}
+
+fn render_history(ui: &mut egui::Ui, history: &BridgeStatsHistory) {
+    if history.samples().len() < 2 {
+        return;
+    }
+
+    let x_axis_formatter = |grid: egui_plot::GridMark, _range: &std::ops::RangeInclusive<f64>| {
+        chrono::DateTime::<chrono::Utc>::from_timestamp((grid.value / 1000.0) as i64, 0)
+            .map(|time| time.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+            .unwrap_or_default()
+    };
+
+    let mut workers = history
+        .samples()
+        .iter()
+        .flat_map(|sample| sample.workers.keys().cloned())
+        .collect::<Vec<_>>();
+    workers.sort();
+    workers.dedup();
+
+    ui.label(RichText::new(i18n("Hashrate")).strong());
+    Plot::new("rk_bridge_hashrate")
+        .legend(Legend::default())
+        .height(180.)
+        .allow_drag(false)
+        .allow_scroll(false)
+        .x_axis_formatter(x_axis_formatter)
+        .y_axis_formatter(|grid, _range| format_hashrate(grid.value))
+        .show(ui, |plot_ui| {
+            let total = history
+                .samples()
+                .iter()
+                .map(|sample| [sample.timestamp, sample.hashrate])
+                .collect::<Vec<_>>();
+            plot_ui.line(Line::new(i18n("Total"), PlotPoints::from(total)).fill(0.0));
+
+            if workers.len() > 1 {
+                for key in workers.iter() {
+                    let (wallet, worker) = key;
+                    // the same worker name may be used with several wallets
+                    let name = if workers.iter().filter(|(_, other)| other == worker).count() > 1 {
+                        format!("{worker} ({})", format_address_string(wallet, Some(4)))
+                    } else {
+                        worker.clone()
+                    };
+                    let points = history
+                        .samples()
+                        .iter()
+                        .filter_map(|sample| {
+                            sample
+                                .workers
+                                .get(key)
+                                .map(|hashrate| [sample.timestamp, *hashrate])
+                        })
+                        .collect::<Vec<_>>();
+                    plot_ui.line(Line::new(name, PlotPoints::from(points)));
+                }
+            }
+        });
+
+    ui.add_space(8.);
+    ui.label(RichText::new(i18n("Shares")).strong());
+    Plot::new("rk_bridge_shares")
+        .legend(Legend::default())
+        .height(140.)
+        .allow_drag(false)
+        .allow_scroll(false)
+        .x_axis_formatter(x_axis_formatter)
+        .show(ui, |plot_ui| {
+            let shares = history
+                .samples()
+                .iter()
+                .map(|sample| [sample.timestamp, sample.shares as f64])
+                .collect::<Vec<_>>();
+            let rejected = history
+                .samples()
+                .iter()
+                .map(|sample| [sample.timestamp, sample.rejected as f64])
+                .collect::<Vec<_>>();
+            plot_ui.line(Line::new(i18n("Accepted"), PlotPoints::from(shares)));
+            plot_ui.line(
+                Line::new(i18n("Rejected"), PlotPoints::from(rejected))
+                    .color(theme_color().error_color),
+            );
+        });
 }
 
 impl ModuleT for RkBridgeLogs {
@@ -22,48 +435,22 @@ impl ModuleT for RkBridgeLogs {
         _frame: &mut eframe::Frame,
         ui: &mut egui::Ui,
     ) {
-        use egui_phosphor::light::CLIPBOARD_TEXT;
-
-        let available_width = ui.available_width();
-
         if !core.settings.node.stratum_bridge_enabled {
             ui.colored_label(theme_color().warning_color, i18n("RK Bridge is disabled in Settings."));
             ui.add_space(8.);
         }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        egui::ScrollArea::vertical()
-            .id_salt("rk_bridge_logs")
-            .auto_shrink([false; 2])
-            .stick_to_bottom(true)
-            .show(ui, |ui| {
-                for log in self.runtime.stratum_bridge_service().logs().iter() {
-                    ui.label(RichText::from(log));
-                }
-            });
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.view, BridgeView::Statistics, i18n("Statistics"));
+            ui.selectable_value(&mut self.view, BridgeView::Blocks, i18n("Blocks"));
+            ui.selectable_value(&mut self.view, BridgeView::Logs, i18n("Logs"));
+        });
+        ui.separator();
 
-        let copy_to_clipboard = Button::new(RichText::new(format!(" {CLIPBOARD_TEXT} ")).size(20.));
-
-        let button_rect = Rect::from_min_size(
-            pos2(available_width - 48.0, core.device().top_offset() + 32.0),
-            vec2(38.0, 20.0),
-        );
-
-        if ui
-            .put(button_rect, copy_to_clipboard)
-            .on_hover_text_at_pointer(i18n("Copy logs to clipboard"))
-            .clicked()
-        {
-            let logs = self
-                .runtime
-                .stratum_bridge_service()
-                .logs()
-                .iter()
-                .map(|log| log.to_string())
-                .collect::<Vec<String>>()
-                .join("\n");
-            ui.ctx().copy_text(logs);
-            runtime().notify_clipboard(i18n("Copied to clipboard"));
+        match self.view {
+            BridgeView::Statistics => self.render_statistics(ui),
+            BridgeView::Blocks => self.render_blocks(ui),
+            BridgeView::Logs => self.logs.render(ui),
         }
     }
 }
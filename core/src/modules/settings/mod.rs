@@ -105,33 +105,68 @@ impl Settings {
             match settings.connection_config_kind {
                 NodeConnectionConfigKind::Custom => {
 
-                    CollapsingHeader::new(i18n("wRPC Connection Settings"))
-                        .default_open(true)
-                        .show(ui, |ui| {
-
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.horizontal_wrapped(|ui|{
+                        ui.label(i18n("RPC Protocol:"));
+                        crate::settings::RpcKind::iter().for_each(|kind| {
+                            ui.radio_value(&mut settings.rpc_kind, *kind, kind.to_string());
+                        });
+                    });
 
-                            ui.horizontal(|ui|{
-                                ui.label(i18n("wRPC Encoding:"));
-                                WrpcEncoding::iter().for_each(|encoding| {
-                                    ui.radio_value(&mut settings.wrpc_encoding, *encoding, encoding.to_string());
+                    if settings.rpc_kind == crate::settings::RpcKind::Grpc {
+                        CollapsingHeader::new(i18n("gRPC Connection Settings"))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                ui.horizontal(|ui|{
+                                    ui.label(i18n("gRPC URL:"));
+                                    ui.add(TextEdit::singleline(&mut settings.grpc_url));
                                 });
+
+                                if settings.grpc_url.trim().contains(char::is_whitespace) {
+                                    ui.label(
+                                        RichText::new(i18n("gRPC URL must not contain whitespace"))
+                                            .color(theme_color().warning_color),
+                                    );
+                                    node_settings_error = Some(i18n("Invalid gRPC URL"));
+                                } else {
+                                    ui.label(
+                                        RichText::new(i18n_args(
+                                            "If the port is omitted, the default gRPC port {port} will be used.",
+                                            &[("port", &crate::settings::node_grpc_port_for_network(settings.network).to_string())],
+                                        ))
+                                        .small(),
+                                    );
+                                }
                             });
+                    } else {
+                        CollapsingHeader::new(i18n("wRPC Connection Settings"))
+                            .default_open(true)
+                            .show(ui, |ui| {
 
 
-                            ui.horizontal(|ui|{
-                                ui.label(i18n("wRPC URL:"));
-                                ui.add(TextEdit::singleline(&mut settings.wrpc_url));
+                                ui.horizontal(|ui|{
+                                    ui.label(i18n("wRPC Encoding:"));
+                                    WrpcEncoding::iter().for_each(|encoding| {
+                                        ui.radio_value(&mut settings.wrpc_encoding, *encoding, encoding.to_string());
+                                    });
+                                });
+
+
+                                ui.horizontal(|ui|{
+                                    ui.label(i18n("wRPC URL:"));
+                                    ui.add(TextEdit::singleline(&mut settings.wrpc_url));
                                 
-                            });
+                                });
 
-                            if let Err(err) = KaspaRpcClient::parse_url(settings.wrpc_url.clone(), settings.wrpc_encoding, settings.network.into()) {
-                                ui.label(
-                                    RichText::new(err.to_string())
-                                        .color(theme_color().warning_color),
-                                );
-                                node_settings_error = Some(i18n("Invalid wRPC URL"));
-                            }
-                        });
+                                if let Err(err) = KaspaRpcClient::parse_url(settings.wrpc_url.clone(), settings.wrpc_encoding, settings.network.into()) {
+                                    ui.label(
+                                        RichText::new(err.to_string())
+                                            .color(theme_color().warning_color),
+                                    );
+                                    node_settings_error = Some(i18n("Invalid wRPC URL"));
+                                }
+                            });
+                    }
                     // cfg_if! {
                     //     if #[cfg(not(target_arch = "wasm32"))] {
                     //         ui.horizontal_wrapped(|ui|{
@@ -511,6 +546,18 @@ impl Settings {
                                 self.runtime
                                     .stratum_bridge_service()
                                     .enable(bridge_enabled, &core.settings.node);
+                                self.runtime
+                                    .cpu_miner_service()
+                                    .update_settings(&core.settings.node);
+                                self.runtime
+                                    .rothschild_service()
+                                    .update_settings(&core.settings.node);
+                                self.runtime
+                                    .peer_monitor_service()
+                                    .update_node_settings(&core.settings.node);
+                                self.runtime
+                                    .alert_monitor_service()
+                                    .update_settings(core.settings.alerts.clone());
                                 #[cfg(not(target_arch = "wasm32"))]
                                 self.runtime
                                     .self_hosted_db_service()
@@ -535,6 +582,10 @@ impl Settings {
                                 self.runtime
                                     .self_hosted_loader_service()
                                     .enable(core.settings.self_hosted.enabled);
+                                #[cfg(not(target_arch = "wasm32"))]
+                                self.runtime
+                                    .control_api_service()
+                                    .update_settings(&core.settings.control_api, core.settings.node.network);
 
                                 if restart {
                                     self.runtime.kaspa_service().update_services(&self.settings.node, None);
@@ -679,6 +730,17 @@ impl Settings {
                                         });
                                     }
 
+                                    #[cfg(target_os = "windows")]
+                                    {
+                                        ui.add_space(4.);
+                                        if ui.button(i18n("Open kaspa: links with Kaspa NG")).clicked() {
+                                            match crate::utils::register_payment_uri_handler() {
+                                                Ok(()) => runtime().notify(UserNotification::success(i18n("Kaspa NG now opens kaspa: links"))),
+                                                Err(err) => runtime().error(err.to_string()),
+                                            }
+                                        }
+                                    }
+
                                     ui.add_space(1.);
                                 });
 
@@ -1480,6 +1542,202 @@ impl Settings {
                         }
                     });
 
+                #[cfg(not(target_arch = "wasm32"))]
+                CollapsingHeader::new(i18n("CPU Miner"))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        let mut enabled = self.settings.node.cpu_miner_enabled;
+
+                        let response = ui.checkbox(&mut enabled, i18n("Enable CPU Miner"));
+
+                        if matches!(core.settings.node.network, Network::Mainnet) {
+                            ui.colored_label(
+                                theme_color().warning_color,
+                                i18n("CPU mining is intended for testnet and devnet use."),
+                            );
+                        }
+                        if core.settings.node.node_kind.is_local() && !core.settings.node.enable_grpc {
+                            ui.colored_label(
+                                theme_color().warning_color,
+                                i18n("Enable gRPC in Node settings to use CPU Miner."),
+                            );
+                        }
+
+                        if response.changed() {
+                            self.settings.node.cpu_miner_enabled = enabled;
+                            core.settings.node.cpu_miner_enabled = enabled;
+                            self.runtime
+                                .cpu_miner_service()
+                                .enable(enabled, &core.settings.node);
+                            core.store_settings();
+                        }
+
+                        ui.add_space(6.);
+                        ui.separator();
+                        ui.add_space(6.);
+
+                        let mut changed = false;
+                        let miner = &mut self.settings.node.cpu_miner;
+                        let max_threads = std::thread::available_parallelism()
+                            .map(|threads| threads.get() as u16)
+                            .unwrap_or(1);
+
+                        Grid::new("cpu_miner_settings_grid")
+                            .num_columns(2)
+                            .spacing([16.0, 6.0])
+                            .show(ui, |ui| {
+                                ui.label(i18n("Mining Address"));
+                                changed |= ui
+                                    .add(
+                                        TextEdit::singleline(&mut miner.mining_address)
+                                            .desired_width(360.0)
+                                            .hint_text(i18n("kaspa address")),
+                                    )
+                                    .changed();
+                                ui.end_row();
+
+                                ui.label(i18n("Threads"));
+                                changed |= ui
+                                    .add(
+                                        DragValue::new(&mut miner.threads)
+                                            .speed(1)
+                                            .range(1..=max_threads),
+                                    )
+                                    .changed();
+                                ui.end_row();
+                            });
+
+                        let address = miner.mining_address.trim();
+                        let address_valid = address.is_empty() || Address::try_from(address).is_ok();
+                        if !address_valid {
+                            ui.colored_label(
+                                theme_color().error_color,
+                                i18n("Invalid mining address"),
+                            );
+                        }
+
+                        if changed {
+                            core.settings.node.cpu_miner = miner.clone();
+                            // avoid restarting the miner on every keystroke while the address is incomplete
+                            if address_valid {
+                                self.runtime
+                                    .cpu_miner_service()
+                                    .update_settings(&core.settings.node);
+                            }
+                            core.store_settings();
+                        }
+                    });
+
+                #[cfg(not(target_arch = "wasm32"))]
+                CollapsingHeader::new(i18n("Rothschild"))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        use crate::runtime::services::RothschildService;
+
+                        let can_enable = RothschildService::is_network_allowed(core.settings.node.network);
+                        let mut enabled = self.settings.node.rothschild_enabled;
+
+                        let response = ui.add_enabled(
+                            can_enable,
+                            Checkbox::new(&mut enabled, i18n("Enable Rothschild transaction generator")),
+                        );
+
+                        if !can_enable {
+                            ui.colored_label(
+                                theme_color().warning_color,
+                                i18n("Rothschild is not available on Mainnet."),
+                            );
+                        } else if core.settings.node.node_kind.is_local() && !core.settings.node.enable_grpc {
+                            ui.colored_label(
+                                theme_color().warning_color,
+                                i18n("Enable gRPC in Node settings to use Rothschild."),
+                            );
+                        }
+
+                        if response.changed() {
+                            self.settings.node.rothschild_enabled = enabled;
+                            core.settings.node.rothschild_enabled = enabled;
+                            self.runtime
+                                .rothschild_service()
+                                .enable(enabled, &core.settings.node);
+                            core.store_settings();
+                        }
+
+                        ui.add_space(6.);
+                        ui.separator();
+                        ui.add_space(6.);
+
+                        let mut changed = false;
+                        let mut commit = false;
+                        let rothschild = &mut self.settings.node.rothschild;
+
+                        ui.add_enabled_ui(can_enable, |ui| {
+                            Grid::new("rothschild_settings_grid")
+                                .num_columns(2)
+                                .spacing([16.0, 6.0])
+                                .show(ui, |ui| {
+                                    ui.label(i18n("Private Key"));
+                                    let response = ui.add(
+                                        TextEdit::singleline(rothschild.private_key.expose_mut())
+                                            .desired_width(360.0)
+                                            .password(true)
+                                            .hint_text(i18n("kept for this session only")),
+                                    );
+                                    changed |= response.changed();
+                                    commit |= response.lost_focus();
+                                    ui.end_row();
+
+                                    ui.label(i18n("Change Address"));
+                                    let response = ui.add(
+                                        TextEdit::singleline(&mut rothschild.change_address)
+                                            .desired_width(360.0)
+                                            .hint_text(i18n("optional")),
+                                    );
+                                    changed |= response.changed();
+                                    commit |= response.lost_focus();
+                                    ui.end_row();
+
+                                    ui.label(i18n("Target TPS"));
+                                    let response = ui.add(
+                                        DragValue::new(&mut rothschild.tps)
+                                            .speed(1)
+                                            .range(1..=10_000),
+                                    );
+                                    changed |= response.changed();
+                                    commit |= response.changed();
+                                    ui.end_row();
+
+                                    ui.label(i18n("Threads"));
+                                    let response = ui.add(
+                                        DragValue::new(&mut rothschild.threads)
+                                            .speed(1)
+                                            .range(1..=u8::MAX),
+                                    );
+                                    changed |= response.changed();
+                                    commit |= response.changed();
+                                    ui.end_row();
+                                });
+                        });
+
+                        ui.label(
+                            RichText::new(i18n("The private key is kept for this session only and is not saved. If it is empty, rothschild generates a new key on every start. Use a dedicated testnet key."))
+                                .size(12.)
+                                .color(theme_color().warning_color),
+                        );
+
+                        if changed {
+                            core.settings.node.rothschild = rothschild.clone();
+                            core.store_settings();
+                        }
+
+                        // text fields are applied once editing completes to avoid restarting the generator on every keystroke
+                        if commit {
+                            self.runtime
+                                .rothschild_service()
+                                .update_settings(&core.settings.node);
+                        }
+                    });
+
                 CollapsingHeader::new(i18n("Market Monitor"))
                     .default_open(true)
                     .show(ui, |ui| {
@@ -1500,6 +1758,127 @@ impl Settings {
                             core.store_settings();
                         }
                     });    
+
+                #[cfg(not(target_arch = "wasm32"))]
+                CollapsingHeader::new(i18n("Control API"))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        let mut control_api = self.settings.control_api.clone();
+                        let mut changed = false;
+
+                        changed |= ui.checkbox(&mut control_api.enabled, i18n("Enable local JSON-RPC control API")).changed();
+
+                        let network = core.settings.node.network;
+                        let mut endpoint = format!("http://127.0.0.1:{}", control_api.effective_port(network));
+                        ui.horizontal(|ui| {
+                            ui.label(i18n("Endpoint"));
+                            ui.add_enabled(false, TextEdit::singleline(&mut endpoint).desired_width(260.0));
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(i18n("Bearer Token"));
+                            let mut token = control_api.token.clone();
+                            ui.add(TextEdit::singleline(&mut token).password(true).desired_width(260.0).interactive(false));
+                            if ui.button(i18n("Copy")).clicked() {
+                                ui.ctx().copy_text(control_api.token.clone());
+                                runtime().notify_clipboard(i18n("Copied to clipboard"));
+                            }
+                            if ui.button(i18n("Regenerate")).clicked() {
+                                control_api.token = ControlApiSettings::generate_token();
+                                changed = true;
+                            }
+                        });
+
+                        ui.label(RichText::new(i18n("The control API accepts connections from this computer only. Anyone holding the token can send funds from open wallets.")).size(12.0));
+
+                        if changed {
+                            self.settings.control_api = control_api.clone();
+                            core.settings.control_api = control_api;
+                            self.runtime.control_api_service().update_settings(&core.settings.control_api, network);
+                            core.store_settings();
+                        }
+                    });
+
+                CollapsingHeader::new(i18n("Alerts"))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        let mut alerts = self.settings.alerts.clone();
+                        let mut changed = false;
+
+                        changed |= ui.checkbox(&mut alerts.enabled, i18n("Enable alerts")).changed();
+
+                        ui.add_enabled_ui(alerts.enabled, |ui| {
+                            Grid::new("alert_rules")
+                                .num_columns(2)
+                                .spacing([16.0, 4.0])
+                                .show(ui, |ui| {
+                                    for kind in AlertKind::iter() {
+                                        let rule = alerts.rule_mut(*kind);
+                                        changed |= ui.checkbox(&mut rule.enabled, i18n(kind.describe())).changed();
+                                        let speed = if *kind == AlertKind::MempoolAbove { 100.0 } else { 1.0 };
+                                        changed |= ui.add_enabled(rule.enabled, DragValue::new(&mut rule.threshold).speed(speed).range(0.0..=f64::MAX)).changed();
+                                        ui.end_row();
+                                    }
+                                });
+
+                            #[cfg(not(target_arch = "wasm32"))]
+                            {
+                                ui.add_space(4.);
+                                changed |= ui.checkbox(&mut alerts.os_notifications, i18n("Show desktop notifications")).changed();
+                                changed |= ui.checkbox(&mut alerts.webhook_enabled, i18n("Post alerts to a local webhook")).changed();
+                                ui.horizontal(|ui| {
+                                    ui.label(i18n("Webhook URL"));
+                                    changed |= ui.add_enabled(alerts.webhook_enabled, TextEdit::singleline(&mut alerts.webhook_url).desired_width(260.0)).changed();
+                                });
+                                if alerts.webhook_enabled && alerts.webhook_url.trim().is_not_empty() {
+                                    if let Some(err) = crate::alerts::webhook_url_error(alerts.webhook_url.as_str()) {
+                                        ui.colored_label(theme_color().error_color, err);
+                                    }
+                                }
+                            }
+                        });
+
+                        ui.label(RichText::new(i18n("Alerts are shown as notifications when a rule starts to match and again only after it has cleared.")).size(12.0));
+
+                        if changed {
+                            self.settings.alerts = alerts.clone();
+                            self.runtime.alert_monitor_service().update_settings(alerts.clone());
+                            core.settings.alerts = alerts;
+                            core.store_settings();
+                        }
+                    });
+
+                #[cfg(not(target_arch = "wasm32"))]
+                CollapsingHeader::new(i18n("Log Files"))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        let mut logs = self.settings.logs.clone();
+                        let mut changed = false;
+
+                        changed |= ui.checkbox(&mut logs.files_enabled, i18n("Write logs to rotating files")).changed();
+
+                        ui.add_enabled_ui(logs.files_enabled, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(i18n("Maximum file size (MB)"));
+                                changed |= ui.add(DragValue::new(&mut logs.max_file_size_mb).range(1..=1024)).changed();
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label(i18n("Files to keep"));
+                                changed |= ui.add(DragValue::new(&mut logs.max_files).range(1..=100)).changed();
+                            });
+                        });
+
+                        if let Ok(folder) = crate::runtime::services::log_hub().folder() {
+                            ui.label(RichText::new(format!("{} {}", i18n("Folder:"), folder.display())).size(12.0));
+                        }
+
+                        if changed {
+                            self.settings.logs = logs.clone();
+                            core.settings.logs = logs;
+                            crate::runtime::services::log_hub().configure(&core.settings.logs);
+                            core.store_settings();
+                        }
+                    });
             });
 
         CollapsingHeader::new(i18n("Network Fee Estimator"))
@@ -1619,7 +1998,7 @@ impl Settings {
                             if ui
                                 .medium_button(i18n("Reset Database"))
                                 .on_hover_text(i18n(
-                                    "Drops and recreates self-hosted database for mainnet.",
+                                    "Drops and recreates the self-hosted database of the selected network.",
                                 ))
                                 .clicked()
                             {
@@ -1637,10 +2016,11 @@ impl Settings {
                     if let Some(response) = ui.confirm_medium_apply_cancel(Align::Min) {
                         match response {
                             Confirm::Ack => {
-                                let settings = crate::settings::Settings {
+                                let mut settings = crate::settings::Settings {
                                     initialized : true,
                                     ..Default::default()
                                 };
+                                settings.node.network = core.settings.node.network;
                                 self.settings = settings.clone();
                                 settings.store_sync().unwrap();
                                 #[cfg(target_arch = "wasm32")]
@@ -1658,8 +2038,9 @@ impl Settings {
                 if self.reset_database {
                     ui.add_space(16.);
                     ui.label(
-                        RichText::new(i18n(
-                            "Are you sure you want to reset the self-hosted database (mainnet)?",
+                        RichText::new(i18n_args(
+                            "Are you sure you want to reset the self-hosted database ({network})?",
+                            &[("network", core.settings.node.network.to_string())],
                         ))
                         .color(theme_color().warning_color),
                     );
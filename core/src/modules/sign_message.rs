@@ -0,0 +1,290 @@
+use crate::imports::*;
+use crate::modules::account_manager::message::{sign_message, verify_message};
+use egui_phosphor::light::{CHECK_CIRCLE, CLIPBOARD_TEXT, X_CIRCLE};
+
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
+enum Mode {
+    #[default]
+    Sign,
+    Verify,
+}
+
+#[derive(Clone, Default)]
+enum State {
+    #[default]
+    Edit,
+    Secret,
+    Processing,
+}
+
+/// Signs arbitrary messages with the key of an account address (e.g. to
+/// prove address ownership) and verifies signatures produced by others.
+pub struct SignMessage {
+    #[allow(dead_code)]
+    runtime: Runtime,
+    account : Option<Account>,
+    mode : Mode,
+    state : State,
+    address : String,
+    message : String,
+    signature : String,
+    verification : Option<std::result::Result<bool, String>>,
+    secret : SecretPrompt,
+    error : Option<String>,
+}
+
+impl SignMessage {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            runtime,
+            account : None,
+            mode : Mode::default(),
+            state : State::default(),
+            address : String::default(),
+            message : String::default(),
+            signature : String::default(),
+            verification : None,
+            secret : SecretPrompt::default(),
+            error : None,
+        }
+    }
+
+    /// Prepares the module for signing with the given account, using its
+    /// current receive address by default.
+    pub fn select(&mut self, account : &Account) {
+        self.account = Some(account.clone());
+        self.set_mode(Mode::Sign);
+        self.address = account.receive_address().to_string();
+    }
+
+    fn set_mode(&mut self, mode : Mode) {
+        self.mode = mode;
+        self.state = State::Edit;
+        self.address.clear();
+        self.message.clear();
+        self.signature.clear();
+        self.verification = None;
+        self.error = None;
+    }
+
+    fn editor(ui : &mut Ui, caption : &str, text : &mut String) {
+        ui.label(RichText::new(caption).size(12.).raised());
+        ui.add_sized(theme_style().panel_editor_size, TextEdit::singleline(text)
+            .vertical_align(Align::Center));
+        ui.add_space(8.);
+    }
+
+    fn render_message(ui : &mut Ui, message : &mut String) {
+        ui.label(RichText::new(i18n("Message")).size(12.).raised());
+        ui.add_sized([ui.available_width(), 96.], TextEdit::multiline(message));
+        ui.add_space(8.);
+    }
+
+    fn verify(&mut self, network_type : &NetworkType) {
+        self.verification = Some(
+            try_user_string_to_address(self.address.trim(), network_type)
+                .and_then(|address| verify_message(&address, self.message.as_str(), self.signature.as_str()))
+                .map_err(|err| err.to_string())
+        );
+    }
+}
+
+impl ModuleT for SignMessage {
+
+    fn style(&self) -> ModuleStyle {
+        ModuleStyle::Mobile
+    }
+
+    fn secure(&self) -> bool {
+        true
+    }
+
+    fn deactivate(&mut self, _core: &mut Core) {
+        self.secret.clear();
+        if matches!(self.state, State::Secret) {
+            self.state = State::Edit;
+        }
+    }
+
+    fn render(
+        &mut self,
+        core: &mut Core,
+        _ctx: &egui::Context,
+        _frame: &mut eframe::Frame,
+        ui: &mut egui::Ui,
+    ) {
+        let network_type : NetworkType = core.network().into();
+        let requires_bip39_passphrase = self.account.as_ref().map(|account| account.requires_bip39_passphrase(core)).unwrap_or(false);
+        let sign_message_result = Payload::<Result<String>>::new("sign_message_result");
+
+        if let Some(result) = sign_message_result.take() {
+            match result {
+                Ok(signature) => {
+                    self.signature = signature;
+                    runtime().notify(UserNotification::success(i18n("Message signed")));
+                }
+                Err(err) => {
+                    self.error = Some(err.to_string());
+                }
+            }
+            self.state = State::Edit;
+        }
+
+        let close = Rc::new(RefCell::new(false));
+        let submit = Rc::new(RefCell::new(false));
+        let mode = RefCell::new(None);
+
+        Panel::new(self)
+            .with_caption(i18n("Sign / Verify Message"))
+            .with_back_enabled(core.has_stack(), |_|{
+                *close.borrow_mut() = true;
+            })
+            .with_header(|this, ui| {
+                ui.horizontal(|ui| {
+                    if ui.selectable_label(this.mode == Mode::Sign, i18n("Sign")).clicked() && this.mode != Mode::Sign {
+                        mode.borrow_mut().replace(Mode::Sign);
+                    }
+                    if ui.selectable_label(this.mode == Mode::Verify, i18n("Verify")).clicked() && this.mode != Mode::Verify {
+                        mode.borrow_mut().replace(Mode::Verify);
+                    }
+                });
+                if this.mode == Mode::Sign {
+                    ui.add_space(4.);
+                    if let Some(account) = this.account.as_ref() {
+                        ui.label(i18n_args("Account: {account}", &[("account", account.name_or_id())]));
+                    } else {
+                        ui.label(i18n("Please select an account"));
+                    }
+                }
+            })
+            .with_body(|this, ui| {
+                match (this.mode, this.state.clone()) {
+                    (Mode::Sign, State::Edit) => {
+                        ui.label(i18n("Sign a message with the key of one of your addresses to prove that you own it."));
+                        ui.add_space(8.);
+                        Self::editor(ui, i18n("Address"), &mut this.address);
+                        Self::render_message(ui, &mut this.message);
+
+                        if this.signature.is_not_empty() {
+                            ui.label(RichText::new(i18n("Signature")).size(12.).raised());
+                            ui.label(RichText::new(this.signature.as_str()).monospace());
+                            ui.add_space(4.);
+                            if ui.button(format!("{CLIPBOARD_TEXT} {}", i18n("Copy"))).clicked() {
+                                ui.ctx().copy_text(this.signature.clone());
+                                runtime().notify_clipboard(i18n("Copied to clipboard"));
+                            }
+                        }
+
+                        if let Some(error) = this.error.as_ref() {
+                            ui.add_space(8.);
+                            ui.colored_label(error_color(), error);
+                        }
+                    }
+
+                    (Mode::Sign, State::Secret) => {
+                        if this.secret.render(ui, requires_bip39_passphrase) {
+                            *submit.borrow_mut() = true;
+                        }
+                    }
+
+                    (Mode::Sign, State::Processing) => {
+                        ui.add_space(64.);
+                        ui.add(egui::Spinner::new().size(92.));
+                    }
+
+                    (Mode::Verify, _) => {
+                        ui.label(i18n("Verify that a message has been signed by the owner of an address."));
+                        ui.add_space(8.);
+                        let mut changed = false;
+                        ui.label(RichText::new(i18n("Address")).size(12.).raised());
+                        changed |= ui.add_sized(theme_style().panel_editor_size, TextEdit::singleline(&mut this.address)
+                            .vertical_align(Align::Center)).changed();
+                        ui.add_space(8.);
+                        ui.label(RichText::new(i18n("Message")).size(12.).raised());
+                        changed |= ui.add_sized([ui.available_width(), 96.], TextEdit::multiline(&mut this.message)).changed();
+                        ui.add_space(8.);
+                        ui.label(RichText::new(i18n("Signature")).size(12.).raised());
+                        changed |= ui.add_sized(theme_style().panel_editor_size, TextEdit::singleline(&mut this.signature)
+                            .font(egui::TextStyle::Monospace)
+                            .vertical_align(Align::Center)).changed();
+                        if changed {
+                            this.verification = None;
+                        }
+
+                        ui.add_space(8.);
+                        match this.verification.as_ref() {
+                            Some(Ok(true)) => { ui.colored_label(theme_color().strong_color, format!("{CHECK_CIRCLE} {}", i18n("The signature is valid"))); }
+                            Some(Ok(false)) => { ui.colored_label(error_color(), format!("{X_CIRCLE} {}", i18n("The signature is not valid"))); }
+                            Some(Err(err)) => { ui.colored_label(error_color(), err); }
+                            None => { }
+                        }
+                    }
+                }
+            })
+            .with_footer(|this, ui| {
+                match (this.mode, this.state.clone()) {
+                    (Mode::Sign, State::Edit) => {
+                        let ready = this.account.is_some() && this.address.trim().is_not_empty() && this.message.is_not_empty();
+                        if ui.large_button_enabled(ready, i18n("Sign")).clicked() {
+                            this.error = None;
+                            this.signature.clear();
+                            this.state = State::Secret;
+                            this.secret.activate();
+                        }
+                    }
+                    (Mode::Sign, State::Secret) => {
+                        match this.secret.render_footer(ui, i18n("Sign"), requires_bip39_passphrase) {
+                            SecretPromptAction::Submit => { *submit.borrow_mut() = true; }
+                            SecretPromptAction::Cancel => { this.state = State::Edit; }
+                            SecretPromptAction::None => { }
+                        }
+                    }
+                    (Mode::Sign, State::Processing) => { }
+                    (Mode::Verify, _) => {
+                        let ready = this.address.trim().is_not_empty() && this.signature.trim().is_not_empty();
+                        if ui.large_button_enabled(ready, i18n("Verify")).clicked() {
+                            this.verify(&network_type);
+                        }
+                    }
+                }
+            })
+            .render(ui);
+
+        if let Some(mode) = mode.take() {
+            self.secret.clear();
+            self.set_mode(mode);
+            if let (Mode::Sign, Some(account)) = (mode, self.account.as_ref()) {
+                self.address = account.receive_address().to_string();
+            }
+        }
+
+        if *submit.borrow() {
+            if let Some(account_id) = self.account.as_ref().map(|account| account.id()) {
+                match try_user_string_to_address(self.address.trim(), &network_type) {
+                    Ok(address) => {
+                        let message = self.message.clone();
+                        let (wallet_secret, payment_secret) = self.secret.secrets(requires_bip39_passphrase);
+                        self.state = State::Processing;
+
+                        spawn_with_result(&sign_message_result, async move {
+                            let signature = sign_message(account_id, address, message, wallet_secret, payment_secret).await?;
+                            runtime().request_repaint();
+                            Ok(signature)
+                        });
+                    }
+                    Err(err) => {
+                        self.error = Some(err.to_string());
+                        self.state = State::Edit;
+                    }
+                }
+            }
+            self.secret.clear();
+        }
+
+        if *close.borrow() {
+            self.secret.clear();
+            self.set_mode(Mode::Sign);
+            core.back();
+        }
+    }
+}
@@ -1,133 +1,318 @@
 use crate::imports::*;
+use crate::watch_list::parse_addresses;
+use egui_phosphor::light::{CLIPBOARD_TEXT, EYE, TRASH};
 
-pub enum State {
-    Select,
-    CheckBalanceStart,
-    CheckBalanceProcess,
-    CheckBalanceResult(Result<u64>),
+/// Number of most recent UTXOs listed for each address.
+const RECENT_UTXOS: usize = 10;
+
+#[derive(Clone, Default)]
+enum State {
+    #[default]
+    Edit,
+    Processing,
+    Result(Arc<Vec<AddressSummary>>),
+}
+
+#[derive(Clone, Debug)]
+struct UtxoSummary {
+    transaction_id : String,
+    index : u32,
+    amount : u64,
+    block_daa_score : u64,
+    is_coinbase : bool,
+}
+
+#[derive(Clone, Debug)]
+struct AddressSummary {
+    address : Address,
+    balance : u64,
+    utxo_count : usize,
+    /// Most recent unspent outputs, newest first.
+    recent : Vec<UtxoSummary>,
+}
+
+/// Looks up the balances and UTXOs of the addresses on the connected node,
+/// which must be running with the UTXO index enabled.
+async fn check_balances(rpc_api : Arc<DynRpcApi>, addresses : Vec<Address>) -> Result<Vec<AddressSummary>> {
+    let balances = rpc_api.get_balances_by_addresses(addresses.clone()).await?;
+    let utxos = rpc_api.get_utxos_by_addresses(addresses.clone()).await?;
+
+    let summaries = addresses.into_iter().map(|address| {
+        let balance = balances.iter()
+            .find(|entry| entry.address == address)
+            .and_then(|entry| entry.balance)
+            .unwrap_or_default();
+
+        let mut entries = utxos.iter()
+            .filter(|entry| entry.address.as_ref() == Some(&address))
+            .map(|entry| UtxoSummary {
+                transaction_id : entry.outpoint.transaction_id.to_string(),
+                index : entry.outpoint.index,
+                amount : entry.utxo_entry.amount,
+                block_daa_score : entry.utxo_entry.block_daa_score,
+                is_coinbase : entry.utxo_entry.is_coinbase,
+            })
+            .collect::<Vec<_>>();
+        let utxo_count = entries.len();
+        entries.sort_by(|a, b| b.block_daa_score.cmp(&a.block_daa_score));
+        entries.truncate(RECENT_UTXOS);
+
+        AddressSummary { address, balance, utxo_count, recent : entries }
+    }).collect();
+
+    Ok(summaries)
 }
 
-pub struct Tools {
+/// Looks up the balance and UTXOs of arbitrary addresses on the connected
+/// node; does not require an open wallet.
+pub struct CheckBalance {
     #[allow(dead_code)]
     runtime: Runtime,
-    state: State,
-    address_string: String,
-    address: Option<Address>,
+    state : State,
+    addresses : String,
+    /// Name of the watch-only address list being checked.
+    watching : Option<String>,
+    /// Checks the addresses on the next frame (after opening a watch list).
+    check_pending : bool,
+    error : Option<String>,
 }
 
-impl Tools {
+impl CheckBalance {
     pub fn new(runtime: Runtime) -> Self {
         Self {
             runtime,
-            state: State::Select,
-            address_string: Default::default(),
-            address: None,
+            state : State::default(),
+            addresses : String::default(),
+            watching : None,
+            check_pending : false,
+            error : None,
+        }
+    }
+
+    /// Loads a watch-only address list and checks its balances.
+    pub fn watch(&mut self, list : &WatchedAddresses) {
+        self.addresses = list.addresses.join("\n");
+        self.watching = Some(list.name.clone());
+        self.check_pending = true;
+        self.error = None;
+        self.state = State::Edit;
+    }
+
+    fn render_watch_lists(ui : &mut Ui, lists : &[(usize, WatchedAddresses)], open : &RefCell<Option<WatchedAddresses>>, remove : &RefCell<Option<usize>>) {
+        ui.add_space(8.);
+        ui.label(RichText::new(i18n("Watch-only address lists")).size(12.).raised());
+        for (index, list) in lists.iter() {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(list.name.as_str()).strong());
+                ui.label(i18n_args("{count} addresses", &[("count", list.addresses.len().separated_string())]));
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui.button(TRASH).on_hover_text(i18n("Remove")).clicked() {
+                        remove.borrow_mut().replace(*index);
+                    }
+                    if ui.button(EYE).on_hover_text(i18n("Check Balance")).clicked() {
+                        open.borrow_mut().replace(list.clone());
+                    }
+                });
+            });
+        }
+        ui.separator();
+    }
+
+    fn render_summary(ui : &mut Ui, summary : &AddressSummary, network_type : &NetworkType) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format_address_string(summary.address.to_string().as_str(), Some(16))).monospace());
+            if ui.button(CLIPBOARD_TEXT).on_hover_text(i18n("Copy address")).clicked() {
+                ui.ctx().copy_text(summary.address.to_string());
+                runtime().notify_clipboard(i18n("Address copied to clipboard"));
+            }
+        });
+        ui.label(RichText::new(sompi_to_kaspa_string_with_suffix(summary.balance, network_type)).strong());
+        ui.label(i18n_args("UTXOs: {count}", &[("count", summary.utxo_count.separated_string())]));
+
+        if !summary.recent.is_empty() {
+            CollapsingHeader::new(i18n("Recent UTXOs"))
+                .id_salt(summary.address.to_string())
+                .default_open(false)
+                .show(ui, |ui| {
+                    egui::Grid::new(format!("check_balance_utxos_{}", summary.address))
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for utxo in summary.recent.iter() {
+                                let outpoint = format!("{}:{}", format_partial_string(utxo.transaction_id.as_str(), Some(12)), utxo.index);
+                                if ui.add(Label::new(RichText::new(outpoint).monospace()).sense(Sense::click())).on_hover_text(i18n("Click to copy the transaction id")).clicked() {
+                                    ui.ctx().copy_text(utxo.transaction_id.clone());
+                                    runtime().notify_clipboard(i18n("Copied to clipboard"));
+                                }
+                                let mut amount = sompi_to_kaspa_string_with_suffix(utxo.amount, network_type);
+                                if utxo.is_coinbase {
+                                    amount = format!("{amount} ({})", i18n("coinbase"));
+                                }
+                                ui.label(amount);
+                                ui.label(i18n_args("DAA {score}", &[("score", utxo.block_daa_score.separated_string())]));
+                                ui.end_row();
+                            }
+                        });
+                    if summary.utxo_count > summary.recent.len() {
+                        ui.label(RichText::new(i18n_args("{count} older UTXOs not shown", &[("count", (summary.utxo_count - summary.recent.len()).separated_string())])).small());
+                    }
+                });
         }
+        ui.separator();
     }
 }
 
-impl ModuleT for Tools {
+impl ModuleT for CheckBalance {
+
+    fn style(&self) -> ModuleStyle {
+        ModuleStyle::Mobile
+    }
+
+    fn network_change(&mut self, _core: &mut Core, _network: Network) {
+        self.state = State::Edit;
+        self.error = None;
+    }
+
     fn render(
         &mut self,
-        core: &mut Wallet,
+        core: &mut Core,
         _ctx: &egui::Context,
         _frame: &mut eframe::Frame,
         ui: &mut egui::Ui,
     ) {
-        match &self.state {
-            State::Select => {
-                ui.heading("Tools");
-                ui.separator();
-                ui.label("This is the tools page");
-                if ui.button("Check Balance").clicked() {
-                    self.state = State::CheckBalanceStart;
+        let network_type : NetworkType = core.network().into();
+        let is_connected = core.state().is_connected();
+        let watch_lists = core.watch_list().as_ref().map(|watch_list| {
+            watch_list.lists_for_network(core.network()).map(|(index, list)| (index, list.clone())).collect::<Vec<_>>()
+        }).unwrap_or_default();
+        let check_balance_result = Payload::<Result<Vec<AddressSummary>>>::new("check_balance_result");
+
+        if let Some(result) = check_balance_result.take() {
+            match result {
+                Ok(summaries) => self.state = State::Result(Arc::new(summaries)),
+                Err(err) => {
+                    self.error = Some(err.to_string());
+                    self.state = State::Edit;
                 }
             }
-            State::CheckBalanceStart => {
-                Panel::new(self)
-                    .with_caption("Check Balance")
-                    .with_back(|this| {
-                        this.state = State::Select;
-                    })
-                    .with_close(|_| {
-                        wallet.back();
-                    })
-                    .with_header(|_, ui| {
-                        ui.label("Check Balance for a specific address");
-                    })
-                    .with_body(|this, ui| {
-                        let address_string = this.address_string.clone();
-                        ui.add(
-                            TextEdit::singleline(&mut this.address_string)
-                                .hint_text("Wallet Name...")
-                                .vertical_align(Align::Center),
-                        );
-
-                        if address_string != this.address_string {
-                            this.address_string = address_string.clone();
-                            this.address = Address::try_from(address_string).ok();
-                        }
-                    })
-                    .with_footer(|this, ui| {
-                        if ui
-                            .large_button_enabled(this.address.is_some(), "Check Balance")
-                            .clicked()
-                        {
-                            this.state = State::CheckBalanceProcess;
+        }
+
+        let close = Rc::new(RefCell::new(false));
+        let submit = Rc::new(RefCell::new(std::mem::take(&mut self.check_pending) && is_connected));
+        let open_list = RefCell::new(None);
+        let remove_list = RefCell::new(None);
+
+        Panel::new(self)
+            .with_caption(i18n("Check Balance"))
+            .with_back_enabled(core.has_stack(), |_|{
+                *close.borrow_mut() = true;
+            })
+            .with_header(|this, ui| {
+                if is_connected {
+                    ui.label(i18n("Look up the balance and UTXOs of any address on the connected node"));
+                } else {
+                    ui.colored_label(error_color(), i18n("Not connected to a node"));
+                }
+                if let Some(name) = this.watching.as_ref() {
+                    ui.label(RichText::new(i18n_args("Watch-only: {name}", &[("name", name.clone())])).strong());
+                }
+            })
+            .with_body(|this, ui| {
+                match this.state.clone() {
+                    State::Edit => {
+                        if !watch_lists.is_empty() {
+                            Self::render_watch_lists(ui, &watch_lists, &open_list, &remove_list);
                         }
-                    })
-                    .render(ui);
-            }
-            State::CheckBalanceProcess => {
-                Panel::new(self)
-                    .with_caption("Check Balance")
-                    .with_back(|this| {
-                        this.state = State::Select;
-                    })
-                    .with_close(|_| {
-                        wallet.back();
-                    })
-                    .with_header(|_, ui| {
-                        ui.label("Check Balance for a specific address");
-                    })
-                    .with_body(|this, _ui| {
-                        let address_balance_result =
-                            Payload::<Result<u64>>::new("tools_check_balance_result");
-                        if !address_balance_result.is_pending() {
-                            let _wallet = this.runtime.wallet().clone();
-                            spawn_with_result(&address_balance_result, async move {
-                                todo!("CheckBalanceProcess")
-                            });
+
+                        ui.label(RichText::new(i18n("Addresses (one per line)")).size(12.).raised());
+                        ui.add_sized([ui.available_width(), 96.], TextEdit::multiline(&mut this.addresses)
+                            .font(egui::TextStyle::Monospace));
+                        ui.add_space(8.);
+                        ui.label(RichText::new(i18n("The node must be running with the UTXO index enabled.")).small());
+
+                        if let Some(error) = this.error.as_ref() {
+                            ui.add_space(8.);
+                            ui.colored_label(error_color(), error);
                         }
+                    }
+
+                    State::Processing => {
+                        ui.add_space(64.);
+                        ui.add(egui::Spinner::new().size(92.));
+                    }
 
-                        if let Some(balance_result) = address_balance_result.take() {
-                            this.state = State::CheckBalanceResult(balance_result);
+                    State::Result(summaries) => {
+                        let total = summaries.iter().map(|summary| summary.balance).sum::<u64>();
+                        if summaries.len() > 1 {
+                            ui.label(i18n_args("Total: {amount}", &[("amount", sompi_to_kaspa_string_with_suffix(total, &network_type))]));
+                            ui.separator();
+                        }
+                        for summary in summaries.iter() {
+                            Self::render_summary(ui, summary, &network_type);
+                        }
+                    }
+                }
+            })
+            .with_footer(|this, ui| {
+                match this.state {
+                    State::Edit => {
+                        let ready = is_connected && this.addresses.trim().is_not_empty();
+                        if ui.large_button_enabled(ready, i18n("Check Balance")).clicked() {
+                            *submit.borrow_mut() = true;
                         }
-                    })
-                    .render(ui);
+                    }
+                    State::Processing => { }
+                    State::Result(_) => {
+                        if ui.large_button(i18n("Refresh")).clicked() {
+                            *submit.borrow_mut() = true;
+                        }
+                        if ui.large_button(i18n("Edit addresses")).clicked() {
+                            this.watching = None;
+                            this.state = State::Edit;
+                        }
+                    }
+                }
+            })
+            .render(ui);
+
+        if let Some(list) = open_list.take() {
+            self.watch(&list);
+        }
+
+        if let Some(index) = remove_list.take() {
+            if let Err(err) = core.update_watch_list(|watch_list| { watch_list.remove(index); Ok(()) }) {
+                self.error = Some(err.to_string());
             }
-            State::CheckBalanceResult(_result) => {
-                Panel::new(self)
-                    .with_caption("Check Balance")
-                    .with_back(|this| {
-                        this.state = State::Select;
-                    })
-                    .with_close(|_| {
-                        wallet.back();
-                    })
-                    .with_header(|_, ui| {
-                        ui.label("Check Balance for a specific address");
-                    })
-                    .with_body(|_this, _ui| {
-                        todo!("CheckBalanceResult");
-                    })
-                    .with_handler(|_this| {})
-                    .render(ui);
+        }
+
+        if *submit.borrow() {
+            self.error = None;
+            let addresses = parse_addresses(self.addresses.as_str(), &network_type);
+            let rpc_api = self.runtime.kaspa_service().rpc_api();
+            match (addresses, rpc_api) {
+                (Ok(addresses), Some(rpc_api)) => {
+                    self.state = State::Processing;
+                    spawn_with_result(&check_balance_result, async move {
+                        let summaries = check_balances(rpc_api, addresses).await?;
+                        runtime().request_repaint();
+                        Ok(summaries)
+                    });
+                }
+                (Err(err), _) => {
+                    self.error = Some(err.to_string());
+                    self.state = State::Edit;
+                }
+                (_, None) => {
+                    self.error = Some(i18n("Not connected to a node").to_string());
+                    self.state = State::Edit;
+                }
             }
         }
 
-        ui.heading("Request");
-        ui.separator();
-        ui.label("This is the payment request page");
+        if *close.borrow() {
+            self.watching = None;
+            self.state = State::Edit;
+            core.back();
+        }
     }
 }
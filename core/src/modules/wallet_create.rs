@@ -385,9 +385,6 @@ impl ModuleT for WalletCreate {
                             this.state = State::WalletName;
                             this.focus.next(Focus::WalletName);
                         }
-                        // ui.label("");
-                        // if ui.large_button_enabled(false,"MultiSig account").clicked() {
-                        // }
                         ui.label("");
                         ui.separator();
                         ui.label("");
@@ -1190,6 +1187,7 @@ impl ModuleT for WalletCreate {
                         );
                         
                         wallet.clone().wallet_create(wallet_secret.clone(), wallet_args).await?;
+                        WalletFileKey::unlock(&wallet_secret);
 
                         let prv_key_data_args = PrvKeyDataCreateArgs::new(
                             None,
@@ -1291,6 +1289,7 @@ impl ModuleT for WalletCreate {
                         );
                         
                         wallet.clone().wallet_create(wallet_secret.clone(), wallet_args).await?;
+                        WalletFileKey::unlock(&wallet_secret);
 
                         let mnemonic = Mnemonic::random(args.word_count, Language::default())?;
                         let mnemonic_phrase_string = mnemonic.phrase_string();
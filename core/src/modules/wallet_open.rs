@@ -191,6 +191,7 @@ impl ModuleT for WalletOpen {
                         self.state = State::Unlocking { wallet_descriptor };
                         spawn_with_result(&unlock_result, async move {
                             sleep(Duration::from_secs(1)).await;
+                            let wallet_file_secret = wallet_secret.clone();
                             match wallet
                                 .clone()
                                 .wallet_open(
@@ -220,6 +221,7 @@ impl ModuleT for WalletOpen {
                                     }
                                 }
                             }
+                            WalletFileKey::unlock(&wallet_file_secret);
                             Ok(())
                         });
                     }
@@ -285,7 +285,8 @@ impl ModuleT for WalletSecret {
                         let new_wallet_secret = Secret::from(self.context.new_wallet_secret.as_str());
                         let wallet = self.runtime.wallet().clone();
                         spawn_with_result(&secret_change_result, async move {
-                            wallet.wallet_change_secret(old_wallet_secret, new_wallet_secret).await?;
+                            wallet.wallet_change_secret(old_wallet_secret, new_wallet_secret.clone()).await?;
+                            WalletFileKey::unlock(&new_wallet_secret);
                             Ok(())
                         });
                     }
@@ -9,6 +9,8 @@ pub const BASIC_TRANSACTION_MASS: u64 = 2036;
 pub enum Network {
     #[default]
     Mainnet,
+    Testnet10,
+    Devnet,
 }
 
 impl<'de> Deserialize<'de> for Network {
@@ -17,20 +19,18 @@ impl<'de> Deserialize<'de> for Network {
         D: serde::Deserializer<'de>,
     {
         let value = <String as serde::Deserialize>::deserialize(deserializer)?;
-        match value.trim().to_ascii_lowercase().as_str() {
-            "mainnet" => Ok(Network::Mainnet),
-            _ => Err(serde::de::Error::custom(format!(
-                "invalid network value: {}",
-                value
-            ))),
-        }
+        Network::from_str(&value)
+            .map_err(|_| serde::de::Error::custom(format!("invalid network value: {}", value)))
     }
 }
 
 impl std::fmt::Display for Network {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let _ = self;
-        write!(f, "mainnet")
+        match self {
+            Network::Mainnet => write!(f, "mainnet"),
+            Network::Testnet10 => write!(f, "testnet-10"),
+            Network::Devnet => write!(f, "devnet"),
+        }
     }
 }
 
@@ -40,6 +40,11 @@ impl FromStr for Network {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         match s.trim().to_ascii_lowercase().as_str() {
             "mainnet" => Ok(Network::Mainnet),
+            "testnet-10" | "testnet10" | "testnet_10" => Ok(Network::Testnet10),
+            "testnet-11" | "testnet11" | "testnet_11" => Err(Error::custom(
+                "Testnet 11 is no longer supported by Rusty Kaspa, please use testnet-10",
+            )),
+            "devnet" => Ok(Network::Devnet),
             _ => Err(Error::InvalidNetwork(s.to_string())),
         }
     }
@@ -47,22 +52,27 @@ impl FromStr for Network {
 
 impl From<Network> for NetworkType {
     fn from(network: Network) -> Self {
-        let _ = network;
-        NetworkType::Mainnet
+        match network {
+            Network::Mainnet => NetworkType::Mainnet,
+            Network::Testnet10 => NetworkType::Testnet,
+            Network::Devnet => NetworkType::Devnet,
+        }
     }
 }
 
 impl From<&Network> for NetworkType {
     fn from(network: &Network) -> Self {
-        let _ = network;
-        NetworkType::Mainnet
+        NetworkType::from(*network)
     }
 }
 
 impl From<Network> for NetworkId {
     fn from(network: Network) -> Self {
-        let _ = network;
-        NetworkId::new(NetworkType::Mainnet)
+        match network {
+            Network::Mainnet => NetworkId::new(NetworkType::Mainnet),
+            Network::Testnet10 => NetworkId::with_suffix(NetworkType::Testnet, 10),
+            Network::Devnet => NetworkId::new(NetworkType::Devnet),
+        }
     }
 }
 
@@ -80,8 +90,7 @@ impl From<Network> for AddressPrefix {
 
 impl From<&Network> for NetworkId {
     fn from(network: &Network) -> Self {
-        let _ = network;
-        NetworkId::new(NetworkType::Mainnet)
+        NetworkId::from(*network)
     }
 }
 
@@ -89,8 +98,8 @@ impl From<NetworkId> for Network {
     fn from(value: NetworkId) -> Self {
         match value.network_type {
             NetworkType::Mainnet => Network::Mainnet,
-            NetworkType::Testnet => Network::Mainnet,
-            NetworkType::Devnet => unreachable!("Devnet is not supported"),
+            NetworkType::Testnet => Network::Testnet10,
+            NetworkType::Devnet => Network::Devnet,
             NetworkType::Simnet => unreachable!("Simnet is not supported"),
         }
     }
@@ -120,21 +129,46 @@ impl From<&Network> for &'static NetworkParams {
     }
 }
 
-const NETWORKS: [Network; 1] = [Network::Mainnet];
+const NETWORKS: [Network; 3] = [Network::Mainnet, Network::Testnet10, Network::Devnet];
 
 impl Network {
     pub fn iter() -> impl Iterator<Item = &'static Network> {
         NETWORKS.iter()
     }
 
+    /// Public block explorer of the network; devnet has none.
+    pub fn explorer_url(&self) -> Option<&'static str> {
+        match self {
+            Network::Mainnet => Some(MAINNET_EXPLORER),
+            Network::Testnet10 => Some(TESTNET10_EXPLORER),
+            Network::Devnet => None,
+        }
+    }
+
+    /// Network of a node's network id, or `None` for networks that are
+    /// not supported (simnet and testnets other than testnet-10).
+    pub fn try_from_network_id(network_id: NetworkId) -> Option<Network> {
+        match (network_id.network_type, network_id.suffix) {
+            (NetworkType::Simnet, _) => None,
+            (NetworkType::Testnet, Some(suffix)) if suffix != 10 => None,
+            _ => Some(Network::from(network_id)),
+        }
+    }
+
     pub fn name(&self) -> &str {
-        let _ = self;
-        i18n("Mainnet")
+        match self {
+            Network::Mainnet => i18n("Mainnet"),
+            Network::Testnet10 => i18n("Testnet 10"),
+            Network::Devnet => i18n("Devnet"),
+        }
     }
 
     pub fn describe(&self) -> &str {
-        let _ = self;
-        i18n("Main Kaspa network")
+        match self {
+            Network::Mainnet => i18n("Main Kaspa network"),
+            Network::Testnet10 => i18n("Public test network (Testnet 10)"),
+            Network::Devnet => i18n("Local development network"),
+        }
     }
 
     pub fn tps(&self) -> u64 {
@@ -1,5 +1,6 @@
 use kaspa_wallet_core::account::{
-    BIP32_ACCOUNT_KIND, KEYPAIR_ACCOUNT_KIND, LEGACY_ACCOUNT_KIND, MULTISIG_ACCOUNT_KIND,
+    BIP32_ACCOUNT_KIND, BIP32_WATCH_ACCOUNT_KIND, KEYPAIR_ACCOUNT_KIND, LEGACY_ACCOUNT_KIND,
+    MULTISIG_ACCOUNT_KIND,
 };
 
 use crate::imports::*;
@@ -136,6 +137,16 @@ impl Account {
         &self.inner.account_kind
     }
 
+    pub fn is_multisig(&self) -> bool {
+        self.inner.account_kind.as_ref() == MULTISIG_ACCOUNT_KIND
+    }
+
+    /// Watch-only accounts are derived from an extended public key
+    /// and have no private key data to sign transactions with.
+    pub fn is_watch_only(&self) -> bool {
+        self.inner.account_kind.as_ref() == BIP32_WATCH_ACCOUNT_KIND
+    }
+
     pub fn balance(&self) -> Option<Balance> {
         self.inner.balance.lock().unwrap().clone()
     }
@@ -249,6 +260,7 @@ impl DescribeAccount for AccountKind {
             LEGACY_ACCOUNT_KIND => ("Legacy Account", "KDX, PWA (kaspanet.io)"),
             BIP32_ACCOUNT_KIND => ("Kaspa Core BIP32", "kaspawallet, kaspium"),
             MULTISIG_ACCOUNT_KIND => ("Multi-Signature", ""),
+            BIP32_WATCH_ACCOUNT_KIND => ("Watch-only", "xpub, kpub"),
             KEYPAIR_ACCOUNT_KIND => ("Keypair", "secp256k1"),
             _ => ("", ""),
         }
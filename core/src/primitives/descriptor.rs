@@ -30,8 +30,11 @@ impl RenderAccountDescriptor for AccountDescriptor {
             let default_color = theme_color().default_color;
             let color = theme_color().strong_color;
 
-            let _ = network;
-            let explorer = MAINNET_EXPLORER;
+            let address_url = |address: &Address| {
+                network
+                    .explorer_url()
+                    .map(|explorer| format!("{explorer}/addresses/{address}"))
+            };
             let pixels_per_point = ui.ctx().pixels_per_point();
             let one_char_width = ui
                 .painter()
@@ -63,7 +66,7 @@ impl RenderAccountDescriptor for AccountDescriptor {
                     job.address(
                         ui,
                         &address.to_string(),
-                        &format!("{explorer}/addresses/{address}"),
+                        address_url(address).as_deref(),
                         color,
                         Some(6),
                     );
@@ -80,7 +83,7 @@ impl RenderAccountDescriptor for AccountDescriptor {
                     job.address(
                         ui,
                         &address.to_string(),
-                        &format!("{explorer}/addresses/{address}"),
+                        address_url(address).as_deref(),
                         color,
                         Some(6),
                     );
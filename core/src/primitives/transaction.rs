@@ -6,6 +6,7 @@ use kaspa_wallet_core::storage::{
     TransactionKind,
     transaction::{TransactionData, UtxoRecord},
 };
+use std::cell::Cell;
 
 pub trait AsColor {
     fn as_color(&self) -> Color32;
@@ -123,6 +124,9 @@ impl From<Arc<TransactionRecord>> for Transaction {
 }
 
 impl Transaction {
+    /// Renders the transaction. Returns `true` if the user has
+    /// requested to add or edit the transaction note.
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
         &self,
         ui: &mut Ui,
@@ -131,7 +135,9 @@ impl Transaction {
         current_daa_score: Option<u64>,
         _include_utxos: bool,
         largest: Option<u64>,
-    ) {
+        address_book: Option<&AddressBook>,
+        note: Option<&str>,
+    ) -> bool {
         let width = ui.available_width() / ui.ctx().pixels_per_point();
         let w_min = 250.0;
         let w_span = 196.0;
@@ -168,8 +174,34 @@ impl Transaction {
 
         let is_transfer = record.is_transfer();
 
-        let _ = network;
-        let explorer = MAINNET_EXPLORER;
+        let explorer = network.explorer_url();
+        let transaction_url = |transaction_id: &str| {
+            explorer.map(|explorer| format!("{explorer}/txs/{transaction_id}"))
+        };
+        let address_url =
+            |address: &str| explorer.map(|explorer| format!("{explorer}/addresses/{address}"));
+
+        // known addresses are shown by their address book name
+        let contact_name = |address: &str| {
+            address_book
+                .and_then(|address_book| address_book.find(network, address))
+                .map(|contact| contact.name.clone())
+        };
+
+        let edit_note = Cell::new(false);
+        let note_button = |ui: &mut Ui| {
+            let caption = if note.is_some() {
+                i18n("Edit note")
+            } else {
+                i18n("Add note")
+            };
+            if ui
+                .small_button(format!("{NOTE_PENCIL} {caption}"))
+                .clicked()
+            {
+                edit_note.set(true);
+            }
+        };
 
         match record.transaction_data() {
             TransactionData::Reorg { utxo_entries, .. }
@@ -269,7 +301,7 @@ impl Transaction {
                         .transaction_id(
                             ui,
                             &transaction_id,
-                            &format!("{explorer}/txs/{transaction_id}"),
+                            transaction_url(&transaction_id).as_deref(),
                             default_color,
                             padding_range,
                         );
@@ -292,13 +324,23 @@ impl Transaction {
                             .map(|addr| addr.to_string())
                             .unwrap_or_else(|| "n/a".to_string());
 
-                        ljb(&content).address(
-                            ui,
-                            &address,
-                            &format!("{explorer}/addresses/{address}"),
-                            default_color,
-                            padding_range,
-                        );
+                        if let Some(name) = contact_name(&address) {
+                            ljb(&content).hyperlink_with_clipboard_icon(
+                                ui,
+                                &name,
+                                address_url(&address).as_deref(),
+                                default_color,
+                                Some(address.clone()),
+                            );
+                        } else {
+                            ljb(&content).address(
+                                ui,
+                                &address,
+                                address_url(&address).as_deref(),
+                                default_color,
+                                padding_range,
+                            );
+                        }
 
                         if *is_coinbase {
                             ljb(&content)
@@ -324,6 +366,8 @@ impl Transaction {
                         //     )
                         //     .label(ui);
                     });
+
+                    note_button(ui);
                 });
             }
             TransactionData::Outgoing {
@@ -446,7 +490,7 @@ impl Transaction {
                         .transaction_id(
                             ui,
                             &transaction_id,
-                            &format!("{explorer}/txs/{transaction_id}"),
+                            transaction_url(&transaction_id).as_deref(),
                             default_color,
                             padding_range,
                         );
@@ -549,13 +593,25 @@ impl Transaction {
                         match address_info {
                             Ok(address) => {
                                 let address = address.to_string();
-                                ljb(&content).padded(2, "", default_color).address(
-                                    ui,
-                                    &address,
-                                    &format!("{explorer}/addresses/{address}"),
-                                    default_color,
-                                    padding_range,
-                                );
+                                if let Some(name) = contact_name(&address) {
+                                    ljb(&content)
+                                        .padded(2, "", default_color)
+                                        .hyperlink_with_clipboard_icon(
+                                            ui,
+                                            &name,
+                                            address_url(&address).as_deref(),
+                                            default_color,
+                                            Some(address.clone()),
+                                        );
+                                } else {
+                                    ljb(&content).padded(2, "", default_color).address(
+                                        ui,
+                                        &address,
+                                        address_url(&address).as_deref(),
+                                        default_color,
+                                        padding_range,
+                                    );
+                                }
                             }
                             Err(err) => {
                                 log_info!("scriptpubkey to address error: {:?}", err)
@@ -573,6 +629,8 @@ impl Transaction {
                         //     )
                         //     .label(ui);
                     }
+
+                    note_button(ui);
                 });
             }
             TransactionData::Batch { fees, .. } => {
@@ -609,10 +667,18 @@ impl Transaction {
                             TransactionKind::Outgoing.as_color(),
                         )
                         .label(ui);
+
+                    note_button(ui);
                 });
             }
             TransactionData::Change { .. } => {}
         }
+
+        if let Some(note) = note {
+            ui.label(RichText::new(format!("{NOTE} {note}")).color(default_color));
+        }
+
+        edit_note.get()
     }
 }
 
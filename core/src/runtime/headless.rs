@@ -0,0 +1,85 @@
+//! Headless mode: runs the [`Runtime`] services (node, self-hosted stack,
+//! stratum bridge, miners) without the egui user interface.
+
+use crate::imports::*;
+use crate::runtime::Runtime;
+use crate::runtime::signals::Signals;
+use kaspa_wallet_core::events::Events as CoreWalletEvents;
+
+/// Starts the runtime services and blocks until the process receives
+/// SIGINT or SIGTERM, after which the services are shut down in order.
+///
+/// Service status is exposed through the self-hosted API (`/api/services`),
+/// which is always enabled in this mode.
+pub async fn run(
+    mut settings: Settings,
+    wallet_api: Option<Arc<dyn WalletApi>>,
+    application_events: Option<ApplicationEventsChannel>,
+) -> Result<()> {
+    if !settings.initialized {
+        log_info!("headless: initializing default settings");
+        settings.initialized = true;
+        settings.store_sync()?;
+    }
+    // there is nobody to answer the startup network selection prompt
+    settings.user_interface.startup_network_selection_on_launch = false;
+
+    let runtime = Runtime::new(
+        &egui::Context::default(),
+        &settings,
+        wallet_api,
+        application_events,
+        None,
+    );
+    Signals::bind(&runtime);
+    runtime.start();
+
+    runtime.self_hosted_db_service().enable(true);
+    runtime
+        .self_hosted_loader_service()
+        .enable(settings.self_hosted.enabled);
+
+    log_info!(
+        "headless: running on {}; service status at http://{}:{}/api/services",
+        settings.node.network,
+        settings.self_hosted.api_bind,
+        settings
+            .self_hosted
+            .effective_api_port(settings.node.network)
+    );
+
+    let events = runtime.application_events().clone();
+    while let Ok(event) = events.recv().await {
+        match event {
+            Events::Exit => break,
+            Events::Error(error) => log_error!("headless: {error}"),
+            Events::Wallet { event } => {
+                // the user interface activates accounts once it has loaded
+                // them; wallets opened through the control API need the same
+                if matches!(
+                    *event,
+                    CoreWalletEvents::WalletOpen { .. } | CoreWalletEvents::WalletReload { .. }
+                ) {
+                    let wallet = runtime.wallet();
+                    runtime.spawn_task(async move {
+                        wallet.accounts_activate(None).await?;
+                        Ok(())
+                    });
+                }
+            }
+            Events::Notify { user_notification } => match user_notification.kind {
+                UserNotifyKind::Error => log_error!("headless: {}", user_notification.message),
+                UserNotifyKind::Warning => log_warn!("headless: {}", user_notification.message),
+                _ => log_info!("headless: {}", user_notification.message),
+            },
+            // UI state updates have no consumer in headless mode
+            _ => {}
+        }
+    }
+
+    log_info!("headless: shutting down services...");
+    runtime.shutdown().await;
+    log_info!("headless: bye!");
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ cfg_if! {
     if #[cfg(not(target_arch = "wasm32"))] {
         pub mod signals;
         pub mod panic;
+        pub mod headless;
     } else {
         // ...
     }
@@ -39,6 +40,10 @@ pub struct Inner {
     update_monitor_service: Arc<UpdateMonitorService>,
     market_monitor_service: Arc<MarketMonitorService>,
     stratum_bridge_service: Arc<StratumBridgeService>,
+    cpu_miner_service: Arc<CpuMinerService>,
+    rothschild_service: Arc<RothschildService>,
+    #[cfg(not(target_arch = "wasm32"))]
+    control_api_service: Arc<ControlApiService>,
     #[cfg(not(target_arch = "wasm32"))]
     self_hosted_db_service: Arc<SelfHostedDbService>,
     #[cfg(not(target_arch = "wasm32"))]
@@ -56,6 +61,7 @@ pub struct Inner {
 
     // #[cfg(not(feature = "lean"))]
     metrics_service: Arc<MetricsService>,
+    alert_monitor_service: Arc<AlertMonitorService>,
     #[cfg(not(feature = "lean"))]
     block_dag_monitor_service: Arc<BlockDagMonitorService>,
 }
@@ -79,6 +85,9 @@ impl Runtime {
     ) -> Self {
         let system = System::new();
 
+        #[cfg(not(target_arch = "wasm32"))]
+        log_hub().configure(&settings.logs);
+
         let application_events =
             application_events.unwrap_or_else(ApplicationEventsChannel::unbounded);
         let repaint_service = Arc::new(RepaintService::new(application_events.clone(), settings));
@@ -103,15 +112,22 @@ impl Runtime {
             application_events.clone(),
             settings,
         ));
+        let cpu_miner_service =
+            Arc::new(CpuMinerService::new(application_events.clone(), settings));
+        let rothschild_service =
+            Arc::new(RothschildService::new(application_events.clone(), settings));
+        #[cfg(not(target_arch = "wasm32"))]
+        let control_api_service =
+            Arc::new(ControlApiService::new(application_events.clone(), settings));
         #[cfg(not(target_arch = "wasm32"))]
         let self_hosted_logs = LogStores {
-            loader: Arc::new(LogStore::new(1000)),
-            postgres: Arc::new(LogStore::new(1000)),
-            indexer: Arc::new(LogStore::new(1000)),
-            k_indexer: Arc::new(LogStore::new(1000)),
-            kasia_indexer: Arc::new(LogStore::new(1000)),
-            rest: Arc::new(LogStore::new(1000)),
-            socket: Arc::new(LogStore::new(1000)),
+            loader: Arc::new(LogStore::new(LogSource::Loader, 1000)),
+            postgres: Arc::new(LogStore::new(LogSource::Postgres, 1000)),
+            indexer: Arc::new(LogStore::new(LogSource::Indexer, 1000)),
+            k_indexer: Arc::new(LogStore::new(LogSource::KIndexer, 1000)),
+            kasia_indexer: Arc::new(LogStore::new(LogSource::KasiaIndexer, 1000)),
+            rest: Arc::new(LogStore::new(LogSource::Rest, 1000)),
+            socket: Arc::new(LogStore::new(LogSource::Socket, 1000)),
         };
         #[cfg(not(target_arch = "wasm32"))]
         let self_hosted_loader_status = SharedLoaderStatus::default();
@@ -173,6 +189,10 @@ impl Runtime {
         ));
 
         let metrics_service = Arc::new(MetricsService::new(application_events.clone(), settings));
+        let alert_monitor_service = Arc::new(AlertMonitorService::new(
+            application_events.clone(),
+            settings,
+        ));
         cfg_if! {
             if #[cfg(not(feature = "lean"))] {
                 let block_dag_monitor_service = Arc::new(BlockDagMonitorService::new(
@@ -194,6 +214,10 @@ impl Runtime {
             feerate_monitor_service.clone(),
             market_monitor_service.clone(),
             stratum_bridge_service.clone(),
+            cpu_miner_service.clone(),
+            rothschild_service.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            control_api_service.clone(),
             #[cfg(not(target_arch = "wasm32"))]
             self_hosted_db_service.clone(),
             #[cfg(not(target_arch = "wasm32"))]
@@ -211,6 +235,7 @@ impl Runtime {
             update_monitor_service.clone(),
             // #[cfg(not(feature = "lean"))]
             metrics_service.clone(),
+            alert_monitor_service.clone(),
             #[cfg(not(feature = "lean"))]
             block_dag_monitor_service.clone(),
         ]);
@@ -225,6 +250,10 @@ impl Runtime {
                 peer_monitor_service,
                 market_monitor_service,
                 stratum_bridge_service,
+                cpu_miner_service,
+                rothschild_service,
+                #[cfg(not(target_arch = "wasm32"))]
+                control_api_service,
                 #[cfg(not(target_arch = "wasm32"))]
                 self_hosted_db_service,
                 #[cfg(not(target_arch = "wasm32"))]
@@ -247,6 +276,7 @@ impl Runtime {
                 adaptor,
                 // #[cfg(not(feature = "lean"))]
                 metrics_service,
+                alert_monitor_service,
                 #[cfg(not(feature = "lean"))]
                 block_dag_monitor_service,
             }),
@@ -381,6 +411,10 @@ impl Runtime {
         &self.inner.metrics_service
     }
 
+    pub fn alert_monitor_service(&self) -> &Arc<AlertMonitorService> {
+        &self.inner.alert_monitor_service
+    }
+
     cfg_if! {
         if #[cfg(not(feature = "lean"))] {
 
@@ -398,6 +432,19 @@ impl Runtime {
         &self.inner.stratum_bridge_service
     }
 
+    pub fn cpu_miner_service(&self) -> &Arc<CpuMinerService> {
+        &self.inner.cpu_miner_service
+    }
+
+    pub fn rothschild_service(&self) -> &Arc<RothschildService> {
+        &self.inner.rothschild_service
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn control_api_service(&self) -> &Arc<ControlApiService> {
+        &self.inner.control_api_service
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub fn self_hosted_db_service(&self) -> &Arc<SelfHostedDbService> {
         &self.inner.self_hosted_db_service
@@ -0,0 +1,129 @@
+//! Evaluates the alert rules in the runtime, so that alerts are raised in
+//! headless mode as well as while the user interface is running.
+
+use crate::alerts::{self, AlertMonitor, ServiceHealth};
+use crate::imports::*;
+use kaspa_wallet_core::events::Events as CoreWalletEvents;
+
+pub enum AlertMonitorEvents {
+    Metrics(Box<MetricsSnapshot>),
+    Connect(NetworkId),
+    Disconnect,
+    Transaction(Box<TransactionRecord>),
+    Exit,
+}
+
+pub struct AlertMonitorService {
+    pub application_events: ApplicationEventsChannel,
+    pub service_events: Channel<AlertMonitorEvents>,
+    pub task_ctl: Channel<()>,
+    pub settings: Mutex<AlertSettings>,
+    pub network: Mutex<Network>,
+}
+
+impl AlertMonitorService {
+    pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
+        Self {
+            application_events,
+            service_events: Channel::unbounded(),
+            task_ctl: Channel::oneshot(),
+            settings: Mutex::new(settings.alerts.clone()),
+            network: Mutex::new(settings.node.network),
+        }
+    }
+
+    pub fn update_settings(&self, settings: AlertSettings) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
+    fn settings(&self) -> AlertSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    fn network(&self) -> Network {
+        *self.network.lock().unwrap()
+    }
+
+    fn send(&self, event: AlertMonitorEvents) {
+        self.service_events.sender.try_send(event).unwrap();
+    }
+
+    /// Metrics snapshot received from the node.
+    pub fn metrics(&self, snapshot: &MetricsSnapshot) {
+        if self.settings.lock().unwrap().enabled {
+            self.send(AlertMonitorEvents::Metrics(Box::new(snapshot.clone())));
+        }
+    }
+
+    /// Wallet event received from the wallet framework.
+    pub fn wallet_event(&self, event: &CoreWalletEvents) {
+        match event {
+            CoreWalletEvents::Connect { network_id, .. } => {
+                self.send(AlertMonitorEvents::Connect(*network_id));
+            }
+            CoreWalletEvents::Disconnect { .. } => {
+                self.send(AlertMonitorEvents::Disconnect);
+            }
+            CoreWalletEvents::Pending { record } => {
+                if self.settings.lock().unwrap().enabled {
+                    self.send(AlertMonitorEvents::Transaction(Box::new(record.clone())));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[async_trait]
+impl Service for AlertMonitorService {
+    fn name(&self) -> &'static str {
+        "alert-monitor"
+    }
+
+    async fn spawn(self: Arc<Self>) -> Result<()> {
+        let mut monitor = AlertMonitor::default();
+
+        while let Ok(event) = self.service_events.receiver.recv().await {
+            match event {
+                AlertMonitorEvents::Metrics(snapshot) => {
+                    let settings = self.settings();
+                    let health = ServiceHealth::sample();
+                    for alert in monitor.evaluate(&settings, &snapshot, &health) {
+                        alerts::deliver(&settings, self.network(), alert);
+                    }
+                }
+                AlertMonitorEvents::Connect(network_id) => {
+                    monitor.reset();
+                    if let Some(network) = Network::try_from_network_id(network_id) {
+                        *self.network.lock().unwrap() = network;
+                    }
+                }
+                AlertMonitorEvents::Disconnect => {
+                    monitor.reset();
+                }
+                AlertMonitorEvents::Transaction(record) => {
+                    let settings = self.settings();
+                    let network = self.network();
+                    if let Some(alert) =
+                        monitor.incoming_transaction(&settings, &record, &network.into())
+                    {
+                        alerts::deliver(&settings, network, alert);
+                    }
+                }
+                AlertMonitorEvents::Exit => break,
+            }
+        }
+
+        self.task_ctl.send(()).await.unwrap();
+        Ok(())
+    }
+
+    fn terminate(self: Arc<Self>) {
+        self.send(AlertMonitorEvents::Exit);
+    }
+
+    async fn join(self: Arc<Self>) -> Result<()> {
+        self.task_ctl.recv().await.unwrap();
+        Ok(())
+    }
+}
@@ -0,0 +1,601 @@
+//! Authenticated JSON-RPC 2.0 control API for wallet and node operations.
+//!
+//! The server listens on the loopback interface only and requires the
+//! bearer token from [`ControlApiSettings`] on every request. Calls are
+//! dispatched to the runtime [`WalletApi`], so they observe the same wallet
+//! state as the user interface (a wallet must be opened before account
+//! methods can be used).
+
+use crate::imports::*;
+use axum::{
+    Json, Router,
+    extract::State,
+    http::{HeaderMap, StatusCode, header::AUTHORIZATION},
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use kaspa_addresses::Prefix as AddressPrefix;
+use kaspa_wallet_core::api::*;
+use kaspa_wallet_core::tx::{Fees, GeneratorSummary, PaymentOutput};
+use serde_json::Value;
+use std::net::{Ipv4Addr, SocketAddr};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const WALLET_ERROR: i64 = -32000;
+const UNAUTHORIZED: i64 = -32001;
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    jsonrpc: Option<String>,
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    fn result(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    fn error(id: Value, error: RpcError) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(error),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_params(err: impl std::fmt::Display) -> Self {
+        Self::new(INVALID_PARAMS, format!("invalid params: {err}"))
+    }
+}
+
+impl From<Error> for RpcError {
+    fn from(err: Error) -> Self {
+        Self::new(WALLET_ERROR, err.to_string())
+    }
+}
+
+impl From<kaspa_wallet_core::error::Error> for RpcError {
+    fn from(err: kaspa_wallet_core::error::Error) -> Self {
+        Self::new(WALLET_ERROR, err.to_string())
+    }
+}
+
+type RpcResult = std::result::Result<Value, RpcError>;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WalletOpenParams {
+    filename: String,
+    wallet_secret: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountParams {
+    account_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PaymentParams {
+    account_id: String,
+    address: String,
+    amount_sompi: u64,
+    #[serde(default)]
+    priority_fee_sompi: u64,
+    /// Feerate in sompi per gram; the minimum relay feerate is used if omitted.
+    fee_rate: Option<f64>,
+    wallet_secret: Option<String>,
+    payment_secret: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountEntry {
+    account_id: String,
+    name: String,
+    kind: String,
+    receive_address: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SummaryEntry {
+    fees_sompi: u64,
+    mass: u64,
+    transactions: usize,
+    utxos: usize,
+    final_amount_sompi: Option<u64>,
+}
+
+impl From<&GeneratorSummary> for SummaryEntry {
+    fn from(summary: &GeneratorSummary) -> Self {
+        Self {
+            fees_sompi: summary.aggregate_fees,
+            mass: summary.aggregate_mass,
+            transactions: summary.number_of_generated_transactions,
+            utxos: summary.aggregated_utxos,
+            final_amount_sompi: summary.final_transaction_amount,
+        }
+    }
+}
+
+fn params<T: serde::de::DeserializeOwned>(params: Value) -> std::result::Result<T, RpcError> {
+    serde_json::from_value(params).map_err(RpcError::invalid_params)
+}
+
+fn to_value<T: Serialize>(value: T) -> RpcResult {
+    serde_json::to_value(value).map_err(|err| RpcError::new(WALLET_ERROR, err.to_string()))
+}
+
+/// Resolves an account of the open wallet from its hex id.
+async fn account_descriptor(
+    wallet: &Arc<dyn WalletApi>,
+    account_id: &str,
+) -> std::result::Result<AccountDescriptor, RpcError> {
+    wallet
+        .clone()
+        .accounts_enumerate()
+        .await?
+        .into_iter()
+        .find(|descriptor| descriptor.account_id().to_string() == account_id)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("unknown account: {account_id}")))
+}
+
+fn payment_output(
+    network: Network,
+    params: &PaymentParams,
+) -> std::result::Result<PaymentOutput, RpcError> {
+    let address = Address::try_from(params.address.as_str()).map_err(RpcError::invalid_params)?;
+    if address.prefix != AddressPrefix::from(network) {
+        return Err(RpcError::invalid_params(format!(
+            "address {} does not belong to {network}",
+            params.address
+        )));
+    }
+    if params.amount_sompi == 0 {
+        return Err(RpcError::invalid_params(
+            "amountSompi must be greater than zero",
+        ));
+    }
+
+    Ok(PaymentOutput {
+        address,
+        amount: params.amount_sompi,
+    })
+}
+
+async fn dispatch(
+    wallet: Arc<dyn WalletApi>,
+    network: Network,
+    method: &str,
+    request_params: Value,
+) -> RpcResult {
+    match method {
+        "status" => {
+            let status = wallet.get_status(None).await?;
+            Ok(serde_json::json!({
+                "connected": status.is_connected,
+                "synced": status.is_synced,
+                "open": status.is_open,
+                "network": status.network_id.map(|network_id| network_id.to_string()),
+                "url": status.url,
+            }))
+        }
+        "wallet.list" => {
+            let wallets = wallet.wallet_enumerate().await?;
+            Ok(wallets
+                .into_iter()
+                .map(|descriptor| {
+                    serde_json::json!({
+                        "filename": descriptor.filename,
+                        "title": descriptor.title,
+                    })
+                })
+                .collect())
+        }
+        "wallet.open" => {
+            let WalletOpenParams {
+                filename,
+                wallet_secret,
+            } = params(request_params)?;
+            let accounts = wallet
+                .wallet_open(Secret::from(wallet_secret), Some(filename), true, true)
+                .await?
+                .map(|descriptors| descriptors.len())
+                .unwrap_or_default();
+            Ok(serde_json::json!({ "accounts": accounts }))
+        }
+        "account.list" => {
+            let accounts = wallet
+                .accounts_enumerate()
+                .await?
+                .into_iter()
+                .map(|descriptor| AccountEntry {
+                    account_id: descriptor.account_id().to_string(),
+                    name: descriptor.name_or_id(),
+                    kind: descriptor.account_kind().to_string(),
+                    receive_address: descriptor
+                        .receive_address()
+                        .map(|address| address.to_string()),
+                })
+                .collect::<Vec<_>>();
+            to_value(accounts)
+        }
+        "account.balance" => {
+            let AccountParams { account_id } = params(request_params)?;
+            let descriptor = account_descriptor(&wallet, &account_id).await?;
+            let core_wallet = wallet
+                .downcast_arc::<CoreWallet>()
+                .map_err(|_| RpcError::from(Error::WalletIsNotLocal))?;
+            let account = {
+                let binding = core_wallet.guard();
+                let guard = binding.lock().await;
+                core_wallet
+                    .get_account_by_id(descriptor.account_id(), &guard)
+                    .await?
+            };
+            // the balance is unknown until the account has been activated and synced
+            Ok(account
+                .and_then(|account| account.balance())
+                .map(|balance| {
+                    serde_json::json!({
+                        "matureSompi": balance.mature,
+                        "pendingSompi": balance.pending,
+                        "outgoingSompi": balance.outgoing,
+                        "matureUtxos": balance.mature_utxo_count,
+                        "pendingUtxos": balance.pending_utxo_count,
+                    })
+                })
+                .unwrap_or(Value::Null))
+        }
+        "account.createReceiveAddress" => {
+            let AccountParams { account_id } = params(request_params)?;
+            let descriptor = account_descriptor(&wallet, &account_id).await?;
+            let response = wallet
+                .accounts_create_new_address(*descriptor.account_id(), NewAddressKind::Receive)
+                .await?;
+            Ok(serde_json::json!({ "address": response.address.to_string() }))
+        }
+        "fee.estimate" => {
+            let request: PaymentParams = params(request_params)?;
+            let descriptor = account_descriptor(&wallet, &request.account_id).await?;
+            let output = payment_output(network, &request)?;
+            let response = wallet
+                .accounts_estimate_call(AccountsEstimateRequest {
+                    account_id: *descriptor.account_id(),
+                    destination: output.into(),
+                    priority_fee_sompi: Fees::SenderPays(request.priority_fee_sompi),
+                    fee_rate: request.fee_rate,
+                    payload: None,
+                })
+                .await?;
+            to_value(SummaryEntry::from(&response.generator_summary))
+        }
+        "send" => {
+            let request: PaymentParams = params(request_params)?;
+            let descriptor = account_descriptor(&wallet, &request.account_id).await?;
+            let output = payment_output(network, &request)?;
+            let wallet_secret = request
+                .wallet_secret
+                .clone()
+                .ok_or_else(|| RpcError::invalid_params("walletSecret is required"))?;
+            let response = wallet
+                .accounts_send_call(AccountsSendRequest {
+                    account_id: *descriptor.account_id(),
+                    destination: output.into(),
+                    wallet_secret: Secret::from(wallet_secret),
+                    payment_secret: request.payment_secret.clone().map(Secret::from),
+                    fee_rate: request.fee_rate,
+                    priority_fee_sompi: Fees::SenderPays(request.priority_fee_sompi),
+                    payload: None,
+                })
+                .await?;
+            Ok(serde_json::json!({
+                "transactionIds": response
+                    .transaction_ids
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>(),
+                "summary": SummaryEntry::from(&response.generator_summary),
+            }))
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("method not found: {method}"),
+        )),
+    }
+}
+
+#[derive(Clone)]
+struct AppState {
+    token: Arc<String>,
+    network: Network,
+}
+
+/// Compares the bearer token without short-circuiting on the first
+/// mismatching byte.
+fn is_authorized(headers: &HeaderMap, token: &str) -> bool {
+    let Some(provided) = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+
+    provided.len() == token.len()
+        && provided
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn rpc_handler(State(state): State<AppState>, headers: HeaderMap, body: String) -> Response {
+    if !is_authorized(&headers, &state.token) {
+        log_warn!("control-api: rejected unauthenticated request");
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(RpcResponse::error(
+                Value::Null,
+                RpcError::new(UNAUTHORIZED, "missing or invalid bearer token"),
+            )),
+        )
+            .into_response();
+    }
+
+    let request = match serde_json::from_str::<RpcRequest>(&body) {
+        Ok(request) => request,
+        Err(err) => {
+            log_warn!("control-api: unable to parse request: {err}");
+            let code = if serde_json::from_str::<Value>(&body).is_ok() {
+                INVALID_REQUEST
+            } else {
+                PARSE_ERROR
+            };
+            return Json(RpcResponse::error(
+                Value::Null,
+                RpcError::new(code, err.to_string()),
+            ))
+            .into_response();
+        }
+    };
+
+    let RpcRequest {
+        jsonrpc,
+        id,
+        method,
+        params,
+    } = request;
+
+    if jsonrpc.as_deref().is_some_and(|version| version != "2.0") {
+        return Json(RpcResponse::error(
+            id,
+            RpcError::new(INVALID_REQUEST, "unsupported jsonrpc version"),
+        ))
+        .into_response();
+    }
+
+    let Some(runtime) = crate::runtime::try_runtime() else {
+        return Json(RpcResponse::error(
+            id,
+            RpcError::new(WALLET_ERROR, "runtime is not running"),
+        ))
+        .into_response();
+    };
+
+    // params are not logged as they may carry wallet secrets
+    let started = std::time::Instant::now();
+    let result = dispatch(runtime.wallet(), state.network, &method, params).await;
+    let elapsed = started.elapsed().as_millis();
+    match result {
+        Ok(result) => {
+            log_info!("control-api: {method} (id={id}) ok in {elapsed}ms");
+            Json(RpcResponse::result(id, result)).into_response()
+        }
+        Err(error) => {
+            log_warn!(
+                "control-api: {method} (id={id}) failed in {elapsed}ms: {}",
+                error.message
+            );
+            Json(RpcResponse::error(id, error)).into_response()
+        }
+    }
+}
+
+pub enum ControlApiEvents {
+    UpdateSettings {
+        settings: ControlApiSettings,
+        network: Network,
+    },
+    Exit,
+}
+
+struct ServerHandle {
+    shutdown: Option<oneshot::Sender<()>>,
+    join: tokio::task::JoinHandle<()>,
+}
+
+pub struct ControlApiService {
+    pub application_events: ApplicationEventsChannel,
+    pub service_events: Channel<ControlApiEvents>,
+    pub task_ctl: Channel<()>,
+    settings: Mutex<ControlApiSettings>,
+    network: Mutex<Network>,
+    server: Mutex<Option<ServerHandle>>,
+}
+
+impl ControlApiService {
+    pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
+        Self {
+            application_events,
+            service_events: Channel::unbounded(),
+            task_ctl: Channel::oneshot(),
+            settings: Mutex::new(settings.control_api.clone()),
+            network: Mutex::new(settings.node.network),
+            server: Mutex::new(None),
+        }
+    }
+
+    pub fn update_settings(&self, settings: &ControlApiSettings, network: Network) {
+        self.service_events
+            .try_send(ControlApiEvents::UpdateSettings {
+                settings: settings.clone(),
+                network,
+            })
+            .unwrap();
+    }
+
+    pub fn is_listening(&self) -> bool {
+        self.server.lock().unwrap().is_some()
+    }
+
+    async fn start_server(&self) -> Result<()> {
+        let settings = self.settings.lock().unwrap().clone();
+        if !settings.enabled || self.is_listening() {
+            return Ok(());
+        }
+
+        let token = settings.token.trim().to_string();
+        if token.is_empty() {
+            return Err(Error::custom("control API token is not set"));
+        }
+
+        let network = *self.network.lock().unwrap();
+        let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.effective_port(network)));
+        let listener = TcpListener::bind(addr).await?;
+        let app = Router::new()
+            .route("/", post(rpc_handler))
+            .route("/rpc", post(rpc_handler))
+            .with_state(AppState {
+                token: Arc::new(token),
+                network,
+            });
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let join = tokio::spawn(async move {
+            let server = axum::serve(listener, app).with_graceful_shutdown(async move {
+                let _ = shutdown_rx.await;
+            });
+            if let Err(err) = server.await {
+                log_warn!("control-api: server error: {err}");
+            }
+        });
+
+        log_info!("control-api: listening on http://{addr}");
+        self.server.lock().unwrap().replace(ServerHandle {
+            shutdown: Some(shutdown_tx),
+            join,
+        });
+
+        Ok(())
+    }
+
+    async fn stop_server(&self) {
+        let handle = self.server.lock().unwrap().take();
+        if let Some(mut handle) = handle {
+            if let Some(shutdown) = handle.shutdown.take() {
+                let _ = shutdown.send(());
+            }
+            let _ = handle.join.await;
+            log_info!("control-api: stopped");
+        }
+    }
+
+    async fn restart_server(&self) {
+        self.stop_server().await;
+        if let Err(err) = self.start_server().await {
+            log_warn!("control-api: failed to start server: {err}");
+        }
+    }
+}
+
+#[async_trait]
+impl Service for ControlApiService {
+    fn name(&self) -> &'static str {
+        "control-api"
+    }
+
+    async fn spawn(self: Arc<Self>) -> Result<()> {
+        self.restart_server().await;
+
+        loop {
+            select! {
+                msg = self.service_events.receiver.recv().fuse() => {
+                    match msg {
+                        Ok(ControlApiEvents::UpdateSettings { settings, network }) => {
+                            let changed = {
+                                let mut current = self.settings.lock().unwrap();
+                                let mut current_network = self.network.lock().unwrap();
+                                let changed = *current != settings || *current_network != network;
+                                *current = settings;
+                                *current_network = network;
+                                changed
+                            };
+                            if changed {
+                                self.restart_server().await;
+                            }
+                        }
+                        Ok(ControlApiEvents::Exit) | Err(_) => {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.stop_server().await;
+        self.task_ctl.send(()).await.unwrap();
+        Ok(())
+    }
+
+    fn terminate(self: Arc<Self>) {
+        let _ = self.service_events.sender.try_send(ControlApiEvents::Exit);
+    }
+
+    async fn join(self: Arc<Self>) -> Result<()> {
+        self.task_ctl.recv().await.unwrap();
+        Ok(())
+    }
+}
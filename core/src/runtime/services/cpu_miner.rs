@@ -0,0 +1,292 @@
+use crate::imports::*;
+use crate::runtime::Service;
+
+cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+        use crate::runtime::services::log_hub::LogSource;
+        use crate::runtime::services::kaspa::grpc::node_grpc_address;
+        use crate::runtime::services::node_client_process::{NodeClientProcess, NodeClientService};
+        use crate::runtime::services::process_supervisor::{self, ProcessStatus};
+        use tokio::process::Command;
+
+        pub fn update_logs_flag() -> &'static Arc<AtomicBool> {
+            static FLAG: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+            FLAG.get_or_init(|| Arc::new(AtomicBool::new(false)))
+        }
+
+        #[derive(Debug, Clone)]
+        enum MinerEvents {
+            SetEnabled { enabled: bool, settings: NodeSettings },
+            UpdateSettings(NodeSettings),
+            Exit,
+        }
+
+        pub struct CpuMinerService {
+            service_events: Channel<MinerEvents>,
+            task_ctl: Channel<()>,
+            node_settings: Mutex<NodeSettings>,
+            client: NodeClientProcess,
+        }
+
+        impl CpuMinerService {
+            pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
+                Self {
+                    service_events: Channel::unbounded(),
+                    task_ctl: Channel::oneshot(),
+                    node_settings: Mutex::new(settings.node.clone()),
+                    client: NodeClientProcess::new(
+                        "CPU Miner",
+                        LogSource::CpuMiner,
+                        update_logs_flag(),
+                        application_events,
+                        settings.node.cpu_miner_enabled,
+                    ),
+                }
+            }
+
+            pub fn enable(&self, enabled: bool, node_settings: &NodeSettings) {
+                self.service_events
+                    .try_send(MinerEvents::SetEnabled {
+                        enabled,
+                        settings: node_settings.clone(),
+                    })
+                    .unwrap();
+            }
+
+            pub fn update_settings(&self, node_settings: &NodeSettings) {
+                self.service_events
+                    .try_send(MinerEvents::UpdateSettings(node_settings.clone()))
+                    .unwrap();
+            }
+
+            pub fn process_status(&self) -> Vec<ProcessStatus> {
+                self.client.process_status()
+            }
+
+            async fn update_logs(&self, line: String) {
+                self.client.update_logs(line).await;
+            }
+
+            fn split_host_port(address: &str) -> Option<(String, u16)> {
+                let (host, port) = address.rsplit_once(':')?;
+                let port = port.parse::<u16>().ok()?;
+                let host = host.trim_matches(['[', ']']);
+                Some((host.to_string(), port))
+            }
+
+            fn network_args(network: Network) -> &'static [&'static str] {
+                match network {
+                    Network::Mainnet => &[],
+                    Network::Testnet10 => &["--testnet"],
+                    Network::Devnet => &["--devnet"],
+                }
+            }
+
+            fn validate_mining_address(settings: &NodeSettings) -> std::result::Result<Address, String> {
+                let address = settings.cpu_miner.mining_address.trim();
+                if address.is_empty() {
+                    return Err(i18n("CPU Miner: mining address is not set").to_string());
+                }
+
+                let address = Address::try_from(address)
+                    .map_err(|err| format!("{} ({err})", i18n("CPU Miner: invalid mining address")))?;
+
+                let network_type = NetworkType::from(settings.network);
+                if address.prefix != kaspa_addresses::Prefix::from(network_type) {
+                    return Err(i18n_args(
+                        "CPU Miner: mining address does not belong to {network}",
+                        &[("network", settings.network.to_string())],
+                    ));
+                }
+
+                Ok(address)
+            }
+
+            async fn start_miner(self: &Arc<Self>) -> Result<()> {
+                let Some(_guard) = self.client.begin_start() else {
+                    return Ok(());
+                };
+
+                let settings = self.node_settings.lock().unwrap().clone();
+
+                let mining_address = match Self::validate_mining_address(&settings) {
+                    Ok(address) => address,
+                    Err(err) => {
+                        self.update_logs(err).await;
+                        return Ok(());
+                    }
+                };
+
+                let Some(kaspad_address) = node_grpc_address(&settings) else {
+                    self.update_logs(i18n("CPU Miner: a gRPC connection to the node is required; enable gRPC for the local node or connect to a remote node over gRPC.").to_string()).await;
+                    return Ok(());
+                };
+
+                let Some((host, port)) = Self::split_host_port(&kaspad_address) else {
+                    self.update_logs(format!("CPU Miner: invalid gRPC address {kaspad_address}")).await;
+                    return Ok(());
+                };
+
+                let miner_bin = match process_supervisor::find_binary("kaspa-miner", &[]) {
+                    Some(path) => path,
+                    None => {
+                        self.update_logs(i18n("CPU Miner: kaspa-miner binary not found").to_string()).await;
+                        self.client.record_failure();
+                        self.client.schedule_restart(self, "kaspa-miner binary not found");
+                        return Ok(());
+                    }
+                };
+
+                if !self.client.wait_for_grpc(&kaspad_address).await {
+                    return Ok(());
+                }
+
+                let threads = settings.cpu_miner.threads.max(1);
+
+                let mut cmd = Command::new(miner_bin);
+                cmd.arg("--mining-address")
+                    .arg(mining_address.to_string())
+                    .arg("--kaspad-address")
+                    .arg(host)
+                    .arg("--port")
+                    .arg(port.to_string())
+                    .arg("--threads")
+                    .arg(threads.to_string())
+                    .args(Self::network_args(settings.network));
+
+                if let Err(err) = self.client.spawn(cmd) {
+                    self.update_logs(format!("CPU Miner: failed to start ({})", err))
+                        .await;
+                    self.client.schedule_restart(self, "failed to start");
+                    return Err(err);
+                }
+
+                self.update_logs(format!(
+                    "CPU Miner: started with {threads} thread(s), mining to {mining_address}"
+                ))
+                .await;
+
+                self.client.supervise(self);
+                Ok(())
+            }
+
+            async fn stop_miner(self: &Arc<Self>) -> Result<()> {
+                if self.client.stop().await {
+                    self.update_logs(i18n("CPU Miner: stopped").to_string()).await;
+                }
+                Ok(())
+            }
+        }
+
+        #[async_trait]
+        impl NodeClientService for CpuMinerService {
+            fn client(&self) -> &NodeClientProcess {
+                &self.client
+            }
+
+            async fn start(self: Arc<Self>) -> Result<()> {
+                self.start_miner().await
+            }
+        }
+
+        #[async_trait]
+        impl Service for CpuMinerService {
+            fn name(&self) -> &'static str {
+                "cpu-miner-service"
+            }
+
+            async fn spawn(self: Arc<Self>) -> Result<()> {
+                let this = self.clone();
+                tokio::spawn(async move {
+                    if this.client.is_enabled() {
+                        this.client.launch(&this);
+                    }
+
+                    loop {
+                        select! {
+                            msg = this.service_events.receiver.recv().fuse() => {
+                                match msg {
+                                    Ok(MinerEvents::SetEnabled { enabled, settings }) => {
+                                        this.client.set_enabled(enabled);
+                                        *this.node_settings.lock().unwrap() = settings;
+                                        if enabled {
+                                            this.client.launch(&this);
+                                        } else {
+                                            let _ = this.stop_miner().await;
+                                        }
+                                    }
+                                    Ok(MinerEvents::UpdateSettings(settings)) => {
+                                        let changed = {
+                                            let current = this.node_settings.lock().unwrap();
+                                            current.cpu_miner != settings.cpu_miner
+                                                || node_grpc_address(&current) != node_grpc_address(&settings)
+                                                || current.network != settings.network
+                                        };
+                                        *this.node_settings.lock().unwrap() = settings;
+                                        if changed && this.client.is_enabled() {
+                                            let _ = this.stop_miner().await;
+                                            this.client.launch(&this);
+                                        }
+                                    }
+                                    Ok(MinerEvents::Exit) | Err(_) => {
+                                        this.client.set_enabled(false);
+                                        let _ = this.stop_miner().await;
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    this.task_ctl.send(()).await.unwrap();
+                });
+
+                Ok(())
+            }
+
+            fn terminate(self: Arc<Self>) {
+                self.service_events.sender.try_send(MinerEvents::Exit).unwrap();
+            }
+
+            async fn join(self: Arc<Self>) -> Result<()> {
+                self.task_ctl.recv().await.unwrap();
+                Ok(())
+            }
+        }
+
+    } else {
+        pub struct CpuMinerService;
+
+        impl CpuMinerService {
+            pub fn new(_application_events: ApplicationEventsChannel, _settings: &Settings) -> Self {
+                Self
+            }
+
+            pub fn enable(&self, _enabled: bool, _node_settings: &NodeSettings) {}
+
+            pub fn update_settings(&self, _node_settings: &NodeSettings) {}
+        }
+
+        pub fn update_logs_flag() -> &'static Arc<AtomicBool> {
+            static FLAG: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+            FLAG.get_or_init(|| Arc::new(AtomicBool::new(false)))
+        }
+
+        #[async_trait]
+        impl Service for CpuMinerService {
+            fn name(&self) -> &'static str {
+                "cpu-miner-service"
+            }
+
+            async fn spawn(self: Arc<Self>) -> Result<()> {
+                Ok(())
+            }
+
+            fn terminate(self: Arc<Self>) {}
+
+            async fn join(self: Arc<Self>) -> Result<()> {
+                Ok(())
+            }
+        }
+    }
+}
@@ -15,7 +15,7 @@ pub struct FeerateMonitorService {
     pub service_events: Channel<FeerateMonitorEvents>,
     pub task_ctl: Channel<()>,
     pub rpc_api: Mutex<Option<Arc<dyn RpcApi>>>,
-    // pub feerate: Mutex<Option<Arc<RpcFeeEstimate>>>,
+    pub feerate: Mutex<Option<Arc<RpcFeeEstimate>>>,
     pub is_enabled: Arc<AtomicBool>,
 }
 
@@ -26,7 +26,7 @@ impl FeerateMonitorService {
             service_events: Channel::unbounded(),
             task_ctl: Channel::oneshot(),
             rpc_api: Mutex::new(None),
-            // feerate: Mutex::new(None),
+            feerate: Mutex::new(None),
             is_enabled: Arc::new(AtomicBool::new(true)),
         }
     }
@@ -35,9 +35,10 @@ impl FeerateMonitorService {
         self.rpc_api.lock().unwrap().clone()
     }
 
-    // pub fn peer_info(&self) -> Option<Arc<Vec<RpcPeerInfo>>> {
-    //     self.peer_info.lock().unwrap().clone()
-    // }
+    /// Most recent fee estimate received from the node, if any.
+    pub fn feerate(&self) -> Option<Arc<RpcFeeEstimate>> {
+        self.feerate.lock().unwrap().clone()
+    }
 
     pub fn enable(&self) {
         self.service_events
@@ -56,12 +57,11 @@ impl FeerateMonitorService {
     async fn fetch(self: &Arc<Self>) -> Result<()> {
         if let Some(rpc_api) = self.rpc_api() {
             if let Ok(resp) = rpc_api.get_fee_estimate().await {
-                // println!("{}",resp.priority_bucket.feerate);
-                // let feerate = Arc::new(resp);
-                // self.feerate.lock().unwrap().replace(feerate.clone());
+                let feerate = Arc::new(resp);
+                self.feerate.lock().unwrap().replace(feerate.clone());
                 self.application_events
                     .send(Events::Feerate {
-                        feerate: Some(Arc::new(resp)),
+                        feerate: Some(feerate),
                     })
                     .await
                     .unwrap();
@@ -72,6 +72,7 @@ impl FeerateMonitorService {
     }
 
     async fn clear(&self) {
+        self.feerate.lock().unwrap().take();
         self.application_events
             .send(Events::Feerate { feerate: None })
             .await
@@ -125,7 +126,6 @@ impl Service for FeerateMonitorService {
                             }
                             FeerateMonitorEvents::Disable => {
                                 self.is_enabled.store(false, Ordering::Relaxed);
-                                // self.feerate.lock().unwrap().take();
                                 self.clear().await;
                             }
                             FeerateMonitorEvents::Exit => {
@@ -3,6 +3,7 @@ use crate::imports::*;
 use crate::settings::NodeMemoryScale;
 use crate::utils::Arglist;
 use kaspa_core::kaspad_env;
+use kaspa_utils::networking::ContextualNetAddress;
 #[cfg(not(target_arch = "wasm32"))]
 pub use kaspad_lib::args::Args;
 
@@ -35,6 +36,7 @@ pub struct Config {
     kaspad_daemon_storage_folder_enable: bool,
     kaspad_daemon_storage_folder: String,
     memory_scale: NodeMemoryScale,
+    preferred_peers: Vec<ContextualNetAddress>,
 }
 
 impl From<NodeSettings> for Config {
@@ -51,6 +53,7 @@ impl From<NodeSettings> for Config {
             kaspad_daemon_storage_folder_enable: node_settings.kaspad_daemon_storage_folder_enable,
             kaspad_daemon_storage_folder: node_settings.kaspad_daemon_storage_folder,
             memory_scale: node_settings.memory_scale,
+            preferred_peers: node_settings.peers.preferred_peers(),
         }
     }
 }
@@ -86,7 +89,16 @@ cfg_if! {
             type Error = Error;
             fn try_from(config: Config) -> Result<Self> {
                 let mut args = Args::default();
-                let _ = config.network;
+                match config.network {
+                    Network::Mainnet => {}
+                    Network::Testnet10 => {
+                        args.testnet = true;
+                        args.testnet_suffix = 10;
+                    }
+                    Network::Devnet => {
+                        args.devnet = true;
+                    }
+                }
 
                 args.perf_metrics = true;
                 args.perf_metrics_interval_sec = 1;
@@ -104,6 +116,7 @@ cfg_if! {
                 }
 
                 args.user_agent_comments = vec![user_agent_comment()];
+                args.add_peers = config.preferred_peers;
 
                 // TODO - parse custom args and overlap on top of the defaults
 
@@ -115,7 +128,16 @@ cfg_if! {
             fn from(config: Config) -> Self {
                 let mut args = Arglist::default();
 
-                let _ = config.network;
+                match config.network {
+                    Network::Mainnet => {}
+                    Network::Testnet10 => {
+                        args.push("--testnet");
+                        args.push("--netsuffix=10");
+                    }
+                    Network::Devnet => {
+                        args.push("--devnet");
+                    }
+                }
 
                 args.push("--perf-metrics");
                 args.push("--perf-metrics-interval-sec=1");
@@ -152,6 +174,10 @@ cfg_if! {
 
                 args.push(format!("--uacomment={}", user_agent_comment()));
 
+                for peer in config.preferred_peers.iter() {
+                    args.push(format!("--addpeer={peer}"));
+                }
+
                 if !(config.kaspad_daemon_args_enable
                     && config.kaspad_daemon_args.contains("--appdir"))
                 {
@@ -0,0 +1,113 @@
+use crate::imports::*;
+use kaspa_grpc_client::GrpcClient;
+use kaspa_wallet_core::rpc::{NotificationMode, Rpc, RpcCtl};
+
+const CONNECTIVITY_POLL_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// Normalizes a user-supplied gRPC endpoint into the `grpc://host:port`
+/// form expected by [`GrpcClient`], applying the network default port
+/// if the endpoint does not specify one.
+pub fn grpc_url_with_network_default_port(url: &str, network: Network) -> String {
+    let default_port = crate::settings::node_grpc_port_for_network(network);
+    let trimmed = url.trim();
+    let trimmed = trimmed.strip_prefix("grpc://").unwrap_or(trimmed);
+    let trimmed = trimmed.trim_end_matches('/');
+
+    let host_port = if trimmed.is_empty() {
+        format!("127.0.0.1:{default_port}")
+    } else if trimmed.starts_with('[') {
+        if trimmed.contains("]:") {
+            trimmed.to_string()
+        } else {
+            format!("{trimmed}:{default_port}")
+        }
+    } else if trimmed.matches(':').count() == 1 {
+        trimmed.to_string()
+    } else if trimmed.contains(':') {
+        format!("[{trimmed}]:{default_port}")
+    } else {
+        format!("{trimmed}:{default_port}")
+    };
+
+    format!("grpc://{host_port}")
+}
+
+/// Resolves the `host:port` gRPC endpoint of the node configured in
+/// [`NodeSettings`] for use by external tools (CPU miner, Rothschild).
+/// A local node is reached through its gRPC interface settings, a remote
+/// node only if the wallet itself is connected to it over gRPC.
+pub fn node_grpc_address(settings: &NodeSettings) -> Option<String> {
+    if settings.node_kind.is_local() {
+        crate::runtime::services::StratumBridgeService::grpc_address_from_settings(settings)
+    } else if settings.node_kind == KaspadNodeKind::Remote
+        && settings.connection_config_kind == NodeConnectionConfigKind::Custom
+        && settings.rpc_kind == crate::settings::RpcKind::Grpc
+    {
+        let url = grpc_url_with_network_default_port(&settings.grpc_url, settings.network);
+        url.strip_prefix("grpc://").map(String::from)
+    } else {
+        None
+    }
+}
+
+/// Connects to a gRPC endpoint and wraps the resulting client into an [`Rpc`]
+/// instance. Unlike the wRPC client, [`GrpcClient`] does not carry its own
+/// [`RpcCtl`], so connectivity changes are observed by a monitor task and
+/// relayed to the wallet as open/close signals.
+pub async fn connect(url: &str) -> Result<Rpc> {
+    let client = Arc::new(
+        GrpcClient::connect_with_args(
+            NotificationMode::MultiListeners,
+            url.to_string(),
+            None,
+            true,
+            None,
+            false,
+            None,
+            Default::default(),
+        )
+        .await?,
+    );
+    client.start(None).await;
+
+    let rpc_ctl = RpcCtl::new();
+    spawn_connectivity_monitor(Arc::downgrade(&client), rpc_ctl.clone());
+
+    let rpc_api: Arc<DynRpcApi> = client;
+    Ok(Rpc::new(rpc_api, rpc_ctl))
+}
+
+/// Polls the client connection state and signals [`RpcCtl`] on transitions.
+/// The initial open signal is issued by `KaspaService::connect_rpc_client()`,
+/// so the monitor only reacts to subsequent disconnects and reconnects.
+/// The task exits once the client has been released by all services.
+fn spawn_connectivity_monitor(client: std::sync::Weak<GrpcClient>, rpc_ctl: RpcCtl) {
+    tokio::spawn(async move {
+        let mut was_connected = true;
+        loop {
+            sleep(CONNECTIVITY_POLL_INTERVAL).await;
+
+            let Some(client) = client.upgrade() else {
+                break;
+            };
+
+            let is_connected = client.is_connected();
+            drop(client);
+
+            if is_connected == was_connected {
+                continue;
+            }
+            was_connected = is_connected;
+
+            let result = if is_connected {
+                rpc_ctl.signal_open().await
+            } else {
+                rpc_ctl.signal_close().await
+            };
+
+            if let Err(err) = result {
+                log_warn!("gRPC connectivity monitor: unable to signal rpc ctl: {err}");
+            }
+        }
+    });
+}
@@ -16,9 +16,7 @@ cfg_if! {
     if #[cfg(not(target_arch = "wasm32"))] {
         #[cfg(not(target_arch = "wasm32"))]
         use kaspa_rpc_service::service::RpcCoreService;
-
-        const LOG_BUFFER_LINES: usize = 4096;
-        const LOG_BUFFER_MARGIN: usize = 128;
+        use kaspa_grpc_client::GrpcClient;
     }
 }
 
@@ -29,9 +27,9 @@ cfg_if! {
         pub mod config;
         pub use config::Config;
         pub mod daemon;
+        pub mod grpc;
         pub mod inproc;
-        pub mod logs;
-        use logs::Log;
+        use crate::runtime::services::log_hub::{LogSource, log_hub};
         pub use kaspad_lib::args::Args;
 
         #[async_trait]
@@ -87,7 +85,7 @@ pub struct KaspaService {
     #[cfg(not(target_arch = "wasm32"))]
     pub kaspad: Mutex<Option<Arc<dyn Kaspad + Send + Sync + 'static>>>,
     #[cfg(not(target_arch = "wasm32"))]
-    pub logs: Mutex<Vec<Log>>,
+    pub grpc_url: Mutex<Option<String>>,
     pub connect_on_startup: Option<NodeSettings>,
 }
 
@@ -133,7 +131,7 @@ impl KaspaService {
             #[cfg(not(target_arch = "wasm32"))]
             kaspad: Mutex::new(None),
             #[cfg(not(target_arch = "wasm32"))]
-            logs: Mutex::new(Vec::new()),
+            grpc_url: Mutex::new(None),
         }
     }
 
@@ -160,7 +158,7 @@ impl KaspaService {
         self.kaspad.lock().unwrap().replace(kaspad);
     }
 
-    pub fn create_rpc_client(config: &RpcConfig, network: Network) -> Result<Rpc> {
+    pub async fn create_rpc_client(config: &RpcConfig, network: Network) -> Result<Rpc> {
         match config {
             RpcConfig::Wrpc {
                 url,
@@ -197,8 +195,19 @@ impl KaspaService {
                 let rpc_api: Arc<DynRpcApi> = wrpc_client;
                 Ok(Rpc::new(rpc_api, rpc_ctl))
             }
-            RpcConfig::Grpc { url: _ } => {
-                unimplemented!("GPRC is not currently supported")
+            RpcConfig::Grpc { url } => {
+                cfg_if! {
+                    if #[cfg(not(target_arch = "wasm32"))] {
+                        let url = grpc::grpc_url_with_network_default_port(
+                            url.as_deref().unwrap_or_default(),
+                            network,
+                        );
+                        grpc::connect(url.as_str()).await
+                    } else {
+                        let _ = (url, network);
+                        Err(Error::custom("gRPC is not supported on this platform"))
+                    }
+                }
             }
         }
     }
@@ -225,11 +234,9 @@ impl KaspaService {
             } else {
                 #[cfg(not(target_arch = "wasm32"))]
                 {
-                    if wallet
-                        .rpc_api()
-                        .clone()
-                        .downcast_arc::<RpcCoreService>()
-                        .is_ok()
+                    let rpc_api = wallet.rpc_api().clone();
+                    if rpc_api.clone().downcast_arc::<RpcCoreService>().is_ok()
+                        || rpc_api.downcast_arc::<GrpcClient>().is_ok()
                     {
                         wallet.rpc_ctl().signal_open().await?;
                     } else {
@@ -249,20 +256,9 @@ impl KaspaService {
         self.wallet.clone().downcast_arc::<CoreWallet>().ok()
     }
 
-    #[cfg(not(target_arch = "wasm32"))]
-    pub fn logs(&self) -> MutexGuard<'_, Vec<Log>> {
-        self.logs.lock().unwrap()
-    }
-
     #[cfg(not(target_arch = "wasm32"))]
     pub async fn update_logs(&self, line: String) {
-        {
-            let mut logs = self.logs.lock().unwrap();
-            if logs.len() > LOG_BUFFER_LINES {
-                logs.drain(0..LOG_BUFFER_MARGIN);
-            }
-            logs.push(line.as_str().into());
-        }
+        log_hub().push_line(LogSource::Node, &line);
 
         if update_logs_flag().load(Ordering::SeqCst) {
             self.application_events
@@ -282,6 +278,11 @@ impl KaspaService {
             {
                 wrpc_client.url()
             } else {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.grpc_url.lock().unwrap().clone()
+                }
+                #[cfg(target_arch = "wasm32")]
                 None
             }
         } else {
@@ -289,6 +290,14 @@ impl KaspaService {
         }
     }
 
+    /// RPC client of the current node connection. Unlike the wallet
+    /// APIs, it does not require an open wallet.
+    pub fn rpc_api(&self) -> Option<Arc<DynRpcApi>> {
+        self.core_wallet()
+            .filter(|wallet| wallet.has_rpc())
+            .map(|wallet| wallet.rpc_api().clone())
+    }
+
     fn is_wrpc_client(&self) -> bool {
         if let Some(wallet) = self.core_wallet() {
             wallet.has_rpc()
@@ -307,6 +316,11 @@ impl KaspaService {
             if let Ok(wrpc_client) = wallet.rpc_api().clone().downcast_arc::<KaspaRpcClient>() {
                 wrpc_client.disconnect().await?;
             } else {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Ok(grpc_client) = wallet.rpc_api().clone().downcast_arc::<GrpcClient>() {
+                    grpc_client.disconnect().await?;
+                    self.grpc_url.lock().unwrap().take();
+                }
                 wallet.rpc_ctl().signal_close().await?;
             }
         }
@@ -518,6 +532,7 @@ impl KaspaService {
                 };
 
                 let rpc = Self::create_rpc_client(&rpc_config, network)
+                    .await
                     .expect("Kaspad Service - unable to create wRPC client");
                 self.start_all_services(Some(rpc), network).await?;
                 self.connect_rpc_client().await?;
@@ -541,6 +556,7 @@ impl KaspaService {
                 };
 
                 let rpc = Self::create_rpc_client(&rpc_config, network)
+                    .await
                     .expect("Kaspad Service - unable to create wRPC client");
                 self.start_all_services(Some(rpc), network).await?;
                 self.connect_rpc_client().await?;
@@ -569,6 +585,7 @@ impl KaspaService {
                 };
 
                 let rpc = Self::create_rpc_client(&rpc_config, network)
+                    .await
                     .expect("Kaspad Service - unable to create wRPC client");
                 self.start_all_services(Some(rpc), network).await?;
                 self.connect_rpc_client().await?;
@@ -592,8 +609,26 @@ impl KaspaService {
 
                     self.handle_network_change(network).await?;
 
-                    let rpc = Self::create_rpc_client(&rpc_config, network)
-                        .expect("Kaspad Service - unable to create wRPC client");
+                    let rpc = match Self::create_rpc_client(&rpc_config, network).await {
+                        Ok(rpc) => rpc,
+                        Err(err) => {
+                            log_error!("Kaspad Service - unable to create RPC client: {err}");
+                            crate::runtime::runtime()
+                                .error(format!("{}: {err}", i18n("Unable to connect to the node")));
+                            return Ok(false);
+                        }
+                    };
+
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if let RpcConfig::Grpc { url } = &rpc_config {
+                        self.grpc_url.lock().unwrap().replace(
+                            grpc::grpc_url_with_network_default_port(
+                                url.as_deref().unwrap_or_default(),
+                                network,
+                            ),
+                        );
+                    }
+
                     self.start_all_services(Some(rpc), network).await?;
                     self.connect_rpc_client().await?;
                 }
@@ -649,6 +684,7 @@ impl KaspaService {
                 // println!("wallet event: {:?}", event);
             }
         }
+        runtime().alert_monitor_service().wallet_event(&event);
         self.application_events
             .sender
             .send(crate::events::Events::Wallet { event })
@@ -0,0 +1,510 @@
+use crate::imports::*;
+
+/// Number of phase transitions retained in the loader history.
+pub const LOADER_HISTORY_CAPACITY: usize = 64;
+
+/// Phase of the self-hosted loader. Phases are listed in startup order;
+/// each one waits for a single component to become ready.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoaderPhase {
+    Disabled,
+    PostgresDisabled,
+    WaitingForPostgres,
+    WaitingForNodeSync,
+    WaitingForIndexer,
+    WaitingForKIndexer,
+    WaitingForKasiaIndexer,
+    WaitingForExplorer,
+    Connected,
+}
+
+impl LoaderPhase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LoaderPhase::Disabled => i18n("Disabled"),
+            LoaderPhase::PostgresDisabled => i18n("Postgres disabled"),
+            LoaderPhase::WaitingForPostgres => i18n("Waiting for Postgres"),
+            LoaderPhase::WaitingForNodeSync => i18n("Waiting for Node sync"),
+            LoaderPhase::WaitingForIndexer => i18n("Waiting for indexer"),
+            LoaderPhase::WaitingForKIndexer => i18n("Waiting for k-indexer"),
+            LoaderPhase::WaitingForKasiaIndexer => i18n("Waiting for kasia-indexer"),
+            LoaderPhase::WaitingForExplorer => i18n("Waiting for explorer"),
+            LoaderPhase::Connected => i18n("Connected"),
+        }
+    }
+
+    /// Stable identifier used in logs and metric labels.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            LoaderPhase::Disabled => "disabled",
+            LoaderPhase::PostgresDisabled => "postgres-disabled",
+            LoaderPhase::WaitingForPostgres => "waiting-for-postgres",
+            LoaderPhase::WaitingForNodeSync => "waiting-for-node-sync",
+            LoaderPhase::WaitingForIndexer => "waiting-for-indexer",
+            LoaderPhase::WaitingForKIndexer => "waiting-for-k-indexer",
+            LoaderPhase::WaitingForKasiaIndexer => "waiting-for-kasia-indexer",
+            LoaderPhase::WaitingForExplorer => "waiting-for-explorer",
+            LoaderPhase::Connected => "connected",
+        }
+    }
+
+    /// Phase reported in the `phase` field of `/api/loader-status`, kept
+    /// as published before the loader phases were distinguished.
+    pub fn status_name(&self) -> &'static str {
+        match self {
+            LoaderPhase::Disabled => "Disabled",
+            LoaderPhase::Connected => "Connected",
+            _ => "Initialisation",
+        }
+    }
+
+    /// Whether the loader is still working towards [`LoaderPhase::Connected`].
+    pub fn is_pending(&self) -> bool {
+        !matches!(
+            self,
+            LoaderPhase::Disabled | LoaderPhase::PostgresDisabled | LoaderPhase::Connected
+        )
+    }
+
+    /// Components that must be ready (or disabled) before the phase can be entered.
+    pub fn prerequisites(&self) -> &'static [LoaderComponent] {
+        use LoaderComponent::*;
+        match self {
+            LoaderPhase::Disabled
+            | LoaderPhase::PostgresDisabled
+            | LoaderPhase::WaitingForPostgres => &[],
+            LoaderPhase::WaitingForNodeSync => &[Postgres],
+            LoaderPhase::WaitingForIndexer => &[Postgres, Node],
+            LoaderPhase::WaitingForKIndexer => &[Postgres, Node, Indexer],
+            LoaderPhase::WaitingForKasiaIndexer => &[Postgres, Node, Indexer, KIndexer],
+            LoaderPhase::WaitingForExplorer => &[Postgres, Node, Indexer, KIndexer, KasiaIndexer],
+            LoaderPhase::Connected => &LoaderComponent::ALL,
+        }
+    }
+}
+
+/// Component supervised by the self-hosted loader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoaderComponent {
+    Postgres,
+    Node,
+    Indexer,
+    KIndexer,
+    KasiaIndexer,
+    Rest,
+    Socket,
+}
+
+impl LoaderComponent {
+    /// All components, in startup order.
+    pub const ALL: [LoaderComponent; 7] = [
+        LoaderComponent::Postgres,
+        LoaderComponent::Node,
+        LoaderComponent::Indexer,
+        LoaderComponent::KIndexer,
+        LoaderComponent::KasiaIndexer,
+        LoaderComponent::Rest,
+        LoaderComponent::Socket,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LoaderComponent::Postgres => "Postgres",
+            LoaderComponent::Node => "Node",
+            LoaderComponent::Indexer => "Indexer",
+            LoaderComponent::KIndexer => "K Indexer",
+            LoaderComponent::KasiaIndexer => "Kasia Indexer",
+            LoaderComponent::Rest => "REST",
+            LoaderComponent::Socket => "Socket",
+        }
+    }
+}
+
+/// Readiness of a single component.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ComponentState {
+    /// Not required by the current settings.
+    #[default]
+    Disabled,
+    Waiting,
+    Ready,
+    /// Failing its health checks, which is tolerated for now.
+    Degraded,
+}
+
+impl ComponentState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComponentState::Disabled => i18n("Disabled"),
+            ComponentState::Waiting => i18n("Waiting"),
+            ComponentState::Ready => i18n("Ready"),
+            ComponentState::Degraded => i18n("Degraded"),
+        }
+    }
+
+    /// Whether the component does not hold back the phases that depend on it.
+    pub fn is_satisfied(&self) -> bool {
+        !matches!(self, ComponentState::Waiting)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentStatus {
+    pub component: LoaderComponent,
+    pub state: ComponentState,
+    /// Consecutive failed health checks.
+    pub failures: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoaderTransition {
+    pub from: LoaderPhase,
+    pub to: LoaderPhase,
+    pub reason: String,
+    /// Unix time in milliseconds.
+    pub timestamp: f64,
+}
+
+/// State of the loader as published to the UI and `/api/loader-status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoaderStatusSnapshot {
+    /// Display name of the phase, see [`LoaderPhase::status_name`].
+    pub phase: String,
+    pub phase_id: LoaderPhase,
+    pub message: String,
+    /// Unix time in milliseconds at which the current phase was entered.
+    pub phase_since: f64,
+    pub connected: bool,
+    pub postgres_ready: bool,
+    pub indexers_ready: bool,
+    pub rest_ready: bool,
+    pub socket_ready: bool,
+    pub components: Vec<ComponentStatus>,
+    /// Phase transitions, oldest first.
+    pub history: Vec<LoaderTransition>,
+    pub last_ping_at: String,
+}
+
+impl Default for LoaderStatusSnapshot {
+    fn default() -> Self {
+        LoaderStateMachine::default().snapshot()
+    }
+}
+
+/// Loader phase with guarded transitions, per-component readiness and
+/// a bounded history of phase changes.
+#[derive(Debug, Clone)]
+pub struct LoaderStateMachine {
+    phase: LoaderPhase,
+    message: String,
+    phase_since: f64,
+    components: Vec<ComponentStatus>,
+    history: VecDeque<LoaderTransition>,
+    revision: u64,
+    updated_at: String,
+}
+
+impl Default for LoaderStateMachine {
+    fn default() -> Self {
+        Self {
+            phase: LoaderPhase::Disabled,
+            message: "Loader is disabled".to_string(),
+            phase_since: unixtime_as_millis_f64(),
+            components: LoaderComponent::ALL
+                .iter()
+                .map(|component| ComponentStatus {
+                    component: *component,
+                    state: ComponentState::Disabled,
+                    failures: 0,
+                })
+                .collect(),
+            history: VecDeque::new(),
+            revision: 0,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+impl LoaderStateMachine {
+    pub fn phase(&self) -> LoaderPhase {
+        self.phase
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Incremented on every change of the phase, message or components.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn history(&self) -> impl Iterator<Item = &LoaderTransition> {
+        self.history.iter()
+    }
+
+    fn status_mut(&mut self, component: LoaderComponent) -> &mut ComponentStatus {
+        self.components
+            .iter_mut()
+            .find(|status| status.component == component)
+            .expect("loader component")
+    }
+
+    pub fn state(&self, component: LoaderComponent) -> ComponentState {
+        self.components
+            .iter()
+            .find(|status| status.component == component)
+            .map(|status| status.state)
+            .unwrap_or_default()
+    }
+
+    pub fn failures(&self, component: LoaderComponent) -> u32 {
+        self.components
+            .iter()
+            .find(|status| status.component == component)
+            .map(|status| status.failures)
+            .unwrap_or_default()
+    }
+
+    fn touch(&mut self) {
+        self.revision += 1;
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+    }
+
+    pub fn set_state(&mut self, component: LoaderComponent, state: ComponentState) {
+        let status = self.status_mut(component);
+        if status.state != state {
+            status.state = state;
+            self.touch();
+        }
+    }
+
+    /// Marks a required component as ready or waiting; disabled
+    /// components are left untouched.
+    pub fn set_ready(&mut self, component: LoaderComponent, ready: bool) {
+        if self.state(component) != ComponentState::Disabled {
+            let state = if ready {
+                ComponentState::Ready
+            } else {
+                ComponentState::Waiting
+            };
+            self.set_state(component, state);
+        }
+    }
+
+    /// Sets the state of every component that follows `component` in
+    /// startup order and is not disabled.
+    pub fn set_dependents_state(&mut self, component: LoaderComponent, state: ComponentState) {
+        let dependents = LoaderComponent::ALL
+            .iter()
+            .skip_while(|item| **item != component)
+            .skip(1)
+            .copied()
+            .collect::<Vec<_>>();
+        for dependent in dependents {
+            if self.state(dependent) != ComponentState::Disabled {
+                self.set_state(dependent, state);
+            }
+        }
+    }
+
+    /// Marks a component as required or not. Newly required components
+    /// start out waiting.
+    pub fn set_required(&mut self, component: LoaderComponent, required: bool) {
+        match (required, self.state(component)) {
+            (true, ComponentState::Disabled) => self.set_state(component, ComponentState::Waiting),
+            (false, _) => {
+                self.set_state(component, ComponentState::Disabled);
+                self.status_mut(component).failures = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// Records the result of a health check and returns the number of
+    /// consecutive failures.
+    pub fn record_health(&mut self, component: LoaderComponent, healthy: bool) -> u32 {
+        let status = self.status_mut(component);
+        status.failures = if healthy {
+            0
+        } else {
+            status.failures.saturating_add(1)
+        };
+        status.failures
+    }
+
+    pub fn reset_failures(&mut self) {
+        for status in self.components.iter_mut() {
+            status.failures = 0;
+        }
+    }
+
+    /// Moves the loader to `phase`, recording the transition with `reason`.
+    /// Fails without changes when a prerequisite of `phase` is not ready.
+    /// Returns the recorded transition if the phase has changed.
+    pub fn transition(
+        &mut self,
+        phase: LoaderPhase,
+        reason: &str,
+        now: f64,
+    ) -> Result<Option<LoaderTransition>> {
+        if let Some(blocking) = phase
+            .prerequisites()
+            .iter()
+            .find(|component| !self.state(**component).is_satisfied())
+        {
+            return Err(Error::custom(format!(
+                "loader cannot enter {} while {} is not ready",
+                phase.slug(),
+                blocking.name()
+            )));
+        }
+
+        if self.message != reason {
+            self.message = reason.to_string();
+            self.touch();
+        }
+
+        if self.phase == phase {
+            return Ok(None);
+        }
+
+        let transition = LoaderTransition {
+            from: self.phase,
+            to: phase,
+            reason: reason.to_string(),
+            timestamp: now,
+        };
+        if self.history.len() >= LOADER_HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+        self.history.push_back(transition.clone());
+        self.phase = phase;
+        self.phase_since = now;
+        self.touch();
+
+        Ok(Some(transition))
+    }
+
+    pub fn snapshot(&self) -> LoaderStatusSnapshot {
+        LoaderStatusSnapshot {
+            phase: self.phase.status_name().to_string(),
+            phase_id: self.phase,
+            message: self.message.clone(),
+            phase_since: self.phase_since,
+            connected: self.phase == LoaderPhase::Connected,
+            postgres_ready: self.state(LoaderComponent::Postgres) == ComponentState::Ready,
+            indexers_ready: matches!(
+                self.phase,
+                LoaderPhase::WaitingForExplorer | LoaderPhase::Connected
+            ),
+            rest_ready: self.state(LoaderComponent::Rest) == ComponentState::Ready,
+            socket_ready: self.state(LoaderComponent::Socket) == ComponentState::Ready,
+            components: self.components.clone(),
+            history: self.history.iter().cloned().collect(),
+            last_ping_at: self.updated_at.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ready(machine: &mut LoaderStateMachine, components: &[LoaderComponent]) {
+        for component in components {
+            machine.set_state(*component, ComponentState::Ready);
+        }
+    }
+
+    #[test]
+    fn transitions_are_guarded_by_component_readiness() {
+        let mut machine = LoaderStateMachine::default();
+        for component in LoaderComponent::ALL {
+            machine.set_required(component, true);
+        }
+
+        let transition = machine
+            .transition(LoaderPhase::WaitingForPostgres, "Waiting for Postgres", 1.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(transition.from, LoaderPhase::Disabled);
+        assert_eq!(transition.to, LoaderPhase::WaitingForPostgres);
+
+        assert!(
+            machine
+                .transition(LoaderPhase::Connected, "All services are running", 2.0)
+                .is_err()
+        );
+        assert_eq!(machine.phase(), LoaderPhase::WaitingForPostgres);
+
+        ready(&mut machine, &[LoaderComponent::Postgres]);
+        machine.set_state(LoaderComponent::Node, ComponentState::Degraded);
+        assert!(
+            machine
+                .transition(LoaderPhase::WaitingForIndexer, "Waiting for indexer", 3.0)
+                .unwrap()
+                .is_some()
+        );
+        // repeating the phase only updates the message
+        assert!(
+            machine
+                .transition(LoaderPhase::WaitingForIndexer, "Still waiting", 4.0)
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(machine.message(), "Still waiting");
+
+        ready(&mut machine, &LoaderComponent::ALL);
+        machine
+            .transition(LoaderPhase::Connected, "All services are running", 5.0)
+            .unwrap();
+        let snapshot = machine.snapshot();
+        assert!(snapshot.connected && snapshot.indexers_ready);
+        assert_eq!(snapshot.history.len(), 3);
+        assert_eq!(snapshot.phase_since, 5.0);
+        assert_eq!(snapshot.phase, "Connected");
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["phase"], "Connected");
+        assert_eq!(json["phaseId"], "connected");
+
+        machine.set_dependents_state(LoaderComponent::Postgres, ComponentState::Waiting);
+        assert_eq!(
+            machine.state(LoaderComponent::Node),
+            ComponentState::Waiting
+        );
+        assert_eq!(
+            machine.state(LoaderComponent::Postgres),
+            ComponentState::Ready
+        );
+    }
+
+    #[test]
+    fn history_and_failures_are_bounded() {
+        let mut machine = LoaderStateMachine::default();
+        machine.set_required(LoaderComponent::Postgres, true);
+        for n in 0..LOADER_HISTORY_CAPACITY + 10 {
+            let phase = if n % 2 == 0 {
+                LoaderPhase::WaitingForPostgres
+            } else {
+                LoaderPhase::Disabled
+            };
+            machine.transition(phase, "toggle", n as f64).unwrap();
+        }
+        assert_eq!(machine.history().count(), LOADER_HISTORY_CAPACITY);
+        assert_eq!(machine.history().last().unwrap().timestamp, 73.0);
+
+        assert_eq!(machine.record_health(LoaderComponent::Postgres, false), 1);
+        assert_eq!(machine.record_health(LoaderComponent::Postgres, false), 2);
+        assert_eq!(machine.record_health(LoaderComponent::Postgres, true), 0);
+        machine.record_health(LoaderComponent::Postgres, false);
+        machine.set_required(LoaderComponent::Postgres, false);
+        assert_eq!(machine.failures(LoaderComponent::Postgres), 0);
+    }
+}
@@ -0,0 +1,529 @@
+//! Unified log subsystem. Output of the node, the stratum bridge, the
+//! miners and the self-hosted services is recorded as [`LogRecord`]s in a
+//! ring buffer per source that the log viewers filter, search and export,
+//! and is optionally mirrored to rotating files (`<storage>/logs/kaspa-ng.log`)
+//! by a dedicated writer thread.
+
+use crate::imports::*;
+use regex::Regex;
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, LineWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+/// Number of records kept in memory for each source, so that a chatty
+/// source can not push the records of the others out of the buffer.
+const LOG_SOURCE_CAPACITY: usize = 10_000;
+const LOG_FILE_NAME: &str = "kaspa-ng";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    pub fn iter() -> impl Iterator<Item = LogLevel> {
+        [
+            LogLevel::Trace,
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warning,
+            LogLevel::Error,
+        ]
+        .into_iter()
+    }
+
+    /// Parses level names as used by the services (`WARN`, `error`, ...);
+    /// unknown names map to `Info`.
+    pub fn parse(level: &str) -> Self {
+        match level.trim().to_ascii_uppercase().as_str() {
+            "TRACE" => LogLevel::Trace,
+            "DEBUG" => LogLevel::Debug,
+            "WARN" | "WARNING" => LogLevel::Warning,
+            "ERROR" | "ERR" | "FATAL" | "PANIC" => LogLevel::Error,
+            _ => LogLevel::Info,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warning => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+
+    pub fn color(&self) -> Color32 {
+        match self {
+            LogLevel::Trace => theme_color().logs_trace_color,
+            LogLevel::Debug => theme_color().logs_debug_color,
+            LogLevel::Info => theme_color().logs_info_color,
+            LogLevel::Warning => theme_color().logs_warning_color,
+            LogLevel::Error => theme_color().logs_error_color,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogSource {
+    Node,
+    Bridge,
+    CpuMiner,
+    Rothschild,
+    Loader,
+    Postgres,
+    Indexer,
+    KIndexer,
+    KasiaIndexer,
+    Rest,
+    Socket,
+}
+
+impl LogSource {
+    pub const ALL: [LogSource; 11] = [
+        LogSource::Node,
+        LogSource::Bridge,
+        LogSource::CpuMiner,
+        LogSource::Rothschild,
+        LogSource::Loader,
+        LogSource::Postgres,
+        LogSource::Indexer,
+        LogSource::KIndexer,
+        LogSource::KasiaIndexer,
+        LogSource::Rest,
+        LogSource::Socket,
+    ];
+
+    pub const SELF_HOSTED: [LogSource; 7] = [
+        LogSource::Loader,
+        LogSource::Postgres,
+        LogSource::Indexer,
+        LogSource::KIndexer,
+        LogSource::KasiaIndexer,
+        LogSource::Rest,
+        LogSource::Socket,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogSource::Node => i18n("Rusty Kaspa"),
+            LogSource::Bridge => i18n("RK Bridge"),
+            LogSource::CpuMiner => i18n("CPU Miner"),
+            LogSource::Rothschild => i18n("Rothschild"),
+            LogSource::Loader => i18n("Loader"),
+            LogSource::Postgres => i18n("Postgres"),
+            LogSource::Indexer => i18n("Indexer"),
+            LogSource::KIndexer => i18n("K-indexer"),
+            LogSource::KasiaIndexer => i18n("Kasia-indexer"),
+            LogSource::Rest => i18n("REST API"),
+            LogSource::Socket => i18n("Socket"),
+        }
+    }
+
+    /// Short identifier used in exported and file logs.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            LogSource::Node => "node",
+            LogSource::Bridge => "bridge",
+            LogSource::CpuMiner => "cpu-miner",
+            LogSource::Rothschild => "rothschild",
+            LogSource::Loader => "loader",
+            LogSource::Postgres => "postgres",
+            LogSource::Indexer => "indexer",
+            LogSource::KIndexer => "k-indexer",
+            LogSource::KasiaIndexer => "kasia-indexer",
+            LogSource::Rest => "rest",
+            LogSource::Socket => "socket",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    /// Sequence number, increasing in the order records are received.
+    pub seq: u64,
+    /// Unix time in milliseconds.
+    pub timestamp: f64,
+    pub source: LogSource,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+impl LogRecord {
+    fn datetime(&self) -> chrono::DateTime<chrono::Local> {
+        chrono::DateTime::<chrono::Utc>::from_timestamp_millis(self.timestamp as i64)
+            .unwrap_or_default()
+            .with_timezone(&chrono::Local)
+    }
+
+    /// Local time of the record, for display.
+    pub fn time(&self) -> String {
+        self.datetime().format("%H:%M:%S%.3f").to_string()
+    }
+
+    /// Single-line representation used for exports and log files.
+    pub fn format(&self) -> String {
+        format!(
+            "{} [{}] {:<5} {}",
+            self.datetime()
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+            self.source.slug(),
+            self.level.name(),
+            self.message
+        )
+    }
+}
+
+/// Splits a Rusty Kaspa formatted line (`YYYY-MM-DD HH:MM:SS.mmm+TZ [LEVEL] text`)
+/// into level and text. Lines in other formats are returned as `Info`.
+pub fn parse_rusty_kaspa_line(line: &str) -> (LogLevel, &str) {
+    let line = line.trim();
+    match (line.get(30..31), line.get(31..36), line.get(38..)) {
+        (Some("["), Some(level), Some(text)) => (LogLevel::parse(level), text),
+        _ => (LogLevel::Info, line),
+    }
+}
+
+/// Record selection applied by the log viewers and exports.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Sources to include; all sources if empty.
+    pub sources: Vec<LogSource>,
+    pub min_level: Option<LogLevel>,
+    pub search: Option<Regex>,
+    /// Records whose message matches are left out.
+    pub exclude: Option<Regex>,
+    /// Unix time range in milliseconds.
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+    /// Only records received up to this sequence number (used when paused).
+    pub until_seq: Option<u64>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        (self.sources.is_empty() || self.sources.contains(&record.source))
+            && self.min_level.is_none_or(|level| record.level >= level)
+            && self.from.is_none_or(|from| record.timestamp >= from)
+            && self.to.is_none_or(|to| record.timestamp <= to)
+            && self.until_seq.is_none_or(|seq| record.seq <= seq)
+            && self
+                .search
+                .as_ref()
+                .is_none_or(|search| search.is_match(&record.message))
+            && !self
+                .exclude
+                .as_ref()
+                .is_some_and(|exclude| exclude.is_match(&record.message))
+    }
+}
+
+/// Current log file and the rotation policy.
+struct LogFiles {
+    folder: PathBuf,
+    writer: LineWriter<File>,
+    size: u64,
+    max_size: u64,
+    max_files: usize,
+}
+
+impl LogFiles {
+    fn folder() -> Result<PathBuf> {
+        let storage_folder = kaspa_wallet_core::storage::local::default_storage_folder();
+        let storage_folder = workflow_store::fs::resolve_path(storage_folder)?;
+        Ok(storage_folder.join("logs"))
+    }
+
+    fn path(folder: &Path, index: usize) -> PathBuf {
+        if index == 0 {
+            folder.join(format!("{LOG_FILE_NAME}.log"))
+        } else {
+            folder.join(format!("{LOG_FILE_NAME}.{index}.log"))
+        }
+    }
+
+    fn open(settings: &LogSettings) -> Result<Self> {
+        let folder = Self::folder()?;
+        std::fs::create_dir_all(&folder)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::path(&folder, 0))?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            folder,
+            writer: LineWriter::new(file),
+            size,
+            max_size: settings.max_file_size_mb.max(1) * 1024 * 1024,
+            max_files: settings.max_files,
+        })
+    }
+
+    fn write(&mut self, record: &LogRecord) -> Result<()> {
+        let line = record.format();
+        writeln!(self.writer, "{line}")?;
+        self.size += line.len() as u64 + 1;
+        if self.size >= self.max_size {
+            self.rotate()?;
+        }
+        Ok(())
+    }
+
+    /// Shifts `kaspa-ng.log` to `kaspa-ng.1.log` (and so on), dropping the
+    /// oldest file, and starts a new current file.
+    fn rotate(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        let oldest = Self::path(&self.folder, self.max_files);
+        if oldest.exists() {
+            std::fs::remove_file(&oldest)?;
+        }
+        for index in (0..self.max_files).rev() {
+            let path = Self::path(&self.folder, index);
+            if path.exists() {
+                std::fs::rename(&path, Self::path(&self.folder, index + 1))?;
+            }
+        }
+
+        let file = File::create(Self::path(&self.folder, 0))?;
+        self.writer = LineWriter::new(file);
+        self.size = 0;
+        Ok(())
+    }
+}
+
+enum LogFileEvent {
+    Record(Box<LogRecord>),
+    Configure(LogSettings),
+}
+
+/// Owns the log files; runs on its own thread so that pushing a record
+/// never waits on disk writes or rotation.
+fn log_file_writer(receiver: mpsc::Receiver<LogFileEvent>) {
+    let mut files: Option<LogFiles> = None;
+    while let Ok(event) = receiver.recv() {
+        match event {
+            LogFileEvent::Record(record) => {
+                if let Some(files) = files.as_mut() {
+                    if let Err(err) = files.write(&record) {
+                        // avoid feeding the error back into the hub
+                        eprintln!("Unable to write log file: {err}");
+                    }
+                }
+            }
+            LogFileEvent::Configure(settings) => {
+                files = if settings.files_enabled {
+                    match LogFiles::open(&settings) {
+                        Ok(files) => Some(files),
+                        Err(err) => {
+                            log_error!("Unable to open log file: {err}");
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+            }
+        }
+    }
+}
+
+pub struct LogHub {
+    records: Mutex<AHashMap<LogSource, VecDeque<LogRecord>>>,
+    seq: AtomicU64,
+    files_enabled: AtomicBool,
+    files: mpsc::Sender<LogFileEvent>,
+}
+
+pub fn log_hub() -> &'static LogHub {
+    static LOG_HUB: OnceLock<LogHub> = OnceLock::new();
+    LOG_HUB.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("log-files".to_string())
+            .spawn(move || log_file_writer(receiver))
+            .expect("Unable to start the log file writer");
+
+        LogHub {
+            records: Mutex::new(AHashMap::new()),
+            seq: AtomicU64::new(0),
+            files_enabled: AtomicBool::new(false),
+            files: sender,
+        }
+    })
+}
+
+impl LogHub {
+    pub fn push(&self, source: LogSource, level: LogLevel, message: impl Into<String>) {
+        let record = LogRecord {
+            seq: self.seq.fetch_add(1, Ordering::SeqCst) + 1,
+            timestamp: unixtime_as_millis_f64(),
+            source,
+            level,
+            message: message.into(),
+        };
+
+        if self.files_enabled.load(Ordering::Relaxed) {
+            self.files
+                .send(LogFileEvent::Record(Box::new(record.clone())))
+                .ok();
+        }
+
+        let mut records = self.records.lock().unwrap();
+        let records = records
+            .entry(source)
+            .or_insert_with(|| VecDeque::with_capacity(LOG_SOURCE_CAPACITY));
+        if records.len() >= LOG_SOURCE_CAPACITY {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Records a line in the Rusty Kaspa log format.
+    pub fn push_line(&self, source: LogSource, line: &str) {
+        let (level, text) = parse_rusty_kaspa_line(line);
+        if text.is_not_empty() {
+            self.push(source, level, text);
+        }
+    }
+
+    /// Sequence number of the most recent record.
+    pub fn last_seq(&self) -> u64 {
+        self.seq.load(Ordering::SeqCst)
+    }
+
+    /// Returns up to `limit` of the most recent records matching the filter.
+    pub fn records(&self, filter: &LogFilter, limit: usize) -> Vec<LogRecord> {
+        let records = self.records.lock().unwrap();
+        let mut selected = records
+            .values()
+            .flat_map(|records| {
+                records
+                    .iter()
+                    .rev()
+                    .filter(|record| filter.matches(record))
+                    .take(limit)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        drop(records);
+
+        selected.sort_unstable_by_key(|record| record.seq);
+        let skip = selected.len().saturating_sub(limit);
+        selected.drain(..skip);
+        selected
+    }
+
+    /// Writes all records matching the filter to `path`, returning the
+    /// number of records written.
+    pub fn export(&self, path: &Path, filter: &LogFilter) -> Result<usize> {
+        let records = self.records(filter, usize::MAX);
+        let mut writer = BufWriter::new(File::create(path)?);
+        for record in records.iter() {
+            writeln!(writer, "{}", record.format())?;
+        }
+        writer.flush()?;
+        Ok(records.len())
+    }
+
+    /// Starts, stops or reconfigures mirroring of the logs to files.
+    pub fn configure(&self, settings: &LogSettings) {
+        self.files_enabled
+            .store(settings.files_enabled, Ordering::Relaxed);
+        self.files
+            .send(LogFileEvent::Configure(settings.clone()))
+            .ok();
+    }
+
+    pub fn folder(&self) -> Result<PathBuf> {
+        LogFiles::folder()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(seq: u64, source: LogSource, level: LogLevel, message: &str) -> LogRecord {
+        LogRecord {
+            seq,
+            timestamp: seq as f64 * 1000.0,
+            source,
+            level,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn records_are_filtered() {
+        let records = [
+            record(1, LogSource::Node, LogLevel::Info, "Processed 12 blocks"),
+            record(
+                2,
+                LogSource::Bridge,
+                LogLevel::Warning,
+                "worker disconnected",
+            ),
+            record(3, LogSource::Node, LogLevel::Error, "connection refused"),
+        ];
+        let select = |filter: &LogFilter| {
+            records
+                .iter()
+                .filter(|record| filter.matches(record))
+                .map(|record| record.seq)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(select(&LogFilter::default()), vec![1, 2, 3]);
+        assert_eq!(
+            select(&LogFilter {
+                sources: vec![LogSource::Node],
+                ..Default::default()
+            }),
+            vec![1, 3]
+        );
+        assert_eq!(
+            select(&LogFilter {
+                min_level: Some(LogLevel::Warning),
+                ..Default::default()
+            }),
+            vec![2, 3]
+        );
+        assert_eq!(
+            select(&LogFilter {
+                search: Some(Regex::new("(?i)^processed \\d+").unwrap()),
+                ..Default::default()
+            }),
+            vec![1]
+        );
+        assert_eq!(
+            select(&LogFilter {
+                from: Some(2000.0),
+                until_seq: Some(2),
+                ..Default::default()
+            }),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn rusty_kaspa_lines_are_parsed() {
+        let line = "2024-01-01 12:00:00.000+00:00 [WARN ] Connection lost";
+        assert_eq!(
+            parse_rusty_kaspa_line(line),
+            (LogLevel::Warning, "Connection lost")
+        );
+        assert_eq!(
+            parse_rusty_kaspa_line("plain output"),
+            (LogLevel::Info, "plain output")
+        );
+        assert_eq!(LogLevel::parse("error"), LogLevel::Error);
+    }
+}
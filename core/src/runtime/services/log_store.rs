@@ -1,4 +1,5 @@
 use crate::imports::*;
+use crate::runtime::services::log_hub::{LogLevel, LogSource, log_hub};
 use serde::Serialize;
 use std::collections::VecDeque;
 
@@ -10,15 +11,19 @@ pub struct LogLine {
     pub message: String,
 }
 
+/// Per-service buffer of recent lines served by the self-hosted API. Lines
+/// are also recorded in the shared [`log_hub`].
 #[derive(Clone)]
 pub struct LogStore {
+    source: LogSource,
     capacity: usize,
     lines: Arc<Mutex<VecDeque<LogLine>>>,
 }
 
 impl LogStore {
-    pub fn new(capacity: usize) -> Self {
+    pub fn new(source: LogSource, capacity: usize) -> Self {
         Self {
+            source,
             capacity,
             lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
         }
@@ -26,6 +31,7 @@ impl LogStore {
 
     pub fn push(&self, level: &str, message: &str) {
         let message = strip_ansi_codes(message);
+        log_hub().push(self.source, LogLevel::parse(level), message.as_str());
         let line = LogLine {
             ts: chrono::Utc::now().to_rfc3339(),
             level: level.to_string(),
@@ -15,6 +15,13 @@ use std::collections::hash_map::Entry;
 //     }
 //   }
 
+// https://api.coingecko.com/api/v3/coins/kaspa/market_chart/range?vs_currency=usd&from=1704067200&to=1706745600
+// {
+//     "prices": [[1704067200000, 0.0927], [1704153600000, 0.1053]],
+//     "market_caps": [[1704067200000, 2058372711.31]],
+//     "total_volumes": [[1704067200000, 38517064.24]]
+// }
+
 // https://api.coingecko.com/api/v3/coins/list
 // [
 //   {
@@ -53,6 +60,23 @@ impl From<CoinGeckoSimplePrice> for MarketDataMap {
     }
 }
 
+#[derive(Default, Debug, Serialize, Deserialize)]
+struct CoinGeckoMarketChart {
+    prices: Vec<(f64, f64)>,
+}
+
+impl CoinGeckoMarketChart {
+    pub async fn get(currency: &str, from_msec: u64, to_msec: u64) -> Result<Self> {
+        let currency = currency.to_lowercase();
+        let from = from_msec / 1000;
+        let to = to_msec.div_ceil(1000);
+        let url = format!(
+            "https://api.coingecko.com/api/v3/coins/kaspa/market_chart/range?vs_currency={currency}&from={from}&to={to}"
+        );
+        Ok(http::get_json::<Self>(url).await?)
+    }
+}
+
 pub async fn fetch_available_currencies() -> Result<CurrencyDescriptorList> {
     let url = "https://api.coingecko.com/api/v3/coins/list";
     let available_currencies = http::get_json::<CurrencyDescriptorList>(url).await?;
@@ -66,6 +90,46 @@ pub async fn fetch_market_price_list(currencies: &[&str]) -> Result<MarketDataMa
     Ok(market_data.into())
 }
 
+/// Length of the time range requested at once; CoinGecko returns hourly
+/// prices for ranges of up to 90 days and limits the range per request.
+const PRICE_HISTORY_CHUNK_MSEC: u64 = 90 * 24 * 60 * 60 * 1000;
+
+/// Fetches the price history in chunks of [`PRICE_HISTORY_CHUNK_MSEC`].
+/// Chunks that can not be fetched are left out of the history; an error
+/// is returned only if no chunk could be fetched.
+pub async fn fetch_price_history(
+    currency: &str,
+    from_msec: u64,
+    to_msec: u64,
+) -> Result<PriceHistory> {
+    let mut prices = Vec::new();
+    let mut error = None;
+    let mut chunk_from = from_msec;
+    while chunk_from < to_msec {
+        let chunk_to = (chunk_from + PRICE_HISTORY_CHUNK_MSEC).min(to_msec);
+        match CoinGeckoMarketChart::get(currency, chunk_from, chunk_to).await {
+            Ok(chart) => prices.extend(
+                chart
+                    .prices
+                    .into_iter()
+                    .map(|(unixtime_msec, price)| (unixtime_msec as u64, price)),
+            ),
+            Err(err) => {
+                log_warn!(
+                    "Unable to fetch {currency} prices from {chunk_from} to {chunk_to}: {err}"
+                );
+                error = Some(err);
+            }
+        }
+        chunk_from = chunk_to;
+    }
+
+    match error {
+        Some(err) if prices.is_empty() => Err(err),
+        _ => Ok(PriceHistory::new(currency, prices)),
+    }
+}
+
 fn group_by_currency_prefix(data: &AHashMap<String, f64>) -> MarketDataMap {
     let mut grouped_data: MarketDataMap = AHashMap::new();
 
@@ -43,6 +43,16 @@ pub async fn fetch_market_price_list(currencies: &[&str]) -> Result<MarketDataMa
     Ok(market_data.into())
 }
 
+pub async fn fetch_price_history(
+    currency: &str,
+    from_msec: u64,
+    to_msec: u64,
+) -> Result<PriceHistory> {
+    // historical quotes are not available on the free
+    // CoinMarketCap plan, fall back to CoinGecko
+    super::coingecko::fetch_price_history(currency, from_msec, to_msec).await
+}
+
 fn group_by_currency_prefix(data: &AHashMap<String, f64>) -> MarketDataMap {
     let mut grouped_data: MarketDataMap = AHashMap::new();
 
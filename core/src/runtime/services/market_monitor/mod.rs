@@ -29,6 +29,20 @@ impl MarketDataProvider {
             Self::CoinMarketCap => coinmarketcap::fetch_market_price_list(currencies).await,
         }
     }
+
+    async fn fetch_price_history(
+        &self,
+        currency: &str,
+        from_msec: u64,
+        to_msec: u64,
+    ) -> Result<PriceHistory> {
+        match self {
+            Self::CoinGecko => coingecko::fetch_price_history(currency, from_msec, to_msec).await,
+            Self::CoinMarketCap => {
+                coinmarketcap::fetch_price_history(currency, from_msec, to_msec).await
+            }
+        }
+    }
 }
 
 #[derive(Default, Serialize, Deserialize)]
@@ -114,6 +128,19 @@ impl MarketMonitorService {
         Ok(())
     }
 
+    /// Fetches KAS prices in the given currency for the time range,
+    /// used to value past transactions.
+    pub async fn fetch_price_history(
+        &self,
+        currency: &str,
+        from_msec: u64,
+        to_msec: u64,
+    ) -> Result<PriceHistory> {
+        self.provider()
+            .fetch_price_history(currency, from_msec, to_msec)
+            .await
+    }
+
     async fn update_market_price_list(&self) -> Result<()> {
         if let Some(currencies) = self.currencies() {
             let currencies = currencies.iter().map(String::as_str).collect::<Vec<_>>();
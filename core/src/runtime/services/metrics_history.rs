@@ -0,0 +1,527 @@
+//! On-disk history of node metrics. Samples are kept per network in three
+//! tiers: one-second samples for a few hours, one-minute averages for a
+//! month and one-hour averages for a year. Each tier is an append-only CSV
+//! file (`<storage>/metrics/<network>/<tier>.csv`) that is compacted when
+//! loaded and periodically while running. Recording samples only produces
+//! [`HistoryWrites`]; the files are written by the metrics service off the
+//! runtime thread.
+
+use crate::imports::*;
+use crate::settings::network_profile_slug;
+use kaspa_metrics_core::{Metric, MetricsSnapshot};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+const MSEC_PER_SECOND: f64 = 1000.0;
+const MSEC_PER_MINUTE: f64 = 60.0 * MSEC_PER_SECOND;
+const MSEC_PER_HOUR: f64 = 60.0 * MSEC_PER_MINUTE;
+const MSEC_PER_DAY: f64 = 24.0 * MSEC_PER_HOUR;
+
+/// Number of pending one-second rows after which the tiers are written.
+const FLUSH_ROWS: usize = 30;
+/// Interval at which expired rows are removed from the files.
+const COMPACTION_INTERVAL_MSEC: f64 = MSEC_PER_HOUR;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricsTier {
+    Second,
+    Minute,
+    Hour,
+}
+
+impl MetricsTier {
+    pub fn iter() -> [MetricsTier; 3] {
+        [MetricsTier::Second, MetricsTier::Minute, MetricsTier::Hour]
+    }
+
+    pub fn resolution_msec(&self) -> f64 {
+        match self {
+            MetricsTier::Second => MSEC_PER_SECOND,
+            MetricsTier::Minute => MSEC_PER_MINUTE,
+            MetricsTier::Hour => MSEC_PER_HOUR,
+        }
+    }
+
+    pub fn retention_msec(&self) -> f64 {
+        match self {
+            MetricsTier::Second => 6.0 * MSEC_PER_HOUR,
+            MetricsTier::Minute => 30.0 * MSEC_PER_DAY,
+            MetricsTier::Hour => 365.0 * MSEC_PER_DAY,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MetricsTier::Second => "second",
+            MetricsTier::Minute => "minute",
+            MetricsTier::Hour => "hour",
+        }
+    }
+
+    /// The finest tier that still retains samples `age_msec` old.
+    pub fn for_age(age_msec: f64) -> MetricsTier {
+        Self::iter()
+            .into_iter()
+            .find(|tier| age_msec <= tier.retention_msec())
+            .unwrap_or(MetricsTier::Hour)
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            MetricsTier::Second => 0,
+            MetricsTier::Minute => 1,
+            MetricsTier::Hour => 2,
+        }
+    }
+}
+
+type Row = (f64, Vec<f64>);
+
+/// Averages the rows of a time bucket; a row is returned once a sample
+/// of the following bucket arrives.
+#[derive(Default)]
+struct Accumulator {
+    bucket: Option<f64>,
+    sums: Vec<f64>,
+    count: usize,
+}
+
+impl Accumulator {
+    fn push(&mut self, resolution_msec: f64, timestamp: f64, values: &[f64]) -> Option<Row> {
+        let bucket = (timestamp / resolution_msec).floor() * resolution_msec;
+        let completed = match self.bucket {
+            Some(current) if current != bucket => self.take(),
+            _ => None,
+        };
+
+        if self.bucket.is_none() {
+            self.bucket = Some(bucket);
+            self.sums = vec![0.0; values.len()];
+        }
+        self.sums
+            .iter_mut()
+            .zip(values.iter())
+            .for_each(|(sum, value)| *sum += value);
+        self.count += 1;
+
+        completed
+    }
+
+    fn take(&mut self) -> Option<Row> {
+        let bucket = self.bucket.take()?;
+        let count = std::mem::take(&mut self.count).max(1) as f64;
+        let values = std::mem::take(&mut self.sums)
+            .into_iter()
+            .map(|sum| sum / count)
+            .collect();
+        Some((bucket, values))
+    }
+}
+
+struct Tier {
+    tier: MetricsTier,
+    rows: VecDeque<Row>,
+    pending: Vec<Row>,
+}
+
+impl Tier {
+    fn new(tier: MetricsTier) -> Self {
+        Self {
+            tier,
+            rows: VecDeque::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, row: Row) {
+        self.rows.push_back(row.clone());
+        self.pending.push(row);
+    }
+
+    /// Drops in-memory rows older than the retention period; returns
+    /// `true` if any row has been removed.
+    fn expire(&mut self, now: f64) -> bool {
+        let cutoff = now - self.tier.retention_msec();
+        let len = self.rows.len();
+        while self
+            .rows
+            .front()
+            .is_some_and(|(timestamp, _)| *timestamp < cutoff)
+        {
+            self.rows.pop_front();
+        }
+        self.rows.len() != len
+    }
+}
+
+/// Appends rows to a tier file or replaces its content.
+struct TierWrite {
+    filename: PathBuf,
+    rows: Vec<Row>,
+    append: bool,
+}
+
+impl TierWrite {
+    fn write(&self, header: &str) -> Result<()> {
+        let exists = self.append && self.filename.exists();
+        let file = if self.append {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.filename)?
+        } else {
+            File::create(&self.filename)?
+        };
+
+        let mut writer = BufWriter::new(file);
+        if !exists {
+            writeln!(writer, "{header}")?;
+        }
+        for row in self.rows.iter() {
+            writeln!(writer, "{}", format_row(row))?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Pending file writes of a [`MetricsHistory`].
+#[derive(Default)]
+pub struct HistoryWrites {
+    header: String,
+    writes: Vec<TierWrite>,
+}
+
+impl HistoryWrites {
+    fn new(header: String) -> Self {
+        Self {
+            header,
+            writes: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Performs the writes in order. This does blocking file I/O.
+    pub fn write(self) -> Result<()> {
+        for write in self.writes.iter() {
+            write.write(&self.header)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct MetricsHistory {
+    folder: PathBuf,
+    metrics: Vec<Metric>,
+    tiers: [Tier; 3],
+    accumulators: [Accumulator; 2],
+    last_compaction: f64,
+}
+
+impl MetricsHistory {
+    pub fn folder(network: Network) -> Result<PathBuf> {
+        let storage_folder = kaspa_wallet_core::storage::local::default_storage_folder();
+        let storage_folder = workflow_store::fs::resolve_path(storage_folder)?;
+        Ok(storage_folder
+            .join("metrics")
+            .join(network_profile_slug(network)))
+    }
+
+    fn new(folder: PathBuf) -> Self {
+        Self {
+            folder,
+            metrics: Metric::into_iter().collect(),
+            tiers: MetricsTier::iter().map(Tier::new),
+            accumulators: Default::default(),
+            last_compaction: 0.0,
+        }
+    }
+
+    /// Loads the history of the network, dropping expired samples. This
+    /// does blocking file I/O.
+    pub fn load(network: Network) -> Result<Self> {
+        let mut history = Self::new(Self::folder(network)?);
+        std::fs::create_dir_all(&history.folder)?;
+
+        let now = unixtime_as_millis_f64();
+        for tier in MetricsTier::iter() {
+            let (rows, rewrite) = history.read_tier(tier)?;
+            let data = &mut history.tiers[tier.index()];
+            data.rows = rows.into();
+            if data.expire(now) || rewrite {
+                history.rewrite_tier(tier).write(&history.header())?;
+            }
+        }
+        history.last_compaction = now;
+
+        Ok(history)
+    }
+
+    fn filename(&self, tier: MetricsTier) -> PathBuf {
+        self.folder.join(format!("{}.csv", tier.name()))
+    }
+
+    fn header(&self) -> String {
+        let mut header = vec!["timestamp"];
+        header.extend(self.metrics.iter().map(|metric| metric.as_str()));
+        header.join(",")
+    }
+
+    /// Reads a tier file, mapping its columns to the current metrics.
+    /// Returns the rows and whether the file must be rewritten because
+    /// its columns differ from the current set of metrics.
+    fn read_tier(&self, tier: MetricsTier) -> Result<(Vec<Row>, bool)> {
+        let filename = self.filename(tier);
+        if !filename.exists() {
+            return Ok((Vec::new(), false));
+        }
+
+        let mut lines = BufReader::new(File::open(&filename)?).lines();
+        let Some(header) = lines.next().transpose()? else {
+            return Ok((Vec::new(), false));
+        };
+        let columns = header
+            .split(',')
+            .skip(1)
+            .map(|name| {
+                self.metrics
+                    .iter()
+                    .position(|metric| metric.as_str() == name)
+            })
+            .collect::<Vec<_>>();
+        let rewrite = header != self.header();
+
+        let mut rows = Vec::new();
+        for line in lines {
+            let line = line?;
+            let mut fields = line.split(',');
+            let Some(Ok(timestamp)) = fields.next().map(str::parse::<f64>) else {
+                continue;
+            };
+            let mut values = vec![0.0; self.metrics.len()];
+            for (column, field) in columns.iter().zip(fields) {
+                if let (Some(index), Ok(value)) = (column, field.parse::<f64>()) {
+                    values[*index] = value;
+                }
+            }
+            rows.push((timestamp, values));
+        }
+
+        Ok((rows, rewrite))
+    }
+
+    fn rewrite_tier(&self, tier: MetricsTier) -> TierWrite {
+        TierWrite {
+            filename: self.filename(tier),
+            rows: self.tiers[tier.index()].rows.iter().cloned().collect(),
+            append: false,
+        }
+    }
+
+    /// Records a metrics snapshot, downsampling it into the minute and
+    /// hour tiers. Returns the file writes that are due.
+    pub fn ingest(&mut self, snapshot: &MetricsSnapshot) -> HistoryWrites {
+        let timestamp = snapshot.unixtime_millis;
+        let values = self
+            .metrics
+            .iter()
+            .map(|metric| {
+                let value = snapshot.get(metric);
+                if value.is_finite() { value } else { 0.0 }
+            })
+            .collect::<Vec<_>>();
+
+        self.tiers[MetricsTier::Second.index()].push((timestamp, values.clone()));
+        if let Some(minute) = self.accumulators[0].push(MSEC_PER_MINUTE, timestamp, &values) {
+            if let Some(hour) = self.accumulators[1].push(MSEC_PER_HOUR, minute.0, &minute.1) {
+                self.tiers[MetricsTier::Hour.index()].push(hour);
+            }
+            self.tiers[MetricsTier::Minute.index()].push(minute);
+        }
+
+        if timestamp - self.last_compaction > COMPACTION_INTERVAL_MSEC {
+            self.compact(timestamp)
+        } else if self.tiers[MetricsTier::Second.index()].pending.len() >= FLUSH_ROWS {
+            self.flush()
+        } else {
+            HistoryWrites::default()
+        }
+    }
+
+    /// Takes the pending rows of every tier as appends to its file.
+    pub fn flush(&mut self) -> HistoryWrites {
+        let mut writes = HistoryWrites::new(self.header());
+        for tier in MetricsTier::iter() {
+            let filename = self.filename(tier);
+            let data = &mut self.tiers[tier.index()];
+            if data.pending.is_empty() {
+                continue;
+            }
+
+            writes.writes.push(TierWrite {
+                filename,
+                rows: data.pending.drain(..).collect(),
+                append: true,
+            });
+        }
+        writes
+    }
+
+    fn compact(&mut self, now: f64) -> HistoryWrites {
+        let mut writes = self.flush();
+        for tier in MetricsTier::iter() {
+            if self.tiers[tier.index()].expire(now) {
+                writes.writes.push(self.rewrite_tier(tier));
+            }
+        }
+        self.last_compaction = now;
+        writes
+    }
+
+    /// Samples of a metric between `from` and `to` (unix time in msec),
+    /// thinned out to at most `max_points` points by averaging.
+    pub fn series(
+        &self,
+        tier: MetricsTier,
+        metric: Metric,
+        from: f64,
+        to: f64,
+        max_points: usize,
+    ) -> Vec<PlotPoint> {
+        let Some(index) = self.metrics.iter().position(|m| *m == metric) else {
+            return Vec::new();
+        };
+
+        let points = self.tiers[tier.index()]
+            .rows
+            .iter()
+            .filter(|(timestamp, _)| *timestamp >= from && *timestamp <= to)
+            .map(|(timestamp, values)| PlotPoint {
+                x: *timestamp,
+                y: values[index],
+            })
+            .collect::<Vec<_>>();
+        thin_out(points, max_points)
+    }
+
+    /// Copies all metrics of a tier between `from` and `to` (unix time in
+    /// msec) for export.
+    pub fn export(&self, tier: MetricsTier, from: f64, to: f64) -> MetricsExport {
+        let rows = self.tiers[tier.index()]
+            .rows
+            .iter()
+            .filter(|(timestamp, _)| *timestamp >= from && *timestamp <= to)
+            .cloned()
+            .collect();
+        MetricsExport {
+            metrics: self.metrics.clone(),
+            rows,
+        }
+    }
+}
+
+/// Metrics history rows copied out of [`MetricsHistory`] to be written
+/// without holding the history lock.
+pub struct MetricsExport {
+    metrics: Vec<Metric>,
+    rows: Vec<Row>,
+}
+
+impl MetricsExport {
+    /// Writes the rows as CSV; returns the number of rows written.
+    pub fn write_csv(&self, path: &Path) -> Result<usize> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let mut header = vec!["timestamp", "unixtime_msec"];
+        header.extend(self.metrics.iter().map(|metric| metric.as_str()));
+        writeln!(writer, "{}", header.join(","))?;
+
+        for row in self.rows.iter() {
+            let datetime = chrono::DateTime::<chrono::Utc>::from_timestamp_millis(row.0 as i64)
+                .map(|datetime| datetime.to_rfc3339())
+                .unwrap_or_default();
+            writeln!(writer, "{datetime},{}", format_row(row))?;
+        }
+        writer.flush()?;
+
+        Ok(self.rows.len())
+    }
+}
+
+fn format_row((timestamp, values): &Row) -> String {
+    let mut line = format!("{timestamp}");
+    for value in values.iter() {
+        line.push(',');
+        line.push_str(&value.to_string());
+    }
+    line
+}
+
+/// Reduces a series to at most `max_points` points by averaging
+/// consecutive samples.
+fn thin_out(points: Vec<PlotPoint>, max_points: usize) -> Vec<PlotPoint> {
+    if max_points == 0 || points.len() <= max_points {
+        return points;
+    }
+
+    let chunk = points.len().div_ceil(max_points);
+    points
+        .chunks(chunk)
+        .map(|chunk| {
+            let len = chunk.len() as f64;
+            PlotPoint {
+                x: chunk.iter().map(|point| point.x).sum::<f64>() / len,
+                y: chunk.iter().map(|point| point.y).sum::<f64>() / len,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_are_averaged_per_bucket() {
+        let mut accumulator = Accumulator::default();
+        assert!(
+            accumulator
+                .push(MSEC_PER_MINUTE, 0.0, &[1.0, 10.0])
+                .is_none()
+        );
+        assert!(
+            accumulator
+                .push(MSEC_PER_MINUTE, 59_000.0, &[3.0, 20.0])
+                .is_none()
+        );
+
+        let (bucket, values) = accumulator
+            .push(MSEC_PER_MINUTE, 60_000.0, &[5.0, 0.0])
+            .unwrap();
+        assert_eq!(bucket, 0.0);
+        assert_eq!(values, vec![2.0, 15.0]);
+        assert_eq!(accumulator.take(), Some((60_000.0, vec![5.0, 0.0])));
+
+        let points = (0..10)
+            .map(|x| PlotPoint {
+                x: x as f64,
+                y: x as f64,
+            })
+            .collect();
+        let points = thin_out(points, 5);
+        assert_eq!(points.len(), 5);
+        assert_eq!(points[0].y, 0.5);
+
+        assert_eq!(MetricsTier::for_age(MSEC_PER_HOUR), MetricsTier::Second);
+        assert_eq!(
+            MetricsTier::for_age(7.0 * MSEC_PER_DAY),
+            MetricsTier::Minute
+        );
+        assert_eq!(
+            MetricsTier::for_age(400.0 * MSEC_PER_DAY),
+            MetricsTier::Hour
+        );
+    }
+}
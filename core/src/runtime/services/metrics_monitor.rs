@@ -1,5 +1,7 @@
 use crate::imports::*;
 use crate::runtime::Service;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::runtime::services::{HistoryWrites, MetricsHistory, MetricsTier};
 pub use futures::{Future, future::FutureExt, select};
 use kaspa_metrics_core::{Metric, Metrics, MetricsSnapshot};
 use kaspa_rpc_core::GetSystemInfoResponse;
@@ -9,13 +11,25 @@ use kaspa_wallet_core::rpc::{NotificationMode, Rpc, RpcCtl, WrpcEncoding};
 #[allow(clippy::identity_op)]
 pub const MAX_METRICS_SAMPLES: usize = 60 * 60 * 24 * 1; // 1 day
 
+/// Metrics history file operations, performed in order by the service task.
+#[cfg(not(target_arch = "wasm32"))]
+pub enum MetricsServiceEvents {
+    LoadHistory(Network),
+    WriteHistory(HistoryWrites),
+    Exit,
+}
+
 pub struct MetricsService {
     pub application_events: ApplicationEventsChannel,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub service_events: Channel<MetricsServiceEvents>,
     pub task_ctl: Channel<()>,
     pub metrics: Arc<Metrics>,
     pub metrics_data: Mutex<HashMap<Metric, Vec<PlotPoint>>>,
     pub samples_since_connection: Arc<AtomicUsize>,
     pub rpc_api: Mutex<Option<Arc<dyn RpcApi>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub metrics_history: Mutex<Option<MetricsHistory>>,
 }
 
 impl MetricsService {
@@ -27,11 +41,15 @@ impl MetricsService {
 
         Self {
             application_events,
+            #[cfg(not(target_arch = "wasm32"))]
+            service_events: Channel::unbounded(),
             task_ctl: Channel::oneshot(),
             metrics,
             metrics_data: Mutex::new(metrics_data),
             samples_since_connection: Arc::new(AtomicUsize::new(0)),
             rpc_api: Mutex::new(None),
+            #[cfg(not(target_arch = "wasm32"))]
+            metrics_history: Mutex::new(None),
         }
     }
 
@@ -57,6 +75,17 @@ impl MetricsService {
 
     pub fn ingest_metrics_snapshot(&self, snapshot: Box<MetricsSnapshot>) -> Result<()> {
         let timestamp = snapshot.unixtime_millis;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if snapshot.duration_millis >= 0.0 {
+            if let Some(history) = self.metrics_history.lock().unwrap().as_mut() {
+                let writes = history.ingest(&snapshot);
+                if !writes.is_empty() {
+                    self.write_metrics_history(writes);
+                }
+            }
+        }
+
         let mut metrics_data = self.metrics_data.lock().unwrap();
         for metric in Metric::into_iter() {
             let dest = metrics_data.get_mut(&metric).unwrap();
@@ -88,6 +117,10 @@ impl MetricsService {
         }
 
         if snapshot.node_cpu_cores > 0.0 {
+            if let Some(runtime) = crate::runtime::try_runtime() {
+                runtime.alert_monitor_service().metrics(&snapshot);
+            }
+
             self.application_events
                 .sender
                 .try_send(crate::events::Events::MempoolSize {
@@ -106,9 +139,95 @@ impl MetricsService {
         Ok(())
     }
 
+    /// On-disk metrics history of the connected network.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn metrics_history(&self) -> MutexGuard<'_, Option<MetricsHistory>> {
+        self.metrics_history.lock().unwrap()
+    }
+
+    /// Installs the loaded metrics history and restores its recent
+    /// one-second samples ahead of the samples received since connecting.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn install_metrics_history(&self, history: MetricsHistory) {
+        let samples = self.samples_since_connection();
+        let mut metrics_data = self.metrics_data.lock().unwrap();
+        for metric in Metric::into_iter() {
+            let dest = metrics_data.get_mut(&metric).unwrap();
+            let live = dest.split_off(dest.len().saturating_sub(samples));
+            let start = live
+                .first()
+                .map(|point| point.x)
+                .unwrap_or_else(unixtime_as_millis_f64);
+            let mut restored = history.series(
+                MetricsTier::Second,
+                metric,
+                start - MAX_METRICS_SAMPLES as f64 * 1000.0,
+                start - 1.0,
+                0,
+            );
+            if restored.is_empty() {
+                dest.extend(live);
+            } else {
+                restored.extend(live);
+                *dest = restored;
+            }
+        }
+        drop(metrics_data);
+
+        self.metrics_history.lock().unwrap().replace(history);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn write_metrics_history(&self, writes: HistoryWrites) {
+        if let Err(err) = self
+            .service_events
+            .sender
+            .try_send(MetricsServiceEvents::WriteHistory(writes))
+        {
+            log_warn!("Unable to store metrics history: {err}");
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn close_metrics_history(&self) {
+        let history = self.metrics_history.lock().unwrap().take();
+        if let Some(mut history) = history {
+            self.write_metrics_history(history.flush());
+        }
+    }
+
+    /// Runs blocking history file I/O off the runtime thread.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn history_io<T: Send + 'static>(
+        f: impl FnOnce() -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        tokio::task::spawn_blocking(f)
+            .await
+            .map_err(|err| Error::custom(err.to_string()))?
+    }
+
     pub fn samples_since_connection(&self) -> usize {
         self.samples_since_connection.load(Ordering::SeqCst)
     }
+
+    /// Most recent value of every metric, or `None` if no snapshot
+    /// has been received since the node connection was established.
+    pub fn latest_values(&self) -> Option<Vec<(Metric, f64)>> {
+        if self.samples_since_connection() == 0 {
+            return None;
+        }
+
+        let metrics_data = self.metrics_data();
+        let values = Metric::into_iter()
+            .filter_map(|metric| {
+                metrics_data
+                    .get(&metric)
+                    .and_then(|points| points.last())
+                    .map(|point| (metric, point.y))
+            })
+            .collect();
+        Some(values)
+    }
 }
 
 #[async_trait]
@@ -164,12 +283,28 @@ impl Service for MetricsService {
                     })
                     .unwrap();
             }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Ok(server_info) = rpc_api.get_server_info().await {
+                match Network::try_from_network_id(server_info.network_id) {
+                    Some(network) => {
+                        self.service_events
+                            .sender
+                            .try_send(MetricsServiceEvents::LoadHistory(network))
+                            .unwrap();
+                    }
+                    None => log_info!("Metrics history is not kept for {}", server_info.network_id),
+                }
+            }
         }
 
         Ok(())
     }
 
     async fn disconnect_rpc(self: Arc<Self>) -> Result<()> {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.close_metrics_history();
+
         self.application_events
             .sender
             .try_send(crate::events::Events::NodeInfo { node_info: None })
@@ -178,12 +313,49 @@ impl Service for MetricsService {
     }
 
     async fn spawn(self: Arc<Self>) -> Result<()> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            while let Ok(event) = self.service_events.receiver.recv().await {
+                match event {
+                    MetricsServiceEvents::LoadHistory(network) => {
+                        match Self::history_io(move || MetricsHistory::load(network)).await {
+                            // the node may have disconnected while loading
+                            Ok(history) if self.rpc_api().is_some() => {
+                                self.install_metrics_history(history)
+                            }
+                            Ok(_) => {}
+                            Err(err) => log_warn!("Unable to load metrics history: {err}"),
+                        }
+                    }
+                    MetricsServiceEvents::WriteHistory(writes) => {
+                        if let Err(err) = Self::history_io(move || writes.write()).await {
+                            log_warn!("Unable to store metrics history: {err}");
+                        }
+                    }
+                    MetricsServiceEvents::Exit => break,
+                }
+            }
+
+            self.task_ctl.send(()).await.unwrap();
+        }
+
         Ok(())
     }
 
-    fn terminate(self: Arc<Self>) {}
+    fn terminate(self: Arc<Self>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.close_metrics_history();
+            self.service_events
+                .sender
+                .try_send(MetricsServiceEvents::Exit)
+                .unwrap();
+        }
+    }
 
     async fn join(self: Arc<Self>) -> Result<()> {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.task_ctl.recv().await.unwrap();
         Ok(())
     }
 }
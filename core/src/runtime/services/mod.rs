@@ -21,19 +21,57 @@ pub use update_monitor::UpdateMonitorService;
 pub mod metrics_monitor;
 pub use metrics_monitor::MetricsService;
 
+pub mod alert_monitor;
+pub use alert_monitor::AlertMonitorService;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod metrics_history;
+#[cfg(not(target_arch = "wasm32"))]
+pub use metrics_history::{HistoryWrites, MetricsExport, MetricsHistory, MetricsTier};
+
 pub mod stratum_bridge;
 pub use stratum_bridge::StratumBridgeService;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod node_client_process;
+
+pub mod cpu_miner;
+pub use cpu_miner::CpuMinerService;
+
+pub mod rothschild;
+pub use rothschild::RothschildService;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod control_api;
+#[cfg(not(target_arch = "wasm32"))]
+pub use control_api::ControlApiService;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod self_hosted_db;
 #[cfg(not(target_arch = "wasm32"))]
 pub use self_hosted_db::SelfHostedDbService;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod log_hub;
+#[cfg(not(target_arch = "wasm32"))]
+pub use log_hub::{LogFilter, LogLevel, LogRecord, LogSource, log_hub};
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod log_store;
 #[cfg(not(target_arch = "wasm32"))]
 pub use log_store::{LogStore, LogStores};
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod prometheus;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod process_supervisor;
+#[cfg(not(target_arch = "wasm32"))]
+pub use process_supervisor::{
+    HealthProbe, LogStoreOutput, ProcessOutput, ProcessStatus, ProcessSupervisor, RestartPolicy,
+    StopPolicy,
+};
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod self_hosted_indexer;
 #[cfg(not(target_arch = "wasm32"))]
@@ -54,11 +92,17 @@ pub mod self_hosted_kasia_indexer;
 #[cfg(not(target_arch = "wasm32"))]
 pub use self_hosted_kasia_indexer::SelfHostedKasiaIndexerService;
 
+pub mod loader_state;
+pub use loader_state::{
+    ComponentState, ComponentStatus, LoaderComponent, LoaderPhase, LoaderStatusSnapshot,
+    LoaderTransition,
+};
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod self_hosted_loader;
 #[cfg(not(target_arch = "wasm32"))]
 pub use self_hosted_loader::{
-    LoaderStatusSnapshot, SelfHostedLoaderService, SelfHostedLoaderServices, SharedLoaderStatus,
+    SelfHostedLoaderService, SelfHostedLoaderServices, SharedLoaderStatus,
 };
 
 #[cfg(not(target_arch = "wasm32"))]
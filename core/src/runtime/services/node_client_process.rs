@@ -0,0 +1,251 @@
+//! Lifecycle shared by the external processes that connect to the node over
+//! gRPC (`kaspa-miner`, `rothschild`): log forwarding, waiting for the node,
+//! start de-duplication, restarts and supervision.
+
+use crate::imports::*;
+use crate::runtime::services::log_hub::{LogSource, log_hub};
+use crate::runtime::services::process_supervisor::{
+    OutputStream, ProcessOutput, ProcessStatus, ProcessSupervisor, RestartPolicy, StopPolicy,
+};
+use tokio::net::TcpStream;
+use tokio::process::Command;
+use tokio::task::JoinHandle;
+
+const RESTART_POLICY: RestartPolicy =
+    RestartPolicy::backoff(Duration::from_secs(3), Duration::from_secs(60));
+
+/// A service owning a [`NodeClientProcess`].
+#[async_trait]
+pub trait NodeClientService: Send + Sync + 'static {
+    fn client(&self) -> &NodeClientProcess;
+
+    /// Starts the process unless it is already running or starting.
+    async fn start(self: Arc<Self>) -> Result<()>;
+}
+
+/// Forwards process output to the log hub and refreshes the log view.
+struct LogOutput {
+    source: LogSource,
+    update_logs_flag: &'static Arc<AtomicBool>,
+    application_events: ApplicationEventsChannel,
+}
+
+impl LogOutput {
+    async fn push(&self, line: String) {
+        log_hub().push_line(self.source, &line);
+
+        if self.update_logs_flag.load(Ordering::SeqCst) && crate::runtime::try_runtime().is_some() {
+            self.application_events
+                .sender
+                .send(Events::UpdateLogs)
+                .await
+                .unwrap();
+        }
+    }
+}
+
+#[async_trait]
+impl ProcessOutput for LogOutput {
+    async fn line(&self, _stream: OutputStream, line: String) {
+        self.push(line).await;
+    }
+
+    async fn notice(&self, _level: &'static str, message: String) {
+        self.push(message).await;
+    }
+}
+
+/// Clears the `starting` flag when a start attempt ends.
+pub struct StartGuard<'a>(&'a AtomicBool);
+
+impl Drop for StartGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+pub struct NodeClientProcess {
+    name: &'static str,
+    output: Arc<LogOutput>,
+    is_enabled: AtomicBool,
+    starting: AtomicBool,
+    restart_pending: AtomicBool,
+    start_task: Mutex<Option<JoinHandle<()>>>,
+    process: Arc<ProcessSupervisor>,
+}
+
+impl NodeClientProcess {
+    pub fn new(
+        name: &'static str,
+        source: LogSource,
+        update_logs_flag: &'static Arc<AtomicBool>,
+        application_events: ApplicationEventsChannel,
+        enabled: bool,
+    ) -> Self {
+        Self {
+            name,
+            output: Arc::new(LogOutput {
+                source,
+                update_logs_flag,
+                application_events,
+            }),
+            is_enabled: AtomicBool::new(enabled),
+            starting: AtomicBool::new(false),
+            restart_pending: AtomicBool::new(false),
+            start_task: Mutex::new(None),
+            process: Arc::new(
+                ProcessSupervisor::new(name)
+                    .with_restart_policy(RESTART_POLICY)
+                    .with_stop_policy(StopPolicy::Kill),
+            ),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled.load(Ordering::SeqCst)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.is_enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    pub fn process_status(&self) -> Vec<ProcessStatus> {
+        vec![self.process.status()]
+    }
+
+    pub async fn update_logs(&self, line: String) {
+        self.output.push(line).await;
+    }
+
+    /// Returns a guard for a new start attempt, or `None` if the process is
+    /// already running or being started.
+    pub fn begin_start(&self) -> Option<StartGuard<'_>> {
+        if self.process.has_child() || self.starting.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+        Some(StartGuard(&self.starting))
+    }
+
+    /// Waits until the node accepts connections at `address`. Returns
+    /// `false` if the process is disabled in the meantime.
+    pub async fn wait_for_grpc(&self, address: &str) -> bool {
+        let mut attempts: u32 = 0;
+        loop {
+            if !self.is_enabled() {
+                return false;
+            }
+
+            match TcpStream::connect(address).await {
+                Ok(_) => return self.is_enabled(),
+                Err(_) => {
+                    if attempts == 0 {
+                        self.update_logs(format!(
+                            "{}: waiting for Kaspa node gRPC at {}",
+                            self.name, address
+                        ))
+                        .await;
+                    } else if attempts % 10 == 0 {
+                        self.update_logs(format!(
+                            "{}: still waiting for Kaspa node gRPC at {}",
+                            self.name, address
+                        ))
+                        .await;
+                    }
+                    attempts = attempts.saturating_add(1);
+                    task::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    }
+
+    pub fn record_failure(&self) {
+        self.process.record_failure();
+    }
+
+    pub fn spawn(&self, cmd: Command) -> Result<u32> {
+        self.process.spawn(cmd, self.output.clone())
+    }
+
+    /// Starts the process in a separate task so that waiting for the node
+    /// does not block the service event loop.
+    pub fn launch<S: NodeClientService>(&self, service: &Arc<S>) {
+        let mut start_task = self.start_task.lock().unwrap();
+        if start_task
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+        {
+            return;
+        }
+
+        let service = Arc::clone(service);
+        *start_task = Some(tokio::spawn(async move {
+            let _ = service.start().await;
+        }));
+    }
+
+    async fn cancel_launch(&self) {
+        let start_task = self.start_task.lock().unwrap().take();
+        if let Some(handle) = start_task {
+            handle.abort();
+            let _ = handle.await;
+        }
+    }
+
+    /// Retries a failed start according to the restart policy.
+    pub fn schedule_restart<S: NodeClientService>(&self, service: &Arc<S>, reason: &str) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        if self.restart_pending.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let service = Arc::clone(service);
+        let reason = reason.to_string();
+        tokio::spawn(async move {
+            let this = service.client();
+            let Some(delay) = this.process.retry_delay() else {
+                this.update_logs(format!(
+                    "{}: {reason}; giving up after {} attempts",
+                    this.name,
+                    this.process.failures()
+                ))
+                .await;
+                this.restart_pending.store(false, Ordering::SeqCst);
+                return;
+            };
+            this.update_logs(format!(
+                "{}: {reason}; restarting in {}s",
+                this.name,
+                delay.as_secs().max(1)
+            ))
+            .await;
+            task::sleep(delay).await;
+            this.restart_pending.store(false, Ordering::SeqCst);
+            if this.is_enabled() {
+                let _ = service.clone().start().await;
+            }
+        });
+    }
+
+    /// Restarts the process according to the restart policy when it exits.
+    pub fn supervise<S: NodeClientService>(&self, service: &Arc<S>) {
+        let this = Arc::clone(service);
+        let is_enabled = Arc::clone(service);
+        self.process.monitor(
+            move || is_enabled.client().is_enabled(),
+            move || async move {
+                let _ = this.start().await;
+            },
+        );
+    }
+
+    /// Cancels a pending start and stops the process. Returns `true` if a
+    /// running process was stopped.
+    pub async fn stop(&self) -> bool {
+        self.cancel_launch().await;
+        self.process.reset();
+        self.process.stop().await
+    }
+}
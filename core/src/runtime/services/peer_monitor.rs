@@ -2,6 +2,8 @@ use crate::imports::*;
 use kaspa_rpc_core::RpcPeerInfo;
 
 pub const PEER_POLLING_INTERVAL_SECONDS: u64 = 1; // 1 sec
+/// Number of ping samples kept per peer (one per polling interval).
+pub const PEER_PING_HISTORY_LENGTH: usize = 120;
 
 pub enum PeerMonitorEvents {
     Enable,
@@ -15,17 +17,21 @@ pub struct PeerMonitorService {
     pub task_ctl: Channel<()>,
     pub rpc_api: Mutex<Option<Arc<dyn RpcApi>>>,
     pub peer_info: Mutex<Option<Arc<Vec<RpcPeerInfo>>>>,
+    pub ping_history: Mutex<HashMap<String, VecDeque<u64>>>,
+    pub banned_peers: Mutex<Vec<String>>,
     pub is_enabled: Arc<AtomicBool>,
 }
 
 impl PeerMonitorService {
-    pub fn new(application_events: ApplicationEventsChannel, _settings: &Settings) -> Self {
+    pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
         Self {
             application_events,
             service_events: Channel::unbounded(),
             task_ctl: Channel::oneshot(),
             rpc_api: Mutex::new(None),
             peer_info: Mutex::new(None),
+            ping_history: Mutex::new(HashMap::new()),
+            banned_peers: Mutex::new(Self::banned_peers_for(&settings.node)),
             is_enabled: Arc::new(AtomicBool::new(false)),
         }
     }
@@ -38,6 +44,41 @@ impl PeerMonitorService {
         self.peer_info.lock().unwrap().clone()
     }
 
+    /// Recent ping durations (msec) of a peer, oldest first.
+    pub fn ping_history(&self, peer_id: &str) -> Vec<u64> {
+        self.ping_history
+            .lock()
+            .unwrap()
+            .get(peer_id)
+            .map(|history| history.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    fn banned_peers_for(node_settings: &NodeSettings) -> Vec<String> {
+        if node_settings.node_kind.is_local() {
+            node_settings.peers.banned.clone()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Updates the ban list applied to the local node when it connects.
+    pub fn update_node_settings(&self, node_settings: &NodeSettings) {
+        *self.banned_peers.lock().unwrap() = Self::banned_peers_for(node_settings);
+    }
+
+    fn update_ping_history(&self, peers: &[RpcPeerInfo]) {
+        let mut ping_history = self.ping_history.lock().unwrap();
+        ping_history.retain(|id, _| peers.iter().any(|peer| peer.id.to_string() == *id));
+        for peer in peers.iter() {
+            let history = ping_history.entry(peer.id.to_string()).or_default();
+            history.push_back(peer.last_ping_duration);
+            while history.len() > PEER_PING_HISTORY_LENGTH {
+                history.pop_front();
+            }
+        }
+    }
+
     pub fn enable(&self) {
         self.service_events
             .sender
@@ -61,12 +102,32 @@ impl Service for PeerMonitorService {
 
     async fn attach_rpc(self: Arc<Self>, rpc_api: &Arc<dyn RpcApi>) -> Result<()> {
         self.rpc_api.lock().unwrap().replace(rpc_api.clone());
+
+        let banned_peers = self.banned_peers.lock().unwrap().clone();
+        if !banned_peers.is_empty() {
+            let rpc_api = rpc_api.clone();
+            spawn(async move {
+                for ip in banned_peers {
+                    match PeerSettings::parse_ip(&ip) {
+                        Ok(addr) => {
+                            if let Err(err) = rpc_api.ban(addr.into()).await {
+                                log_warn!("Unable to ban peer {ip}: {err}");
+                            }
+                        }
+                        Err(err) => log_warn!("{err}"),
+                    }
+                }
+                Ok(())
+            });
+        }
+
         Ok(())
     }
 
     async fn detach_rpc(self: Arc<Self>) -> Result<()> {
         self.rpc_api.lock().unwrap().take();
         self.peer_info.lock().unwrap().take();
+        self.ping_history.lock().unwrap().clear();
 
         Ok(())
     }
@@ -87,6 +148,7 @@ impl Service for PeerMonitorService {
 
                     if let Some(rpc_api) = this.rpc_api() {
                         if let Ok(resp) = rpc_api.get_connected_peer_info().await {
+                            this.update_ping_history(&resp.peer_info);
                             this.peer_info.lock().unwrap().replace(Arc::new(resp.peer_info));
                         }
                     }
@@ -100,6 +162,7 @@ impl Service for PeerMonitorService {
                             PeerMonitorEvents::Disable => {
                                 self.is_enabled.store(false, Ordering::Relaxed);
                                 this.peer_info.lock().unwrap().take();
+                                this.ping_history.lock().unwrap().clear();
                             }
                             PeerMonitorEvents::Exit => {
                                 break;
@@ -0,0 +1,1075 @@
+//! Shared supervision of external processes (node companions, indexers,
+//! explorer backends, postgres, miners) spawned by the runtime services.
+//!
+//! A [`ProcessSupervisor`] owns a single child process and applies a
+//! declarative [`RestartPolicy`], [`StopPolicy`] and optional [`HealthProbe`]
+//! to it. Services keep their own settings handling and only hand the
+//! supervisor a prepared [`Command`] together with a [`ProcessOutput`] sink.
+
+use crate::imports::*;
+use crate::runtime::services::LogStore;
+use std::collections::HashSet;
+use std::net::TcpListener;
+use std::process::{ExitStatus, Stdio};
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::net::TcpStream;
+use tokio::process::{Child, Command};
+use tokio::time::timeout;
+
+/// Interval at which [`ProcessSupervisor::monitor`] checks the child process.
+const MONITOR_INTERVAL: Duration = Duration::from_secs(1);
+/// Interval between health probes of a running process.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+/// Health probes are not evaluated during this period after a start.
+const HEALTH_STARTUP_GRACE: Duration = Duration::from_secs(30);
+/// Consecutive failed health probes after which the process is restarted.
+const HEALTH_FAILURE_THRESHOLD: u32 = 3;
+/// Level returned by a [`LevelDetector`] for lines that should be dropped.
+pub const IGNORE_LEVEL: &str = "IGNORE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    pub fn default_level(&self) -> &'static str {
+        match self {
+            OutputStream::Stdout => "INFO",
+            OutputStream::Stderr => "WARN",
+        }
+    }
+}
+
+/// Receives the output of a supervised process and the supervisor's own
+/// notices (exits, restarts, health failures).
+#[async_trait]
+pub trait ProcessOutput: Send + Sync + 'static {
+    async fn line(&self, stream: OutputStream, line: String);
+    async fn notice(&self, level: &'static str, message: String);
+}
+
+/// Maps a line of output to a log level, falling back to the stream default.
+pub type LevelDetector = for<'a> fn(&str, &'a str) -> &'a str;
+
+/// [`ProcessOutput`] that records lines in a self-hosted [`LogStore`].
+pub struct LogStoreOutput {
+    store: Arc<LogStore>,
+    prefix: Option<&'static str>,
+    console: Option<&'static str>,
+    detect_level: Option<LevelDetector>,
+}
+
+impl LogStoreOutput {
+    pub fn new(store: &Arc<LogStore>) -> Self {
+        Self {
+            store: store.clone(),
+            prefix: None,
+            console: None,
+            detect_level: None,
+        }
+    }
+
+    /// Prefix prepended to every line (e.g. `"processor: "`).
+    pub fn with_prefix(mut self, prefix: &'static str) -> Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// Also mirror lines to the application log under the given target.
+    pub fn with_console(mut self, target: &'static str) -> Self {
+        self.console = Some(target);
+        self
+    }
+
+    pub fn with_level_detector(mut self, detect_level: LevelDetector) -> Self {
+        self.detect_level = Some(detect_level);
+        self
+    }
+
+    fn console(&self, level: &str, line: &str) {
+        if let Some(target) = self.console {
+            match level {
+                "ERROR" | "WARN" => log_warn!("{target}: {line}"),
+                _ => log_info!("{target}: {line}"),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ProcessOutput for LogStoreOutput {
+    async fn line(&self, stream: OutputStream, line: String) {
+        let fallback = stream.default_level();
+        let level = self
+            .detect_level
+            .map(|detect_level| detect_level(&line, fallback))
+            .unwrap_or(fallback);
+        if level == IGNORE_LEVEL {
+            return;
+        }
+
+        self.console(level, &line);
+        match self.prefix {
+            Some(prefix) => self.store.push(level, &format!("{prefix}{line}")),
+            None => self.store.push(level, &line),
+        }
+    }
+
+    async fn notice(&self, level: &'static str, message: String) {
+        self.console(level, &message);
+        self.store.push(level, &message);
+    }
+}
+
+/// How a supervised process is shut down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopPolicy {
+    /// Kill the process immediately.
+    Kill,
+    /// Spawn the process in its own process group, send `SIGTERM` to the
+    /// group and escalate to `SIGKILL` after `grace` (kill on Windows).
+    TerminateGroup { grace: Duration },
+    /// Send `SIGTERM` and then `SIGINT` to the process, waiting the respective
+    /// period after each, before killing it. Used by processes that must shut
+    /// down cleanly to avoid recovery on the next start.
+    Graceful {
+        terminate: Duration,
+        interrupt: Duration,
+    },
+}
+
+impl StopPolicy {
+    fn uses_process_group(&self) -> bool {
+        !matches!(self, StopPolicy::Kill)
+    }
+}
+
+/// When and how often a process that exited unexpectedly is started again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// Delay before the first restart.
+    pub initial_delay: Duration,
+    /// Upper bound of the exponentially growing delay.
+    pub max_delay: Duration,
+    /// Consecutive failures after which no further restarts are attempted.
+    pub max_retries: Option<u32>,
+    /// Uptime after which the process is considered stable and the failure
+    /// count is reset.
+    pub stable_after: Duration,
+    /// Restart the process even if it exited with a success status.
+    pub restart_on_success: bool,
+}
+
+impl RestartPolicy {
+    /// Restart after a constant delay, indefinitely.
+    pub const fn fixed(delay: Duration) -> Self {
+        Self::backoff(delay, delay)
+    }
+
+    /// Restart with a delay doubling on each consecutive failure.
+    pub const fn backoff(initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            initial_delay,
+            max_delay,
+            max_retries: None,
+            stable_after: Duration::from_secs(60),
+            restart_on_success: false,
+        }
+    }
+
+    /// Never restart automatically; the owning service decides.
+    pub const fn never() -> Self {
+        Self::fixed(Duration::ZERO).with_max_retries(0)
+    }
+
+    pub const fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    pub const fn with_restart_on_success(mut self) -> Self {
+        self.restart_on_success = true;
+        self
+    }
+
+    /// Delay before the restart following `failures` consecutive failures.
+    pub fn delay(&self, failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(16);
+        self.initial_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay)
+    }
+
+    fn exhausted(&self, failures: u32) -> bool {
+        self.max_retries.is_some_and(|max| failures > max)
+    }
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self::backoff(Duration::from_secs(3), Duration::from_secs(60))
+    }
+}
+
+/// Readiness check of a supervised process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthProbe {
+    /// The process accepts TCP connections on the given address.
+    Tcp { host: String, port: u16 },
+}
+
+impl HealthProbe {
+    pub fn tcp(host: impl Into<String>, port: u16) -> Self {
+        HealthProbe::Tcp {
+            host: host.into(),
+            port,
+        }
+    }
+
+    pub async fn check(&self) -> bool {
+        match self {
+            HealthProbe::Tcp { host, port } => matches!(
+                timeout(
+                    Duration::from_millis(900),
+                    TcpStream::connect((host.as_str(), *port))
+                )
+                .await,
+                Ok(Ok(_))
+            ),
+        }
+    }
+
+    /// Polls the probe until it succeeds or `deadline` elapses.
+    pub async fn wait(&self, deadline: Duration) -> bool {
+        let started = Instant::now();
+        loop {
+            if self.check().await {
+                return true;
+            }
+            if started.elapsed() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(300)).await;
+        }
+    }
+}
+
+impl std::fmt::Display for HealthProbe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HealthProbe::Tcp { host, port } => write!(f, "tcp://{host}:{port}"),
+        }
+    }
+}
+
+/// Result of polling a supervised process.
+#[derive(Debug)]
+pub enum ProcessState {
+    /// No process has been started (or it was stopped on request).
+    Stopped,
+    Running,
+    /// The process exited on its own since the last poll.
+    Exited(ExitStatus),
+    /// The process state could not be determined and the handle was dropped.
+    Lost(String),
+}
+
+/// Point-in-time view of a supervised process, as reported by the
+/// service status API.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessStatus {
+    pub name: String,
+    pub running: bool,
+    pub pid: Option<u32>,
+    pub uptime_seconds: Option<u64>,
+    pub failures: u32,
+}
+
+#[derive(Default)]
+struct SupervisorState {
+    started_at: Option<Instant>,
+    failures: u32,
+    retry_at: Option<Instant>,
+    health_failures: u32,
+    last_health_check: Option<Instant>,
+}
+
+/// Owns a single external process and applies restart, stop and health
+/// policies to it.
+pub struct ProcessSupervisor {
+    name: String,
+    restart: RestartPolicy,
+    stop: StopPolicy,
+    dependencies: Vec<Arc<ProcessSupervisor>>,
+    health: Mutex<Option<HealthProbe>>,
+    pidfile: Mutex<Option<PathBuf>>,
+    child: Mutex<Option<Child>>,
+    output: Mutex<Option<Arc<dyn ProcessOutput>>>,
+    state: Mutex<SupervisorState>,
+}
+
+impl ProcessSupervisor {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            restart: RestartPolicy::default(),
+            stop: StopPolicy::TerminateGroup {
+                grace: Duration::from_secs(2),
+            },
+            dependencies: Vec::new(),
+            health: Mutex::new(None),
+            pidfile: Mutex::new(None),
+            child: Mutex::new(None),
+            output: Mutex::new(None),
+            state: Mutex::new(SupervisorState::default()),
+        }
+    }
+
+    pub fn with_restart_policy(mut self, restart: RestartPolicy) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    pub fn with_stop_policy(mut self, stop: StopPolicy) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    pub fn with_health_probe(self, probe: HealthProbe) -> Self {
+        *self.health.lock().unwrap() = Some(probe);
+        self
+    }
+
+    /// Declares a process that must be running and healthy before this one
+    /// is started (see [`dependencies_ready`](Self::dependencies_ready)).
+    pub fn depends_on(mut self, dependency: &Arc<ProcessSupervisor>) -> Self {
+        self.dependencies.push(dependency.clone());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn restart_policy(&self) -> &RestartPolicy {
+        &self.restart
+    }
+
+    /// Replaces the health probe, e.g. after the listen port changed.
+    pub fn set_health_probe(&self, probe: Option<HealthProbe>) {
+        *self.health.lock().unwrap() = probe;
+    }
+
+    pub fn health_probe(&self) -> Option<HealthProbe> {
+        self.health.lock().unwrap().clone()
+    }
+
+    /// Pid file maintained while the process runs; used to find processes
+    /// left behind by a previous instance of the application.
+    pub fn set_pidfile(&self, path: Option<PathBuf>) {
+        *self.pidfile.lock().unwrap() = path;
+    }
+
+    pub fn pid(&self) -> Option<u32> {
+        self.child
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|child| child.id())
+    }
+
+    /// Returns `true` if a process handle is held, without reaping it.
+    pub fn has_child(&self) -> bool {
+        self.child.lock().unwrap().is_some()
+    }
+
+    pub fn uptime(&self) -> Option<Duration> {
+        self.state
+            .lock()
+            .unwrap()
+            .started_at
+            .map(|started_at| started_at.elapsed())
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.state.lock().unwrap().failures
+    }
+
+    pub fn status(&self) -> ProcessStatus {
+        let running = self.has_child();
+        ProcessStatus {
+            name: self.name.clone(),
+            running,
+            pid: self.pid(),
+            uptime_seconds: running
+                .then(|| self.uptime().map(|uptime| uptime.as_secs()))
+                .flatten(),
+            failures: self.failures(),
+        }
+    }
+
+    /// Spawns `cmd` with piped output forwarded to `output`.
+    ///
+    /// A spawn failure counts as a failed start for the restart policy.
+    pub fn spawn(&self, mut cmd: Command, output: Arc<dyn ProcessOutput>) -> Result<u32> {
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        #[cfg(windows)]
+        {
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+        #[cfg(unix)]
+        if self.stop.uses_process_group() {
+            cmd.process_group(0);
+        }
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(err) => {
+                self.record_failure();
+                return Err(Error::NodeStartupError(err));
+            }
+        };
+
+        if let Some(stdout) = child.stdout.take() {
+            Self::pump(stdout, OutputStream::Stdout, output.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            Self::pump(stderr, OutputStream::Stderr, output.clone());
+        }
+
+        let pid = child.id().unwrap_or_default();
+        if let Some(path) = self.pidfile.lock().unwrap().as_ref() {
+            let _ = std::fs::write(path, pid.to_string());
+        }
+
+        *self.child.lock().unwrap() = Some(child);
+        *self.output.lock().unwrap() = Some(output);
+        {
+            let mut state = self.state.lock().unwrap();
+            state.started_at = Some(Instant::now());
+            state.retry_at = None;
+            state.health_failures = 0;
+            state.last_health_check = None;
+        }
+
+        Ok(pid)
+    }
+
+    fn pump<R>(reader: R, stream: OutputStream, output: Arc<dyn ProcessOutput>)
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                output.line(stream, line).await;
+            }
+        });
+    }
+
+    async fn notice(&self, level: &'static str, message: String) {
+        let output = self.output.lock().unwrap().clone();
+        match output {
+            Some(output) => output.notice(level, message).await,
+            None => log_warn!("{message}"),
+        }
+    }
+
+    /// Counts a failed start or an unexpected exit towards the restart policy.
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        let stable = state
+            .started_at
+            .take()
+            .is_some_and(|started_at| started_at.elapsed() >= self.restart.stable_after);
+        state.failures = if stable {
+            1
+        } else {
+            state.failures.saturating_add(1)
+        };
+        state.retry_at = Some(Instant::now() + self.restart.delay(state.failures));
+    }
+
+    /// Reaps the process if it exited and reports its state. An exit that
+    /// was not requested through [`stop`](Self::stop) counts as a failure.
+    pub async fn poll(&self) -> ProcessState {
+        let state = {
+            let mut guard = self.child.lock().unwrap();
+            let Some(child) = guard.as_mut() else {
+                return ProcessState::Stopped;
+            };
+            match child.try_wait() {
+                Ok(None) => return ProcessState::Running,
+                Ok(Some(status)) => ProcessState::Exited(status),
+                Err(err) => ProcessState::Lost(err.to_string()),
+            }
+        };
+
+        self.child.lock().unwrap().take();
+        self.remove_pidfile();
+        self.record_failure();
+        match &state {
+            ProcessState::Exited(status) => {
+                self.notice(
+                    "WARN",
+                    format!("{} exited with status: {status}", self.name),
+                )
+                .await
+            }
+            ProcessState::Lost(err) => {
+                self.notice("WARN", format!("{} state check failed: {err}", self.name))
+                    .await
+            }
+            _ => {}
+        }
+        state
+    }
+
+    pub async fn is_running(&self) -> bool {
+        matches!(self.poll().await, ProcessState::Running)
+    }
+
+    /// Time left until the restart policy allows another start, or `None`
+    /// if the retry budget is exhausted.
+    pub fn retry_delay(&self) -> Option<Duration> {
+        let state = self.state.lock().unwrap();
+        if self.restart.exhausted(state.failures) {
+            return None;
+        }
+        Some(
+            state
+                .retry_at
+                .map(|retry_at| retry_at.saturating_duration_since(Instant::now()))
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Returns `true` if the restart policy allows starting the process now.
+    pub fn ready_to_start(&self) -> bool {
+        self.retry_delay() == Some(Duration::ZERO)
+    }
+
+    /// Clears the failure history, e.g. after settings changed or the
+    /// service was re-enabled by the user.
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.failures = 0;
+        state.retry_at = None;
+        state.health_failures = 0;
+    }
+
+    /// Stops the process according to the stop policy. Returns `true` if a
+    /// process was running.
+    pub async fn stop(&self) -> bool {
+        let child = self.child.lock().unwrap().take();
+        self.state.lock().unwrap().started_at = None;
+        let stopped = match child {
+            Some(mut child) => {
+                match self.stop {
+                    StopPolicy::Kill => {
+                        let _ = child.start_kill();
+                        let _ = child.wait().await;
+                    }
+                    StopPolicy::TerminateGroup { grace } => {
+                        terminate_process_tree(&mut child, grace).await;
+                    }
+                    StopPolicy::Graceful {
+                        terminate,
+                        interrupt,
+                    } => {
+                        terminate_gracefully(&mut child, terminate, interrupt).await;
+                    }
+                }
+                true
+            }
+            None => false,
+        };
+        self.remove_pidfile();
+        stopped
+    }
+
+    /// Stops a process recorded in the pid file by an earlier instance of the
+    /// application. Returns the pid of the stopped process.
+    pub async fn stop_stale(&self) -> Option<u32> {
+        let path = self.pidfile.lock().unwrap().clone()?;
+        let pid = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| raw.trim().parse::<u32>().ok());
+        let pid = match pid {
+            Some(pid) if process_is_running(pid) && Some(pid) != self.pid() => pid,
+            _ => {
+                let _ = std::fs::remove_file(&path);
+                return None;
+            }
+        };
+
+        terminate_pid(pid).await;
+        let _ = std::fs::remove_file(&path);
+        Some(pid)
+    }
+
+    fn remove_pidfile(&self) {
+        if let Some(path) = self.pidfile.lock().unwrap().as_ref() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Returns `true` if the process is running and passes its health probe
+    /// (processes without a probe are healthy while running).
+    pub async fn is_healthy(&self) -> bool {
+        if !self.is_running().await {
+            return false;
+        }
+        match self.health_probe() {
+            Some(probe) => probe.check().await,
+            None => true,
+        }
+    }
+
+    /// Waits until the health probe succeeds, giving up after `deadline` or
+    /// as soon as the process exits.
+    pub async fn wait_healthy(&self, deadline: Duration) -> bool {
+        let Some(probe) = self.health_probe() else {
+            return self.is_running().await;
+        };
+        let started = Instant::now();
+        loop {
+            if !self.is_running().await {
+                return false;
+            }
+            if probe.check().await {
+                return true;
+            }
+            if started.elapsed() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(300)).await;
+        }
+    }
+
+    /// Returns `true` if every declared dependency is running and healthy.
+    pub async fn dependencies_ready(&self) -> bool {
+        for dependency in self.dependencies.iter() {
+            if !dependency.is_healthy().await {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Evaluates the health probe at most every [`HEALTH_CHECK_INTERVAL`].
+    /// Returns `false` once the failure threshold has been reached.
+    async fn check_health(&self) -> bool {
+        let Some(probe) = self.health_probe() else {
+            return true;
+        };
+        {
+            let state = self.state.lock().unwrap();
+            let in_grace = state
+                .started_at
+                .is_none_or(|started_at| started_at.elapsed() < HEALTH_STARTUP_GRACE);
+            let recently_checked = state
+                .last_health_check
+                .is_some_and(|checked| checked.elapsed() < HEALTH_CHECK_INTERVAL);
+            if in_grace || recently_checked {
+                return true;
+            }
+        }
+
+        let healthy = probe.check().await;
+        let mut state = self.state.lock().unwrap();
+        state.last_health_check = Some(Instant::now());
+        if healthy {
+            state.health_failures = 0;
+            true
+        } else {
+            state.health_failures = state.health_failures.saturating_add(1);
+            state.health_failures < HEALTH_FAILURE_THRESHOLD
+        }
+    }
+
+    /// Watches the process in the background and calls `restart` whenever
+    /// the restart policy requires a new start. The monitor ends when the
+    /// process is stopped on request, exits cleanly (unless the policy says
+    /// otherwise), the retry budget is exhausted or `is_enabled` turns false.
+    /// `restart` is expected to spawn the process again and start a new
+    /// monitor.
+    pub fn monitor<E, F, Fut>(self: &Arc<Self>, is_enabled: E, restart: F)
+    where
+        E: Fn() -> bool + Send + Sync + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let this = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(MONITOR_INTERVAL).await;
+                match this.poll().await {
+                    ProcessState::Running => {
+                        if !this.check_health().await {
+                            this.notice(
+                                "WARN",
+                                format!("{} failed its health check; restarting", this.name),
+                            )
+                            .await;
+                            this.stop().await;
+                            this.record_failure();
+                            break;
+                        }
+                        let mut state = this.state.lock().unwrap();
+                        if state.failures > 0
+                            && state.started_at.is_some_and(|started| {
+                                started.elapsed() >= this.restart.stable_after
+                            })
+                        {
+                            state.failures = 0;
+                        }
+                    }
+                    ProcessState::Stopped => return,
+                    ProcessState::Exited(status)
+                        if status.success() && !this.restart.restart_on_success =>
+                    {
+                        this.notice(
+                            "INFO",
+                            format!("{} exited cleanly; automatic restart skipped.", this.name),
+                        )
+                        .await;
+                        return;
+                    }
+                    ProcessState::Exited(_) | ProcessState::Lost(_) => break,
+                }
+            }
+
+            if !is_enabled() {
+                return;
+            }
+
+            let Some(delay) = this.retry_delay() else {
+                this.notice(
+                    "ERROR",
+                    format!("{} failed {} times; giving up", this.name, this.failures()),
+                )
+                .await;
+                return;
+            };
+
+            this.notice(
+                "INFO",
+                format!("restarting {} in {}s", this.name, delay.as_secs().max(1)),
+            )
+            .await;
+            tokio::time::sleep(delay).await;
+            if is_enabled() && !this.has_child() {
+                restart().await;
+            }
+        });
+    }
+}
+
+/// Returns `true` if the application runs from a macOS `.app` bundle, in
+/// which case the working directory is not searched for binaries.
+pub fn running_from_macos_bundle() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        if let Ok(exe) = std::env::current_exe() {
+            return exe.to_string_lossy().contains(".app/Contents/MacOS/");
+        }
+    }
+    false
+}
+
+/// Appends the platform executable extension to `name`.
+pub fn executable_name(name: &str) -> String {
+    if cfg!(windows) && !name.ends_with(".exe") {
+        format!("{name}.exe")
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(unix)]
+pub fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|meta| meta.is_file() && (meta.permissions().mode() & 0o111 != 0))
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+pub fn is_executable(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .map(|meta| meta.is_file())
+        .unwrap_or(false)
+}
+
+/// Restores the executable bit on bundled binaries (archives and app
+/// updates may drop it). Returns `true` if `path` is executable afterwards.
+pub fn ensure_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if !is_executable(path)
+            && let Ok(meta) = std::fs::metadata(path)
+            && meta.is_file()
+        {
+            let mut perms = meta.permissions();
+            perms.set_mode(perms.mode() | 0o755);
+            let _ = std::fs::set_permissions(path, perms);
+        }
+    }
+    is_executable(path)
+}
+
+/// Locates a companion binary shipped with the application.
+///
+/// Searched are the `resources` folder next to the executable (and inside
+/// the macOS bundle), the executable folder itself and, unless running from
+/// a bundle, the working directory and its ancestors including cargo
+/// `target` folders. `project_dirs` are source checkouts (e.g. `K-indexer`)
+/// whose `target` folders are searched as well.
+pub fn find_binary(bin_name: &str, project_dirs: &[&str]) -> Option<PathBuf> {
+    let bin = executable_name(bin_name);
+    let mut candidates: Vec<PathBuf> = Vec::new();
+
+    let push_root = |candidates: &mut Vec<PathBuf>, root: &Path| {
+        candidates.push(root.join("resources").join(&bin));
+        candidates.push(root.join(&bin));
+        for profile in ["release", "debug"] {
+            let target = root.join("target").join(profile);
+            candidates.push(target.join("resources").join(&bin));
+            candidates.push(target.join(&bin));
+        }
+        for project in project_dirs {
+            candidates.push(root.join(project).join(&bin));
+            for profile in ["release", "debug"] {
+                candidates.push(root.join(project).join("target").join(profile).join(&bin));
+            }
+        }
+    };
+
+    if let Ok(exe) = std::env::current_exe()
+        && let Some(dir) = exe.parent()
+    {
+        push_root(&mut candidates, dir);
+
+        #[cfg(target_os = "macos")]
+        if let Some(contents) = dir.parent() {
+            push_root(&mut candidates, &contents.join("Resources"));
+        }
+    }
+
+    // only binaries shipped next to the executable may get their
+    // executable bit restored
+    let bundled = candidates.len();
+
+    if !running_from_macos_bundle()
+        && let Ok(cwd) = std::env::current_dir()
+    {
+        for ancestor in cwd.ancestors().take(8) {
+            push_root(&mut candidates, ancestor);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .enumerate()
+        .filter(|(_, path)| seen.insert(path.clone()))
+        .find(|(index, path)| {
+            is_executable(path) || (*index < bundled && path.is_file() && ensure_executable(path))
+        })
+        .map(|(_, path)| path.canonicalize().unwrap_or(path))
+}
+
+/// Locates a binary in the directories listed in `PATH`.
+pub fn find_in_path(bin_name: &str) -> Option<PathBuf> {
+    find_in_path_with(bin_name, |_| true)
+}
+
+/// Locates a binary in `PATH` that satisfies `filter` (e.g. a version check).
+pub fn find_in_path_with(bin_name: &str, filter: impl Fn(&Path) -> bool) -> Option<PathBuf> {
+    let path_var = std::env::var_os("PATH")?;
+
+    #[cfg(windows)]
+    let names: Vec<String> = if Path::new(bin_name).extension().is_some() {
+        vec![bin_name.to_string()]
+    } else {
+        let extensions =
+            std::env::var("PATHEXT").unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".to_string());
+        std::iter::once(bin_name.to_string())
+            .chain(
+                extensions
+                    .split(';')
+                    .filter(|ext| !ext.is_empty())
+                    .map(|ext| format!("{bin_name}{}", ext.to_ascii_lowercase())),
+            )
+            .collect()
+    };
+    #[cfg(not(windows))]
+    let names = vec![bin_name.to_string()];
+
+    std::env::split_paths(&path_var)
+        .flat_map(|dir| {
+            names
+                .iter()
+                .map(move |name| dir.join(name))
+                .collect::<Vec<_>>()
+        })
+        .find(|path| is_executable(path) && filter(path))
+}
+
+/// Returns `true` if `addr` can be bound, i.e. no other process listens on it.
+pub fn listen_addr_available(addr: &str) -> bool {
+    TcpListener::bind(addr).is_ok()
+}
+
+#[cfg(unix)]
+pub fn process_is_running(pid: u32) -> bool {
+    use nix::errno::Errno;
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+
+    match kill(Pid::from_raw(pid), None) {
+        Ok(_) => true,
+        Err(Errno::EPERM) => true,
+        Err(_) => false,
+    }
+}
+
+#[cfg(windows)]
+pub fn process_is_running(pid: u32) -> bool {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let output = std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/FO", "CSV", "/NH"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+    match output {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).contains(&format!(",\"{pid}\",")),
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn process_is_running(_pid: u32) -> bool {
+    false
+}
+
+/// Terminates a process that is not a child of this application.
+async fn terminate_pid(pid: u32) {
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{Signal, kill};
+        use nix::unistd::Pid;
+
+        let Ok(raw) = i32::try_from(pid) else {
+            return;
+        };
+        let _ = kill(Pid::from_raw(raw), Signal::SIGTERM);
+        for _ in 0..12 {
+            if !process_is_running(pid) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+        let _ = kill(Pid::from_raw(raw), Signal::SIGKILL);
+        for _ in 0..8 {
+            if !process_is_running(pid) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+        let _ = std::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .status();
+    }
+}
+
+/// Sends `SIGTERM` to the process group of `child` and escalates to
+/// `SIGKILL` after `grace`. On other platforms the child is killed.
+pub async fn terminate_process_tree(child: &mut Child, grace: Duration) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        use nix::sys::signal::{Signal, killpg};
+        use nix::unistd::Pid;
+
+        let pgid = Pid::from_raw(pid as i32);
+        let _ = killpg(pgid, Signal::SIGTERM);
+        if timeout(grace, child.wait()).await.is_ok() {
+            return;
+        }
+        let _ = killpg(pgid, Signal::SIGKILL);
+        let _ = timeout(Duration::from_secs(2), child.wait()).await;
+        return;
+    }
+
+    let _ = grace;
+    let _ = child.start_kill();
+    let _ = child.wait().await;
+}
+
+async fn terminate_gracefully(child: &mut Child, terminate: Duration, interrupt: Duration) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        use nix::sys::signal::{Signal, kill};
+        use nix::unistd::Pid;
+
+        let pid = Pid::from_raw(pid as i32);
+        let _ = kill(pid, Signal::SIGTERM);
+        if timeout(terminate, child.wait()).await.is_ok() {
+            return;
+        }
+        let _ = kill(pid, Signal::SIGINT);
+        if timeout(interrupt, child.wait()).await.is_ok() {
+            return;
+        }
+    }
+
+    let _ = (terminate, interrupt);
+    let _ = child.start_kill();
+    let _ = timeout(Duration::from_secs(5), child.wait()).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_backoff() {
+        let policy = RestartPolicy::backoff(Duration::from_secs(2), Duration::from_secs(30));
+        assert_eq!(policy.delay(1), Duration::from_secs(2));
+        assert_eq!(policy.delay(2), Duration::from_secs(4));
+        assert_eq!(policy.delay(4), Duration::from_secs(16));
+        assert_eq!(policy.delay(5), Duration::from_secs(30));
+        assert_eq!(policy.delay(100), Duration::from_secs(30));
+
+        let fixed = RestartPolicy::fixed(Duration::from_secs(3));
+        assert_eq!(fixed.delay(1), fixed.delay(10));
+    }
+
+    #[test]
+    fn test_restart_retry_budget() {
+        let policy = RestartPolicy::default().with_max_retries(2);
+        assert!(!policy.exhausted(2));
+        assert!(policy.exhausted(3));
+        assert!(RestartPolicy::never().exhausted(1));
+        assert!(!RestartPolicy::default().exhausted(u32::MAX));
+    }
+}
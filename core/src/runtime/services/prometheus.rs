@@ -0,0 +1,189 @@
+//! Minimal encoder for the Prometheus text exposition format (version 0.0.4).
+
+use std::fmt::Write;
+
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Gauge,
+    Counter,
+}
+
+impl MetricKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Gauge => "gauge",
+            MetricKind::Counter => "counter",
+        }
+    }
+}
+
+struct Family {
+    name: String,
+    help: String,
+    kind: MetricKind,
+    samples: Vec<String>,
+}
+
+/// Collects samples and renders them grouped by metric family, as
+/// required by the exposition format. Common labels (e.g. the network)
+/// are prepended to the labels of every sample.
+pub struct Exposition {
+    common_labels: Vec<(String, String)>,
+    families: Vec<Family>,
+}
+
+impl Exposition {
+    pub fn new(common_labels: &[(&str, &str)]) -> Self {
+        Self {
+            common_labels: common_labels
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            families: Vec::new(),
+        }
+    }
+
+    pub fn gauge(&mut self, name: &str, help: &str, labels: &[(&str, &str)], value: f64) {
+        self.sample(MetricKind::Gauge, name, help, labels, value);
+    }
+
+    pub fn counter(&mut self, name: &str, help: &str, labels: &[(&str, &str)], value: f64) {
+        self.sample(MetricKind::Counter, name, help, labels, value);
+    }
+
+    pub fn sample(
+        &mut self,
+        kind: MetricKind,
+        name: &str,
+        help: &str,
+        labels: &[(&str, &str)],
+        value: f64,
+    ) {
+        let name = sanitize_name(name);
+
+        let mut line = name.clone();
+        let labels = self
+            .common_labels
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .chain(labels.iter().copied())
+            .map(|(key, value)| format!("{}=\"{}\"", sanitize_name(key), escape_label(value)))
+            .collect::<Vec<_>>();
+        if !labels.is_empty() {
+            line.push('{');
+            line.push_str(&labels.join(","));
+            line.push('}');
+        }
+        line.push(' ');
+        line.push_str(&format_value(value));
+
+        if let Some(family) = self.families.iter_mut().find(|family| family.name == name) {
+            family.samples.push(line);
+        } else {
+            self.families.push(Family {
+                name,
+                help: help.to_string(),
+                kind,
+                samples: vec![line],
+            });
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut text = String::new();
+        for family in self.families.iter() {
+            let _ = writeln!(text, "# HELP {} {}", family.name, escape_help(&family.help));
+            let _ = writeln!(text, "# TYPE {} {}", family.name, family.kind.as_str());
+            for sample in family.samples.iter() {
+                text.push_str(sample);
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .enumerate()
+        .map(|(index, c)| {
+            if c.is_ascii_alphabetic() || c == '_' || c == ':' || (index > 0 && c.is_ascii_digit())
+            {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::services::stratum_bridge::stats::parse_prometheus;
+
+    #[test]
+    fn groups_samples_by_family() {
+        let mut exposition = Exposition::new(&[("network", "mainnet")]);
+        exposition.gauge("kaspa_peers", "Connected peers", &[], 8.0);
+        exposition.gauge("kaspa_table_rows", "Rows", &[("table", "blocks")], 10.0);
+        exposition.gauge("kaspa_peers", "Connected peers", &[], 9.0);
+
+        let text = exposition.render();
+        assert_eq!(text.matches("# TYPE kaspa_peers gauge").count(), 1);
+        let peers = text.find("kaspa_peers{").unwrap();
+        let rows = text.find("kaspa_table_rows{").unwrap();
+        assert!(text.rfind("kaspa_peers{").unwrap() < rows && peers < rows);
+
+        let samples = parse_prometheus(&text);
+        assert_eq!(samples.len(), 3);
+        assert!(
+            samples
+                .iter()
+                .all(|sample| sample.label("network") == Some("mainnet"))
+        );
+    }
+
+    #[test]
+    fn escapes_labels_and_names() {
+        let mut exposition = Exposition::new(&[]);
+        exposition.counter(
+            "bridge-blocks",
+            "Blocks",
+            &[("worker", "rig \"1\"\\a")],
+            f64::INFINITY,
+        );
+
+        let text = exposition.render();
+        assert!(text.contains("# TYPE bridge_blocks counter"));
+
+        let samples = parse_prometheus(&text);
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].name, "bridge_blocks");
+        assert_eq!(samples[0].label("worker"), Some("rig \"1\"\\a"));
+        assert_eq!(samples[0].value, f64::INFINITY);
+    }
+}
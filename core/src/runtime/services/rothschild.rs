@@ -0,0 +1,368 @@
+use crate::imports::*;
+use crate::runtime::Service;
+
+cfg_if! {
+    if #[cfg(not(target_arch = "wasm32"))] {
+        use crate::runtime::services::log_hub::LogSource;
+        use crate::runtime::services::kaspa::grpc::node_grpc_address;
+        use crate::runtime::services::node_client_process::{NodeClientProcess, NodeClientService};
+        use crate::runtime::services::process_supervisor::{self, ProcessStatus};
+        use tokio::process::Command;
+
+        pub fn update_logs_flag() -> &'static Arc<AtomicBool> {
+            static FLAG: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+            FLAG.get_or_init(|| Arc::new(AtomicBool::new(false)))
+        }
+
+        #[derive(Debug, Clone)]
+        enum RothschildEvents {
+            SetEnabled { enabled: bool, settings: NodeSettings },
+            UpdateSettings(NodeSettings),
+            Exit,
+        }
+
+        pub struct RothschildService {
+            service_events: Channel<RothschildEvents>,
+            task_ctl: Channel<()>,
+            node_settings: Mutex<NodeSettings>,
+            client: NodeClientProcess,
+        }
+
+        impl RothschildService {
+            pub fn new(application_events: ApplicationEventsChannel, settings: &Settings) -> Self {
+                Self {
+                    service_events: Channel::unbounded(),
+                    task_ctl: Channel::oneshot(),
+                    node_settings: Mutex::new(settings.node.clone()),
+                    client: NodeClientProcess::new(
+                        "Rothschild",
+                        LogSource::Rothschild,
+                        update_logs_flag(),
+                        application_events,
+                        settings.node.rothschild_enabled && Self::is_network_allowed(settings.node.network),
+                    ),
+                }
+            }
+
+            pub fn enable(&self, enabled: bool, node_settings: &NodeSettings) {
+                self.service_events
+                    .try_send(RothschildEvents::SetEnabled {
+                        enabled,
+                        settings: node_settings.clone(),
+                    })
+                    .unwrap();
+            }
+
+            pub fn update_settings(&self, node_settings: &NodeSettings) {
+                self.service_events
+                    .try_send(RothschildEvents::UpdateSettings(node_settings.clone()))
+                    .unwrap();
+            }
+
+            pub fn process_status(&self) -> Vec<ProcessStatus> {
+                self.client.process_status()
+            }
+
+            async fn update_logs(&self, line: String) {
+                self.client.update_logs(line).await;
+            }
+
+            /// Rothschild floods the network with transactions and must
+            /// never be pointed at Mainnet.
+            pub fn is_network_allowed(network: Network) -> bool {
+                !matches!(network, Network::Mainnet)
+            }
+
+            fn validate_private_key(settings: &NodeSettings) -> std::result::Result<Option<String>, String> {
+                let private_key = settings.rothschild.private_key.expose().trim();
+                if private_key.is_empty() {
+                    return Ok(None);
+                }
+
+                if private_key.len() != 64 || !private_key.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(i18n("Rothschild: private key must be 64 hex characters").to_string());
+                }
+
+                Ok(Some(private_key.to_string()))
+            }
+
+            fn validate_change_address(settings: &NodeSettings) -> std::result::Result<Option<Address>, String> {
+                let address = settings.rothschild.change_address.trim();
+                if address.is_empty() {
+                    return Ok(None);
+                }
+
+                let address = Address::try_from(address)
+                    .map_err(|err| format!("{} ({err})", i18n("Rothschild: invalid change address")))?;
+
+                let network_type = NetworkType::from(settings.network);
+                if address.prefix != kaspa_addresses::Prefix::from(network_type) {
+                    return Err(i18n_args(
+                        "Rothschild: change address does not belong to {network}",
+                        &[("network", settings.network.to_string())],
+                    ));
+                }
+
+                Ok(Some(address))
+            }
+
+            /// Command line arguments of the generator. rothschild only
+            /// accepts the private key as `--private-key`.
+            fn generator_args(
+                kaspad_address: &str,
+                tps: u64,
+                threads: u8,
+                private_key: Option<&str>,
+                change_address: Option<&Address>,
+            ) -> Vec<String> {
+                let mut args = vec![
+                    "--rpcserver".to_string(),
+                    kaspad_address.to_string(),
+                    "--tps".to_string(),
+                    tps.to_string(),
+                    "--threads".to_string(),
+                    threads.to_string(),
+                ];
+                if let Some(private_key) = private_key {
+                    args.push("--private-key".to_string());
+                    args.push(private_key.to_string());
+                }
+                if let Some(change_address) = change_address {
+                    args.push("--to-addr".to_string());
+                    args.push(change_address.to_string());
+                }
+                args
+            }
+
+            async fn start_generator(self: &Arc<Self>) -> Result<()> {
+                let Some(_guard) = self.client.begin_start() else {
+                    return Ok(());
+                };
+
+                let settings = self.node_settings.lock().unwrap().clone();
+
+                if !Self::is_network_allowed(settings.network) {
+                    self.update_logs(i18n("Rothschild: refusing to start on Mainnet").to_string()).await;
+                    return Ok(());
+                }
+
+                let (mut private_key, change_address) = match Self::validate_private_key(&settings)
+                    .and_then(|private_key| Ok((private_key, Self::validate_change_address(&settings)?)))
+                {
+                    Ok(values) => values,
+                    Err(err) => {
+                        self.update_logs(err).await;
+                        return Ok(());
+                    }
+                };
+
+                let Some(kaspad_address) = node_grpc_address(&settings) else {
+                    self.update_logs(i18n("Rothschild: a gRPC connection to the node is required; enable gRPC for the local node or connect to a remote node over gRPC.").to_string()).await;
+                    return Ok(());
+                };
+
+                let generator_bin = match process_supervisor::find_binary("rothschild", &["rusty-kaspa"]) {
+                    Some(path) => path,
+                    None => {
+                        self.update_logs(i18n("Rothschild: rothschild binary not found (build rusty-kaspa first)").to_string()).await;
+                        self.client.record_failure();
+                        self.client.schedule_restart(self, "rothschild binary not found");
+                        return Ok(());
+                    }
+                };
+
+                if !self.client.wait_for_grpc(&kaspad_address).await {
+                    return Ok(());
+                }
+
+                let tps = settings.rothschild.tps.max(1);
+                let threads = settings.rothschild.threads.max(1);
+
+                let mut args = Self::generator_args(
+                    &kaspad_address,
+                    tps,
+                    threads,
+                    private_key.as_deref(),
+                    change_address.as_ref(),
+                );
+                let mut cmd = Command::new(generator_bin);
+                cmd.args(args.iter());
+
+                let spawned = self.client.spawn(cmd);
+                private_key.zeroize();
+                args.zeroize();
+                if let Err(err) = spawned {
+                    self.update_logs(format!("Rothschild: failed to start ({})", err))
+                        .await;
+                    self.client.schedule_restart(self, "failed to start");
+                    return Err(err);
+                }
+
+                self.update_logs(format!(
+                    "Rothschild: started at {tps} TPS against {kaspad_address}"
+                ))
+                .await;
+
+                self.client.supervise(self);
+                Ok(())
+            }
+
+            async fn stop_generator(self: &Arc<Self>) -> Result<()> {
+                if self.client.stop().await {
+                    self.update_logs(i18n("Rothschild: stopped").to_string()).await;
+                }
+                Ok(())
+            }
+        }
+
+        #[async_trait]
+        impl NodeClientService for RothschildService {
+            fn client(&self) -> &NodeClientProcess {
+                &self.client
+            }
+
+            async fn start(self: Arc<Self>) -> Result<()> {
+                self.start_generator().await
+            }
+        }
+
+        #[async_trait]
+        impl Service for RothschildService {
+            fn name(&self) -> &'static str {
+                "rothschild-service"
+            }
+
+            async fn spawn(self: Arc<Self>) -> Result<()> {
+                let this = self.clone();
+                tokio::spawn(async move {
+                    if this.client.is_enabled() {
+                        this.client.launch(&this);
+                    }
+
+                    loop {
+                        select! {
+                            msg = this.service_events.receiver.recv().fuse() => {
+                                match msg {
+                                    Ok(RothschildEvents::SetEnabled { enabled, settings }) => {
+                                        let enabled = enabled && Self::is_network_allowed(settings.network);
+                                        this.client.set_enabled(enabled);
+                                        *this.node_settings.lock().unwrap() = settings;
+                                        if enabled {
+                                            this.client.launch(&this);
+                                        } else {
+                                            let _ = this.stop_generator().await;
+                                        }
+                                    }
+                                    Ok(RothschildEvents::UpdateSettings(settings)) => {
+                                        let changed = {
+                                            let current = this.node_settings.lock().unwrap();
+                                            current.rothschild != settings.rothschild
+                                                || node_grpc_address(&current) != node_grpc_address(&settings)
+                                                || current.network != settings.network
+                                        };
+                                        if !Self::is_network_allowed(settings.network) {
+                                            this.client.set_enabled(false);
+                                        }
+                                        *this.node_settings.lock().unwrap() = settings;
+                                        if !this.client.is_enabled() {
+                                            let _ = this.stop_generator().await;
+                                        } else if changed {
+                                            let _ = this.stop_generator().await;
+                                            this.client.launch(&this);
+                                        }
+                                    }
+                                    Ok(RothschildEvents::Exit) | Err(_) => {
+                                        this.client.set_enabled(false);
+                                        let _ = this.stop_generator().await;
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    this.task_ctl.send(()).await.unwrap();
+                });
+
+                Ok(())
+            }
+
+            fn terminate(self: Arc<Self>) {
+                self.service_events.sender.try_send(RothschildEvents::Exit).unwrap();
+            }
+
+            async fn join(self: Arc<Self>) -> Result<()> {
+                self.task_ctl.recv().await.unwrap();
+                Ok(())
+            }
+        }
+
+    } else {
+        pub struct RothschildService;
+
+        impl RothschildService {
+            pub fn new(_application_events: ApplicationEventsChannel, _settings: &Settings) -> Self {
+                Self
+            }
+
+            pub fn enable(&self, _enabled: bool, _node_settings: &NodeSettings) {}
+
+            pub fn update_settings(&self, _node_settings: &NodeSettings) {}
+
+            pub fn is_network_allowed(_network: Network) -> bool {
+                false
+            }
+        }
+
+        pub fn update_logs_flag() -> &'static Arc<AtomicBool> {
+            static FLAG: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+            FLAG.get_or_init(|| Arc::new(AtomicBool::new(false)))
+        }
+
+        #[async_trait]
+        impl Service for RothschildService {
+            fn name(&self) -> &'static str {
+                "rothschild-service"
+            }
+
+            async fn spawn(self: Arc<Self>) -> Result<()> {
+                Ok(())
+            }
+
+            fn terminate(self: Arc<Self>) {}
+
+            async fn join(self: Arc<Self>) -> Result<()> {
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn private_key_is_passed_as_argument() {
+        let private_key = "ab".repeat(32);
+        let change_address = Address::new(
+            kaspa_addresses::Prefix::Testnet,
+            kaspa_addresses::Version::PubKey,
+            &[1u8; 32],
+        );
+        let args = RothschildService::generator_args(
+            "127.0.0.1:16210",
+            10,
+            2,
+            Some(private_key.as_str()),
+            Some(&change_address),
+        );
+        let position = args.iter().position(|arg| arg == "--private-key").unwrap();
+        assert_eq!(args[position + 1], private_key);
+        assert!(args.contains(&change_address.to_string()));
+
+        let args = RothschildService::generator_args("127.0.0.1:16210", 10, 2, None, None);
+        assert!(!args.iter().any(|arg| arg == "--private-key"));
+        assert_eq!(args[..2], ["--rpcserver", "127.0.0.1:16210"]);
+    }
+}
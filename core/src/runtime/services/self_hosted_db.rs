@@ -1,11 +1,15 @@
 use crate::imports::*;
+use crate::runtime::Runtime;
+use crate::runtime::services::prometheus::{Exposition, PROMETHEUS_CONTENT_TYPE};
+use crate::runtime::services::stratum_bridge::BridgeStats;
 use crate::runtime::services::{
-    LoaderStatusSnapshot, LogStores, SelfHostedKasiaIndexerService, SharedLoaderStatus,
+    LoaderStatusSnapshot, LogStores, ProcessStatus, SelfHostedKasiaIndexerService,
+    SharedLoaderStatus,
 };
 use axum::{
     Json, Router,
     extract::{Path as AxumPath, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Response, Sse},
     routing::get,
 };
@@ -53,6 +57,7 @@ impl DbConfig {
 
 #[derive(Clone)]
 struct AppState {
+    network: Network,
     db: DbConfig,
     logs: LogStores,
     loader_status: SharedLoaderStatus,
@@ -99,6 +104,25 @@ struct LogResponse {
     lines: Vec<crate::runtime::services::log_store::LogLine>,
 }
 
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NodeStatus {
+    connected: bool,
+    synced: bool,
+    network: Option<String>,
+    url: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ServicesPayload {
+    uptime_seconds: u64,
+    node: NodeStatus,
+    loader: LoaderStatusSnapshot,
+    processes: Vec<ProcessStatus>,
+    timestamp: String,
+}
+
 #[derive(Clone, Default, Deserialize)]
 #[serde(rename_all = "snake_case")]
 struct KasiaMetricsSnapshot {
@@ -453,6 +477,320 @@ async fn loader_status_handler(State(state): State<AppState>) -> impl IntoRespon
     Json(snapshot)
 }
 
+fn process_statuses(runtime: &Runtime) -> Vec<ProcessStatus> {
+    [
+        runtime.self_hosted_postgres_service().process_status(),
+        runtime.self_hosted_indexer_service().process_status(),
+        runtime.self_hosted_k_indexer_service().process_status(),
+        runtime.self_hosted_kasia_indexer_service().process_status(),
+        runtime.self_hosted_explorer_service().process_status(),
+        runtime.stratum_bridge_service().process_status(),
+        runtime.cpu_miner_service().process_status(),
+        runtime.rothschild_service().process_status(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+async fn services_handler(State(state): State<AppState>) -> Response {
+    let Some(runtime) = crate::runtime::try_runtime() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorPayload {
+                error: "runtime is not running".to_string(),
+            }),
+        )
+            .into_response();
+    };
+
+    let wallet = runtime.wallet();
+    let node = match tokio::time::timeout(Duration::from_secs(4), wallet.get_status(None)).await {
+        Ok(Ok(status)) => NodeStatus {
+            connected: status.is_connected,
+            synced: status.is_synced,
+            network: status.network_id.map(|network_id| network_id.to_string()),
+            url: status.url,
+        },
+        _ => NodeStatus::default(),
+    };
+
+    Json(ServicesPayload {
+        uptime_seconds: runtime.uptime().as_secs(),
+        node,
+        loader: state.loader_status.snapshot(),
+        processes: process_statuses(&runtime),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    })
+    .into_response()
+}
+
+/// Converts a metric identifier such as `NodeCpuUsage` to `node_cpu_usage`.
+fn snake_case(name: &str) -> String {
+    let mut text = String::with_capacity(name.len() + 8);
+    for (index, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if index > 0 && !text.ends_with('_') {
+                text.push('_');
+            }
+            text.push(c.to_ascii_lowercase());
+        } else {
+            text.push(c);
+        }
+    }
+    text
+}
+
+fn collect_runtime_metrics(runtime: &Runtime, metrics: &mut Exposition) {
+    metrics.gauge(
+        "kaspa_ng_uptime_seconds",
+        "Time since kaspa-ng was started",
+        &[],
+        runtime.uptime().as_secs_f64(),
+    );
+
+    let node_metrics = runtime.metrics_service().latest_values();
+    metrics.gauge(
+        "kaspa_ng_node_up",
+        "Whether node metrics are being received",
+        &[],
+        if node_metrics.is_some() { 1.0 } else { 0.0 },
+    );
+    for (metric, value) in node_metrics.unwrap_or_default() {
+        metrics.gauge(
+            &format!("kaspa_{}", snake_case(metric.as_str())),
+            metric.title().1,
+            &[],
+            value,
+        );
+    }
+
+    if let Some(peers) = runtime.peer_monitor_service().peer_info() {
+        let outbound = peers.iter().filter(|peer| peer.is_outbound).count();
+        for (direction, count) in [("outbound", outbound), ("inbound", peers.len() - outbound)] {
+            metrics.gauge(
+                "kaspa_ng_peers",
+                "Connected peers by direction",
+                &[("direction", direction)],
+                count as f64,
+            );
+        }
+    }
+
+    if let Some(estimate) = runtime.feerate_monitor_service().feerate() {
+        for (kind, buckets) in [
+            ("priority", std::slice::from_ref(&estimate.priority_bucket)),
+            ("normal", estimate.normal_buckets.as_slice()),
+            ("low", estimate.low_buckets.as_slice()),
+        ] {
+            for (index, bucket) in buckets.iter().enumerate() {
+                let index = index.to_string();
+                let labels = [("bucket", kind), ("index", index.as_str())];
+                metrics.gauge(
+                    "kaspa_feerate",
+                    "Fee estimate in sompi per gram of mass",
+                    &labels,
+                    bucket.feerate,
+                );
+                metrics.gauge(
+                    "kaspa_feerate_estimated_seconds",
+                    "Estimated confirmation time of the feerate bucket",
+                    &labels,
+                    bucket.estimated_seconds,
+                );
+            }
+        }
+    }
+
+    for process in process_statuses(runtime) {
+        let labels = [("process", process.name.as_str())];
+        metrics.gauge(
+            "kaspa_ng_process_up",
+            "Whether the supervised process is running",
+            &labels,
+            if process.running { 1.0 } else { 0.0 },
+        );
+        if let Some(uptime) = process.uptime_seconds {
+            metrics.gauge(
+                "kaspa_ng_process_uptime_seconds",
+                "Time since the supervised process was started",
+                &labels,
+                uptime as f64,
+            );
+        }
+        metrics.counter(
+            "kaspa_ng_process_failures_total",
+            "Unexpected exits of the supervised process",
+            &labels,
+            process.failures as f64,
+        );
+    }
+
+    if let Some(stats) = runtime.stratum_bridge_service().stats() {
+        collect_bridge_metrics(&stats, metrics);
+    }
+}
+
+fn collect_bridge_metrics(stats: &BridgeStats, metrics: &mut Exposition) {
+    metrics.gauge(
+        "kaspa_ng_bridge_workers",
+        "Workers connected to the stratum bridge",
+        &[],
+        stats.workers.len() as f64,
+    );
+    if let Some(difficulty) = stats.network_difficulty {
+        metrics.gauge(
+            "kaspa_ng_bridge_network_difficulty",
+            "Network difficulty reported by the stratum bridge",
+            &[],
+            difficulty,
+        );
+    }
+    if let Some(hashrate) = stats.network_hashrate {
+        metrics.gauge(
+            "kaspa_ng_bridge_network_hashrate",
+            "Estimated network hashrate in hashes per second",
+            &[],
+            hashrate,
+        );
+    }
+
+    for worker in stats.workers.iter() {
+        let labels = [
+            ("worker", worker.worker.as_str()),
+            ("wallet", worker.wallet.as_str()),
+        ];
+        metrics.gauge(
+            "kaspa_ng_bridge_worker_hashrate",
+            "Estimated worker hashrate in hashes per second",
+            &labels,
+            worker.hashrate,
+        );
+        metrics.gauge(
+            "kaspa_ng_bridge_worker_difficulty",
+            "Current stratum difficulty of the worker",
+            &labels,
+            worker.difficulty,
+        );
+        metrics.counter(
+            "kaspa_ng_bridge_worker_shares_total",
+            "Valid shares submitted by the worker",
+            &labels,
+            worker.shares as f64,
+        );
+        for (kind, count) in [
+            ("stale", worker.stale),
+            ("duplicate", worker.duplicate),
+            ("invalid", worker.invalid),
+        ] {
+            metrics.counter(
+                "kaspa_ng_bridge_worker_rejected_shares_total",
+                "Rejected shares submitted by the worker",
+                &[labels[0], labels[1], ("type", kind)],
+                count as f64,
+            );
+        }
+        metrics.counter(
+            "kaspa_ng_bridge_worker_blocks_total",
+            "Blocks found by the worker",
+            &labels,
+            worker.blocks as f64,
+        );
+    }
+}
+
+fn collect_loader_metrics(status: &LoaderStatusSnapshot, metrics: &mut Exposition) {
+    metrics.gauge(
+        "kaspa_ng_loader_phase",
+        "Current phase of the self-hosted loader",
+        &[("phase", status.phase_id.slug())],
+        1.0,
+    );
+    for (component, ready) in [
+        ("node", status.connected),
+        ("postgres", status.postgres_ready),
+        ("indexers", status.indexers_ready),
+        ("rest", status.rest_ready),
+        ("socket", status.socket_ready),
+    ] {
+        metrics.gauge(
+            "kaspa_ng_loader_ready",
+            "Readiness of the self-hosted components",
+            &[("component", component)],
+            if ready { 1.0 } else { 0.0 },
+        );
+    }
+}
+
+fn collect_postgres_metrics(payload: Option<&StatusPayload>, metrics: &mut Exposition) {
+    metrics.gauge(
+        "kaspa_ng_postgres_up",
+        "Whether the self-hosted database is reachable",
+        &[],
+        if payload.is_some() { 1.0 } else { 0.0 },
+    );
+    let Some(payload) = payload else {
+        return;
+    };
+
+    metrics.gauge(
+        "kaspa_ng_postgres_database_size_bytes",
+        "Size of the self-hosted database",
+        &[],
+        payload.db_size_bytes as f64,
+    );
+    metrics.gauge(
+        "kaspa_ng_postgres_connected_clients",
+        "Clients connected to the self-hosted database",
+        &[],
+        payload.connected_clients as f64,
+    );
+    metrics.gauge(
+        "kaspa_ng_postgres_uptime_seconds",
+        "Time since the database server was started",
+        &[],
+        payload.uptime_seconds as f64,
+    );
+    for table in payload.table_stats.iter() {
+        let labels = [("table", table.table_name.as_str())];
+        metrics.gauge(
+            "kaspa_ng_postgres_table_rows",
+            "Estimated live rows per table",
+            &labels,
+            table.live_rows as f64,
+        );
+        metrics.gauge(
+            "kaspa_ng_postgres_table_size_bytes",
+            "Total size per table including indexes",
+            &labels,
+            table.total_size_bytes as f64,
+        );
+    }
+}
+
+async fn metrics_handler(State(state): State<AppState>) -> Response {
+    let network = state.network.to_string();
+    let mut metrics = Exposition::new(&[("network", network.as_str())]);
+
+    if let Some(runtime) = crate::runtime::try_runtime() {
+        collect_runtime_metrics(&runtime, &mut metrics);
+    }
+    collect_loader_metrics(&state.loader_status.snapshot(), &mut metrics);
+
+    // keep the scrape responsive when the database is down
+    let payload = tokio::time::timeout(Duration::from_secs(5), collect_stats(&state))
+        .await
+        .ok()
+        .and_then(|result| result.ok());
+    collect_postgres_metrics(payload.as_ref(), &mut metrics);
+
+    (
+        [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        metrics.render(),
+    )
+        .into_response()
+}
+
 #[derive(Deserialize)]
 struct LogsQuery {
     limit: Option<usize>,
@@ -590,6 +928,7 @@ impl SelfHostedDbService {
             .join("partitions");
 
         let state = AppState {
+            network: node_settings.network,
             db: DbConfig {
                 host: settings.db_host,
                 port: db_port,
@@ -608,7 +947,9 @@ impl SelfHostedDbService {
             .route("/api/status/stream", get(status_stream_handler))
             .route("/api/healthz", get(health_handler))
             .route("/api/loader-status", get(loader_status_handler))
+            .route("/api/services", get(services_handler))
             .route("/api/logs/:service", get(logs_handler))
+            .route("/metrics", get(metrics_handler))
             .with_state(state);
 
         let listener = match TcpListener::bind(addr).await {
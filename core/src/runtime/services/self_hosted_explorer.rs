@@ -1,12 +1,16 @@
 use crate::imports::*;
+use crate::runtime::services::process_supervisor::{
+    self, HealthProbe, LogStoreOutput, ProcessStatus, ProcessSupervisor, RestartPolicy,
+};
 use crate::runtime::services::{LogStore, LogStores};
 use std::net::TcpListener;
 use std::process::Stdio;
-use std::time::{Duration as StdDuration, Instant};
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::net::TcpStream;
-use tokio::process::{Child, Command};
-use tokio::time::{sleep, timeout};
+use std::time::Duration as StdDuration;
+use tokio::process::Command;
+use tokio::time::sleep;
+
+const RESTART_POLICY: RestartPolicy =
+    RestartPolicy::backoff(StdDuration::from_secs(5), StdDuration::from_secs(120));
 
 pub enum SelfHostedExplorerEvents {
     Enable,
@@ -27,10 +31,8 @@ pub struct SelfHostedExplorerService {
     pub is_enabled: AtomicBool,
     rest_logs: Arc<LogStore>,
     socket_logs: Arc<LogStore>,
-    rest_child: Mutex<Option<Child>>,
-    socket_child: Mutex<Option<Child>>,
-    rest_start_cooldown_until: Mutex<Option<Instant>>,
-    socket_start_cooldown_until: Mutex<Option<Instant>>,
+    rest: Arc<ProcessSupervisor>,
+    socket: ProcessSupervisor,
 }
 
 impl SelfHostedExplorerService {
@@ -93,16 +95,6 @@ impl SelfHostedExplorerService {
         crate::settings::node_grpc_port_for_network(network)
     }
 
-    fn running_from_macos_bundle() -> bool {
-        #[cfg(target_os = "macos")]
-        {
-            if let Ok(exe) = std::env::current_exe() {
-                return exe.to_string_lossy().contains(".app/Contents/MacOS/");
-            }
-        }
-        false
-    }
-
     fn detect_log_level<'a>(line: &str, fallback: &'a str) -> &'a str {
         let upper = line.to_ascii_uppercase();
         if upper.contains("DEBUG") || upper.contains("[DEBUG]") || upper.contains("::DEBUG::") {
@@ -128,6 +120,13 @@ impl SelfHostedExplorerService {
         settings: &Settings,
         logs: LogStores,
     ) -> Self {
+        let rest =
+            Arc::new(ProcessSupervisor::new("REST server").with_restart_policy(RESTART_POLICY));
+        // the socket server subscribes to the REST server and only starts once it answers
+        let socket = ProcessSupervisor::new("socket server")
+            .with_restart_policy(RESTART_POLICY)
+            .depends_on(&rest);
+
         Self {
             application_events,
             service_events: Channel::unbounded(),
@@ -137,10 +136,8 @@ impl SelfHostedExplorerService {
             is_enabled: AtomicBool::new(false),
             rest_logs: logs.rest,
             socket_logs: logs.socket,
-            rest_child: Mutex::new(None),
-            socket_child: Mutex::new(None),
-            rest_start_cooldown_until: Mutex::new(None),
-            socket_start_cooldown_until: Mutex::new(None),
+            rest,
+            socket,
         }
     }
 
@@ -168,6 +165,10 @@ impl SelfHostedExplorerService {
             || prev.grpc_network_interface != next.grpc_network_interface
     }
 
+    pub fn process_status(&self) -> Vec<ProcessStatus> {
+        vec![self.rest.status(), self.socket.status()]
+    }
+
     pub fn enable(&self, enable: bool) {
         if enable {
             self.service_events
@@ -247,49 +248,6 @@ impl SelfHostedExplorerService {
         )
     }
 
-    fn find_in_path(bin_name: &str) -> Option<PathBuf> {
-        let path_var = std::env::var_os("PATH")?;
-        for dir in std::env::split_paths(&path_var) {
-            let candidate = dir.join(bin_name);
-            if candidate.exists() {
-                return Some(candidate);
-            }
-
-            #[cfg(windows)]
-            {
-                if Path::new(bin_name).extension().is_none() {
-                    let pathext = std::env::var_os("PATHEXT")
-                        .map(|value| {
-                            value
-                                .to_string_lossy()
-                                .split(';')
-                                .map(|s| s.trim().to_ascii_lowercase())
-                                .filter(|s| !s.is_empty())
-                                .collect::<Vec<_>>()
-                        })
-                        .unwrap_or_else(|| {
-                            vec![
-                                ".exe".to_string(),
-                                ".cmd".to_string(),
-                                ".bat".to_string(),
-                                ".com".to_string(),
-                            ]
-                        });
-
-                    for ext in pathext {
-                        let ext = ext.trim_start_matches('.');
-                        let candidate = dir.join(format!("{bin_name}.{ext}"));
-                        if candidate.exists() {
-                            return Some(candidate);
-                        }
-                    }
-                }
-            }
-        }
-
-        None
-    }
-
     fn find_executable(bin_name: &str, extra_dirs: &[PathBuf]) -> Option<PathBuf> {
         for dir in extra_dirs {
             let candidate = dir.join(bin_name);
@@ -297,7 +255,7 @@ impl SelfHostedExplorerService {
                 return Some(candidate);
             }
         }
-        Self::find_in_path(bin_name)
+        process_supervisor::find_in_path(bin_name)
     }
 
     fn find_poetry() -> Option<PathBuf> {
@@ -606,16 +564,16 @@ impl SelfHostedExplorerService {
                 );
             }
 
-            if let Some(path_python3) = Self::find_in_path("python3") {
+            if let Some(path_python3) = process_supervisor::find_in_path("python3") {
                 candidates.push(path_python3);
             }
-            if let Some(path_python) = Self::find_in_path("python") {
+            if let Some(path_python) = process_supervisor::find_in_path("python") {
                 candidates.push(path_python);
             }
 
             #[cfg(windows)]
             {
-                if let Some(path_py) = Self::find_in_path("py") {
+                if let Some(path_py) = process_supervisor::find_in_path("py") {
                     candidates.push(path_py);
                 }
             }
@@ -780,7 +738,7 @@ impl SelfHostedExplorerService {
 
     fn find_server_root(name: &str) -> Option<PathBuf> {
         let mut candidates = Vec::new();
-        let is_macos_bundle = Self::running_from_macos_bundle();
+        let is_macos_bundle = process_supervisor::running_from_macos_bundle();
 
         if let Ok(root) = std::env::var("KASPA_NG_EXPLORER_SERVERS_ROOT") {
             candidates.push(PathBuf::from(root).join(name));
@@ -1239,33 +1197,6 @@ impl SelfHostedExplorerService {
         TcpListener::bind(addr).is_ok()
     }
 
-    fn child_is_running(
-        child: &mut Option<Child>,
-        process_name: &str,
-        logs: &Arc<LogStore>,
-    ) -> bool {
-        let Some(proc) = child.as_mut() else {
-            return false;
-        };
-
-        match proc.try_wait() {
-            Ok(Some(status)) => {
-                logs.push(
-                    "WARN",
-                    &format!("{process_name} exited with status: {status}"),
-                );
-                *child = None;
-                false
-            }
-            Ok(None) => true,
-            Err(err) => {
-                logs.push("WARN", &format!("{process_name} state check failed: {err}"));
-                *child = None;
-                false
-            }
-        }
-    }
-
     fn probe_host_from_bind(bind: &str) -> String {
         let trimmed = bind.trim();
         if trimmed.is_empty() || trimmed == "0.0.0.0" || trimmed == "::" || trimmed == "[::]" {
@@ -1275,34 +1206,11 @@ impl SelfHostedExplorerService {
         }
     }
 
-    async fn wait_for_tcp_healthy(host: &str, port: u16, deadline: StdDuration) -> bool {
-        let started = Instant::now();
-        while started.elapsed() < deadline {
-            let connect = timeout(
-                StdDuration::from_millis(900),
-                TcpStream::connect((host, port)),
-            )
-            .await;
-            if let Ok(Ok(_)) = connect {
-                return true;
-            }
-            sleep(StdDuration::from_millis(250)).await;
-        }
-        false
-    }
-
     async fn start_rest(self: &Arc<Self>) -> Result<()> {
-        if self.rest_child.lock().unwrap().is_some() {
+        if self.rest.is_running().await || !self.rest.ready_to_start() {
             return Ok(());
         }
 
-        if let Some(until) = *self.rest_start_cooldown_until.lock().unwrap() {
-            if Instant::now() < until {
-                return Ok(());
-            }
-            *self.rest_start_cooldown_until.lock().unwrap() = None;
-        }
-
         let settings = self.settings.lock().unwrap().clone();
         let node_settings = self.node_settings.lock().unwrap().clone();
 
@@ -1355,92 +1263,39 @@ impl SelfHostedExplorerService {
 
         cmd.current_dir(&root);
         Self::apply_common_env(&mut cmd, &settings, &node_settings);
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
-        #[cfg(unix)]
-        {
-            cmd.process_group(0);
+        self.rest.set_health_probe(Some(HealthProbe::tcp(
+            Self::probe_host_from_bind(&settings.api_bind),
+            rest_port,
+        )));
+        let output = LogStoreOutput::new(&self.rest_logs)
+            .with_console("self-hosted-rest")
+            .with_level_detector(Self::detect_log_level);
+        if let Err(err) = self.rest.spawn(cmd, Arc::new(output)) {
+            log_warn!("self-hosted-explorer: failed to start rest server ({err})");
+            let retry = self.rest.retry_delay().unwrap_or_default();
+            self.rest_logs.push(
+                "WARN",
+                &format!(
+                    "REST server start failed; suppressing restart attempts for {}s",
+                    retry.as_secs()
+                ),
+            );
+            return Err(err);
         }
 
-        #[cfg(windows)]
-        {
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
-        }
-
-        let mut child = match cmd.spawn() {
-            Ok(child) => child,
-            Err(err) => {
-                let err = Error::NodeStartupError(err);
-                let cooldown = StdDuration::from_secs(30);
-                *self.rest_start_cooldown_until.lock().unwrap() = Some(Instant::now() + cooldown);
-                log_warn!("self-hosted-explorer: failed to start rest server ({err})");
-                self.rest_logs.push(
-                    "WARN",
-                    &format!(
-                        "REST server start failed; suppressing restart attempts for {}s",
-                        cooldown.as_secs()
-                    ),
-                );
-                return Err(err);
-            }
-        };
-
-        *self.rest_start_cooldown_until.lock().unwrap() = None;
-
         self.rest_logs.push(
             "INFO",
             &format!("REST API listening on {}:{}", settings.api_bind, rest_port),
         );
-
-        let logs_info = self.rest_logs.clone();
-        if let Some(stdout) = child.stdout.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stdout).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    let level = Self::detect_log_level(&line, "INFO");
-                    match level {
-                        "ERROR" => log_warn!("self-hosted-rest: {line}"),
-                        "WARN" => log_warn!("self-hosted-rest: {line}"),
-                        _ => log_info!("self-hosted-rest: {line}"),
-                    }
-                    logs_info.push(level, &line);
-                }
-            });
-        }
-
-        let logs_warn = self.rest_logs.clone();
-        if let Some(stderr) = child.stderr.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stderr).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    let level = Self::detect_log_level(&line, "WARN");
-                    match level {
-                        "ERROR" => log_warn!("self-hosted-rest: {line}"),
-                        "WARN" => log_warn!("self-hosted-rest: {line}"),
-                        _ => log_info!("self-hosted-rest: {line}"),
-                    }
-                    logs_warn.push(level, &line);
-                }
-            });
-        }
-
-        *self.rest_child.lock().unwrap() = Some(child);
         Ok(())
     }
 
     async fn start_socket(self: &Arc<Self>) -> Result<()> {
-        if self.socket_child.lock().unwrap().is_some() {
+        if self.socket.is_running().await || !self.socket.ready_to_start() {
             return Ok(());
         }
 
-        if let Some(until) = *self.socket_start_cooldown_until.lock().unwrap() {
-            if Instant::now() < until {
-                return Ok(());
-            }
-            *self.socket_start_cooldown_until.lock().unwrap() = None;
-        }
-
         let settings = self.settings.lock().unwrap().clone();
         let node_settings = self.node_settings.lock().unwrap().clone();
 
@@ -1494,39 +1349,27 @@ impl SelfHostedExplorerService {
 
         cmd.current_dir(&root);
         Self::apply_common_env(&mut cmd, &settings, &node_settings);
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
-        #[cfg(unix)]
-        {
-            cmd.process_group(0);
+        self.socket.set_health_probe(Some(HealthProbe::tcp(
+            Self::probe_host_from_bind(&settings.api_bind),
+            socket_port,
+        )));
+        let output = LogStoreOutput::new(&self.socket_logs)
+            .with_console("self-hosted-socket")
+            .with_level_detector(Self::detect_log_level);
+        if let Err(err) = self.socket.spawn(cmd, Arc::new(output)) {
+            log_warn!("self-hosted-explorer: failed to start socket server ({err})");
+            let retry = self.socket.retry_delay().unwrap_or_default();
+            self.socket_logs.push(
+                "WARN",
+                &format!(
+                    "Socket server start failed; suppressing restart attempts for {}s",
+                    retry.as_secs()
+                ),
+            );
+            return Err(err);
         }
 
-        #[cfg(windows)]
-        {
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
-        }
-
-        let mut child = match cmd.spawn() {
-            Ok(child) => child,
-            Err(err) => {
-                let err = Error::NodeStartupError(err);
-                let cooldown = StdDuration::from_secs(30);
-                *self.socket_start_cooldown_until.lock().unwrap() = Some(Instant::now() + cooldown);
-                log_warn!("self-hosted-explorer: failed to start socket server ({err})");
-                self.socket_logs.push(
-                    "WARN",
-                    &format!(
-                        "Socket server start failed; suppressing restart attempts for {}s",
-                        cooldown.as_secs()
-                    ),
-                );
-                return Err(err);
-            }
-        };
-
-        *self.socket_start_cooldown_until.lock().unwrap() = None;
-
         self.socket_logs.push(
             "INFO",
             &format!(
@@ -1534,114 +1377,36 @@ impl SelfHostedExplorerService {
                 settings.api_bind, socket_port
             ),
         );
-
-        let logs_info = self.socket_logs.clone();
-        if let Some(stdout) = child.stdout.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stdout).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    let level = Self::detect_log_level(&line, "INFO");
-                    match level {
-                        "ERROR" => log_warn!("self-hosted-socket: {line}"),
-                        "WARN" => log_warn!("self-hosted-socket: {line}"),
-                        _ => log_info!("self-hosted-socket: {line}"),
-                    }
-                    logs_info.push(level, &line);
-                }
-            });
-        }
-
-        let logs_warn = self.socket_logs.clone();
-        if let Some(stderr) = child.stderr.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stderr).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    let level = Self::detect_log_level(&line, "WARN");
-                    match level {
-                        "ERROR" => log_warn!("self-hosted-socket: {line}"),
-                        "WARN" => log_warn!("self-hosted-socket: {line}"),
-                        _ => log_info!("self-hosted-socket: {line}"),
-                    }
-                    logs_warn.push(level, &line);
-                }
-            });
-        }
-
-        *self.socket_child.lock().unwrap() = Some(child);
         Ok(())
     }
 
     async fn stop_rest(&self) -> Result<()> {
-        let child = self.rest_child.lock().unwrap().take();
-        if let Some(mut child) = child {
-            #[cfg(unix)]
-            {
-                Self::terminate_process_tree(&mut child).await;
-            }
-            #[cfg(not(unix))]
-            {
-                let _ = child.start_kill();
-                let _ = child.wait().await;
-            }
-        }
+        self.rest.reset();
+        self.rest.stop().await;
         Ok(())
     }
 
     async fn stop_socket(&self) -> Result<()> {
-        let child = self.socket_child.lock().unwrap().take();
-        if let Some(mut child) = child {
-            #[cfg(unix)]
-            {
-                Self::terminate_process_tree(&mut child).await;
-            }
-            #[cfg(not(unix))]
-            {
-                let _ = child.start_kill();
-                let _ = child.wait().await;
-            }
-        }
+        self.socket.reset();
+        self.socket.stop().await;
         Ok(())
     }
 
-    #[cfg(unix)]
-    async fn terminate_process_tree(child: &mut Child) {
-        use nix::sys::signal::{Signal, killpg};
-        use nix::unistd::Pid;
-
-        if let Some(pid) = child.id() {
-            let pgid = Pid::from_raw(pid as i32);
-            let _ = killpg(pgid, Signal::SIGTERM);
-            if timeout(StdDuration::from_secs(2), child.wait())
-                .await
-                .is_ok()
-            {
-                return;
-            }
-            let _ = killpg(pgid, Signal::SIGKILL);
-            let _ = timeout(StdDuration::from_secs(2), child.wait()).await;
-            sleep(StdDuration::from_millis(100)).await;
-        } else {
-            let _ = child.start_kill();
-            let _ = child.wait().await;
-        }
-    }
-
     async fn start_all(self: &Arc<Self>) -> Result<()> {
         let settings = self.settings.lock().unwrap().clone();
         let node_settings = self.node_settings.lock().unwrap().clone();
         let probe_host = Self::probe_host_from_bind(&settings.api_bind);
         let rest_port = settings.effective_explorer_rest_port(node_settings.network);
-        let socket_port = settings.effective_explorer_socket_port(node_settings.network);
 
         let _ = self.start_rest().await;
-        if self.rest_child.lock().unwrap().is_none() {
+        if !self.rest.has_child() {
             self.socket_logs.push(
                 "WARN",
                 "Socket startup skipped because REST server is not running",
             );
             return Ok(());
         }
-        if !Self::wait_for_tcp_healthy(&probe_host, rest_port, StdDuration::from_secs(30)).await {
+        if !self.rest.wait_healthy(StdDuration::from_secs(30)).await {
             self.socket_logs.push(
                 "WARN",
                 &format!(
@@ -1653,36 +1418,22 @@ impl SelfHostedExplorerService {
         }
 
         let _ = self.start_socket().await;
-        if self.socket_child.lock().unwrap().is_some() {
-            let _ =
-                Self::wait_for_tcp_healthy(&probe_host, socket_port, StdDuration::from_secs(20))
-                    .await;
+        if self.socket.has_child() {
+            let _ = self.socket.wait_healthy(StdDuration::from_secs(20)).await;
         }
         Ok(())
     }
 
     async fn ensure_running(self: &Arc<Self>) {
-        let rest_running = {
-            let mut guard = self.rest_child.lock().unwrap();
-            Self::child_is_running(&mut guard, "REST server", &self.rest_logs)
-        };
-        if !rest_running {
+        if !self.rest.is_running().await {
             let _ = self.start_rest().await;
         }
 
-        let rest_running = {
-            let mut guard = self.rest_child.lock().unwrap();
-            Self::child_is_running(&mut guard, "REST server", &self.rest_logs)
-        };
-        if !rest_running {
+        if !self.socket.dependencies_ready().await {
             return;
         }
 
-        let socket_running = {
-            let mut guard = self.socket_child.lock().unwrap();
-            Self::child_is_running(&mut guard, "socket server", &self.socket_logs)
-        };
-        if !socket_running {
+        if !self.socket.is_running().await {
             let _ = self.start_socket().await;
         }
     }
@@ -1,13 +1,14 @@
 use crate::imports::*;
+use crate::runtime::services::process_supervisor::{
+    self, OutputStream, ProcessOutput, ProcessStatus, ProcessSupervisor, RestartPolicy, StopPolicy,
+};
 use crate::runtime::services::{LogStore, LogStores};
 use std::collections::HashSet;
-use std::net::TcpListener;
 use std::path::PathBuf;
-use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::{Child, Command};
-#[cfg(unix)]
-use tokio::time::timeout;
+use tokio::process::Command;
+
+const RESTART_POLICY: RestartPolicy =
+    RestartPolicy::backoff(Duration::from_secs(5), Duration::from_secs(120));
 
 pub enum SelfHostedIndexerEvents {
     Enable,
@@ -25,7 +26,7 @@ pub struct SelfHostedIndexerService {
     pub node_settings: Mutex<NodeSettings>,
     pub is_enabled: AtomicBool,
     logs: Arc<LogStore>,
-    child: Mutex<Option<Child>>,
+    process: Arc<ProcessSupervisor>,
     retention_checkpoint_recovery_requested: AtomicBool,
     retention_checkpoint_recovery_done_for_network: Mutex<HashSet<String>>,
 }
@@ -38,107 +39,6 @@ impl SelfHostedIndexerService {
         ))
     }
 
-    fn write_pidfile(network: Network, pid: u32) {
-        let _ = std::fs::write(Self::pidfile_path(network), pid.to_string());
-    }
-
-    fn remove_pidfile(network: Network) {
-        let _ = std::fs::remove_file(Self::pidfile_path(network));
-    }
-
-    #[cfg(unix)]
-    fn process_is_running(pid: u32) -> bool {
-        use nix::errno::Errno;
-        use nix::sys::signal::kill;
-        use nix::unistd::Pid;
-
-        let Ok(pid_i32) = i32::try_from(pid) else {
-            return false;
-        };
-
-        match kill(Pid::from_raw(pid_i32), None) {
-            Ok(_) => true,
-            Err(Errno::EPERM) => true,
-            Err(_) => false,
-        }
-    }
-
-    #[cfg(unix)]
-    async fn stop_pidfile_indexer_if_needed(
-        listen: &str,
-        node: &NodeSettings,
-        logs: &Arc<LogStore>,
-    ) {
-        use nix::sys::signal::{Signal, kill};
-        use nix::unistd::Pid;
-
-        let path = Self::pidfile_path(node.network);
-        let Ok(raw) = std::fs::read_to_string(&path) else {
-            return;
-        };
-        let Ok(pid) = raw.trim().parse::<u32>() else {
-            let _ = std::fs::remove_file(&path);
-            return;
-        };
-        if !Self::process_is_running(pid) {
-            let _ = std::fs::remove_file(&path);
-            return;
-        }
-        let Ok(pid_i32) = i32::try_from(pid) else {
-            return;
-        };
-
-        logs.push(
-            "WARN",
-            &format!("indexer listen address busy on {listen}; stopping stale indexer pid {pid}"),
-        );
-        let _ = kill(Pid::from_raw(pid_i32), Signal::SIGTERM);
-
-        for _ in 0..12 {
-            if !Self::process_is_running(pid) {
-                break;
-            }
-            tokio::time::sleep(std::time::Duration::from_millis(250)).await;
-        }
-
-        if Self::process_is_running(pid) {
-            let _ = kill(Pid::from_raw(pid_i32), Signal::SIGKILL);
-            for _ in 0..8 {
-                if !Self::process_is_running(pid) {
-                    break;
-                }
-                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
-            }
-        }
-        let _ = std::fs::remove_file(&path);
-    }
-
-    #[cfg(unix)]
-    async fn terminate_process_tree(child: &mut Child) {
-        use nix::sys::signal::{Signal, killpg};
-        use nix::unistd::Pid;
-
-        if let Some(pid) = child.id() {
-            let pgid = Pid::from_raw(pid as i32);
-            let _ = killpg(pgid, Signal::SIGTERM);
-            if timeout(std::time::Duration::from_secs(2), child.wait())
-                .await
-                .is_ok()
-            {
-                return;
-            }
-            let _ = killpg(pgid, Signal::SIGKILL);
-            let _ = timeout(std::time::Duration::from_secs(2), child.wait()).await;
-        } else {
-            let _ = child.start_kill();
-            let _ = child.wait().await;
-        }
-    }
-
-    fn listen_addr_available(addr: &str) -> bool {
-        TcpListener::bind(addr).is_ok()
-    }
-
     pub fn new(
         application_events: ApplicationEventsChannel,
         settings: &Settings,
@@ -152,12 +52,22 @@ impl SelfHostedIndexerService {
             node_settings: Mutex::new(settings.node.clone()),
             is_enabled: AtomicBool::new(false),
             logs: logs.indexer,
-            child: Mutex::new(None),
+            process: Arc::new(
+                ProcessSupervisor::new("simply-kaspa-indexer")
+                    .with_restart_policy(RESTART_POLICY)
+                    .with_stop_policy(StopPolicy::TerminateGroup {
+                        grace: Duration::from_secs(2),
+                    }),
+            ),
             retention_checkpoint_recovery_requested: AtomicBool::new(false),
             retention_checkpoint_recovery_done_for_network: Mutex::new(HashSet::new()),
         }
     }
 
+    pub fn process_status(&self) -> Vec<ProcessStatus> {
+        vec![self.process.status()]
+    }
+
     pub fn enable(&self, enable: bool) {
         if enable {
             self.service_events
@@ -349,100 +259,30 @@ impl SelfHostedIndexerService {
     }
 
     fn find_indexer_binary(settings: &SelfHostedSettings) -> Option<PathBuf> {
-        #[cfg(unix)]
-        fn is_executable(path: &PathBuf) -> bool {
-            use std::os::unix::fs::PermissionsExt;
-            std::fs::metadata(path)
-                .map(|meta| meta.is_file() && (meta.permissions().mode() & 0o111 != 0))
-                .unwrap_or(false)
-        }
-
-        #[cfg(not(unix))]
-        fn is_executable(path: &PathBuf) -> bool {
-            std::fs::metadata(path)
-                .map(|meta| meta.is_file())
-                .unwrap_or(false)
-        }
-
-        #[cfg(unix)]
-        fn ensure_executable(path: &PathBuf) {
-            use std::os::unix::fs::PermissionsExt;
-            if is_executable(path) {
-                return;
-            }
-            if let Ok(meta) = std::fs::metadata(path) {
-                let mut perms = meta.permissions();
-                let mode = perms.mode() | 0o755;
-                perms.set_mode(mode);
-                let _ = std::fs::set_permissions(path, perms);
-            }
-        }
-
-        #[cfg(not(unix))]
-        fn ensure_executable(_path: &PathBuf) {}
-
-        fn pick(path: PathBuf) -> Option<PathBuf> {
-            ensure_executable(&path);
-            if is_executable(&path) {
-                Some(path.canonicalize().unwrap_or(path))
-            } else {
-                None
-            }
-        }
-
         if !settings.indexer_binary.trim().is_empty() {
             let custom = PathBuf::from(settings.indexer_binary.trim());
-            if let Some(custom) = pick(custom) {
-                return Some(custom);
-            }
-        }
-
-        let bin = if cfg!(windows) {
-            "simply-kaspa-indexer.exe"
-        } else {
-            "simply-kaspa-indexer"
-        };
-        let rel_candidates = [
-            bin.to_string(),
-            format!("resources/{bin}"),
-            format!("target/release/resources/{bin}"),
-            format!("target/release/{bin}"),
-            format!("target/debug/{bin}"),
-            format!("simply-kaspa-indexer/target/release/{bin}"),
-            format!("simply-kaspa-indexer/target/debug/{bin}"),
-        ];
-
-        for candidate in &rel_candidates {
-            let path = PathBuf::from(candidate);
-            if let Some(path) = pick(path) {
-                return Some(path);
+            if process_supervisor::ensure_executable(&custom) {
+                return Some(custom.canonicalize().unwrap_or(custom));
             }
         }
 
-        if let Ok(exe) = std::env::current_exe() {
-            if let Some(dir) = exe.parent() {
-                for candidate in &rel_candidates {
-                    let path = dir.join(candidate);
-                    if let Some(path) = pick(path) {
-                        return Some(path);
-                    }
-                }
-
-                #[cfg(target_os = "macos")]
-                if let Some(contents) = dir.parent() {
-                    let resources_dir = contents.join("Resources").join("resources");
-                    if let Some(path) = pick(resources_dir.join(bin)) {
-                        return Some(path);
-                    }
-                }
-            }
-        }
+        process_supervisor::find_binary("simply-kaspa-indexer", &["simply-kaspa-indexer"])
+    }
 
-        None
+    /// Restarts the process according to the restart policy when it exits.
+    fn supervise(self: &Arc<Self>) {
+        let this = self.clone();
+        let is_enabled = self.clone();
+        self.process.monitor(
+            move || is_enabled.is_enabled.load(Ordering::SeqCst),
+            move || async move {
+                let _ = this.start_indexer().await;
+            },
+        );
     }
 
     async fn start_indexer(self: &Arc<Self>) -> Result<()> {
-        if self.child.lock().unwrap().is_some() {
+        if self.process.is_running().await {
             return Ok(());
         }
 
@@ -453,14 +293,18 @@ impl SelfHostedIndexerService {
             return Ok(());
         }
 
-        if !Self::listen_addr_available(&indexer_listen) {
-            #[cfg(unix)]
-            {
-                Self::stop_pidfile_indexer_if_needed(&indexer_listen, &node, &self.logs).await;
-            }
+        self.process
+            .set_pidfile(Some(Self::pidfile_path(node.network)));
+        if !process_supervisor::listen_addr_available(&indexer_listen)
+            && let Some(pid) = self.process.stop_stale().await
+        {
+            self.logs.push(
+                "WARN",
+                &format!("indexer listen address busy on {indexer_listen}; stopped stale indexer pid {pid}"),
+            );
         }
 
-        if !Self::listen_addr_available(&indexer_listen) {
+        if !process_supervisor::listen_addr_available(&indexer_listen) {
             log_warn!(
                 "self-hosted-indexer: listen address already in use ({}); refusing to start indexer",
                 indexer_listen
@@ -540,61 +384,12 @@ impl SelfHostedIndexerService {
             cmd.args(parts);
         }
 
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
-        #[cfg(windows)]
-        {
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
+        if let Err(err) = self.process.spawn(cmd, self.clone()) {
+            log_warn!("self-hosted-indexer: failed to start ({err})");
+            self.logs.push("ERROR", &format!("failed to start ({err})"));
+            return Err(err);
         }
 
-        #[cfg(unix)]
-        {
-            cmd.process_group(0);
-        }
-
-        let mut child = match cmd.spawn() {
-            Ok(child) => child,
-            Err(err) => {
-                let err = Error::NodeStartupError(err);
-                log_warn!("self-hosted-indexer: failed to start ({err})");
-                self.logs.push("ERROR", &format!("failed to start ({err})"));
-                return Err(err);
-            }
-        };
-
-        let logs_info = self.logs.clone();
-        let this_stdout = self.clone();
-        if let Some(stdout) = child.stdout.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stdout).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    if Self::line_requests_retention_checkpoint_recovery(&line) {
-                        this_stdout.mark_retention_checkpoint_recovery_requested();
-                    }
-                    log_info!("self-hosted-indexer: {line}");
-                    logs_info.push("INFO", &line);
-                }
-            });
-        }
-
-        let logs_warn = self.logs.clone();
-        let this_stderr = self.clone();
-        if let Some(stderr) = child.stderr.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stderr).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    if Self::line_requests_retention_checkpoint_recovery(&line) {
-                        this_stderr.mark_retention_checkpoint_recovery_requested();
-                    }
-                    log_warn!("self-hosted-indexer: {line}");
-                    logs_warn.push("WARN", &line);
-                }
-            });
-        }
-
-        if let Some(pid) = child.id() {
-            Self::write_pidfile(node.network, pid);
-        }
         if should_apply_checkpoint_recovery {
             self.retention_checkpoint_recovery_requested
                 .store(false, Ordering::SeqCst);
@@ -603,7 +398,6 @@ impl SelfHostedIndexerService {
                 .unwrap()
                 .insert(network_key);
         }
-        *self.child.lock().unwrap() = Some(child);
         self.logs
             .push("INFO", &format!("using indexer rpc endpoint: {rpc_url}"));
         self.logs.push(
@@ -612,28 +406,37 @@ impl SelfHostedIndexerService {
         );
         self.logs.push("INFO", "using indexer network: mainnet");
         self.logs.push("INFO", "selected app network: mainnet");
+
+        self.supervise();
         Ok(())
     }
 
     async fn stop_indexer(&self) -> Result<()> {
-        let network = self.node_settings.lock().unwrap().network;
-        let child = self.child.lock().unwrap().take();
-        if let Some(mut child) = child {
-            #[cfg(unix)]
-            {
-                Self::terminate_process_tree(&mut child).await;
-            }
-            #[cfg(not(unix))]
-            {
-                let _ = child.start_kill();
-                let _ = child.wait().await;
-            }
-        }
-        Self::remove_pidfile(network);
+        self.process.reset();
+        self.process.stop().await;
         Ok(())
     }
 }
 
+#[async_trait]
+impl ProcessOutput for SelfHostedIndexerService {
+    async fn line(&self, stream: OutputStream, line: String) {
+        if Self::line_requests_retention_checkpoint_recovery(&line) {
+            self.mark_retention_checkpoint_recovery_requested();
+        }
+        match stream {
+            OutputStream::Stdout => log_info!("self-hosted-indexer: {line}"),
+            OutputStream::Stderr => log_warn!("self-hosted-indexer: {line}"),
+        }
+        self.logs.push(stream.default_level(), &line);
+    }
+
+    async fn notice(&self, level: &'static str, message: String) {
+        log_warn!("self-hosted-indexer: {message}");
+        self.logs.push(level, &message);
+    }
+}
+
 #[async_trait]
 impl Service for SelfHostedIndexerService {
     fn name(&self) -> &'static str {
@@ -1,12 +1,12 @@
 use crate::imports::*;
+use crate::runtime::services::process_supervisor::{
+    self, LogStoreOutput, ProcessStatus, ProcessSupervisor, RestartPolicy,
+};
 use crate::runtime::services::{LogStore, LogStores};
-use std::net::TcpListener;
-use std::path::PathBuf;
-use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::{Child, Command};
-#[cfg(unix)]
-use tokio::time::timeout;
+use tokio::process::Command;
+
+const RESTART_POLICY: RestartPolicy =
+    RestartPolicy::backoff(Duration::from_secs(5), Duration::from_secs(120));
 
 pub enum SelfHostedKIndexerEvents {
     Enable,
@@ -24,8 +24,8 @@ pub struct SelfHostedKIndexerService {
     pub node_settings: Mutex<NodeSettings>,
     pub is_enabled: AtomicBool,
     logs: Arc<LogStore>,
-    processor_child: Mutex<Option<Child>>,
-    webserver_child: Mutex<Option<Child>>,
+    processor: ProcessSupervisor,
+    webserver: ProcessSupervisor,
     blocked_reasons: Mutex<std::collections::HashSet<String>>,
 }
 
@@ -34,59 +34,6 @@ impl SelfHostedKIndexerService {
         settings.enabled && settings.k_enabled && matches!(node.network, Network::Mainnet)
     }
 
-    #[cfg(unix)]
-    async fn terminate_process_tree(child: &mut Child) {
-        use nix::sys::signal::{Signal, killpg};
-        use nix::unistd::Pid;
-
-        if let Some(pid) = child.id() {
-            let pgid = Pid::from_raw(pid as i32);
-            let _ = killpg(pgid, Signal::SIGTERM);
-            if timeout(std::time::Duration::from_secs(2), child.wait())
-                .await
-                .is_ok()
-            {
-                return;
-            }
-            let _ = killpg(pgid, Signal::SIGKILL);
-            let _ = timeout(std::time::Duration::from_secs(2), child.wait()).await;
-        } else {
-            let _ = child.start_kill();
-            let _ = child.wait().await;
-        }
-    }
-
-    fn child_is_running(
-        child: &mut Option<Child>,
-        process_name: &str,
-        logs: &Arc<LogStore>,
-    ) -> bool {
-        let Some(proc) = child.as_mut() else {
-            return false;
-        };
-
-        match proc.try_wait() {
-            Ok(Some(status)) => {
-                logs.push(
-                    "WARN",
-                    &format!("{process_name} exited with status: {status}"),
-                );
-                *child = None;
-                false
-            }
-            Ok(None) => true,
-            Err(err) => {
-                logs.push("WARN", &format!("{process_name} state check failed: {err}"));
-                *child = None;
-                false
-            }
-        }
-    }
-
-    fn listen_addr_available(addr: &str) -> bool {
-        TcpListener::bind(addr).is_ok()
-    }
-
     fn resolve_bind_host(bind: &str) -> String {
         let trimmed = bind.trim();
         if trimmed.is_empty() || trimmed == "0.0.0.0" || trimmed == "::" || trimmed == "[::]" {
@@ -114,12 +61,17 @@ impl SelfHostedKIndexerService {
             node_settings: Mutex::new(settings.node.clone()),
             is_enabled: AtomicBool::new(false),
             logs: logs.k_indexer,
-            processor_child: Mutex::new(None),
-            webserver_child: Mutex::new(None),
+            processor: ProcessSupervisor::new("K-transaction-processor")
+                .with_restart_policy(RESTART_POLICY),
+            webserver: ProcessSupervisor::new("K-webserver").with_restart_policy(RESTART_POLICY),
             blocked_reasons: Mutex::new(std::collections::HashSet::new()),
         }
     }
 
+    pub fn process_status(&self) -> Vec<ProcessStatus> {
+        vec![self.processor.status(), self.webserver.status()]
+    }
+
     pub fn enable(&self, enable: bool) {
         if enable {
             self.service_events
@@ -195,129 +147,8 @@ impl SelfHostedKIndexerService {
         self.blocked_reasons.lock().unwrap().clear();
     }
 
-    #[cfg(unix)]
-    fn is_executable(path: &PathBuf) -> bool {
-        use std::os::unix::fs::PermissionsExt;
-        std::fs::metadata(path)
-            .map(|meta| meta.is_file() && (meta.permissions().mode() & 0o111 != 0))
-            .unwrap_or(false)
-    }
-
-    #[cfg(not(unix))]
-    fn is_executable(path: &PathBuf) -> bool {
-        std::fs::metadata(path)
-            .map(|meta| meta.is_file())
-            .unwrap_or(false)
-    }
-
-    fn find_binary(bin_name: &str) -> Option<PathBuf> {
-        let bin = if cfg!(windows) {
-            format!("{bin_name}.exe")
-        } else {
-            bin_name.to_string()
-        };
-        let is_macos_bundle = {
-            #[cfg(target_os = "macos")]
-            {
-                std::env::current_exe()
-                    .ok()
-                    .map(|exe| exe.to_string_lossy().contains(".app/Contents/MacOS/"))
-                    .unwrap_or(false)
-            }
-            #[cfg(not(target_os = "macos"))]
-            {
-                false
-            }
-        };
-        let mut candidates: Vec<PathBuf> = Vec::new();
-        let mut seen = std::collections::HashSet::<PathBuf>::new();
-        let mut push_candidate = |path: PathBuf| {
-            if seen.insert(path.clone()) {
-                candidates.push(path);
-            }
-        };
-
-        if !is_macos_bundle {
-            push_candidate(PathBuf::from(format!("K-indexer/target/release/{bin}")));
-            push_candidate(PathBuf::from(format!("target/release/resources/{bin}")));
-            push_candidate(PathBuf::from(format!("target/release/{bin}")));
-            push_candidate(PathBuf::from(format!("resources/{bin}")));
-            push_candidate(PathBuf::from(&bin));
-        }
-
-        if let Ok(cwd) = std::env::current_dir() {
-            for ancestor in cwd.ancestors().take(8) {
-                push_candidate(ancestor.join("resources").join(&bin));
-                push_candidate(ancestor.join(&bin));
-                push_candidate(
-                    ancestor
-                        .join("target")
-                        .join("release")
-                        .join("resources")
-                        .join(&bin),
-                );
-                push_candidate(ancestor.join("target").join("release").join(&bin));
-                push_candidate(
-                    ancestor
-                        .join("K-indexer")
-                        .join("target")
-                        .join("release")
-                        .join(&bin),
-                );
-            }
-        }
-
-        if let Ok(exe) = std::env::current_exe()
-            && let Some(dir) = exe.parent()
-        {
-            for ancestor in dir.ancestors().take(8) {
-                push_candidate(ancestor.join("resources").join(&bin));
-                push_candidate(ancestor.join(&bin));
-                push_candidate(
-                    ancestor
-                        .join("target")
-                        .join("release")
-                        .join("resources")
-                        .join(&bin),
-                );
-                push_candidate(ancestor.join("target").join("release").join(&bin));
-                push_candidate(
-                    ancestor
-                        .join("K-indexer")
-                        .join("target")
-                        .join("release")
-                        .join(&bin),
-                );
-            }
-            if is_macos_bundle && let Some(contents) = dir.parent() {
-                let resources = contents.join("Resources");
-                push_candidate(resources.join("resources").join(&bin));
-                push_candidate(resources.join("K-indexer").join(&bin));
-                push_candidate(
-                    resources
-                        .join("K-indexer")
-                        .join("target")
-                        .join("release")
-                        .join(&bin),
-                );
-            }
-        }
-
-        candidates
-            .into_iter()
-            .find(Self::is_executable)
-            .map(|path| path.canonicalize().unwrap_or(path))
-    }
-
     async fn start_processor(self: &Arc<Self>) -> Result<()> {
-        {
-            let mut guard = self.processor_child.lock().unwrap();
-            if Self::child_is_running(&mut guard, "K-transaction-processor", &self.logs) {
-                return Ok(());
-            }
-        }
-
-        if self.processor_child.lock().unwrap().is_some() {
+        if self.processor.is_running().await || !self.processor.ready_to_start() {
             return Ok(());
         }
 
@@ -342,7 +173,9 @@ impl SelfHostedKIndexerService {
         let db_name = Self::effective_db_name(&settings, &node);
         let db_port = settings.effective_db_port(node.network);
 
-        let Some(binary) = Self::find_binary("K-transaction-processor") else {
+        let Some(binary) =
+            process_supervisor::find_binary("K-transaction-processor", &["K-indexer"])
+        else {
             self.log_blocked_once("K-transaction-processor binary not found");
             return Ok(());
         };
@@ -372,26 +205,12 @@ impl SelfHostedKIndexerService {
             cmd.arg("-u");
         }
 
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
-        #[cfg(windows)]
-        {
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
-        }
-
-        #[cfg(unix)]
-        {
-            cmd.process_group(0);
+        let output = LogStoreOutput::new(&self.logs).with_prefix("processor: ");
+        if let Err(err) = self.processor.spawn(cmd, Arc::new(output)) {
+            self.log_blocked_once(format!("failed to start K-transaction-processor ({err})"));
+            return Err(err);
         }
 
-        let mut child = match cmd.spawn() {
-            Ok(child) => child,
-            Err(err) => {
-                let err = Error::NodeStartupError(err);
-                self.log_blocked_once(format!("failed to start K-transaction-processor ({err})"));
-                return Err(err);
-            }
-        };
         self.clear_blocked_reason();
         self.logs.push(
             "INFO",
@@ -400,40 +219,11 @@ impl SelfHostedKIndexerService {
                 settings.db_host, db_port, db_name
             ),
         );
-
-        let logs_out = self.logs.clone();
-        if let Some(stdout) = child.stdout.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stdout).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    logs_out.push("INFO", &format!("processor: {line}"));
-                }
-            });
-        }
-
-        let logs_err = self.logs.clone();
-        if let Some(stderr) = child.stderr.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stderr).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    logs_err.push("WARN", &format!("processor: {line}"));
-                }
-            });
-        }
-
-        *self.processor_child.lock().unwrap() = Some(child);
         Ok(())
     }
 
     async fn start_webserver(self: &Arc<Self>) -> Result<()> {
-        {
-            let mut guard = self.webserver_child.lock().unwrap();
-            if Self::child_is_running(&mut guard, "K-webserver", &self.logs) {
-                return Ok(());
-            }
-        }
-
-        if self.webserver_child.lock().unwrap().is_some() {
+        if self.webserver.is_running().await || !self.webserver.ready_to_start() {
             return Ok(());
         }
 
@@ -453,7 +243,7 @@ impl SelfHostedKIndexerService {
         let bind_host = Self::resolve_bind_host(&settings.api_bind);
         let k_web_port = settings.effective_k_web_port(node.network);
         let listen = format!("{}:{}", bind_host, k_web_port);
-        if !Self::listen_addr_available(&listen) {
+        if !process_supervisor::listen_addr_available(&listen) {
             self.log_blocked_once(format!(
                 "K-webserver port already in use on {listen}; refusing to start"
             ));
@@ -467,7 +257,7 @@ impl SelfHostedKIndexerService {
         let db_name = Self::effective_db_name(&settings, &node);
         let db_port = settings.effective_db_port(node.network);
 
-        let Some(binary) = Self::find_binary("K-webserver") else {
+        let Some(binary) = process_supervisor::find_binary("K-webserver", &["K-indexer"]) else {
             self.log_blocked_once("K-webserver binary not found");
             return Ok(());
         };
@@ -490,99 +280,39 @@ impl SelfHostedKIndexerService {
             .arg("-b")
             .arg(&listen);
 
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
-        #[cfg(windows)]
-        {
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
-        }
-
-        #[cfg(unix)]
-        {
-            cmd.process_group(0);
-        }
-
-        let mut child = match cmd.spawn() {
-            Ok(child) => child,
-            Err(err) => {
-                let err = Error::NodeStartupError(err);
-                self.log_blocked_once(format!("failed to start K-webserver ({err})"));
-                return Err(err);
-            }
-        };
-
-        let logs_out = self.logs.clone();
-        if let Some(stdout) = child.stdout.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stdout).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    logs_out.push("INFO", &format!("webserver: {line}"));
-                }
-            });
-        }
-
-        let logs_err = self.logs.clone();
-        if let Some(stderr) = child.stderr.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stderr).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    logs_err.push("WARN", &format!("webserver: {line}"));
-                }
-            });
+        let output = LogStoreOutput::new(&self.logs).with_prefix("webserver: ");
+        if let Err(err) = self.webserver.spawn(cmd, Arc::new(output)) {
+            self.log_blocked_once(format!("failed to start K-webserver ({err})"));
+            return Err(err);
         }
 
         self.clear_blocked_reason();
         self.logs
             .push("INFO", &format!("K-webserver listening on {listen}"));
-        *self.webserver_child.lock().unwrap() = Some(child);
         Ok(())
     }
 
     async fn stop_processor(&self) -> Result<()> {
-        let child = self.processor_child.lock().unwrap().take();
-        if let Some(mut child) = child {
-            let pid = child.id();
+        self.processor.reset();
+        if let Some(pid) = self.processor.pid() {
             self.logs.push(
                 "INFO",
-                &format!(
-                    "stopping K-transaction-processor{}",
-                    pid.map(|v| format!(" (pid={v})")).unwrap_or_default()
-                ),
+                &format!("stopping K-transaction-processor (pid={pid})"),
             );
-            #[cfg(unix)]
-            {
-                Self::terminate_process_tree(&mut child).await;
-            }
-            #[cfg(not(unix))]
-            {
-                let _ = child.start_kill();
-                let _ = child.wait().await;
-            }
+        }
+        if self.processor.stop().await {
             self.logs.push("INFO", "K-transaction-processor stopped");
         }
         Ok(())
     }
 
     async fn stop_webserver(&self) -> Result<()> {
-        let child = self.webserver_child.lock().unwrap().take();
-        if let Some(mut child) = child {
-            let pid = child.id();
-            self.logs.push(
-                "INFO",
-                &format!(
-                    "stopping K-webserver{}",
-                    pid.map(|v| format!(" (pid={v})")).unwrap_or_default()
-                ),
-            );
-            #[cfg(unix)]
-            {
-                Self::terminate_process_tree(&mut child).await;
-            }
-            #[cfg(not(unix))]
-            {
-                let _ = child.start_kill();
-                let _ = child.wait().await;
-            }
+        self.webserver.reset();
+        if let Some(pid) = self.webserver.pid() {
+            self.logs
+                .push("INFO", &format!("stopping K-webserver (pid={pid})"));
+        }
+        if self.webserver.stop().await {
             self.logs.push("INFO", "K-webserver stopped");
         }
         Ok(())
@@ -714,15 +444,11 @@ impl Service for SelfHostedKIndexerService {
                     }
                     _ = retry_tick.tick().fuse() => {
                         if this.is_enabled.load(Ordering::SeqCst) {
-                            let has_processor = {
-                                let mut guard = this.processor_child.lock().unwrap();
-                                Self::child_is_running(&mut guard, "K-transaction-processor", &this.logs)
-                            };
-                            let has_webserver = {
-                                let mut guard = this.webserver_child.lock().unwrap();
-                                Self::child_is_running(&mut guard, "K-webserver", &this.logs)
-                            };
-                            if !has_processor || !has_webserver {
+                            let has_processor = this.processor.is_running().await;
+                            let has_webserver = this.webserver.is_running().await;
+                            let retry_due = (!has_processor && this.processor.ready_to_start())
+                                || (!has_webserver && this.webserver.ready_to_start());
+                            if retry_due {
                                 this.logs.push(
                                     "INFO",
                                     "retrying K-indexer startup (some child processes are not running)",
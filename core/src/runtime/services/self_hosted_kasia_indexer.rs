@@ -1,13 +1,14 @@
 use crate::imports::*;
+use crate::runtime::services::process_supervisor::{
+    self, LogStoreOutput, ProcessStatus, ProcessSupervisor, RestartPolicy,
+};
 use crate::runtime::services::{LogStore, LogStores};
 use std::collections::HashSet;
-use std::net::TcpListener;
 use std::path::PathBuf;
-use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::{Child, Command};
-#[cfg(unix)]
-use tokio::time::timeout;
+use tokio::process::Command;
+
+const RESTART_POLICY: RestartPolicy =
+    RestartPolicy::backoff(Duration::from_secs(5), Duration::from_secs(120));
 
 pub enum SelfHostedKasiaIndexerEvents {
     Enable,
@@ -25,7 +26,7 @@ pub struct SelfHostedKasiaIndexerService {
     pub node_settings: Mutex<NodeSettings>,
     pub is_enabled: AtomicBool,
     logs: Arc<LogStore>,
-    child: Mutex<Option<Child>>,
+    process: ProcessSupervisor,
     last_blocked_reason: Mutex<Option<String>>,
     db_reset_attempted_for_network: Mutex<HashSet<String>>,
 }
@@ -46,59 +47,6 @@ impl SelfHostedKasiaIndexerService {
         settings.enabled && settings.kasia_enabled && matches!(node.network, Network::Mainnet)
     }
 
-    #[cfg(unix)]
-    async fn terminate_process_tree(child: &mut Child) {
-        use nix::sys::signal::{Signal, killpg};
-        use nix::unistd::Pid;
-
-        if let Some(pid) = child.id() {
-            let pgid = Pid::from_raw(pid as i32);
-            let _ = killpg(pgid, Signal::SIGTERM);
-            if timeout(std::time::Duration::from_secs(2), child.wait())
-                .await
-                .is_ok()
-            {
-                return;
-            }
-            let _ = killpg(pgid, Signal::SIGKILL);
-            let _ = timeout(std::time::Duration::from_secs(2), child.wait()).await;
-        } else {
-            let _ = child.start_kill();
-            let _ = child.wait().await;
-        }
-    }
-
-    fn child_is_running(
-        child: &mut Option<Child>,
-        process_name: &str,
-        logs: &Arc<LogStore>,
-    ) -> bool {
-        let Some(proc) = child.as_mut() else {
-            return false;
-        };
-
-        match proc.try_wait() {
-            Ok(Some(status)) => {
-                logs.push(
-                    "WARN",
-                    &format!("{process_name} exited with status: {status}"),
-                );
-                *child = None;
-                false
-            }
-            Ok(None) => true,
-            Err(err) => {
-                logs.push("WARN", &format!("{process_name} state check failed: {err}"));
-                *child = None;
-                false
-            }
-        }
-    }
-
-    fn listen_addr_available(addr: &str) -> bool {
-        TcpListener::bind(addr).is_ok()
-    }
-
     fn resolve_bind_host(bind: &str) -> String {
         let trimmed = bind.trim();
         if trimmed.is_empty() || trimmed == "0.0.0.0" || trimmed == "::" || trimmed == "[::]" {
@@ -159,127 +107,11 @@ impl SelfHostedKasiaIndexerService {
             .join(".kasia-indexer")
     }
 
+    /// Prefers the packaged `kasia-indexer` binary and falls back to the raw
+    /// `indexer` binary of a `kasia-indexer` source checkout.
     fn find_binary() -> Option<PathBuf> {
-        #[cfg(unix)]
-        fn is_executable(path: &PathBuf) -> bool {
-            use std::os::unix::fs::PermissionsExt;
-            std::fs::metadata(path)
-                .map(|meta| meta.is_file() && (meta.permissions().mode() & 0o111 != 0))
-                .unwrap_or(false)
-        }
-
-        #[cfg(not(unix))]
-        fn is_executable(path: &PathBuf) -> bool {
-            std::fs::metadata(path)
-                .map(|meta| meta.is_file())
-                .unwrap_or(false)
-        }
-
-        fn pick(path: PathBuf) -> Option<PathBuf> {
-            if is_executable(&path) {
-                Some(path)
-            } else {
-                None
-            }
-        }
-
-        let app_bin = if cfg!(windows) {
-            "kasia-indexer.exe"
-        } else {
-            "kasia-indexer"
-        };
-        let raw_bin = if cfg!(windows) {
-            "indexer.exe"
-        } else {
-            "indexer"
-        };
-        let is_macos_bundle = {
-            #[cfg(target_os = "macos")]
-            {
-                std::env::current_exe()
-                    .ok()
-                    .map(|exe| exe.to_string_lossy().contains(".app/Contents/MacOS/"))
-                    .unwrap_or(false)
-            }
-            #[cfg(not(target_os = "macos"))]
-            {
-                false
-            }
-        };
-        let rel_candidates = [
-            format!("resources/{app_bin}"),
-            format!("target/release/resources/{app_bin}"),
-            format!("target/release/{app_bin}"),
-            format!("kasia-indexer/target/release/{raw_bin}"),
-            format!("target/release/{raw_bin}"),
-        ];
-
-        if !is_macos_bundle {
-            for candidate in rel_candidates {
-                let path = PathBuf::from(&candidate);
-                if let Some(path) = pick(path) {
-                    return Some(path);
-                }
-            }
-        }
-
-        if let Ok(exe) = std::env::current_exe()
-            && let Some(dir) = exe.parent()
-        {
-            let path = dir.join("resources").join(app_bin);
-            if let Some(path) = pick(path) {
-                return Some(path);
-            }
-
-            let path = dir.join(app_bin);
-            if let Some(path) = pick(path) {
-                return Some(path);
-            }
-
-            // In macOS bundle builds, "kasia-indexer" may be a folder containing the binary.
-            let nested_app_bin = dir.join("kasia-indexer").join(app_bin);
-            if let Some(path) = pick(nested_app_bin) {
-                return Some(path);
-            }
-            let nested_raw_bin = dir.join("kasia-indexer").join(raw_bin);
-            if let Some(path) = pick(nested_raw_bin) {
-                return Some(path);
-            }
-
-            let path = dir
-                .join("kasia-indexer")
-                .join("target")
-                .join("release")
-                .join(raw_bin);
-            if let Some(path) = pick(path) {
-                return Some(path);
-            }
-            if is_macos_bundle && let Some(contents) = dir.parent() {
-                let resources = contents.join("Resources");
-                let path = resources.join("resources").join(app_bin);
-                if let Some(path) = pick(path) {
-                    return Some(path);
-                }
-                let path = resources.join("kasia-indexer").join(app_bin);
-                if let Some(path) = pick(path) {
-                    return Some(path);
-                }
-                let path = resources.join("kasia-indexer").join(raw_bin);
-                if let Some(path) = pick(path) {
-                    return Some(path);
-                }
-                let path = resources
-                    .join("kasia-indexer")
-                    .join("target")
-                    .join("release")
-                    .join(raw_bin);
-                if let Some(path) = pick(path) {
-                    return Some(path);
-                }
-            }
-        }
-
-        None
+        process_supervisor::find_binary("kasia-indexer", &["kasia-indexer"])
+            .or_else(|| process_supervisor::find_binary("indexer", &["kasia-indexer"]))
     }
 
     fn log_blocked_once(&self, message: impl Into<String>) {
@@ -310,12 +142,16 @@ impl SelfHostedKasiaIndexerService {
             node_settings: Mutex::new(settings.node.clone()),
             is_enabled: AtomicBool::new(false),
             logs: logs.kasia_indexer,
-            child: Mutex::new(None),
+            process: ProcessSupervisor::new("kasia-indexer").with_restart_policy(RESTART_POLICY),
             last_blocked_reason: Mutex::new(None),
             db_reset_attempted_for_network: Mutex::new(HashSet::new()),
         }
     }
 
+    pub fn process_status(&self) -> Vec<ProcessStatus> {
+        vec![self.process.status()]
+    }
+
     pub fn enable(&self, enable: bool) {
         if enable {
             self.service_events
@@ -341,14 +177,7 @@ impl SelfHostedKasiaIndexerService {
     }
 
     async fn start_indexer(self: &Arc<Self>) -> Result<()> {
-        {
-            let mut guard = self.child.lock().unwrap();
-            if Self::child_is_running(&mut guard, "kasia-indexer", &self.logs) {
-                return Ok(());
-            }
-        }
-
-        if self.child.lock().unwrap().is_some() {
+        if self.process.is_running().await || !self.process.ready_to_start() {
             return Ok(());
         }
 
@@ -371,7 +200,7 @@ impl SelfHostedKasiaIndexerService {
         let configured_port = settings.effective_kasia_indexer_port(node.network);
         let runtime_port = Self::RUNTIME_API_PORT;
         let runtime_listen = format!("{bind_host}:{runtime_port}");
-        if !Self::listen_addr_available(&runtime_listen) {
+        if !process_supervisor::listen_addr_available(&runtime_listen) {
             self.log_blocked_once(format!(
                 "kasia-indexer API port already in use on {runtime_listen}; refusing to start"
             ));
@@ -397,33 +226,19 @@ impl SelfHostedKasiaIndexerService {
             .env("KASIA_INDEXER_DB_ROOT", Self::default_db_root())
             .env("KASIA_INDEXER_API_BIND", format!("0.0.0.0:{runtime_port}"))
             .env("RUST_BACKTRACE", rust_backtrace)
-            .env("RUST_LIB_BACKTRACE", rust_lib_backtrace)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .env("RUST_LIB_BACKTRACE", rust_lib_backtrace);
 
-        #[cfg(windows)]
+        if let Err(err) = self
+            .process
+            .spawn(cmd, Arc::new(LogStoreOutput::new(&self.logs)))
         {
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
-        }
-
-        #[cfg(unix)]
-        {
-            cmd.process_group(0);
+            self.log_blocked_once(format!(
+                "failed to start kasia-indexer from {} ({err})",
+                binary.display()
+            ));
+            return Err(err);
         }
 
-        let mut child = match cmd.spawn() {
-            Ok(child) => child,
-            Err(err) => {
-                let err = Error::NodeStartupError(err);
-                self.log_blocked_once(format!(
-                    "failed to start kasia-indexer from {} ({err})",
-                    binary.display()
-                ));
-                return Err(err);
-            }
-        };
-
         self.clear_blocked_reason();
         if configured_port != runtime_port {
             self.logs.push(
@@ -440,44 +255,12 @@ impl SelfHostedKasiaIndexerService {
                 "started kasia-indexer (network={network_type}, api=http://{bind_host}:{runtime_port})"
             ),
         );
-
-        let logs_out = self.logs.clone();
-        if let Some(stdout) = child.stdout.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stdout).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    logs_out.push("INFO", &line);
-                }
-            });
-        }
-
-        let logs_err = self.logs.clone();
-        if let Some(stderr) = child.stderr.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stderr).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    logs_err.push("WARN", &line);
-                }
-            });
-        }
-
-        *self.child.lock().unwrap() = Some(child);
         Ok(())
     }
 
     async fn stop_indexer(&self) -> Result<()> {
-        let child = self.child.lock().unwrap().take();
-        if let Some(mut child) = child {
-            #[cfg(unix)]
-            {
-                Self::terminate_process_tree(&mut child).await;
-            }
-            #[cfg(not(unix))]
-            {
-                let _ = child.start_kill();
-                let _ = child.wait().await;
-            }
-        }
+        self.process.reset();
+        self.process.stop().await;
         Ok(())
     }
 
@@ -637,11 +420,8 @@ impl Service for SelfHostedKasiaIndexerService {
                     }
                     _ = retry_tick.tick().fuse() => {
                         if this.is_enabled.load(Ordering::SeqCst) {
-                            let running = {
-                                let mut guard = this.child.lock().unwrap();
-                                Self::child_is_running(&mut guard, "kasia-indexer", &this.logs)
-                            };
-                            if !running {
+                            let running = this.process.is_running().await;
+                            if !running && this.process.ready_to_start() {
                                 this.maybe_reset_db_for_sync_loop();
                                 this.logs.push("INFO", "retrying kasia-indexer startup");
                                 let _ = this.start_indexer().await;
@@ -1,53 +1,27 @@
 use crate::imports::*;
+use crate::runtime::services::loader_state::LoaderStateMachine;
 use crate::runtime::services::{
-    LogStore, LogStores, SelfHostedExplorerService, SelfHostedIndexerService,
-    SelfHostedKIndexerService, SelfHostedKasiaIndexerService, SelfHostedPostgresService,
+    ComponentState, LoaderComponent, LoaderPhase, LoaderStatusSnapshot, LogStore, LogStores,
+    SelfHostedExplorerService, SelfHostedIndexerService, SelfHostedKIndexerService,
+    SelfHostedKasiaIndexerService, SelfHostedPostgresService,
 };
 use std::path::{Path, PathBuf};
 use tokio::net::TcpStream;
 use tokio::time::{MissedTickBehavior, timeout};
 use tokio_postgres::NoTls;
 
-#[derive(Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LoaderStatusSnapshot {
-    pub phase: String,
-    pub message: String,
-    pub connected: bool,
-    pub postgres_ready: bool,
-    pub indexers_ready: bool,
-    pub rest_ready: bool,
-    pub socket_ready: bool,
-    pub last_ping_at: String,
-}
-
-impl Default for LoaderStatusSnapshot {
-    fn default() -> Self {
-        Self {
-            phase: "Disabled".to_string(),
-            message: "Loader is disabled".to_string(),
-            connected: false,
-            postgres_ready: false,
-            indexers_ready: false,
-            rest_ready: false,
-            socket_ready: false,
-            last_ping_at: chrono::Utc::now().to_rfc3339(),
-        }
-    }
-}
-
 #[derive(Clone, Default)]
 pub struct SharedLoaderStatus {
-    inner: Arc<Mutex<LoaderStatusSnapshot>>,
+    inner: Arc<Mutex<LoaderStateMachine>>,
 }
 
 impl SharedLoaderStatus {
     pub fn snapshot(&self) -> LoaderStatusSnapshot {
-        self.inner.lock().unwrap().clone()
+        self.inner.lock().unwrap().snapshot()
     }
 
-    pub fn update(&self, snapshot: LoaderStatusSnapshot) {
-        *self.inner.lock().unwrap() = snapshot;
+    pub fn machine(&self) -> MutexGuard<'_, LoaderStateMachine> {
+        self.inner.lock().unwrap()
     }
 }
 
@@ -68,15 +42,6 @@ pub struct SelfHostedLoaderServices {
     pub explorer_service: Arc<SelfHostedExplorerService>,
 }
 
-#[derive(Clone, Copy)]
-struct LoaderReadiness {
-    connected: bool,
-    postgres_ready: bool,
-    indexers_ready: bool,
-    rest_ready: bool,
-    socket_ready: bool,
-}
-
 pub struct SelfHostedLoaderService {
     pub application_events: ApplicationEventsChannel,
     pub service_events: Channel<SelfHostedLoaderEvents>,
@@ -86,6 +51,7 @@ pub struct SelfHostedLoaderService {
     pub is_enabled: AtomicBool,
     logs: Arc<LogStore>,
     status: SharedLoaderStatus,
+    published_revision: AtomicU64,
     postgres_service: Arc<SelfHostedPostgresService>,
     indexer_service: Arc<SelfHostedIndexerService>,
     k_indexer_service: Arc<SelfHostedKIndexerService>,
@@ -97,10 +63,6 @@ pub struct SelfHostedLoaderService {
     postgres_boot_started_at: Mutex<Option<Instant>>,
     indexer_boot_started_at: Mutex<Option<Instant>>,
     explorer_boot_started_at: Mutex<Option<Instant>>,
-    postgres_failures: Mutex<u32>,
-    node_sync_failures: Mutex<u32>,
-    indexer_failures: Mutex<u32>,
-    explorer_failures: Mutex<u32>,
     last_ping_log_at: Mutex<Option<Instant>>,
     last_postgres_debug_log_at: Mutex<Option<Instant>>,
 }
@@ -595,22 +557,23 @@ impl SelfHostedLoaderService {
         allowed
     }
 
-    fn health_failures(slot: &Mutex<u32>, healthy: bool) -> u32 {
-        let mut guard = slot.lock().unwrap();
-        if healthy {
-            *guard = 0;
-            0
-        } else {
-            *guard = guard.saturating_add(1);
-            *guard
-        }
+    fn record_health(&self, component: LoaderComponent, healthy: bool) -> u32 {
+        self.status.machine().record_health(component, healthy)
+    }
+
+    fn set_ready(&self, component: LoaderComponent, ready: bool) {
+        self.status.machine().set_ready(component, ready);
     }
 
-    fn reset_health_failures(&self) {
-        *self.postgres_failures.lock().unwrap() = 0;
-        *self.node_sync_failures.lock().unwrap() = 0;
-        *self.indexer_failures.lock().unwrap() = 0;
-        *self.explorer_failures.lock().unwrap() = 0;
+    fn set_state(&self, component: LoaderComponent, state: ComponentState) {
+        self.status.machine().set_state(component, state);
+    }
+
+    /// Marks `component` and everything started after it as waiting.
+    fn wait_for(&self, component: LoaderComponent) {
+        let mut machine = self.status.machine();
+        machine.set_state(component, ComponentState::Waiting);
+        machine.set_dependents_state(component, ComponentState::Waiting);
     }
 
     fn reset_restart_cooldowns(&self) {
@@ -620,49 +583,51 @@ impl SelfHostedLoaderService {
         *self.postgres_boot_started_at.lock().unwrap() = None;
         *self.indexer_boot_started_at.lock().unwrap() = None;
         *self.explorer_boot_started_at.lock().unwrap() = None;
-        self.reset_health_failures();
+        self.status.machine().reset_failures();
     }
 
-    fn publish_status(&self, phase: &str, message: String, readiness: LoaderReadiness) {
-        let next = LoaderStatusSnapshot {
-            phase: phase.to_string(),
-            message,
-            connected: readiness.connected,
-            postgres_ready: readiness.postgres_ready,
-            indexers_ready: readiness.indexers_ready,
-            rest_ready: readiness.rest_ready,
-            socket_ready: readiness.socket_ready,
-            last_ping_at: chrono::Utc::now().to_rfc3339(),
+    /// Moves the loader to `phase`, logging the transition (or the
+    /// rejected one) and repainting if the published state has changed.
+    fn publish(&self, phase: LoaderPhase, message: &str) {
+        let (result, revision) = {
+            let mut machine = self.status.machine();
+            let result = machine.transition(phase, message, unixtime_as_millis_f64());
+            (result, machine.revision())
         };
 
-        let current = self.status.snapshot();
-        let unchanged = current.phase == next.phase
-            && current.message == next.message
-            && current.connected == next.connected
-            && current.postgres_ready == next.postgres_ready
-            && current.indexers_ready == next.indexers_ready
-            && current.rest_ready == next.rest_ready
-            && current.socket_ready == next.socket_ready;
-        if unchanged {
-            return;
+        match result {
+            Ok(Some(transition)) => self.logs.push(
+                "INFO",
+                &format!(
+                    "phase {} -> {}: {}",
+                    transition.from.slug(),
+                    transition.to.slug(),
+                    transition.reason
+                ),
+            ),
+            Ok(None) => {}
+            Err(err) => self.logs.push("WARN", &err.to_string()),
         }
 
-        self.status.update(next);
-        runtime().request_repaint();
+        if self.published_revision.swap(revision, Ordering::SeqCst) != revision {
+            runtime().request_repaint();
+        }
     }
 
-    fn publish_disabled(&self, message: &str) {
-        self.publish_status(
-            "Disabled",
-            message.to_string(),
-            LoaderReadiness {
-                connected: false,
-                postgres_ready: false,
-                indexers_ready: false,
-                rest_ready: false,
-                socket_ready: false,
-            },
-        );
+    fn publish_disabled(&self, phase: LoaderPhase, message: &str) {
+        {
+            let mut machine = self.status.machine();
+            for component in LoaderComponent::ALL {
+                machine.set_required(component, false);
+            }
+        }
+        self.publish(phase, message);
+    }
+
+    /// Restarts the startup sequence after a settings change.
+    fn publish_restart(&self, message: &str) {
+        self.wait_for(LoaderComponent::Postgres);
+        self.publish(LoaderPhase::WaitingForPostgres, message);
     }
 
     fn maybe_log_ping(&self, line: String) {
@@ -732,27 +697,33 @@ impl SelfHostedLoaderService {
 
         if !self.is_enabled.load(Ordering::SeqCst) || !settings.enabled {
             self.stop_all().await;
-            self.publish_disabled("Loader is disabled");
+            self.publish_disabled(LoaderPhase::Disabled, "Loader is disabled");
             return;
         }
 
         if !settings.postgres_enabled {
             self.stop_dependents().await;
             self.postgres_service.enable(false);
-            self.publish_status(
-                "Initialisation",
-                "Postgres is disabled; enable Postgres to continue".to_string(),
-                LoaderReadiness {
-                    connected: false,
-                    postgres_ready: false,
-                    indexers_ready: false,
-                    rest_ready: false,
-                    socket_ready: false,
-                },
+            self.publish_disabled(
+                LoaderPhase::PostgresDisabled,
+                "Postgres is disabled; enable Postgres to continue",
             );
             return;
         }
 
+        let k_indexer_required = Self::should_run_k_indexer(&settings, &node);
+        let kasia_required = Self::should_run_kasia_indexer(&settings, &node);
+        {
+            let mut machine = self.status.machine();
+            machine.set_required(LoaderComponent::Postgres, true);
+            machine.set_required(LoaderComponent::Node, true);
+            machine.set_required(LoaderComponent::Indexer, settings.indexer_enabled);
+            machine.set_required(LoaderComponent::KIndexer, k_indexer_required);
+            machine.set_required(LoaderComponent::KasiaIndexer, kasia_required);
+            machine.set_required(LoaderComponent::Rest, true);
+            machine.set_required(LoaderComponent::Socket, true);
+        }
+
         let probe_host = Self::resolve_probe_host(&settings.api_bind);
 
         self.postgres_service.enable(true);
@@ -767,11 +738,10 @@ impl SelfHostedLoaderService {
         let postgres_in_grace = postgres_boot_started_at
             .map(|started| started.elapsed() < Self::POSTGRES_BOOT_GRACE)
             .unwrap_or(false);
-        let postgres_failures = if postgres_ready || postgres_in_grace || switching_network {
-            Self::health_failures(&self.postgres_failures, true)
-        } else {
-            Self::health_failures(&self.postgres_failures, false)
-        };
+        let postgres_failures = self.record_health(
+            LoaderComponent::Postgres,
+            postgres_ready || postgres_in_grace || switching_network,
+        );
         if postgres_ready {
             *self.postgres_boot_started_at.lock().unwrap() = None;
         }
@@ -788,22 +758,14 @@ impl SelfHostedLoaderService {
             {
                 self.restart_postgres_stack().await;
             }
-            self.publish_status(
-                "Initialisation",
-                "Waiting for Postgres".to_string(),
-                LoaderReadiness {
-                    connected: false,
-                    postgres_ready: false,
-                    indexers_ready: false,
-                    rest_ready: false,
-                    socket_ready: false,
-                },
-            );
+            self.wait_for(LoaderComponent::Postgres);
+            self.publish(LoaderPhase::WaitingForPostgres, "Waiting for Postgres");
             self.maybe_log_ping(
                 "ping: postgres=down indexers=waiting rest=waiting socket=waiting".to_string(),
             );
             return;
         }
+        self.set_state(LoaderComponent::Postgres, ComponentState::Ready);
 
         let node_synced = Self::check_node_synced(&node).await;
         let status_snapshot = self.status.snapshot();
@@ -813,11 +775,8 @@ impl SelfHostedLoaderService {
             || status_snapshot.socket_ready
             || self.indexer_boot_started_at.lock().unwrap().is_some()
             || self.explorer_boot_started_at.lock().unwrap().is_some();
-        let node_sync_failures = if node_synced || switching_network {
-            Self::health_failures(&self.node_sync_failures, true)
-        } else {
-            Self::health_failures(&self.node_sync_failures, false)
-        };
+        let node_sync_failures =
+            self.record_health(LoaderComponent::Node, node_synced || switching_network);
         let tolerate_node_sync_glitch = was_connected
             && !node_synced
             && !switching_network
@@ -831,19 +790,11 @@ impl SelfHostedLoaderService {
             self.stop_dependents().await;
             *self.indexer_boot_started_at.lock().unwrap() = None;
             *self.explorer_boot_started_at.lock().unwrap() = None;
-            Self::health_failures(&self.indexer_failures, true);
-            Self::health_failures(&self.explorer_failures, true);
-            self.publish_status(
-                "Initialisation",
-                "Waiting for Node sync".to_string(),
-                LoaderReadiness {
-                    connected: false,
-                    postgres_ready: true,
-                    indexers_ready: false,
-                    rest_ready: false,
-                    socket_ready: false,
-                },
-            );
+            self.record_health(LoaderComponent::Indexer, true);
+            self.record_health(LoaderComponent::Rest, true);
+            self.record_health(LoaderComponent::Socket, true);
+            self.wait_for(LoaderComponent::Node);
+            self.publish(LoaderPhase::WaitingForNodeSync, "Waiting for Node sync");
             self.maybe_log_ping(
                 "ping: postgres=ok node=syncing indexers=waiting rest=waiting socket=waiting"
                     .to_string(),
@@ -857,10 +808,14 @@ impl SelfHostedLoaderService {
                 Self::NODE_SYNC_FAILURE_THRESHOLD
             ));
         }
+        let node_state = if node_synced {
+            ComponentState::Ready
+        } else {
+            ComponentState::Degraded
+        };
+        self.set_state(LoaderComponent::Node, node_state);
 
         self.indexer_service.enable(settings.indexer_enabled);
-        let k_indexer_required = Self::should_run_k_indexer(&settings, &node);
-        let kasia_required = Self::should_run_kasia_indexer(&settings, &node);
 
         let indexer_boot_started_at = {
             let mut guard = self.indexer_boot_started_at.lock().unwrap();
@@ -901,16 +856,22 @@ impl SelfHostedLoaderService {
             true
         };
 
+        {
+            let mut machine = self.status.machine();
+            machine.set_ready(LoaderComponent::Indexer, indexer_ready);
+            machine.set_ready(LoaderComponent::KIndexer, k_indexer_ready);
+            machine.set_ready(LoaderComponent::KasiaIndexer, kasia_ready);
+        }
+
         let critical_indexers_ready = indexer_ready;
         let indexers_ready = critical_indexers_ready && k_indexer_ready && kasia_ready;
         let indexer_in_grace = indexer_boot_started_at
             .map(|started| started.elapsed() < Self::INDEXER_BOOT_GRACE)
             .unwrap_or(false);
-        let indexer_failures = if critical_indexers_ready || indexer_in_grace || switching_network {
-            Self::health_failures(&self.indexer_failures, true)
-        } else {
-            Self::health_failures(&self.indexer_failures, false)
-        };
+        let indexer_failures = self.record_health(
+            LoaderComponent::Indexer,
+            critical_indexers_ready || indexer_in_grace || switching_network,
+        );
         if !critical_indexers_ready {
             self.explorer_service.enable(false);
             *self.explorer_boot_started_at.lock().unwrap() = None;
@@ -922,17 +883,8 @@ impl SelfHostedLoaderService {
                 self.restart_main_indexer(&settings).await;
             }
 
-            self.publish_status(
-                "Initialisation",
-                "Waiting for indexer".to_string(),
-                LoaderReadiness {
-                    connected: false,
-                    postgres_ready: true,
-                    indexers_ready: false,
-                    rest_ready: false,
-                    socket_ready: false,
-                },
-            );
+            self.wait_for(LoaderComponent::Indexer);
+            self.publish(LoaderPhase::WaitingForIndexer, "Waiting for indexer");
             self.maybe_log_ping(format!(
                 "ping: postgres=ok indexers={} rest=waiting socket=waiting",
                 if indexers_ready { "ok" } else { "down" }
@@ -944,17 +896,8 @@ impl SelfHostedLoaderService {
             self.kasia_indexer_service.enable(false);
             self.explorer_service.enable(false);
             *self.explorer_boot_started_at.lock().unwrap() = None;
-            self.publish_status(
-                "Initialisation",
-                "Waiting for k-indexer".to_string(),
-                LoaderReadiness {
-                    connected: false,
-                    postgres_ready: true,
-                    indexers_ready: false,
-                    rest_ready: false,
-                    socket_ready: false,
-                },
-            );
+            self.wait_for(LoaderComponent::KIndexer);
+            self.publish(LoaderPhase::WaitingForKIndexer, "Waiting for k-indexer");
             self.maybe_log_ping(
                 "ping: postgres=ok indexer=ok k-indexer=waiting rest=waiting socket=waiting"
                     .to_string(),
@@ -970,20 +913,15 @@ impl SelfHostedLoaderService {
             if !was_connected {
                 self.explorer_service.enable(false);
                 *self.explorer_boot_started_at.lock().unwrap() = None;
-                self.publish_status(
-                    "Initialisation",
-                    "Waiting for kasia-indexer".to_string(),
-                    LoaderReadiness {
-                        connected: false,
-                        postgres_ready: true,
-                        indexers_ready: false,
-                        rest_ready: false,
-                        socket_ready: false,
-                    },
+                self.wait_for(LoaderComponent::KasiaIndexer);
+                self.publish(
+                    LoaderPhase::WaitingForKasiaIndexer,
+                    "Waiting for kasia-indexer",
                 );
                 self.maybe_log_ping("ping: postgres=ok indexer=ok k-indexer=ok kasia-indexer=waiting rest=waiting socket=waiting".to_string());
                 return;
             }
+            self.set_state(LoaderComponent::KasiaIndexer, ComponentState::Degraded);
             self.maybe_log_ping(
                 "ping: optional kasia-indexer is down; keeping rest/socket online".to_string(),
             );
@@ -1017,11 +955,20 @@ impl SelfHostedLoaderService {
         let explorer_in_grace = explorer_boot_started_at
             .map(|started| started.elapsed() < Self::EXPLORER_BOOT_GRACE)
             .unwrap_or(false);
-        let explorer_failures = if explorer_ready || explorer_in_grace || switching_network {
-            Self::health_failures(&self.explorer_failures, true)
-        } else {
-            Self::health_failures(&self.explorer_failures, false)
-        };
+        let rest_failures = self.record_health(
+            LoaderComponent::Rest,
+            rest_ready || explorer_in_grace || switching_network,
+        );
+        let socket_failures = self.record_health(
+            LoaderComponent::Socket,
+            socket_ready || explorer_in_grace || switching_network,
+        );
+        let explorer_failures = rest_failures.max(socket_failures);
+        {
+            let mut machine = self.status.machine();
+            machine.set_ready(LoaderComponent::Rest, rest_ready);
+            machine.set_ready(LoaderComponent::Socket, socket_ready);
+        }
 
         if !explorer_ready {
             if !switching_network
@@ -1036,23 +983,13 @@ impl SelfHostedLoaderService {
                 }
             }
             let waiting_message = if !rest_ready {
-                "Waiting for REST API".to_string()
+                "Waiting for REST API"
             } else if !socket_ready {
-                "Waiting for socket server".to_string()
+                "Waiting for socket server"
             } else {
-                "Waiting for explorer services".to_string()
+                "Waiting for explorer services"
             };
-            self.publish_status(
-                "Initialisation",
-                waiting_message,
-                LoaderReadiness {
-                    connected: false,
-                    postgres_ready: true,
-                    indexers_ready: true,
-                    rest_ready,
-                    socket_ready,
-                },
-            );
+            self.publish(LoaderPhase::WaitingForExplorer, waiting_message);
             self.maybe_log_ping(format!(
                 "ping: postgres=ok indexers=ok rest={} socket={}",
                 if rest_ready { "ok" } else { "down" },
@@ -1062,16 +999,9 @@ impl SelfHostedLoaderService {
         }
 
         *self.explorer_boot_started_at.lock().unwrap() = None;
-        self.publish_status(
-            "Connected",
-            "All self-hosted database services are running".to_string(),
-            LoaderReadiness {
-                connected: true,
-                postgres_ready: true,
-                indexers_ready: true,
-                rest_ready: true,
-                socket_ready: true,
-            },
+        self.publish(
+            LoaderPhase::Connected,
+            "All self-hosted database services are running",
         );
         self.maybe_log_ping("ping: postgres=ok indexers=ok rest=ok socket=ok".to_string());
     }
@@ -1092,6 +1022,7 @@ impl SelfHostedLoaderService {
             is_enabled: AtomicBool::new(false),
             logs: logs.loader,
             status,
+            published_revision: AtomicU64::new(0),
             postgres_service: services.postgres_service,
             indexer_service: services.indexer_service,
             k_indexer_service: services.k_indexer_service,
@@ -1103,10 +1034,6 @@ impl SelfHostedLoaderService {
             postgres_boot_started_at: Mutex::new(None),
             indexer_boot_started_at: Mutex::new(None),
             explorer_boot_started_at: Mutex::new(None),
-            postgres_failures: Mutex::new(0),
-            node_sync_failures: Mutex::new(0),
-            indexer_failures: Mutex::new(0),
-            explorer_failures: Mutex::new(0),
             last_ping_log_at: Mutex::new(None),
             last_postgres_debug_log_at: Mutex::new(None),
         }
@@ -1164,7 +1091,7 @@ impl Service for SelfHostedLoaderService {
                 );
                 this.reconcile().await;
             } else {
-                this.publish_disabled("Loader is disabled");
+                this.publish_disabled(LoaderPhase::Disabled, "Loader is disabled");
             }
 
             loop {
@@ -1186,7 +1113,7 @@ impl Service for SelfHostedLoaderService {
                                 }
                                 this.reset_restart_cooldowns();
                                 this.stop_all().await;
-                                this.publish_disabled("Loader is disabled");
+                                this.publish_disabled(LoaderPhase::Disabled, "Loader is disabled");
                             }
                             Ok(SelfHostedLoaderEvents::UpdateSettings(settings)) => {
                                 this.postgres_service.update_settings(settings.clone());
@@ -1197,17 +1124,7 @@ impl Service for SelfHostedLoaderService {
                                 *this.settings.lock().unwrap() = settings;
                                 this.reset_restart_cooldowns();
                                 if this.is_enabled.load(Ordering::SeqCst) {
-                                    this.publish_status(
-                                        "Initialisation",
-                                        "Applying updated self-hosted settings".to_string(),
-                                        LoaderReadiness {
-                                            connected: false,
-                                            postgres_ready: false,
-                                            indexers_ready: false,
-                                            rest_ready: false,
-                                            socket_ready: false,
-                                        },
-                                    );
+                                    this.publish_restart("Applying updated self-hosted settings");
                                     this.reconcile().await;
                                 }
                             }
@@ -1220,24 +1137,14 @@ impl Service for SelfHostedLoaderService {
                                 *this.node_settings.lock().unwrap() = settings;
                                 if this.is_enabled.load(Ordering::SeqCst) {
                                     this.reset_restart_cooldowns();
-                                    this.publish_status(
-                                        "Initialisation",
-                                        "Applying updated network settings".to_string(),
-                                        LoaderReadiness {
-                                            connected: false,
-                                            postgres_ready: false,
-                                            indexers_ready: false,
-                                            rest_ready: false,
-                                            socket_ready: false,
-                                        },
-                                    );
+                                    this.publish_restart("Applying updated network settings");
                                     this.reconcile().await;
                                 }
                             }
                             Ok(SelfHostedLoaderEvents::Exit) | Err(_) => {
                                 this.is_enabled.store(false, Ordering::SeqCst);
                                 this.stop_all().await;
-                                this.publish_disabled("Loader stopped");
+                                this.publish_disabled(LoaderPhase::Disabled, "Loader stopped");
                                 break;
                             }
                         }
@@ -1,9 +1,11 @@
 use crate::imports::*;
+use crate::runtime::services::process_supervisor::{
+    self, IGNORE_LEVEL, LogStoreOutput, ProcessStatus, ProcessSupervisor, RestartPolicy, StopPolicy,
+};
 use crate::runtime::services::{LogStore, LogStores};
 use std::process::Stdio;
 use std::sync::OnceLock;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::{Child, Command};
+use tokio::process::Command;
 
 pub enum SelfHostedPostgresEvents {
     Enable,
@@ -22,7 +24,7 @@ pub struct SelfHostedPostgresService {
     pub node_settings: Mutex<NodeSettings>,
     pub is_enabled: AtomicBool,
     logs: Arc<LogStore>,
-    child: Mutex<Option<Child>>,
+    process: ProcessSupervisor,
     last_restart_at: Mutex<Option<Instant>>,
     startup_restart_guard_until: Mutex<Option<Instant>>,
 }
@@ -222,7 +224,7 @@ impl SelfHostedPostgresService {
     }
 
     fn postgres_binary_major_version_from_bin(postgres_bin: &Path) -> Option<u32> {
-        if !Self::prepare_binary(postgres_bin) {
+        if !process_supervisor::ensure_executable(postgres_bin) {
             return None;
         }
         let mut cmd = std::process::Command::new(postgres_bin);
@@ -331,7 +333,7 @@ impl SelfHostedPostgresService {
         if upper.contains("DATABASE SYSTEM IS NOT YET ACCEPTING CONNECTIONS")
             || upper.contains("CONSISTENT RECOVERY STATE HAS NOT BEEN YET REACHED")
         {
-            return "IGNORE";
+            return IGNORE_LEVEL;
         }
         // Expected transient lines during a normal self-managed shutdown/restart.
         if upper.contains("THE DATABASE SYSTEM IS SHUTTING DOWN")
@@ -367,12 +369,22 @@ impl SelfHostedPostgresService {
             node_settings: Mutex::new(settings.node.clone()),
             is_enabled: AtomicBool::new(false),
             logs: logs.postgres,
-            child: Mutex::new(None),
+            // restarts are driven by the self-hosted loader, which also recovers the cluster
+            process: ProcessSupervisor::new("postgres")
+                .with_restart_policy(RestartPolicy::never())
+                .with_stop_policy(StopPolicy::Graceful {
+                    terminate: Self::STOP_GRACEFUL_WAIT,
+                    interrupt: Self::STOP_RETRY_WAIT,
+                }),
             last_restart_at: Mutex::new(None),
             startup_restart_guard_until: Mutex::new(None),
         }
     }
 
+    pub fn process_status(&self) -> Vec<ProcessStatus> {
+        vec![self.process.status()]
+    }
+
     pub fn enable(&self, enable: bool) {
         if enable {
             self.service_events
@@ -413,10 +425,9 @@ impl SelfHostedPostgresService {
 
     fn all_network_db_names(settings: &SelfHostedSettings) -> Vec<String> {
         let base = Self::normalized_db_base_name(settings);
-        vec![crate::settings::self_hosted_db_name_for_network(
-            &base,
-            Network::Mainnet,
-        )]
+        Network::iter()
+            .map(|network| crate::settings::self_hosted_db_name_for_network(&base, *network))
+            .collect()
     }
 
     fn resolve_data_dir(settings: &SelfHostedSettings, network: Network) -> Result<PathBuf> {
@@ -445,36 +456,18 @@ impl SelfHostedPostgresService {
         for bin_dir in Self::candidate_bin_dirs() {
             let candidate = bin_dir.join(&bin_name);
             searched.push(candidate.display().to_string());
-            if !Self::prepare_binary(&candidate) {
+            if !process_supervisor::ensure_executable(&candidate) {
                 continue;
             }
-            let is_supported_major = if binary == "postgres" {
-                if !Self::is_runnable_binary(&candidate) {
-                    false
-                } else {
-                    Self::postgres_binary_major_version_from_bin(&candidate)
-                        .map(|major| major == Self::EXPECTED_POSTGRES_MAJOR)
-                        .unwrap_or(false)
-                }
-            } else {
-                let postgres_candidate = bin_dir.join(if cfg!(windows) {
-                    "postgres.exe"
-                } else {
-                    "postgres"
-                });
-                Self::postgres_binary_major_version_from_bin(&candidate)
-                    .map(|major| major == Self::EXPECTED_POSTGRES_MAJOR)
-                    .unwrap_or(false)
-                    || Self::postgres_binary_major_version_from_bin(&postgres_candidate)
-                        .map(|major| major == Self::EXPECTED_POSTGRES_MAJOR)
-                        .unwrap_or(false)
-            };
+            let is_supported_major = Self::is_supported_binary(binary, &candidate);
             if is_supported_major {
                 return Ok(candidate);
             }
         }
 
-        if let Some(candidate) = Self::find_in_path(binary) {
+        if let Some(candidate) = process_supervisor::find_in_path_with(binary, |candidate| {
+            Self::is_supported_binary(binary, candidate)
+        }) {
             return Ok(candidate);
         }
 
@@ -490,7 +483,7 @@ impl SelfHostedPostgresService {
     }
 
     fn is_runnable_binary(path: &Path) -> bool {
-        if !Self::prepare_binary(path) {
+        if !process_supervisor::ensure_executable(path) {
             return false;
         }
 
@@ -503,37 +496,6 @@ impl SelfHostedPostgresService {
         matches!(cmd.status(), Ok(status) if status.success())
     }
 
-    fn prepare_binary(path: &Path) -> bool {
-        if !path.exists() || !path.is_file() {
-            return false;
-        }
-
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            if let Ok(metadata) = std::fs::metadata(path) {
-                let mode = metadata.permissions().mode();
-                if mode & 0o111 == 0 {
-                    let mut perms = metadata.permissions();
-                    perms.set_mode(mode | 0o111);
-                    let _ = std::fs::set_permissions(path, perms);
-                }
-            }
-        }
-
-        true
-    }
-
-    fn running_from_macos_bundle() -> bool {
-        #[cfg(target_os = "macos")]
-        {
-            if let Ok(exe) = std::env::current_exe() {
-                return exe.to_string_lossy().contains(".app/Contents/MacOS/");
-            }
-        }
-        false
-    }
-
     fn candidate_bin_dirs() -> Vec<PathBuf> {
         let mut dirs: Vec<PathBuf> = Vec::new();
 
@@ -550,7 +512,7 @@ impl SelfHostedPostgresService {
             }
         }
 
-        if !Self::running_from_macos_bundle()
+        if !process_supervisor::running_from_macos_bundle()
             && let Ok(cwd) = std::env::current_dir()
         {
             dirs.push(cwd.join("postgres").join("bin"));
@@ -662,44 +624,33 @@ impl SelfHostedPostgresService {
         false
     }
 
-    #[allow(dead_code)]
-    fn find_in_path(bin_name: &str) -> Option<PathBuf> {
-        let resolved_name = if cfg!(windows) {
-            format!("{bin_name}.exe")
-        } else {
-            bin_name.to_string()
-        };
-        let path_var = std::env::var_os("PATH")?;
-        std::env::split_paths(&path_var)
-            .map(|dir| dir.join(&resolved_name))
-            .find(|candidate| {
-                if !Self::prepare_binary(candidate) {
-                    return false;
-                }
-                if bin_name == "postgres" {
-                    return Self::is_runnable_binary(candidate)
-                        && Self::postgres_binary_major_version_from_bin(candidate)
-                            .map(|major| major == Self::EXPECTED_POSTGRES_MAJOR)
-                            .unwrap_or(false);
-                }
-
-                let sibling_postgres = candidate.parent().map(|dir| {
-                    dir.join(if cfg!(windows) {
-                        "postgres.exe"
-                    } else {
-                        "postgres"
-                    })
-                });
-
-                Self::postgres_binary_major_version_from_bin(candidate)
+    /// Accepts `postgres` only if it runs and reports the expected major
+    /// version; other tools are accepted if they (or the `postgres` binary next
+    /// to them) report the expected major version.
+    fn is_supported_binary(binary: &str, candidate: &Path) -> bool {
+        if binary == "postgres" {
+            return Self::is_runnable_binary(candidate)
+                && Self::postgres_binary_major_version_from_bin(candidate)
                     .map(|major| major == Self::EXPECTED_POSTGRES_MAJOR)
-                    .unwrap_or(false)
-                    || sibling_postgres
-                        .as_ref()
-                        .and_then(|path| Self::postgres_binary_major_version_from_bin(path))
-                        .map(|major| major == Self::EXPECTED_POSTGRES_MAJOR)
-                        .unwrap_or(false)
+                    .unwrap_or(false);
+        }
+
+        let sibling_postgres = candidate.parent().map(|dir| {
+            dir.join(if cfg!(windows) {
+                "postgres.exe"
+            } else {
+                "postgres"
             })
+        });
+
+        Self::postgres_binary_major_version_from_bin(candidate)
+            .map(|major| major == Self::EXPECTED_POSTGRES_MAJOR)
+            .unwrap_or(false)
+            || sibling_postgres
+                .as_ref()
+                .and_then(|path| Self::postgres_binary_major_version_from_bin(path))
+                .map(|major| major == Self::EXPECTED_POSTGRES_MAJOR)
+                .unwrap_or(false)
     }
 
     fn initdb_if_needed(&self, settings: &SelfHostedSettings, data_dir: &Path) -> Result<()> {
@@ -989,39 +940,6 @@ impl SelfHostedPostgresService {
             .and_then(|line| line.parse::<u32>().ok())
     }
 
-    fn is_process_running(pid: u32) -> bool {
-        #[cfg(windows)]
-        {
-            let mut cmd = std::process::Command::new("tasklist");
-            Self::apply_no_window_for_std_command(&mut cmd);
-            let output = cmd
-                .args(["/FI", &format!("PID eq {pid}"), "/FO", "CSV", "/NH"])
-                .output();
-            if let Ok(output) = output
-                && output.status.success()
-            {
-                let stdout = String::from_utf8_lossy(&output.stdout).to_ascii_lowercase();
-                // "INFO: No tasks are running..."
-                if stdout.contains("no tasks are running") {
-                    return false;
-                }
-                // Normal row starts with image name and includes ",\"<pid>\","
-                return stdout.contains(&format!(",\"{pid}\","));
-            }
-            false
-        }
-
-        #[cfg(unix)]
-        {
-            use nix::sys::signal::kill;
-            use nix::unistd::Pid;
-            if let Ok(pid_i32) = i32::try_from(pid) {
-                return kill(Pid::from_raw(pid_i32), None).is_ok();
-            }
-            false
-        }
-    }
-
     async fn ensure_role_and_database(
         settings: &SelfHostedSettings,
         node: &NodeSettings,
@@ -1044,9 +962,8 @@ impl SelfHostedPostgresService {
         );
         let primary_db_port = settings.effective_db_port(node.network);
         let mut db_ports = vec![primary_db_port];
-        {
-            let network = Network::Mainnet;
-            let port = settings.effective_db_port(network);
+        for network in Network::iter() {
+            let port = settings.effective_db_port(*network);
             if !db_ports.contains(&port) {
                 db_ports.push(port);
             }
@@ -1461,7 +1378,7 @@ impl SelfHostedPostgresService {
         let postmaster_pid = data_dir.join("postmaster.pid");
         if postmaster_pid.exists() {
             if let Some(pid) = Self::read_postmaster_pid(&postmaster_pid)
-                && !Self::is_process_running(pid)
+                && !process_supervisor::process_is_running(pid)
             {
                 let msg = format!(
                     "postmaster.pid points to non-running process (pid {}); removing stale pid",
@@ -1540,74 +1457,21 @@ impl SelfHostedPostgresService {
             .arg("lc_messages=C")
             .env("LANG", "C")
             .env("LC_ALL", "C")
-            .env("LC_MESSAGES", "C")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .env("LC_MESSAGES", "C");
 
         #[cfg(unix)]
         cmd.arg("-c")
             .arg(format!("unix_socket_directories={}", socket_dir.display()));
 
-        #[cfg(windows)]
-        {
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
-        }
-
-        #[cfg(unix)]
-        {
-            cmd.process_group(0);
-        }
-
-        let mut child = match cmd.spawn() {
-            Ok(child) => child,
-            Err(err) => {
-                let err = Error::NodeStartupError(err);
-                log_warn!("self-hosted-postgres: failed to start ({err})");
-                self.logs.push("WARN", &format!("failed to start ({err})"));
-                return Err(err);
-            }
-        };
-
-        let logs_info = self.logs.clone();
-        if let Some(stdout) = child.stdout.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stdout).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    let level = Self::detect_log_level(&line, "INFO");
-                    if level == "IGNORE" {
-                        continue;
-                    }
-                    match level {
-                        "ERROR" => log_warn!("self-hosted-postgres: {line}"),
-                        "WARN" => log_warn!("self-hosted-postgres: {line}"),
-                        _ => log_info!("self-hosted-postgres: {line}"),
-                    }
-                    logs_info.push(level, &line);
-                }
-            });
-        }
-
-        let logs_warn = self.logs.clone();
-        if let Some(stderr) = child.stderr.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stderr).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    let level = Self::detect_log_level(&line, "WARN");
-                    if level == "IGNORE" {
-                        continue;
-                    }
-                    match level {
-                        "ERROR" => log_warn!("self-hosted-postgres: {line}"),
-                        "WARN" => log_warn!("self-hosted-postgres: {line}"),
-                        _ => log_info!("self-hosted-postgres: {line}"),
-                    }
-                    logs_warn.push(level, &line);
-                }
-            });
+        let output = LogStoreOutput::new(&self.logs)
+            .with_console("self-hosted-postgres")
+            .with_level_detector(Self::detect_log_level);
+        if let Err(err) = self.process.spawn(cmd, Arc::new(output)) {
+            log_warn!("self-hosted-postgres: failed to start ({err})");
+            self.logs.push("WARN", &format!("failed to start ({err})"));
+            return Err(err);
         }
 
-        *self.child.lock().unwrap() = Some(child);
         *self.last_restart_at.lock().unwrap() = Some(Instant::now());
         if Self::wait_for_ready(&settings, &node_settings, 20)
             .await
@@ -1664,57 +1528,10 @@ impl SelfHostedPostgresService {
     }
 
     async fn stop_postgres(&self) -> Result<()> {
-        let child = self.child.lock().unwrap().take();
-        if let Some(mut child) = child {
-            let mut exited = false;
-
-            #[cfg(unix)]
-            {
-                if let Some(pid_u32) = child.id() {
-                    use nix::sys::signal::{Signal, kill};
-                    use nix::unistd::Pid;
-
-                    if let Ok(pid_i32) = i32::try_from(pid_u32) {
-                        let _ = kill(Pid::from_raw(pid_i32), Signal::SIGTERM);
-                        self.logs
-                            .push("INFO", "sent SIGTERM to postgres for graceful shutdown");
-                    }
-                }
-            }
-
-            if tokio::time::timeout(Self::STOP_GRACEFUL_WAIT, child.wait())
-                .await
-                .is_ok()
-            {
-                exited = true;
-            }
-
-            if !exited {
-                self.logs.push(
-                    "WARN",
-                    "postgres did not exit after SIGTERM; retrying graceful shutdown",
-                );
-                #[cfg(unix)]
-                {
-                    if let Some(pid_u32) = child.id() {
-                        use nix::sys::signal::{Signal, kill};
-                        use nix::unistd::Pid;
-                        if let Ok(pid_i32) = i32::try_from(pid_u32) {
-                            let _ = kill(Pid::from_raw(pid_i32), Signal::SIGINT);
-                        }
-                    }
-                }
-
-                if tokio::time::timeout(Self::STOP_RETRY_WAIT, child.wait())
-                    .await
-                    .is_err()
-                {
-                    self.logs
-                        .push("WARN", "postgres did not exit in time; forcing termination");
-                    let _ = child.start_kill();
-                    let _ = child.wait().await;
-                }
-            }
+        if self.process.has_child() {
+            self.logs
+                .push("INFO", "stopping postgres (SIGTERM, then SIGINT if needed)");
+            self.process.stop().await;
         }
 
         // If this service did not spawn the process (e.g. app restart), we can still
@@ -0,0 +1,304 @@
+use super::BridgeBlock;
+use crate::imports::*;
+use crate::settings::network_profile_slug;
+use kaspa_rpc_core::RpcHash;
+use kaspa_wallet_core::storage::local::storage::Storage;
+use workflow_core::time::unixtime_as_millis_u64;
+
+/// Blocks not known to the node after this period are considered orphaned.
+const ORPHAN_TIMEOUT_MSEC: u64 = 10 * 60 * 1000;
+/// Reconciled blocks keep being re-checked for this period to catch reorgs.
+const RECONCILE_WINDOW_MSEC: u64 = 60 * 60 * 1000;
+
+/// Outcome of checking a found block against the node's view of the DAG.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlockReconciliation {
+    /// Not yet checked, or not yet merged by the virtual chain.
+    #[default]
+    Pending,
+    /// The block is part of the selected (virtual) chain.
+    Chain,
+    /// The block was merged as blue and its coinbase is paid.
+    Blue,
+    /// The block was merged as red and receives no reward.
+    Red,
+    /// A connected, synced node reported that it does not know the block.
+    Orphaned,
+}
+
+impl BlockReconciliation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockReconciliation::Pending => "pending",
+            BlockReconciliation::Chain => "chain",
+            BlockReconciliation::Blue => "blue",
+            BlockReconciliation::Red => "red",
+            BlockReconciliation::Orphaned => "orphaned",
+        }
+    }
+
+    pub fn is_rewarded(&self) -> bool {
+        matches!(self, BlockReconciliation::Chain | BlockReconciliation::Blue)
+    }
+}
+
+impl std::fmt::Display for BlockReconciliation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LedgerBlock {
+    pub hash: String,
+    /// Timestamp as printed by the bridge, if any.
+    pub timestamp: Option<String>,
+    /// Unix time in milliseconds when the block was first recorded.
+    pub recorded_at: u64,
+    pub worker: Option<String>,
+    pub wallet: Option<String>,
+    /// Submission status reported by the bridge (found, accepted, rejected).
+    pub status: String,
+    #[serde(default)]
+    pub reconciliation: BlockReconciliation,
+    #[serde(default)]
+    pub reconciled_at: Option<u64>,
+}
+
+impl LedgerBlock {
+    fn needs_reconciliation(&self, now: u64) -> bool {
+        if self.status.starts_with("Rejected") {
+            return false;
+        }
+
+        match self.reconciliation {
+            BlockReconciliation::Pending => true,
+            _ => now.saturating_sub(self.recorded_at) < RECONCILE_WINDOW_MSEC,
+        }
+    }
+}
+
+/// Persistent per-network record of blocks found through the stratum bridge.
+pub struct BlockLedger {
+    network: Network,
+    path: Option<PathBuf>,
+    blocks: Vec<LedgerBlock>,
+}
+
+impl BlockLedger {
+    pub fn filename(network: Network) -> String {
+        format!(
+            "kaspa-ng.{}.bridge-blocks.json",
+            network_profile_slug(network)
+        )
+    }
+
+    pub fn load(network: Network) -> Self {
+        let path = Storage::try_new(&Self::filename(network))
+            .ok()
+            .map(|storage| storage.filename().to_path_buf());
+
+        let blocks = path
+            .as_ref()
+            .filter(|path| path.exists())
+            .and_then(|path| match std::fs::read_to_string(path) {
+                Ok(data) => serde_json::from_str::<Vec<LedgerBlock>>(&data)
+                    .map_err(|err| log_warn!("Unable to parse block ledger: {err}"))
+                    .ok(),
+                Err(err) => {
+                    log_warn!("Unable to read block ledger: {err}");
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            network,
+            path,
+            blocks,
+        }
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    pub fn blocks(&self) -> &[LedgerBlock] {
+        &self.blocks
+    }
+
+    fn store(&self) -> Result<()> {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+
+        if let Some(folder) = path.parent() {
+            std::fs::create_dir_all(folder)?;
+        }
+
+        let data = serde_json::to_string_pretty(&self.blocks)?;
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, data)?;
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// Inserts or updates the ledger entry for a block reported by the bridge.
+    pub fn record(&mut self, block: &BridgeBlock) -> Result<()> {
+        if let Some(existing) = self
+            .blocks
+            .iter_mut()
+            .find(|entry| entry.hash == block.hash)
+        {
+            existing.status = block.status.clone();
+            if block.timestamp.is_some() {
+                existing.timestamp = block.timestamp.clone();
+            }
+            if block.worker.is_some() {
+                existing.worker = block.worker.clone();
+            }
+            if block.wallet.is_some() {
+                existing.wallet = block.wallet.clone();
+            }
+        } else {
+            self.blocks.push(LedgerBlock {
+                hash: block.hash.clone(),
+                timestamp: block.timestamp.clone(),
+                recorded_at: unixtime_as_millis_u64(),
+                worker: block.worker.clone(),
+                wallet: block.wallet.clone(),
+                status: block.status.clone(),
+                reconciliation: BlockReconciliation::Pending,
+                reconciled_at: None,
+            });
+        }
+
+        self.store()
+    }
+
+    /// Hashes of blocks whose reconciliation state may still change.
+    pub fn pending(&self) -> Vec<(String, u64)> {
+        let now = unixtime_as_millis_u64();
+        self.blocks
+            .iter()
+            .filter(|block| block.needs_reconciliation(now))
+            .map(|block| (block.hash.clone(), block.recorded_at))
+            .collect()
+    }
+
+    pub fn apply(&mut self, updates: &[(String, BlockReconciliation)]) -> Result<()> {
+        let now = unixtime_as_millis_u64();
+        let mut changed = false;
+        for (hash, reconciliation) in updates {
+            if let Some(block) = self.blocks.iter_mut().find(|block| &block.hash == hash) {
+                if block.reconciliation != *reconciliation {
+                    block.reconciliation = *reconciliation;
+                    changed = true;
+                }
+                block.reconciled_at = Some(now);
+            }
+        }
+
+        if changed { self.store() } else { Ok(()) }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "hash,timestamp,recorded_at,worker,wallet,status,reconciliation,rewarded\n",
+        );
+        for block in self.blocks.iter() {
+            let recorded_at =
+                chrono::DateTime::<chrono::Utc>::from_timestamp_millis(block.recorded_at as i64)
+                    .map(|time| time.to_rfc3339())
+                    .unwrap_or_default();
+            let fields = [
+                block.hash.as_str(),
+                block.timestamp.as_deref().unwrap_or_default(),
+                recorded_at.as_str(),
+                block.worker.as_deref().unwrap_or_default(),
+                block.wallet.as_deref().unwrap_or_default(),
+                block.status.as_str(),
+                block.reconciliation.as_str(),
+                if block.reconciliation.is_rewarded() {
+                    "true"
+                } else {
+                    "false"
+                },
+            ];
+            let line = fields
+                .iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<_>>();
+            csv.push_str(&line.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Returns `true` if the node reported that it does not know the block,
+/// as opposed to a transport or connection failure.
+fn is_block_not_found(err: &impl std::fmt::Display) -> bool {
+    let message = err.to_string().to_lowercase();
+    message.contains("not found") || message.contains("cannot find")
+}
+
+/// Determines the current state of a found block as seen by the node.
+///
+/// Must only be called while the node is connected and synced; any error
+/// other than a definite not-found leaves the block pending so that it is
+/// re-checked on the next pass.
+pub async fn reconcile_block(
+    rpc_api: &Arc<dyn RpcApi>,
+    hash: &str,
+    recorded_at: u64,
+) -> Option<BlockReconciliation> {
+    let block_hash = RpcHash::from_str(hash).ok()?;
+    let expired = unixtime_as_millis_u64().saturating_sub(recorded_at) > ORPHAN_TIMEOUT_MSEC;
+
+    let block = match rpc_api.get_block(block_hash, false).await {
+        Ok(block) => block,
+        Err(err) if expired && is_block_not_found(&err) => {
+            return Some(BlockReconciliation::Orphaned);
+        }
+        Err(_) => return None,
+    };
+
+    if block
+        .verbose_data
+        .as_ref()
+        .is_some_and(|verbose| verbose.is_chain_block)
+    {
+        return Some(BlockReconciliation::Chain);
+    }
+
+    // the color is only known once the block has been merged by a chain block
+    match rpc_api.get_current_block_color(block_hash).await {
+        Ok(response) if response.blue => Some(BlockReconciliation::Blue),
+        Ok(_) => Some(BlockReconciliation::Red),
+        Err(_) => Some(BlockReconciliation::Pending),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_not_found_errors_are_definite() {
+        assert!(is_block_not_found(&"Block 1234 not found"));
+        assert!(is_block_not_found(&"cannot find full block 1234"));
+        assert!(!is_block_not_found(&"RPC client is not connected"));
+        assert!(!is_block_not_found(&"request timed out"));
+    }
+}
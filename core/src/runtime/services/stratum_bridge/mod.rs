@@ -1,43 +1,42 @@
 use crate::imports::*;
 use crate::runtime::Service;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ledger;
+#[cfg(not(target_arch = "wasm32"))]
+pub use ledger::{BlockLedger, BlockReconciliation, LedgerBlock};
+#[cfg(not(target_arch = "wasm32"))]
+pub mod stats;
+#[cfg(not(target_arch = "wasm32"))]
+pub use stats::{BridgeStats, BridgeStatsHistory, WorkerStats};
+
 cfg_if! {
     if #[cfg(not(target_arch = "wasm32"))] {
-        use crate::runtime::services::kaspa::logs::Log;
-        use std::collections::HashMap;
-        use std::process::Stdio;
-        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+        use crate::runtime::services::log_hub::{LogSource, log_hub};
+        use crate::runtime::services::process_supervisor::{
+            self, HealthProbe, OutputStream, ProcessOutput, ProcessStatus, ProcessSupervisor,
+            RestartPolicy, StopPolicy,
+        };
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
         use tokio::net::TcpStream;
-        use tokio::process::{Child, Command};
+        use tokio::process::Command;
 
-        const LOG_BUFFER_LINES: usize = 4096;
-        const LOG_BUFFER_MARGIN: usize = 128;
         const BLOCK_BUFFER_LINES: usize = 256;
         const BLOCK_BUFFER_MARGIN: usize = 32;
-        const RESTART_DELAY: Duration = Duration::from_secs(3);
+        const RESTART_POLICY: RestartPolicy =
+            RestartPolicy::backoff(Duration::from_secs(3), Duration::from_secs(60));
         const BRIDGE_PROM_PORT: u16 = 2114;
         const STATS_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
-        const STATS_WORKER_WIDTH: usize = 16;
-        const STATS_WALLET_WIDTH: usize = 18;
-        const STATS_INST_WIDTH: usize = 5;
-        const STATS_HASH_WIDTH: usize = 11;
-        const STATS_DIFF_WIDTH: usize = 6;
-        const STATS_SPM_WIDTH: usize = 11;
-        const STATS_TRND_WIDTH: usize = 4;
-        const STATS_ACC_WIDTH: usize = 12;
-        const STATS_BLK_WIDTH: usize = 6;
-        const STATS_TIME_WIDTH: usize = 11;
+        const LEDGER_RECONCILE_INTERVAL: Duration = Duration::from_secs(30);
 
         fn default_grpc_port_for_network(network: Network) -> u16 {
             crate::settings::node_grpc_port_for_network(network)
         }
 
-        fn local_grpc_ports() -> [u16; 3] {
-            [
-                crate::settings::node_grpc_port_for_network(Network::Mainnet),
-                crate::settings::node_grpc_port_for_network(Network::Mainnet),
-                crate::settings::node_grpc_port_for_network(Network::Mainnet),
-            ]
+        fn local_grpc_ports() -> Vec<u16> {
+            Network::iter()
+                .map(|network| crate::settings::node_grpc_port_for_network(*network))
+                .collect()
         }
 
         fn is_local_host(host: &str) -> bool {
@@ -47,22 +46,6 @@ cfg_if! {
             )
         }
 
-        fn is_bridge_table_line(line: &str) -> bool {
-            let trimmed = line.trim_start();
-            trimmed.starts_with('+') || trimmed.starts_with('|')
-        }
-
-        #[derive(Debug, Clone, Deserialize)]
-        struct BridgeStatsWorker {
-            worker: String,
-            wallet: String,
-        }
-
-        #[derive(Debug, Clone, Deserialize)]
-        struct BridgeStatsResponse {
-            workers: Vec<BridgeStatsWorker>,
-        }
-
         pub fn update_logs_flag() -> &'static Arc<AtomicBool> {
             static FLAG: OnceLock<Arc<AtomicBool>> = OnceLock::new();
             FLAG.get_or_init(|| Arc::new(AtomicBool::new(false)))
@@ -99,12 +82,14 @@ cfg_if! {
             is_enabled: AtomicBool,
             starting: AtomicBool,
             restart_pending: AtomicBool,
-            logs: Mutex<Vec<Log>>,
             blocks: Mutex<Vec<BridgeBlock>>,
-            worker_wallets: Mutex<HashMap<String, String>>,
+            stats: Mutex<Option<BridgeStats>>,
+            stats_history: Mutex<BridgeStatsHistory>,
             last_block_hash: Mutex<Option<String>>,
+            ledger: Mutex<BlockLedger>,
+            rpc_api: Mutex<Option<Arc<dyn RpcApi>>>,
             node_settings: Mutex<NodeSettings>,
-            child: Mutex<Option<Child>>,
+            process: Arc<ProcessSupervisor>,
         }
 
         impl StratumBridgeService {
@@ -117,12 +102,18 @@ cfg_if! {
                     is_enabled: AtomicBool::new(enabled),
                     starting: AtomicBool::new(false),
                     restart_pending: AtomicBool::new(false),
-                    logs: Mutex::new(Vec::new()),
                     blocks: Mutex::new(Vec::new()),
-                    worker_wallets: Mutex::new(HashMap::new()),
+                    stats: Mutex::new(None),
+                    stats_history: Mutex::new(BridgeStatsHistory::default()),
                     last_block_hash: Mutex::new(None),
+                    ledger: Mutex::new(BlockLedger::load(settings.node.network)),
+                    rpc_api: Mutex::new(None),
                     node_settings: Mutex::new(settings.node.clone()),
-                    child: Mutex::new(None),
+                    process: Arc::new(
+                        ProcessSupervisor::new("RK Bridge")
+                            .with_restart_policy(RESTART_POLICY)
+                            .with_stop_policy(StopPolicy::Kill),
+                    ),
                 }
             }
 
@@ -141,27 +132,34 @@ cfg_if! {
                     .unwrap();
             }
 
-            pub fn logs(&self) -> MutexGuard<'_, Vec<Log>> {
-                self.logs.lock().unwrap()
+            pub fn is_enabled(&self) -> bool {
+                self.is_enabled.load(Ordering::SeqCst)
+            }
+
+            pub fn process_status(&self) -> Vec<ProcessStatus> {
+                vec![self.process.status()]
             }
 
             pub fn blocks(&self) -> MutexGuard<'_, Vec<BridgeBlock>> {
                 self.blocks.lock().unwrap()
             }
 
+            /// Persistent record of found blocks for the current network.
+            pub fn ledger(&self) -> MutexGuard<'_, BlockLedger> {
+                self.ledger.lock().unwrap()
+            }
+
+            /// Latest worker statistics scraped from the bridge Prometheus endpoint.
+            pub fn stats(&self) -> Option<BridgeStats> {
+                self.stats.lock().unwrap().clone()
+            }
+
+            pub fn stats_history(&self) -> MutexGuard<'_, BridgeStatsHistory> {
+                self.stats_history.lock().unwrap()
+            }
+
             async fn update_logs(&self, line: String) {
-                let display_line = self.rewrite_stats_table_line(&line);
-                {
-                    let mut logs = self.logs.lock().unwrap();
-                    if logs.len() > LOG_BUFFER_LINES {
-                        logs.drain(0..LOG_BUFFER_MARGIN);
-                    }
-                    if is_bridge_table_line(&display_line) {
-                        logs.push(Log::Processed(display_line.clone()));
-                    } else {
-                        logs.push(display_line.as_str().into());
-                    }
-                }
+                log_hub().push_line(LogSource::Bridge, &line);
 
                 if let Some(block_event) = Self::parse_block_event(&line) {
                     self.set_last_block_hash(block_event.hash.as_str());
@@ -182,6 +180,8 @@ cfg_if! {
             }
 
             fn record_block(&self, event: BridgeBlock) {
+                self.persist_block(&event);
+
                 let mut blocks = self.blocks.lock().unwrap();
                 if blocks.len() > BLOCK_BUFFER_LINES {
                     blocks.drain(0..BLOCK_BUFFER_MARGIN);
@@ -223,18 +223,70 @@ cfg_if! {
                     if details.wallet.is_some() {
                         existing.wallet = details.wallet;
                     }
+                    self.persist_block(existing);
                 } else {
-                    blocks.push(BridgeBlock {
+                    let block = BridgeBlock {
                         timestamp: None,
                         hash,
                         status: "Found".to_string(),
                         worker: details.worker,
                         wallet: details.wallet,
                         line: String::new(),
-                    });
+                    };
+                    self.persist_block(&block);
+                    blocks.push(block);
+                }
+            }
+
+            fn persist_block(&self, block: &BridgeBlock) {
+                if let Err(err) = self.ledger.lock().unwrap().record(block) {
+                    log_warn!("RK Bridge: unable to store block {}: {err}", block.hash);
+                }
+            }
+
+            fn update_ledger_network(&self, network: Network) {
+                let mut ledger = self.ledger.lock().unwrap();
+                if ledger.network() != network {
+                    *ledger = BlockLedger::load(network);
+                }
+            }
+
+            async fn reconcile_blocks(&self) {
+                let Some(rpc_api) = self.rpc_api.lock().unwrap().clone() else {
+                    return;
+                };
+
+                let pending = self.ledger.lock().unwrap().pending();
+                if pending.is_empty() {
+                    return;
+                }
+
+                // a disconnected, restarting or syncing node can not give a
+                // definite answer, so leave the blocks for the next pass
+                match rpc_api.get_server_info().await {
+                    Ok(server_info) if server_info.is_synced => {}
+                    _ => return,
+                }
+
+                let mut updates = Vec::with_capacity(pending.len());
+                for (hash, recorded_at) in pending {
+                    if let Some(reconciliation) = ledger::reconcile_block(&rpc_api, &hash, recorded_at).await {
+                        updates.push((hash, reconciliation));
+                    }
+                }
+
+                if let Err(err) = self.ledger.lock().unwrap().apply(&updates) {
+                    log_warn!("RK Bridge: unable to store block ledger: {err}");
                 }
             }
 
+            /// Found blocks as CSV, suitable for payout accounting.
+            pub fn export_ledger_csv(&self, path: &std::path::Path) -> Result<()> {
+                let csv = self.ledger.lock().unwrap().to_csv();
+                std::fs::write(path, csv)?;
+                Ok(())
+            }
+
             fn parse_block_event(line: &str) -> Option<BridgeBlock> {
                 let status = if line.contains("BLOCK FOUND!") {
                     "Found".to_string()
@@ -348,184 +400,48 @@ cfg_if! {
                 }
             }
 
-            fn truncate_cell(value: &str, width: usize) -> String {
-                let value = value.trim();
-                let chars = value.chars().count();
-                if chars <= width {
-                    return value.to_string();
-                }
-                if width <= 3 {
-                    return value.chars().take(width).collect();
-                }
-                let head: String = value.chars().take(width - 3).collect();
-                format!("{head}...")
-            }
-
-            fn short_wallet_label(value: &str) -> String {
-                let wallet = value.trim();
-                if wallet.is_empty() {
-                    return String::new();
-                }
-
-                let (prefix, payload) = if let Some((prefix, payload)) = wallet.split_once(':') {
-                    (prefix, payload)
-                } else {
-                    ("wallet", wallet)
-                };
-                let normalized_prefix = prefix.trim().to_ascii_lowercase();
-                let compact = payload.trim();
-                let first: String = compact.chars().take(4).collect();
-                let last: String = compact
-                    .chars()
-                    .rev()
-                    .take(4)
-                    .collect::<Vec<char>>()
-                    .into_iter()
-                    .rev()
-                    .collect();
-                if first.is_empty() || last.is_empty() {
-                    format!("{normalized_prefix}:{compact}")
-                } else {
-                    format!("{normalized_prefix}:{first}...{last}")
-                }
-            }
-
-            fn stats_border_line() -> String {
-                format!(
-                    "+-{}-+-{}-+-{}-+-{}-+-{}-+-{}-+-{}-+-{}-+-{}-+-{}-+",
-                    "-".repeat(STATS_WORKER_WIDTH),
-                    "-".repeat(STATS_WALLET_WIDTH),
-                    "-".repeat(STATS_INST_WIDTH),
-                    "-".repeat(STATS_HASH_WIDTH),
-                    "-".repeat(STATS_DIFF_WIDTH),
-                    "-".repeat(STATS_SPM_WIDTH),
-                    "-".repeat(STATS_TRND_WIDTH),
-                    "-".repeat(STATS_ACC_WIDTH),
-                    "-".repeat(STATS_BLK_WIDTH),
-                    "-".repeat(STATS_TIME_WIDTH)
-                )
-            }
-
-            fn stats_header_line() -> String {
-                format!(
-                    "| {:<STATS_WORKER_WIDTH$} | {:<STATS_WALLET_WIDTH$} | {:<STATS_INST_WIDTH$} | {:>STATS_HASH_WIDTH$} | {:>STATS_DIFF_WIDTH$} | {:>STATS_SPM_WIDTH$} | {:<STATS_TRND_WIDTH$} | {:>STATS_ACC_WIDTH$} | {:>STATS_BLK_WIDTH$} | {:>STATS_TIME_WIDTH$} |",
-                    "Worker", "Wallet", "Inst", "Hash", "Diff", "SPM|TGT", "Trnd", "Acc|Stl|Inv", "Blocks", "D|HR|M|S",
-                )
-            }
-
-            fn resolve_wallet_for_worker(&self, worker: &str) -> Option<String> {
-                let worker = worker.trim();
-                if worker.is_empty() {
-                    return None;
-                }
-                let map = self.worker_wallets.lock().unwrap();
-                if let Some(wallet) = map.get(worker) {
-                    return Some(wallet.clone());
-                }
-                map.iter().find_map(|(known_worker, wallet)| {
-                    if known_worker.starts_with(worker) || worker.starts_with(known_worker) {
-                        Some(wallet.clone())
-                    } else {
-                        None
-                    }
-                })
-            }
-
-            fn rewrite_stats_table_line(&self, line: &str) -> String {
-                let trimmed = line.trim_start();
-                if !(trimmed.starts_with('|') || trimmed.starts_with('+')) {
-                    return line.to_string();
-                }
-
-                if trimmed.starts_with('+') && trimmed.contains("-+-") {
-                    return Self::stats_border_line();
-                }
-
-                if trimmed.contains("| Worker") && trimmed.contains("| Inst") {
-                    return Self::stats_header_line();
-                }
-
-                if !trimmed.starts_with('|') {
-                    return line.to_string();
-                }
-
-                let columns: Vec<String> = trimmed
-                    .trim_matches('|')
-                    .split('|')
-                    .map(|part| part.trim().to_string())
-                    .collect();
-                if columns.len() != 9 {
-                    return line.to_string();
-                }
-
-                let worker = columns[0].clone();
-                let inst = columns[1].clone();
-                let wallet_short = self
-                    .resolve_wallet_for_worker(worker.as_str())
-                    .map(|wallet| Self::short_wallet_label(wallet.as_str()))
-                    .filter(|value| !value.is_empty())
-                    .unwrap_or_else(|| "-".to_string());
-
-                format!(
-                    "| {:<STATS_WORKER_WIDTH$} | {:<STATS_WALLET_WIDTH$} | {:<STATS_INST_WIDTH$} | {:>STATS_HASH_WIDTH$} | {:>STATS_DIFF_WIDTH$} | {:>STATS_SPM_WIDTH$} | {:<STATS_TRND_WIDTH$} | {:>STATS_ACC_WIDTH$} | {:>STATS_BLK_WIDTH$} | {:>STATS_TIME_WIDTH$} |",
-                    Self::truncate_cell(worker.as_str(), STATS_WORKER_WIDTH),
-                    Self::truncate_cell(wallet_short.as_str(), STATS_WALLET_WIDTH),
-                    Self::truncate_cell(inst.as_str(), STATS_INST_WIDTH),
-                    Self::truncate_cell(columns[2].as_str(), STATS_HASH_WIDTH),
-                    Self::truncate_cell(columns[3].as_str(), STATS_DIFF_WIDTH),
-                    Self::truncate_cell(columns[4].as_str(), STATS_SPM_WIDTH),
-                    Self::truncate_cell(columns[5].as_str(), STATS_TRND_WIDTH),
-                    Self::truncate_cell(columns[6].as_str(), STATS_ACC_WIDTH),
-                    Self::truncate_cell(columns[7].as_str(), STATS_BLK_WIDTH),
-                    Self::truncate_cell(columns[8].as_str(), STATS_TIME_WIDTH),
-                )
-            }
-
             fn stats_http_addr() -> String {
                 format!("127.0.0.1:{BRIDGE_PROM_PORT}")
             }
 
-            async fn refresh_worker_wallets(&self) {
-                let mut stream = match TcpStream::connect(Self::stats_http_addr()).await {
-                    Ok(stream) => stream,
-                    Err(_) => return,
-                };
+            async fn fetch_metrics() -> Option<String> {
+                let mut stream = TcpStream::connect(Self::stats_http_addr()).await.ok()?;
 
-                let request = b"GET /api/stats HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n";
-                if stream.write_all(request).await.is_err() {
-                    return;
-                }
+                // HTTP/1.0 keeps the server from answering with a chunked body
+                let request = b"GET /metrics HTTP/1.0\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n";
+                stream.write_all(request).await.ok()?;
 
                 let mut bytes = Vec::new();
-                if stream.read_to_end(&mut bytes).await.is_err() {
-                    return;
-                }
+                stream.read_to_end(&mut bytes).await.ok()?;
 
-                let response = String::from_utf8_lossy(&bytes);
-                let Some((_, body)) = response.split_once("\r\n\r\n") else {
-                    return;
-                };
+                stats::http_response_body(&bytes)
+            }
 
-                let Ok(stats) = serde_json::from_str::<BridgeStatsResponse>(body) else {
+            async fn refresh_stats(&self) {
+                let Some(body) = Self::fetch_metrics().await else {
                     return;
                 };
 
-                let mut next = HashMap::<String, String>::new();
-                for worker in stats.workers {
-                    let worker_name = worker.worker.trim();
-                    let wallet = worker.wallet.trim();
-                    if worker_name.is_empty() || wallet.is_empty() {
-                        continue;
-                    }
-                    next.insert(worker_name.to_string(), wallet.to_string());
+                let samples = stats::parse_prometheus(&body);
+                let timestamp = unixtime_as_millis_f64();
+                {
+                    let mut current = self.stats.lock().unwrap();
+                    let stats = BridgeStats::from_samples(&samples, timestamp, current.as_ref());
+                    self.stats_history.lock().unwrap().push(&stats);
+                    current.replace(stats);
                 }
 
-                if !next.is_empty() {
-                    *self.worker_wallets.lock().unwrap() = next;
+                if update_logs_flag().load(Ordering::SeqCst) && crate::runtime::try_runtime().is_some() {
+                    let _ = self.application_events.sender.send(Events::UpdateLogs).await;
                 }
             }
 
-            fn grpc_address_from_settings(settings: &NodeSettings) -> Option<String> {
+            fn clear_stats(&self) {
+                self.stats.lock().unwrap().take();
+                self.stats_history.lock().unwrap().clear();
+            }
+
+            pub(crate) fn grpc_address_from_settings(settings: &NodeSettings) -> Option<String> {
                 if !settings.enable_grpc {
                     return None;
                 }
@@ -551,72 +467,6 @@ cfg_if! {
                 }
             }
 
-            fn bridge_binary_name() -> &'static str {
-                if cfg!(windows) { "stratum-bridge.exe" } else { "stratum-bridge" }
-            }
-
-            fn running_from_macos_bundle() -> bool {
-                #[cfg(target_os = "macos")]
-                {
-                    if let Ok(exe) = std::env::current_exe() {
-                        return exe
-                            .to_string_lossy()
-                            .contains(".app/Contents/MacOS/");
-                    }
-                }
-                false
-            }
-
-            fn find_bridge_binary() -> Option<PathBuf> {
-                let bin_name = Self::bridge_binary_name();
-
-                if let Ok(exe) = std::env::current_exe() {
-                    if let Some(dir) = exe.parent() {
-                        let candidate = dir.join("resources").join(bin_name);
-                        if candidate.exists() {
-                            return Some(candidate);
-                        }
-                        let candidate = dir.join(bin_name);
-                        if candidate.exists() {
-                            return Some(candidate);
-                        }
-
-                        #[cfg(target_os = "macos")]
-                        if let Some(contents) = dir.parent() {
-                            let candidate =
-                                contents.join("Resources").join("resources").join(bin_name);
-                            if candidate.exists() {
-                                return Some(candidate);
-                            }
-                        }
-                    }
-                }
-
-                if !Self::running_from_macos_bundle() && let Ok(cwd) = std::env::current_dir() {
-                    let candidate = cwd.join("resources").join(bin_name);
-                    if candidate.exists() {
-                        return Some(candidate);
-                    }
-                    for profile in ["debug", "release"] {
-                        let candidate =
-                            cwd.join("target").join(profile).join("resources").join(bin_name);
-                        if candidate.exists() {
-                            return Some(candidate);
-                        }
-                        let candidate = cwd.join("target").join(profile).join(bin_name);
-                        if candidate.exists() {
-                            return Some(candidate);
-                        }
-                        let candidate = cwd.join("rusty-kaspa").join("target").join(profile).join(bin_name);
-                        if candidate.exists() {
-                            return Some(candidate);
-                        }
-                    }
-                }
-
-                None
-            }
-
             fn escape_yaml_str(value: &str) -> String {
                 value
                     .replace(['\r', '\n'], " ")
@@ -703,7 +553,14 @@ instances:
             }
 
             fn is_running(&self) -> bool {
-                self.child.lock().unwrap().is_some()
+                self.process.has_child()
+            }
+
+            /// The bridge answers on its health check port once it is serving.
+            fn health_probe(bridge: &StratumBridgeSettings) -> Option<HealthProbe> {
+                let port = Self::normalize_port(&bridge.health_check_port);
+                let port = port.rsplit(':').next()?.parse::<u16>().ok()?;
+                Some(HealthProbe::tcp("127.0.0.1", port))
             }
 
             async fn wait_for_grpc(self: &Arc<Self>, address: &str) -> bool {
@@ -749,12 +606,21 @@ instances:
                 let this = Arc::clone(self);
                 let reason = reason.to_string();
                 tokio::spawn(async move {
+                    let Some(delay) = this.process.retry_delay() else {
+                        this.update_logs(format!(
+                            "RK Bridge: {reason}; giving up after {} attempts",
+                            this.process.failures()
+                        ))
+                        .await;
+                        this.restart_pending.store(false, Ordering::SeqCst);
+                        return;
+                    };
                     this.update_logs(format!(
                         "RK Bridge: {reason}; restarting in {}s",
-                        RESTART_DELAY.as_secs()
+                        delay.as_secs().max(1)
                     ))
                     .await;
-                    task::sleep(RESTART_DELAY).await;
+                    task::sleep(delay).await;
                     if !this.is_enabled.load(Ordering::SeqCst) {
                         this.restart_pending.store(false, Ordering::SeqCst);
                         return;
@@ -764,6 +630,18 @@ instances:
                 });
             }
 
+            /// Restarts the process according to the restart policy when it exits.
+            fn supervise(self: &Arc<Self>) {
+                let this = Arc::clone(self);
+                let is_enabled = Arc::clone(self);
+                self.process.monitor(
+                    move || is_enabled.is_enabled.load(Ordering::SeqCst),
+                    move || async move {
+                        let _ = this.start_bridge().await;
+                    },
+                );
+            }
+
             async fn start_bridge(self: &Arc<Self>) -> Result<()> {
                 if self.is_running() {
                     return Ok(());
@@ -796,10 +674,11 @@ instances:
                     }
                 };
 
-                let bridge_bin = match Self::find_bridge_binary() {
+                let bridge_bin = match process_supervisor::find_binary("stratum-bridge", &["rusty-kaspa"]) {
                     Some(path) => path,
                     None => {
                         self.update_logs(i18n("RK Bridge: stratum-bridge binary not found (build kaspa-stratum-bridge first)").to_string()).await;
+                        self.process.record_failure();
                         self.schedule_restart("stratum-bridge binary not found");
                         return Ok(());
                     }
@@ -815,47 +694,17 @@ instances:
                 cmd.arg("--config")
                     .arg(config_path)
                     .arg("--node-mode")
-                    .arg("external")
-                    .stdout(Stdio::piped())
-                    .stderr(Stdio::piped());
-                #[cfg(windows)]
-                {
-                    const CREATE_NO_WINDOW: u32 = 0x08000000;
-                    cmd.creation_flags(CREATE_NO_WINDOW);
-                }
-
-                let mut child = match cmd.spawn() {
-                    Ok(child) => child,
-                    Err(err) => {
-                        let err = Error::NodeStartupError(err);
-                        self.update_logs(format!("RK Bridge: failed to start ({})", err))
-                            .await;
-                        self.schedule_restart("failed to start");
-                        return Err(err);
-                    }
-                };
+                    .arg("external");
 
-                if let Some(stdout) = child.stdout.take() {
-                    let this = Arc::clone(self);
-                    tokio::spawn(async move {
-                        let mut reader = BufReader::new(stdout).lines();
-                        while let Ok(Some(line)) = reader.next_line().await {
-                            this.update_logs(line).await;
-                        }
-                    });
+                self.process
+                    .set_health_probe(Self::health_probe(&settings.stratum_bridge));
+                if let Err(err) = self.process.spawn(cmd, self.clone()) {
+                    self.update_logs(format!("RK Bridge: failed to start ({})", err))
+                        .await;
+                    self.schedule_restart("failed to start");
+                    return Err(err);
                 }
 
-                if let Some(stderr) = child.stderr.take() {
-                    let this = Arc::clone(self);
-                    tokio::spawn(async move {
-                        let mut reader = BufReader::new(stderr).lines();
-                        while let Ok(Some(line)) = reader.next_line().await {
-                            this.update_logs(line).await;
-                        }
-                    });
-                }
-
-                *self.child.lock().unwrap() = Some(child);
                 self.update_logs(i18n("RK Bridge: started in external mode").to_string()).await;
 
                 let stats_sync = Arc::clone(self);
@@ -866,72 +715,51 @@ instances:
                         if !stats_sync.is_enabled.load(Ordering::SeqCst) || !stats_sync.is_running() {
                             return;
                         }
-                        stats_sync.refresh_worker_wallets().await;
+                        stats_sync.refresh_stats().await;
                     }
                 });
 
-                // Monitor child exit and clear handle
-                let monitor = Arc::clone(self);
-                tokio::spawn(async move {
-                    loop {
-                        task::sleep(Duration::from_secs(1)).await;
-                        let status = {
-                            let mut guard = monitor.child.lock().unwrap();
-                            match guard.as_mut() {
-                                Some(child) => child.try_wait(),
-                                None => return,
-                            }
-                        };
-
-                        match status {
-                            Ok(Some(status)) => {
-                                monitor.child.lock().unwrap().take();
-                                let _ = monitor
-                                    .update_logs(format!("RK Bridge: exited ({})", status))
-                                    .await;
-                                if status.success() {
-                                    let _ = monitor
-                                        .update_logs(
-                                            "RK Bridge: exited cleanly; automatic restart skipped.".to_string(),
-                                        )
-                                        .await;
-                                } else {
-                                    monitor.schedule_restart("bridge exited with error");
-                                }
-                                return;
-                            }
-                            Ok(None) => {}
-                            Err(err) => {
-                                monitor.child.lock().unwrap().take();
-                                let _ = monitor
-                                    .update_logs(format!("RK Bridge: monitor error ({})", err))
-                                    .await;
-                                monitor.schedule_restart("bridge monitor error");
-                                return;
-                            }
-                        }
-                    }
-                });
+                self.supervise();
                 Ok(())
             }
 
             async fn stop_bridge(self: &Arc<Self>) -> Result<()> {
-                let child = self.child.lock().unwrap().take();
-                if let Some(mut child) = child {
-                    let _ = child.start_kill();
-                    let _ = child.wait().await;
+                self.process.reset();
+                if self.process.stop().await {
+                    self.clear_stats();
                     self.update_logs(i18n("RK Bridge: stopped").to_string()).await;
                 }
                 Ok(())
             }
         }
 
+        #[async_trait]
+        impl ProcessOutput for StratumBridgeService {
+            async fn line(&self, _stream: OutputStream, line: String) {
+                self.update_logs(line).await;
+            }
+
+            async fn notice(&self, _level: &'static str, message: String) {
+                self.update_logs(message).await;
+            }
+        }
+
         #[async_trait]
         impl Service for StratumBridgeService {
             fn name(&self) -> &'static str {
                 "stratum-bridge-service"
             }
 
+            async fn attach_rpc(self: Arc<Self>, rpc_api: &Arc<dyn RpcApi>) -> Result<()> {
+                self.rpc_api.lock().unwrap().replace(rpc_api.clone());
+                Ok(())
+            }
+
+            async fn detach_rpc(self: Arc<Self>) -> Result<()> {
+                self.rpc_api.lock().unwrap().take();
+                Ok(())
+            }
+
             async fn spawn(self: Arc<Self>) -> Result<()> {
                 let this = self.clone();
                 tokio::spawn(async move {
@@ -939,12 +767,19 @@ instances:
                         let _ = this.start_bridge().await;
                     }
 
+                    let reconcile = task::interval(LEDGER_RECONCILE_INTERVAL);
+                    pin_mut!(reconcile);
+
                     loop {
                         select! {
+                            _ = reconcile.next().fuse() => {
+                                this.reconcile_blocks().await;
+                            }
                             msg = this.service_events.receiver.recv().fuse() => {
                                 match msg {
                                     Ok(BridgeEvents::SetEnabled { enabled, settings }) => {
                                         this.is_enabled.store(enabled, Ordering::SeqCst);
+                                        this.update_ledger_network(settings.network);
                                         *this.node_settings.lock().unwrap() = settings;
                                         if enabled {
                                             let _ = this.start_bridge().await;
@@ -953,6 +788,7 @@ instances:
                                         }
                                     }
                                     Ok(BridgeEvents::UpdateSettings(settings)) => {
+                                        this.update_ledger_network(settings.network);
                                         *this.node_settings.lock().unwrap() = settings;
                                         if this.is_enabled.load(Ordering::SeqCst) {
                                             let _ = this.stop_bridge().await;
@@ -995,6 +831,10 @@ instances:
             pub fn enable(&self, _enabled: bool, _node_settings: &NodeSettings) {}
 
             pub fn update_settings(&self, _node_settings: &NodeSettings) {}
+
+            pub fn is_enabled(&self) -> bool {
+                false
+            }
         }
 
         pub fn update_logs_flag() -> &'static Arc<AtomicBool> {
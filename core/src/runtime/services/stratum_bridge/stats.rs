@@ -0,0 +1,479 @@
+use crate::imports::*;
+
+/// Maximum number of stats samples retained for history charts
+/// (one hour at the default 5 second refresh interval).
+pub const BRIDGE_STATS_HISTORY_SAMPLES: usize = 720;
+
+/// Prometheus counters exposed by the stratum bridge.
+const VALID_SHARE_COUNTER: &str = "ks_valid_share_counter";
+const VALID_SHARE_DIFF_COUNTER: &str = "ks_valid_share_diff_counter";
+const INVALID_SHARE_COUNTER: &str = "ks_invalid_share_counter";
+const BLOCKS_MINED_COUNTER: &str = "ks_blocks_mined";
+const WORKER_DIFFICULTY_GAUGE: &str = "ks_worker_current_difficulty";
+const NETWORK_DIFFICULTY_GAUGE: &str = "ks_network_difficulty_gauge";
+const NETWORK_HASHRATE_GAUGE: &str = "ks_estimated_network_hashrate_gauge";
+
+/// Stratum difficulty 1 corresponds to 2^32 hashes.
+const HASHES_PER_SHARE_DIFF: f64 = 4_294_967_296.0;
+
+/// A single sample parsed from the Prometheus text exposition format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromSample {
+    pub name: String,
+    pub labels: Vec<(String, String)>,
+    pub value: f64,
+}
+
+impl PromSample {
+    pub fn label(&self, key: &str) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Extracts the body of a `200 OK` HTTP response, decoding
+/// `Transfer-Encoding: chunked` if the server used it.
+pub fn http_response_body(response: &[u8]) -> Option<String> {
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")?;
+    let head = String::from_utf8_lossy(&response[..header_end]);
+    let body = &response[header_end + 4..];
+
+    let mut lines = head.lines();
+    let status = lines.next()?;
+    if !status.starts_with("HTTP/1.1 200") && !status.starts_with("HTTP/1.0 200") {
+        return None;
+    }
+
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.to_ascii_lowercase().contains("chunked")
+        })
+    });
+
+    if chunked {
+        decode_chunked(body)
+    } else {
+        Some(String::from_utf8_lossy(body).into_owned())
+    }
+}
+
+fn decode_chunked(mut body: &[u8]) -> Option<String> {
+    let mut decoded = Vec::new();
+    loop {
+        let line_end = body.windows(2).position(|window| window == b"\r\n")?;
+        let size_line = std::str::from_utf8(&body[..line_end]).ok()?;
+        // chunk extensions (`;name=value`) are ignored
+        let size = size_line.split(';').next()?.trim();
+        let size = usize::from_str_radix(size, 16).ok()?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Some(String::from_utf8_lossy(&decoded).into_owned());
+        }
+
+        decoded.extend_from_slice(body.get(..size)?);
+        body = body.get(size..)?.strip_prefix(b"\r\n")?;
+    }
+}
+
+/// Parses the Prometheus text exposition format, skipping comments,
+/// `HELP`/`TYPE` metadata and malformed lines.
+pub fn parse_prometheus(text: &str) -> Vec<PromSample> {
+    text.lines().filter_map(parse_prometheus_line).collect()
+}
+
+fn parse_prometheus_line(line: &str) -> Option<PromSample> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let (name, labels, rest) = if let Some(open) = line.find('{') {
+        let close = line.rfind('}')?;
+        if close < open {
+            return None;
+        }
+        (
+            &line[..open],
+            parse_labels(&line[open + 1..close])?,
+            &line[close + 1..],
+        )
+    } else {
+        let (name, rest) = line.split_once(char::is_whitespace)?;
+        (name, Vec::new(), rest)
+    };
+
+    // value may be followed by an optional timestamp
+    let value = rest.split_whitespace().next()?;
+    let value = match value {
+        "+Inf" => f64::INFINITY,
+        "-Inf" => f64::NEG_INFINITY,
+        value => value.parse::<f64>().ok()?,
+    };
+
+    Some(PromSample {
+        name: name.trim().to_string(),
+        labels,
+        value,
+    })
+}
+
+fn parse_labels(text: &str) -> Option<Vec<(String, String)>> {
+    let mut labels = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    loop {
+        while matches!(chars.peek(), Some(c) if c.is_whitespace() || *c == ',') {
+            chars.next();
+        }
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let mut key = String::new();
+        for c in chars.by_ref() {
+            if c == '=' {
+                break;
+            }
+            key.push(c);
+        }
+
+        if chars.next()? != '"' {
+            return None;
+        }
+
+        let mut value = String::new();
+        loop {
+            match chars.next()? {
+                '\\' => match chars.next()? {
+                    'n' => value.push('\n'),
+                    c => value.push(c),
+                },
+                '"' => break,
+                c => value.push(c),
+            }
+        }
+
+        labels.push((key.trim().to_string(), value));
+    }
+
+    Some(labels)
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct WorkerStats {
+    pub worker: String,
+    pub wallet: String,
+    pub ip: String,
+    pub miner: String,
+    /// Estimated from the share difficulty accepted since the previous sample.
+    pub hashrate: f64,
+    pub difficulty: f64,
+    pub shares: u64,
+    pub share_diff: f64,
+    pub stale: u64,
+    pub duplicate: u64,
+    pub invalid: u64,
+    pub blocks: u64,
+}
+
+impl WorkerStats {
+    fn key(&self) -> (String, String) {
+        (self.worker.clone(), self.wallet.clone())
+    }
+
+    pub fn rejected(&self) -> u64 {
+        self.stale + self.duplicate + self.invalid
+    }
+}
+
+/// Typed snapshot of the stratum bridge Prometheus metrics.
+#[derive(Debug, Default, Clone)]
+pub struct BridgeStats {
+    /// Unix time in milliseconds when the snapshot was taken.
+    pub timestamp: f64,
+    pub workers: Vec<WorkerStats>,
+    pub network_difficulty: Option<f64>,
+    pub network_hashrate: Option<f64>,
+}
+
+impl BridgeStats {
+    /// Builds a snapshot from parsed samples. When a previous snapshot is
+    /// supplied, per-worker hashrate is derived from the share difficulty
+    /// accumulated between the two snapshots.
+    pub fn from_samples(
+        samples: &[PromSample],
+        timestamp: f64,
+        previous: Option<&BridgeStats>,
+    ) -> Self {
+        let mut workers = AHashMap::<(String, String), WorkerStats>::new();
+        let mut network_difficulty = None;
+        let mut network_hashrate = None;
+
+        for sample in samples {
+            match sample.name.as_str() {
+                NETWORK_DIFFICULTY_GAUGE => {
+                    network_difficulty = Some(sample.value);
+                    continue;
+                }
+                NETWORK_HASHRATE_GAUGE => {
+                    network_hashrate = Some(sample.value);
+                    continue;
+                }
+                VALID_SHARE_COUNTER
+                | VALID_SHARE_DIFF_COUNTER
+                | INVALID_SHARE_COUNTER
+                | BLOCKS_MINED_COUNTER
+                | WORKER_DIFFICULTY_GAUGE => {}
+                _ => continue,
+            }
+
+            let worker = sample.label("worker").unwrap_or_default().to_string();
+            let wallet = sample.label("wallet").unwrap_or_default().to_string();
+            let entry = workers
+                .entry((worker.clone(), wallet.clone()))
+                .or_insert_with(|| WorkerStats {
+                    worker,
+                    wallet,
+                    ..Default::default()
+                });
+
+            if let Some(ip) = sample.label("ip").filter(|ip| !ip.is_empty()) {
+                entry.ip = ip.to_string();
+            }
+            if let Some(miner) = sample.label("miner").filter(|miner| !miner.is_empty()) {
+                entry.miner = miner.to_string();
+            }
+
+            let count = sample.value.max(0.0) as u64;
+            match sample.name.as_str() {
+                VALID_SHARE_COUNTER => entry.shares += count,
+                VALID_SHARE_DIFF_COUNTER => entry.share_diff += sample.value,
+                INVALID_SHARE_COUNTER => match sample.label("type").unwrap_or_default() {
+                    "stale" => entry.stale += count,
+                    "duplicate" => entry.duplicate += count,
+                    _ => entry.invalid += count,
+                },
+                BLOCKS_MINED_COUNTER => entry.blocks += count,
+                WORKER_DIFFICULTY_GAUGE => entry.difficulty = sample.value,
+                _ => {}
+            }
+        }
+
+        let mut workers = workers.into_values().collect::<Vec<_>>();
+
+        if let Some(previous) = previous {
+            let elapsed = (timestamp - previous.timestamp) / 1000.0;
+            if elapsed > 0.0 {
+                let previous = previous
+                    .workers
+                    .iter()
+                    .map(|worker| (worker.key(), worker.share_diff))
+                    .collect::<AHashMap<_, _>>();
+
+                for worker in workers.iter_mut() {
+                    // counters reset when the bridge restarts
+                    let delta = previous
+                        .get(&worker.key())
+                        .map(|share_diff| worker.share_diff - share_diff)
+                        .filter(|delta| *delta >= 0.0)
+                        .unwrap_or_default();
+                    worker.hashrate = delta * HASHES_PER_SHARE_DIFF / elapsed;
+                }
+            }
+        }
+
+        workers.sort_by(|a, b| {
+            a.worker
+                .cmp(&b.worker)
+                .then_with(|| a.wallet.cmp(&b.wallet))
+        });
+
+        Self {
+            timestamp,
+            workers,
+            network_difficulty,
+            network_hashrate,
+        }
+    }
+
+    pub fn total_hashrate(&self) -> f64 {
+        self.workers.iter().map(|worker| worker.hashrate).sum()
+    }
+
+    pub fn total_shares(&self) -> u64 {
+        self.workers.iter().map(|worker| worker.shares).sum()
+    }
+
+    pub fn total_rejected(&self) -> u64 {
+        self.workers.iter().map(|worker| worker.rejected()).sum()
+    }
+
+    pub fn total_blocks(&self) -> u64 {
+        self.workers.iter().map(|worker| worker.blocks).sum()
+    }
+}
+
+/// Formats a hashrate in H/s using SI prefixes (e.g. `1.25 TH/s`).
+pub fn format_hashrate(hashrate: f64) -> String {
+    const UNITS: [&str; 7] = ["H/s", "KH/s", "MH/s", "GH/s", "TH/s", "PH/s", "EH/s"];
+    let mut value = hashrate.max(0.0);
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{value:.2} {}", UNITS[unit])
+}
+
+#[derive(Debug, Clone)]
+pub struct BridgeStatsSample {
+    pub timestamp: f64,
+    pub hashrate: f64,
+    pub shares: u64,
+    pub rejected: u64,
+    /// Hashrate per `(wallet, worker)`.
+    pub workers: AHashMap<(String, String), f64>,
+}
+
+/// Rolling history of bridge stats used for the hashrate and share charts.
+#[derive(Debug, Default, Clone)]
+pub struct BridgeStatsHistory {
+    samples: VecDeque<BridgeStatsSample>,
+}
+
+impl BridgeStatsHistory {
+    pub fn push(&mut self, stats: &BridgeStats) {
+        if self.samples.len() >= BRIDGE_STATS_HISTORY_SAMPLES {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back(BridgeStatsSample {
+            timestamp: stats.timestamp,
+            hashrate: stats.total_hashrate(),
+            shares: stats.total_shares(),
+            rejected: stats.total_rejected(),
+            workers: stats
+                .workers
+                .iter()
+                .map(|worker| {
+                    (
+                        (worker.wallet.clone(), worker.worker.clone()),
+                        worker.hashrate,
+                    )
+                })
+                .collect(),
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn samples(&self) -> &VecDeque<BridgeStatsSample> {
+        &self.samples
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METRICS: &str = r#"
+# HELP ks_valid_share_counter Number of valid shares found by worker over time
+# TYPE ks_valid_share_counter counter
+ks_valid_share_counter{ip="10.0.0.2",miner="BzMiner",wallet="kaspa:qz0example",worker="rig1"} 120
+ks_valid_share_counter{ip="10.0.0.3",miner="IceRiverMiner",wallet="kaspa:qz0example",worker="rig2"} 30
+ks_valid_share_diff_counter{ip="10.0.0.2",miner="BzMiner",wallet="kaspa:qz0example",worker="rig1"} 245760
+ks_invalid_share_counter{ip="10.0.0.2",miner="BzMiner",type="stale",wallet="kaspa:qz0example",worker="rig1"} 2
+ks_invalid_share_counter{ip="10.0.0.2",miner="BzMiner",type="duplicate",wallet="kaspa:qz0example",worker="rig1"} 1
+ks_invalid_share_counter{ip="10.0.0.2",miner="BzMiner",type="invalid",wallet="kaspa:qz0example",worker="rig1"} 3
+ks_blocks_mined{ip="10.0.0.2",miner="BzMiner",wallet="kaspa:qz0example",worker="rig1"} 1
+ks_network_difficulty_gauge 1.5e+15
+go_goroutines 42
+"#;
+
+    #[test]
+    fn test_parse_prometheus() {
+        let samples = parse_prometheus(METRICS);
+        assert_eq!(samples.len(), 9);
+        assert_eq!(samples[0].name, "ks_valid_share_counter");
+        assert_eq!(samples[0].label("worker"), Some("rig1"));
+        assert_eq!(samples[0].label("miner"), Some("BzMiner"));
+        assert_eq!(samples[0].value, 120.0);
+        assert_eq!(samples[7].value, 1.5e15);
+        assert!(samples[7].labels.is_empty());
+    }
+
+    #[test]
+    fn test_parse_escaped_labels() {
+        let sample =
+            parse_prometheus_line(r#"metric{a="x\"y",b="with, comma"} 1 1700000000000"#).unwrap();
+        assert_eq!(sample.label("a"), Some("x\"y"));
+        assert_eq!(sample.label("b"), Some("with, comma"));
+        assert_eq!(sample.value, 1.0);
+        assert!(parse_prometheus_line(r#"metric{a="unterminated} 1"#).is_none());
+    }
+
+    #[test]
+    fn test_http_response_body() {
+        let plain = b"HTTP/1.0 200 OK\r\nContent-Type: text/plain\r\n\r\nmetric 1\n";
+        assert_eq!(http_response_body(plain).as_deref(), Some("metric 1\n"));
+
+        let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+            9\r\nmetric 1\n\r\n\
+            a;ext=1\r\nmetric2 23\r\n\
+            0\r\n\r\n";
+        let body = http_response_body(chunked).unwrap();
+        assert_eq!(body, "metric 1\nmetric2 23");
+        let samples = parse_prometheus(&body);
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[1].value, 23.0);
+
+        assert!(http_response_body(b"HTTP/1.1 404 Not Found\r\n\r\n").is_none());
+        let truncated = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n9\r\nmetric";
+        assert!(http_response_body(truncated).is_none());
+    }
+
+    #[test]
+    fn test_history_keys_workers_by_wallet() {
+        let metrics = r#"
+ks_valid_share_counter{wallet="kaspa:qa",worker="rig"} 1
+ks_valid_share_counter{wallet="kaspa:qb",worker="rig"} 1
+"#;
+        let stats = BridgeStats::from_samples(&parse_prometheus(metrics), 0.0, None);
+        let mut history = BridgeStatsHistory::default();
+        history.push(&stats);
+        assert_eq!(history.samples()[0].workers.len(), 2);
+    }
+
+    #[test]
+    fn test_bridge_stats() {
+        let samples = parse_prometheus(METRICS);
+        let first = BridgeStats::from_samples(&samples, 0.0, None);
+        assert_eq!(first.workers.len(), 2);
+        assert_eq!(first.network_difficulty, Some(1.5e15));
+
+        let rig1 = &first.workers[0];
+        assert_eq!(rig1.worker, "rig1");
+        assert_eq!(rig1.shares, 120);
+        assert_eq!((rig1.stale, rig1.duplicate, rig1.invalid), (2, 1, 3));
+        assert_eq!(rig1.blocks, 1);
+        assert_eq!(rig1.hashrate, 0.0);
+        assert_eq!(first.total_shares(), 150);
+
+        let samples = parse_prometheus(&METRICS.replace("} 245760", "} 245770"));
+        let second = BridgeStats::from_samples(&samples, 10_000.0, Some(&first));
+        assert_eq!(second.workers[0].hashrate, HASHES_PER_SHARE_DIFF);
+        assert_eq!(second.workers[1].hashrate, 0.0);
+        assert_eq!(format_hashrate(second.total_hashrate()), "4.29 GH/s");
+    }
+}
@@ -151,13 +151,28 @@ impl RpcOptions {
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
 pub enum RpcKind {
     #[default]
     Wrpc,
     Grpc,
 }
 
+impl std::fmt::Display for RpcKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpcKind::Wrpc => write!(f, "wRPC"),
+            RpcKind::Grpc => write!(f, "gRPC"),
+        }
+    }
+}
+
+impl RpcKind {
+    pub fn iter() -> impl Iterator<Item = &'static RpcKind> {
+        [RpcKind::Wrpc, RpcKind::Grpc].iter()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum RpcConfig {
@@ -167,7 +182,7 @@ pub enum RpcConfig {
         resolver_urls: Option<Vec<Arc<String>>>,
     },
     Grpc {
-        url: Option<NetworkInterfaceConfig>,
+        url: Option<String>,
     },
 }
 
@@ -410,12 +425,151 @@ impl Default for StratumBridgeSettings {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+#[serde(default)]
+pub struct CpuMinerSettings {
+    pub mining_address: String,
+    pub threads: u16,
+}
+
+impl Default for CpuMinerSettings {
+    fn default() -> Self {
+        Self {
+            mining_address: String::new(),
+            threads: 1,
+        }
+    }
+}
+
+/// A string that is never written to disk or printed by `Debug`
+/// and is zeroed when dropped.
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn expose_mut(&mut self) -> &mut String {
+        &mut self.0
+    }
+}
+
+impl std::fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            write!(f, "\"\"")
+        } else {
+            write!(f, "[redacted]")
+        }
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+#[serde(default)]
+pub struct RothschildSettings {
+    /// Kept for the current session only.
+    #[serde(skip)]
+    pub private_key: SecretString,
+    pub change_address: String,
+    pub tps: u64,
+    pub threads: u8,
+}
+
+impl Default for RothschildSettings {
+    fn default() -> Self {
+        Self {
+            private_key: SecretString::default(),
+            change_address: String::new(),
+            tps: 1,
+            threads: 2,
+        }
+    }
+}
+
+/// Peers of the local node managed from the Node module. Preferred peers
+/// are passed to kaspad on start (`--addpeer`); banned IPs are applied over
+/// RPC once the node is connected.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct PeerSettings {
+    #[serde(default)]
+    pub preferred: Vec<String>,
+    #[serde(default)]
+    pub banned: Vec<String>,
+}
+
+impl PeerSettings {
+    /// Parses a peer address (`ip[:port]`); the default port of the
+    /// network is used if none is given.
+    pub fn parse_peer(address: &str) -> Result<ContextualNetAddress> {
+        ContextualNetAddress::from_str(address.trim())
+            .map_err(|err| Error::custom(format!("Invalid peer address {address}: {err}")))
+    }
+
+    pub fn parse_ip(ip: &str) -> Result<std::net::IpAddr> {
+        ip.trim()
+            .parse()
+            .map_err(|err| Error::custom(format!("Invalid IP address {ip}: {err}")))
+    }
+
+    /// Adds a preferred peer; returns `false` if it is already listed.
+    pub fn add_preferred(&mut self, address: &str) -> Result<bool> {
+        let address = Self::parse_peer(address)?.to_string();
+        if self.preferred.contains(&address) {
+            return Ok(false);
+        }
+        self.preferred.push(address);
+        Ok(true)
+    }
+
+    pub fn remove_preferred(&mut self, address: &str) {
+        self.preferred.retain(|preferred| preferred != address);
+    }
+
+    /// Adds an IP to the ban list; returns `false` if it is already banned.
+    pub fn ban(&mut self, ip: &str) -> Result<bool> {
+        let ip = Self::parse_ip(ip)?.to_string();
+        if self.banned.contains(&ip) {
+            return Ok(false);
+        }
+        self.banned.push(ip);
+        Ok(true)
+    }
+
+    pub fn unban(&mut self, ip: &str) {
+        self.banned.retain(|banned| banned != ip);
+    }
+
+    pub fn is_banned(&self, ip: &str) -> bool {
+        self.banned.iter().any(|banned| banned == ip)
+    }
+
+    pub fn preferred_peers(&self) -> Vec<ContextualNetAddress> {
+        self.preferred
+            .iter()
+            .filter_map(|address| Self::parse_peer(address).ok())
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct NodeSettings {
     pub connection_config_kind: NodeConnectionConfigKind,
     pub rpc_kind: RpcKind,
     pub wrpc_url: String,
+    #[serde(default = "default_grpc_url")]
+    pub grpc_url: String,
     #[serde(default)]
     pub enable_wrpc_borsh: bool,
     #[serde(default)]
@@ -441,20 +595,35 @@ pub struct NodeSettings {
     pub stratum_bridge: StratumBridgeSettings,
     #[serde(default = "default_stratum_bridge_enabled")]
     pub stratum_bridge_enabled: bool,
+    #[serde(default)]
+    pub cpu_miner: CpuMinerSettings,
+    #[serde(default)]
+    pub cpu_miner_enabled: bool,
+    #[serde(default)]
+    pub rothschild: RothschildSettings,
+    #[serde(default)]
+    pub rothschild_enabled: bool,
     #[serde(default = "default_true")]
     pub remove_grpc_info_in_rusty_kaspa_log: bool,
+    #[serde(default)]
+    pub peers: PeerSettings,
 }
 
 fn default_stratum_bridge_enabled() -> bool {
     false
 }
 
+fn default_grpc_url() -> String {
+    "127.0.0.1".to_string()
+}
+
 impl Default for NodeSettings {
     fn default() -> Self {
         Self {
             connection_config_kind: NodeConnectionConfigKind::default(),
             rpc_kind: RpcKind::Wrpc,
             wrpc_url: "127.0.0.1".to_string(),
+            grpc_url: default_grpc_url(),
             wrpc_encoding: WrpcEncoding::Borsh,
             enable_wrpc_borsh: true,
             wrpc_borsh_network_interface: NetworkInterfaceConfig::default(),
@@ -476,7 +645,12 @@ impl Default for NodeSettings {
             kaspad_daemon_storage_folder: String::default(),
             stratum_bridge: StratumBridgeSettings::default(),
             stratum_bridge_enabled: default_stratum_bridge_enabled(),
+            cpu_miner: CpuMinerSettings::default(),
+            cpu_miner_enabled: false,
+            rothschild: RothschildSettings::default(),
+            rothschild_enabled: false,
             remove_grpc_info_in_rusty_kaspa_log: true,
+            peers: PeerSettings::default(),
         }
     }
 }
@@ -497,6 +671,10 @@ impl NodeSettings {
                     Some(true)
                 } else if self.remove_grpc_info_in_rusty_kaspa_log != other.remove_grpc_info_in_rusty_kaspa_log {
                     Some(false)
+                } else if self.node_kind == KaspadNodeKind::Remote
+                    && (self.rpc_kind != other.rpc_kind || self.grpc_url != other.grpc_url)
+                {
+                    Some(true)
                 } else if self.kaspad_daemon_storage_folder_enable != other.kaspad_daemon_storage_folder_enable
                     || other.kaspad_daemon_storage_folder_enable && (self.kaspad_daemon_storage_folder != other.kaspad_daemon_storage_folder)
                 {
@@ -512,6 +690,7 @@ impl NodeSettings {
                     Some(self.node_kind != KaspadNodeKind::IntegratedInProc)
                 } else if self.kaspad_daemon_args != other.kaspad_daemon_args
                     || self.kaspad_daemon_args_enable != other.kaspad_daemon_args_enable
+                    || self.peers.preferred != other.peers.preferred
                 {
                     Some(self.node_kind.is_config_capable())
                 } else if self.kaspad_daemon_binary != other.kaspad_daemon_binary {
@@ -531,6 +710,7 @@ impl NodeSettings {
                     Some(true)
                 } else if self.rpc_kind != other.rpc_kind
                     || self.wrpc_url != other.wrpc_url
+                    || self.grpc_url != other.grpc_url
                     || self.wrpc_encoding != other.wrpc_encoding
                 {
                     Some(true)
@@ -551,12 +731,10 @@ impl RpcConfig {
         matches!(normalized, "127.0.0.1" | "localhost" | "::1")
     }
 
-    fn local_wrpc_ports() -> [u16; 3] {
-        [
-            node_wrpc_borsh_port_for_network(Network::Mainnet),
-            node_wrpc_borsh_port_for_network(Network::Mainnet),
-            node_wrpc_borsh_port_for_network(Network::Mainnet),
-        ]
+    fn local_wrpc_ports() -> Vec<u16> {
+        Network::iter()
+            .map(|network| node_wrpc_borsh_port_for_network(*network))
+            .collect()
     }
 
     fn rewrite_local_port_if_needed(host_port: &str, network: Network) -> Option<String> {
@@ -648,7 +826,7 @@ impl RpcConfig {
                     resolver_urls: None,
                 },
                 RpcKind::Grpc => RpcConfig::Grpc {
-                    url: Some(settings.grpc_network_interface.clone()),
+                    url: Some(settings.grpc_url.clone()),
                 },
             },
             NodeConnectionConfigKind::PublicServerCustom
@@ -795,15 +973,35 @@ impl ExplorerEndpoint {
 #[serde(rename_all = "kebab-case")]
 pub struct ExplorerNetworkProfiles {
     pub mainnet: ExplorerEndpoint,
+    #[serde(default = "default_testnet_10_explorer_endpoint")]
+    pub testnet_10: ExplorerEndpoint,
+    #[serde(default = "default_devnet_explorer_endpoint")]
+    pub devnet: ExplorerEndpoint,
 }
 
 impl ExplorerNetworkProfiles {
     pub fn for_network(&self, network: Network) -> &ExplorerEndpoint {
-        let _ = network;
-        &self.mainnet
+        match network {
+            Network::Mainnet => &self.mainnet,
+            Network::Testnet10 => &self.testnet_10,
+            Network::Devnet => &self.devnet,
+        }
     }
 }
 
+fn default_testnet_10_explorer_endpoint() -> ExplorerEndpoint {
+    ExplorerEndpoint::new(
+        "https://api-tn10.kaspa.org",
+        "wss://api-tn10.kaspa.org",
+        "/ws/socket.io",
+    )
+}
+
+// there is no public explorer API for devnet; use the self-hosted one
+fn default_devnet_explorer_endpoint() -> ExplorerEndpoint {
+    self_hosted_explorer_endpoint(&SelfHostedSettings::default(), Network::Devnet)
+}
+
 impl Default for ExplorerNetworkProfiles {
     fn default() -> Self {
         Self {
@@ -812,6 +1010,8 @@ impl Default for ExplorerNetworkProfiles {
                 "wss://api.kaspa.org",
                 "/ws/socket.io",
             ),
+            testnet_10: default_testnet_10_explorer_endpoint(),
+            devnet: default_devnet_explorer_endpoint(),
         }
     }
 }
@@ -974,23 +1174,38 @@ struct NetworkPorts {
     self_hosted_indexer_port: u16,
     node_grpc_port: u16,
     node_wrpc_borsh_port: u16,
+    control_api_port: u16,
 }
 
 fn network_ports(network: Network) -> NetworkPorts {
-    let _ = network;
+    // local services are shifted by 100 per network so that
+    // instances for different networks can run side by side
+    let offset = match network {
+        Network::Mainnet => 0,
+        Network::Testnet10 => 100,
+        Network::Devnet => 300,
+    };
+    // rusty-kaspa defaults
+    let (node_grpc_port, node_wrpc_borsh_port) = match network {
+        Network::Mainnet => (16110, 17110),
+        Network::Testnet10 => (16210, 17210),
+        Network::Devnet => (16610, 17610),
+    };
+
     NetworkPorts {
-        explorer_ui_port: default_explorer_ui_port(),
-        kasia_ui_port: default_kasia_ui_port(),
-        kasvault_ui_port: default_kasvault_ui_port(),
-        self_hosted_api_port: 19111,
-        self_hosted_rest_port: 19112,
-        self_hosted_socket_port: 19113,
-        self_hosted_db_port: 19114,
-        self_hosted_indexer_port: 19115,
-        self_hosted_k_web_port: 19116,
-        self_hosted_kasia_indexer_port: 19117,
-        node_grpc_port: 16110,
-        node_wrpc_borsh_port: 17110,
+        explorer_ui_port: default_explorer_ui_port() + offset,
+        kasia_ui_port: default_kasia_ui_port() + offset,
+        kasvault_ui_port: default_kasvault_ui_port() + offset,
+        self_hosted_api_port: 19111 + offset,
+        self_hosted_rest_port: 19112 + offset,
+        self_hosted_socket_port: 19113 + offset,
+        self_hosted_db_port: 19114 + offset,
+        self_hosted_indexer_port: 19115 + offset,
+        self_hosted_k_web_port: 19116 + offset,
+        self_hosted_kasia_indexer_port: 19117 + offset,
+        node_grpc_port,
+        node_wrpc_borsh_port,
+        control_api_port: 19120 + offset,
     }
 }
 
@@ -1006,13 +1221,19 @@ pub fn node_wrpc_borsh_port_for_network(network: Network) -> u16 {
 static ACTIVE_NETWORK_LOCK_PATH: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
 
 pub fn network_profile_slug(network: Network) -> &'static str {
-    let _ = network;
-    "mainnet"
+    match network {
+        Network::Mainnet => "mainnet",
+        Network::Testnet10 => "testnet-10",
+        Network::Devnet => "devnet",
+    }
 }
 
 pub fn network_settings_filename(network: Network) -> &'static str {
-    let _ = network;
-    "kaspa-ng.mainnet.settings"
+    match network {
+        Network::Mainnet => "kaspa-ng.mainnet.settings",
+        Network::Testnet10 => "kaspa-ng.testnet-10.settings",
+        Network::Devnet => "kaspa-ng.devnet.settings",
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -1201,32 +1422,43 @@ pub fn self_hosted_db_name_for_network(base: &str, network: Network) -> String {
         }
     };
 
-    let _ = network;
-    normalized
+    match network {
+        Network::Mainnet => normalized,
+        _ => format!(
+            "{}_{}",
+            normalized,
+            network_profile_slug(network).replace('-', "_")
+        ),
+    }
+}
+
+fn self_hosted_explorer_endpoint(
+    settings: &SelfHostedSettings,
+    network: Network,
+) -> ExplorerEndpoint {
+    let host = self_hosted_connect_host(&settings.api_bind);
+    ExplorerEndpoint::new(
+        format!(
+            "http://{}:{}",
+            host,
+            settings.effective_explorer_rest_port(network)
+        ),
+        format!(
+            "http://{}:{}",
+            host,
+            settings.effective_explorer_socket_port(network)
+        ),
+        "/ws/socket.io",
+    )
 }
 
 pub fn self_hosted_explorer_profiles_from_settings(
     settings: &SelfHostedSettings,
 ) -> ExplorerNetworkProfiles {
-    fn endpoint(settings: &SelfHostedSettings, network: Network) -> ExplorerEndpoint {
-        let host = self_hosted_connect_host(&settings.api_bind);
-        ExplorerEndpoint::new(
-            format!(
-                "http://{}:{}",
-                host,
-                settings.effective_explorer_rest_port(network)
-            ),
-            format!(
-                "http://{}:{}",
-                host,
-                settings.effective_explorer_socket_port(network)
-            ),
-            "/ws/socket.io",
-        )
-    }
-
     ExplorerNetworkProfiles {
-        mainnet: endpoint(settings, Network::Mainnet),
+        mainnet: self_hosted_explorer_endpoint(settings, Network::Mainnet),
+        testnet_10: self_hosted_explorer_endpoint(settings, Network::Testnet10),
+        devnet: self_hosted_explorer_endpoint(settings, Network::Devnet),
     }
 }
 
@@ -1332,6 +1564,153 @@ impl SelfHostedSettings {
     }
 }
 
+/// Authenticated JSON-RPC control API served on the loopback interface.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[serde(default)]
+pub struct ControlApiSettings {
+    pub enabled: bool,
+    /// Bearer token expected in the `Authorization` header of every request.
+    pub token: String,
+}
+
+impl ControlApiSettings {
+    pub fn effective_port(&self, network: Network) -> u16 {
+        network_ports(network).control_api_port
+    }
+
+    pub fn generate_token() -> String {
+        rand::random::<[u8; 32]>()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+#[derive(Describe, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlertKind {
+    #[describe("Active peers below")]
+    PeersBelow,
+    #[describe("Sync stalled (minutes)")]
+    SyncStalled,
+    #[describe("Mempool size above")]
+    MempoolAbove,
+    #[describe("Loader phase stuck (minutes)")]
+    LoaderStuck,
+    #[describe("Stratum bridge down (seconds)")]
+    StratumBridgeDown,
+    #[describe("Incoming transaction (minimum KAS)")]
+    IncomingTransaction,
+}
+
+impl AlertKind {
+    pub fn default_threshold(&self) -> f64 {
+        match self {
+            AlertKind::PeersBelow => 3.0,
+            AlertKind::SyncStalled => 5.0,
+            AlertKind::MempoolAbove => 10_000.0,
+            AlertKind::LoaderStuck => 15.0,
+            AlertKind::StratumBridgeDown => 30.0,
+            AlertKind::IncomingTransaction => 0.0,
+        }
+    }
+}
+
+/// A user-defined alert rule; the meaning of `threshold` depends on `kind`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AlertRule {
+    pub kind: AlertKind,
+    pub enabled: bool,
+    pub threshold: f64,
+}
+
+impl AlertRule {
+    pub fn new(kind: AlertKind) -> Self {
+        Self {
+            kind,
+            enabled: false,
+            threshold: kind.default_threshold(),
+        }
+    }
+}
+
+/// Alert rules evaluated against node metrics, service status and wallet
+/// events, and the channels alerts are delivered through.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[serde(default)]
+pub struct AlertSettings {
+    pub enabled: bool,
+    pub rules: Vec<AlertRule>,
+    pub os_notifications: bool,
+    pub webhook_enabled: bool,
+    /// Local `http://` endpoint receiving a JSON POST for every alert.
+    pub webhook_url: String,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules: AlertKind::iter()
+                .map(|kind| AlertRule::new(*kind))
+                .collect(),
+            os_notifications: true,
+            webhook_enabled: false,
+            webhook_url: "http://127.0.0.1:8080/alerts".to_string(),
+        }
+    }
+}
+
+impl AlertSettings {
+    /// Returns the enabled rule of the given kind, if any.
+    pub fn rule(&self, kind: AlertKind) -> Option<&AlertRule> {
+        self.enabled
+            .then(|| {
+                self.rules
+                    .iter()
+                    .find(|rule| rule.kind == kind && rule.enabled)
+            })
+            .flatten()
+    }
+
+    /// Returns a mutable rule of the given kind, adding it if missing
+    /// (e.g. in settings stored by an older version).
+    pub fn rule_mut(&mut self, kind: AlertKind) -> &mut AlertRule {
+        if let Some(index) = self.rules.iter().position(|rule| rule.kind == kind) {
+            &mut self.rules[index]
+        } else {
+            self.rules.push(AlertRule::new(kind));
+            self.rules.last_mut().unwrap()
+        }
+    }
+}
+
+/// Optional mirroring of all application logs to rotating files in
+/// `<storage>/logs`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[serde(default)]
+pub struct LogSettings {
+    pub files_enabled: bool,
+    /// Size in megabytes at which the current log file is rotated.
+    pub max_file_size_mb: u64,
+    /// Number of rotated files kept in addition to the current one.
+    pub max_files: usize,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            files_enabled: false,
+            max_file_size_mb: 10,
+            max_files: 5,
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct DeveloperSettings {
@@ -1423,6 +1802,12 @@ pub struct Settings {
     pub kasvault: KasvaultSettings,
     #[serde(default)]
     pub rendering: RenderingSettings,
+    #[serde(default)]
+    pub control_api: ControlApiSettings,
+    #[serde(default)]
+    pub alerts: AlertSettings,
+    #[serde(default)]
+    pub logs: LogSettings,
     pub node: NodeSettings,
     pub user_interface: UserInterfaceSettings,
     pub language_code: String,
@@ -1447,6 +1832,12 @@ impl Default for Settings {
             self_hosted: SelfHostedSettings::default(),
             kasvault: KasvaultSettings::default(),
             rendering: RenderingSettings::default(),
+            control_api: ControlApiSettings {
+                enabled: false,
+                token: ControlApiSettings::generate_token(),
+            },
+            alerts: AlertSettings::default(),
+            logs: LogSettings::default(),
             node: NodeSettings::default(),
             user_interface: UserInterfaceSettings::default(),
             language_code: "en".to_string(),
@@ -1509,10 +1900,11 @@ fn default_settings_for_network(network: Network) -> Settings {
     settings
 }
 
+/// Network of the most recently stored settings file.
 #[cfg(not(target_arch = "wasm32"))]
-fn latest_settings_network() -> Network {
+pub fn latest_settings_network() -> Network {
     let mut latest: Option<(std::time::SystemTime, Network)> = None;
-    for network in [Network::Mainnet] {
+    for network in Network::iter().copied() {
         let Ok(storage) = settings_storage(network) else {
             continue;
         };
@@ -1537,7 +1929,7 @@ fn latest_settings_network() -> Network {
 }
 
 #[cfg(target_arch = "wasm32")]
-fn latest_settings_network() -> Network {
+pub fn latest_settings_network() -> Network {
     Network::Mainnet
 }
 
@@ -1683,8 +2075,8 @@ impl Settings {
                         Ok(settings)
                     } else {
                         let mut migrated = false;
-                        if settings.node.network != Network::Mainnet {
-                            settings.node.network = Network::Mainnet;
+                        if settings.node.network != network {
+                            settings.node.network = network;
                             migrated = true;
                         }
                         if settings.user_interface.startup_network_selection_on_launch {
@@ -1760,6 +2152,10 @@ impl Settings {
                             settings.self_hosted.kasia_indexer_port = default_kasia_indexer_port();
                             migrated = true;
                         }
+                        if settings.control_api.token.trim().is_empty() {
+                            settings.control_api.token = ControlApiSettings::generate_token();
+                            migrated = true;
+                        }
                         if settings.self_hosted.kasvault_enabled && !settings.kasvault.enabled {
                             settings.kasvault.enabled = true;
                             settings.self_hosted.kasvault_enabled = false;
@@ -0,0 +1,80 @@
+//! User notes attached to wallet transactions, kept in the encrypted
+//! `<wallet>.notes` file next to the wallet file.
+
+use crate::imports::*;
+use crate::wallet_file::{EncryptedWalletFile, WalletFileData};
+use std::collections::BTreeMap;
+
+/// Maximum length of a note in characters.
+pub const TRANSACTION_NOTE_MAX_LENGTH: usize = 256;
+
+/// Notes of the wallet transactions keyed by transaction id.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TransactionNotesData {
+    notes: BTreeMap<String, String>,
+}
+
+impl WalletFileData for TransactionNotesData {
+    const EXTENSION: &'static str = "notes";
+    const VERSION: u32 = 1;
+    const NAME: &'static str = "transaction notes";
+}
+
+pub type TransactionNotes = EncryptedWalletFile<TransactionNotesData>;
+
+impl TransactionNotesData {
+    pub fn get(&self, transaction_id: &TransactionId) -> Option<&str> {
+        self.notes
+            .get(&transaction_id.to_string())
+            .map(String::as_str)
+    }
+
+    /// Checks that a note does not exceed [`TRANSACTION_NOTE_MAX_LENGTH`].
+    pub fn validate(note: &str) -> Result<()> {
+        if note.trim().chars().count() > TRANSACTION_NOTE_MAX_LENGTH {
+            return Err(Error::custom(i18n_args(
+                "The note must not exceed {length} characters",
+                &[("length", TRANSACTION_NOTE_MAX_LENGTH.to_string())],
+            )));
+        }
+        Ok(())
+    }
+
+    /// Sets the note of a transaction; an empty note removes it.
+    pub fn set(&mut self, transaction_id: TransactionId, note: &str) -> Result<()> {
+        Self::validate(note)?;
+        let note = note.trim();
+
+        if note.is_empty() {
+            self.notes.remove(&transaction_id.to_string());
+        } else {
+            self.notes
+                .insert(transaction_id.to_string(), note.to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_are_trimmed_and_removed_when_empty() {
+        let key = WalletFileKey::new(&Secret::from("secret"));
+        let mut notes = TransactionNotes::new("test", key);
+        let transaction_id = TransactionId::from_slice(&[1u8; 32]);
+
+        notes.set(transaction_id, "  invoice #123 ").unwrap();
+        assert_eq!(notes.get(&transaction_id), Some("invoice #123"));
+        assert!(
+            notes
+                .set(transaction_id, &"x".repeat(TRANSACTION_NOTE_MAX_LENGTH + 1))
+                .is_err()
+        );
+        assert_eq!(notes.get(&transaction_id), Some("invoice #123"));
+
+        notes.set(transaction_id, " ").unwrap();
+        assert_eq!(notes.get(&transaction_id), None);
+    }
+}
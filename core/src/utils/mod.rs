@@ -11,6 +11,8 @@ mod math;
 pub use math::*;
 mod parse;
 pub use parse::*;
+mod payment_uri;
+pub use payment_uri::*;
 mod format;
 pub use format::*;
 mod arglist;
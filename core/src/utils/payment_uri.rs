@@ -0,0 +1,264 @@
+use crate::imports::*;
+
+/// A `kaspa:` payment URI of the form
+/// `<address>?amount=<KAS>&label=<text>&message=<text>&<key>=<value>`.
+///
+/// The scheme is the address prefix (`kaspa`, `kaspatest`, ...), so the
+/// address is kept with its prefix. Parameter values are percent-encoded
+/// as defined by RFC 3986. Unknown parameters are preserved, except for
+/// `req-` parameters, which a wallet must understand to process the URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentUri {
+    pub address: Address,
+    pub amount_sompi: Option<u64>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub params: Vec<(String, String)>,
+}
+
+impl PaymentUri {
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            amount_sompi: None,
+            label: None,
+            message: None,
+            params: Vec::new(),
+        }
+    }
+
+    pub fn with_amount(mut self, amount_sompi: Option<u64>) -> Self {
+        self.amount_sompi = amount_sompi;
+        self
+    }
+
+    pub fn with_label(mut self, label: Option<String>) -> Self {
+        self.label = label.filter(|label| label.is_not_empty());
+        self
+    }
+
+    pub fn with_message(mut self, message: Option<String>) -> Self {
+        self.message = message.filter(|message| message.is_not_empty());
+        self
+    }
+
+    /// Returns `true` if the text looks like a URI carrying parameters
+    /// rather than a plain address.
+    pub fn has_query(text: &str) -> bool {
+        text.trim().contains('?')
+    }
+
+    /// Text describing the payment, combining the label and the message.
+    pub fn note(&self) -> Option<String> {
+        match (self.label.as_ref(), self.message.as_ref()) {
+            (Some(label), Some(message)) => Some(format!("{label}: {message}")),
+            (Some(label), None) => Some(label.clone()),
+            (None, Some(message)) => Some(message.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+impl FromStr for PaymentUri {
+    type Err = Error;
+
+    fn from_str(uri: &str) -> Result<Self> {
+        let uri = uri.trim();
+        let (address, query) = uri.split_once('?').unwrap_or((uri, ""));
+        let mut payment_uri = PaymentUri::new(Address::try_from(address)?);
+
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            let key = percent_decode(key)?;
+            let value = percent_decode(value)?;
+
+            let duplicate = match key.as_str() {
+                "amount" => {
+                    let amount_sompi = try_kaspa_str_to_sompi(value.as_str())?
+                        .ok_or_else(|| Error::custom("Payment URI amount is empty"))?;
+                    payment_uri.amount_sompi.replace(amount_sompi).is_some()
+                }
+                "label" => payment_uri.label.replace(value).is_some(),
+                "message" => payment_uri.message.replace(value).is_some(),
+                _ if key.starts_with("req-") => {
+                    return Err(Error::custom(format!(
+                        "Unsupported payment URI parameter: {key}"
+                    )));
+                }
+                _ => {
+                    payment_uri.params.push((key.clone(), value));
+                    false
+                }
+            };
+
+            if duplicate {
+                return Err(Error::custom(format!(
+                    "Duplicate payment URI parameter: {key}"
+                )));
+            }
+        }
+
+        Ok(payment_uri)
+    }
+}
+
+impl std::fmt::Display for PaymentUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut params = Vec::new();
+        if let Some(amount_sompi) = self.amount_sompi {
+            params.push(format!("amount={}", format_kaspa_amount(amount_sompi)));
+        }
+        if let Some(label) = self.label.as_ref() {
+            params.push(format!("label={}", percent_encode(label)));
+        }
+        if let Some(message) = self.message.as_ref() {
+            params.push(format!("message={}", percent_encode(message)));
+        }
+        for (key, value) in self.params.iter() {
+            params.push(format!("{}={}", percent_encode(key), percent_encode(value)));
+        }
+
+        write!(f, "{}", self.address)?;
+        if !params.is_empty() {
+            write!(f, "?{}", params.join("&"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats an amount in KAS without floating point rounding and without
+/// trailing zeros (e.g. `1.5`, `0.00000001`, `42`).
+pub fn format_kaspa_amount(sompi: u64) -> String {
+    let kaspa = sompi / SOMPI_PER_KASPA;
+    let fraction = sompi % SOMPI_PER_KASPA;
+    if fraction == 0 {
+        kaspa.to_string()
+    } else {
+        let fraction = format!("{fraction:08}");
+        format!("{kaspa}.{}", fraction.trim_end_matches('0'))
+    }
+}
+
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn percent_decode(text: &str) -> Result<String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let byte = text
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| Error::custom("Invalid percent-encoding in payment URI"))?;
+            decoded.push(byte);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| Error::custom("Invalid UTF-8 in payment URI"))
+}
+
+/// Registers the running executable as the handler of `kaspa:` and
+/// `kaspatest:` links for the current user. Linux uses the `.desktop`
+/// entry instead; macOS passes links as Apple Events, which are not
+/// supported.
+#[cfg(target_os = "windows")]
+pub fn register_payment_uri_handler() -> Result<()> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let exe = std::env::current_exe()?;
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    for scheme in ["kaspa", "kaspatest"] {
+        let key = format!("HKCU\\Software\\Classes\\{scheme}");
+        let command_key = format!("{key}\\shell\\open\\command");
+        let entries = [
+            vec![key.as_str(), "/ve", "/d", "URL:Kaspa Payment"],
+            vec![key.as_str(), "/v", "URL Protocol", "/d", ""],
+            vec![command_key.as_str(), "/ve", "/d", command.as_str()],
+        ];
+        for args in entries.iter() {
+            let status = std::process::Command::new("reg")
+                .arg("add")
+                .args(args)
+                .arg("/f")
+                .creation_flags(CREATE_NO_WINDOW)
+                .status()?;
+            if !status.success() {
+                return Err(Error::custom(format!(
+                    "Unable to register the {scheme}: link handler"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::{Prefix, Version};
+
+    fn address(prefix: Prefix) -> Address {
+        Address::new(prefix, Version::PubKey, &[7u8; 32])
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut uri = PaymentUri::new(address(Prefix::Mainnet))
+            .with_amount(Some(150_000_001))
+            .with_label(Some("Coffee & cake".to_string()))
+            .with_message(Some("Invoice #42 (ümlaut)".to_string()));
+        uri.params.push(("order".to_string(), "a=b".to_string()));
+
+        let text = uri.to_string();
+        assert_eq!(text.matches('?').count(), 1);
+        assert!(text.contains("?amount=1.50000001&label=Coffee%20%26%20cake&message="));
+        assert!(text.ends_with("&order=a%3Db"));
+        assert_eq!(text.parse::<PaymentUri>().unwrap(), uri);
+
+        let plain = PaymentUri::new(address(Prefix::Testnet));
+        assert_eq!(plain.to_string(), plain.address.to_string());
+        assert_eq!(plain.to_string().parse::<PaymentUri>().unwrap(), plain);
+    }
+
+    #[test]
+    fn parse_parameters() {
+        let address = address(Prefix::Mainnet);
+        let uri = format!("{address}?amount=42&message=Thanks&label=Shop");
+        let uri = uri.parse::<PaymentUri>().unwrap();
+        assert_eq!(uri.amount_sompi, Some(42 * SOMPI_PER_KASPA));
+        assert_eq!(uri.note().as_deref(), Some("Shop: Thanks"));
+        assert_eq!(format_kaspa_amount(1), "0.00000001");
+
+        assert!(
+            format!("{address}?amount=1&amount=2")
+                .parse::<PaymentUri>()
+                .is_err()
+        );
+        assert!(
+            format!("{address}?req-expires=1")
+                .parse::<PaymentUri>()
+                .is_err()
+        );
+        assert!(
+            format!("{address}?label=%zz")
+                .parse::<PaymentUri>()
+                .is_err()
+        );
+        assert!("kaspa:invalid?amount=1".parse::<PaymentUri>().is_err());
+    }
+}
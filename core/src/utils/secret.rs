@@ -41,3 +41,142 @@ pub fn render_secret_score_text(ui: &mut Ui, prefix: impl Into<String>, score: f
 
     ui.colored_label(color, text);
 }
+
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SecretFocus {
+    #[default]
+    None,
+    WalletSecret,
+    PaymentSecret,
+}
+
+/// Outcome of the [`SecretPrompt`] footer buttons.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SecretPromptAction {
+    None,
+    Submit,
+    Cancel,
+}
+
+/// Wallet secret and (if the account requires it) BIP39 passphrase
+/// prompt used by modules that sign with an account key.
+#[derive(Default)]
+pub struct SecretPrompt {
+    wallet_secret: String,
+    payment_secret: String,
+    focus: FocusManager<SecretFocus>,
+}
+
+impl Zeroize for SecretPrompt {
+    fn zeroize(&mut self) {
+        self.wallet_secret.zeroize();
+        self.payment_secret.zeroize();
+    }
+}
+
+impl SecretPrompt {
+    /// Moves the keyboard focus to the wallet secret editor.
+    pub fn activate(&mut self) {
+        self.focus.next(SecretFocus::WalletSecret);
+    }
+
+    /// Clears the entered secrets and the pending focus.
+    pub fn clear(&mut self) {
+        self.zeroize();
+        self.focus.clear();
+    }
+
+    pub fn is_ready(&self, requires_bip39_passphrase: bool) -> bool {
+        self.wallet_secret.is_not_empty()
+            && (!requires_bip39_passphrase || self.payment_secret.is_not_empty())
+    }
+
+    /// Returns the entered wallet secret and BIP39 passphrase.
+    pub fn secrets(&self, requires_bip39_passphrase: bool) -> (Secret, Option<Secret>) {
+        (
+            Secret::from(self.wallet_secret.as_str()),
+            requires_bip39_passphrase.then(|| Secret::from(self.payment_secret.as_str())),
+        )
+    }
+
+    /// Renders the secret editors. Returns `true` if the user submitted
+    /// the prompt with the Enter key.
+    pub fn render(&mut self, ui: &mut Ui, requires_bip39_passphrase: bool) -> bool {
+        let mut submit = false;
+
+        TextEditor::new(
+            &mut self.wallet_secret,
+            &mut self.focus,
+            SecretFocus::WalletSecret,
+            |ui, text| {
+                ui.label(
+                    RichText::new(i18n("Enter your wallet secret"))
+                        .size(12.)
+                        .raised(),
+                );
+                ui.add_sized(
+                    theme_style().panel_editor_size,
+                    TextEdit::singleline(text)
+                        .vertical_align(Align::Center)
+                        .password(true),
+                )
+            },
+        )
+        .submit(|text, focus| {
+            if requires_bip39_passphrase {
+                focus.next(SecretFocus::PaymentSecret);
+            } else if !text.is_empty() {
+                submit = true;
+            }
+        })
+        .build(ui);
+
+        if requires_bip39_passphrase {
+            TextEditor::new(
+                &mut self.payment_secret,
+                &mut self.focus,
+                SecretFocus::PaymentSecret,
+                |ui, text| {
+                    ui.label(
+                        RichText::new(i18n("Enter your BIP39 passphrase"))
+                            .size(12.)
+                            .raised(),
+                    );
+                    ui.add_sized(
+                        theme_style().panel_editor_size,
+                        TextEdit::singleline(text)
+                            .vertical_align(Align::Center)
+                            .password(true),
+                    )
+                },
+            )
+            .submit(|text, _focus| {
+                if !text.is_empty() {
+                    submit = true;
+                }
+            })
+            .build(ui);
+        }
+
+        submit && self.is_ready(requires_bip39_passphrase)
+    }
+
+    /// Renders the submit and cancel buttons. The secrets are cleared
+    /// when the prompt is cancelled.
+    pub fn render_footer(
+        &mut self,
+        ui: &mut Ui,
+        submit_caption: &str,
+        requires_bip39_passphrase: bool,
+    ) -> SecretPromptAction {
+        let enabled = self.is_ready(requires_bip39_passphrase);
+        if ui.large_button_enabled(enabled, submit_caption).clicked() {
+            SecretPromptAction::Submit
+        } else if ui.large_button(i18n("Cancel")).clicked() {
+            self.clear();
+            SecretPromptAction::Cancel
+        } else {
+            SecretPromptAction::None
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Files kept next to the wallet file (`<wallet>.<extension>`), such as
+//! the address book. They are encrypted with a key derived from the
+//! wallet secret and are only available while the wallet is open.
+
+use crate::imports::*;
+use kaspa_wallet_core::encryption::{
+    decrypt_xchacha20poly1305, encrypt_xchacha20poly1305, sha256_hash,
+};
+use kaspa_wallet_core::storage::local::storage::Storage;
+use serde::de::DeserializeOwned;
+
+/// Key derivation domain of the wallet files.
+const WALLET_FILE_KEY_DOMAIN: &[u8] = b"kaspa-ng-wallet-file:";
+
+/// Encryption key of the wallet files, derived from the wallet secret.
+#[derive(Clone)]
+pub struct WalletFileKey(Secret);
+
+impl WalletFileKey {
+    pub fn new(wallet_secret: &Secret) -> Self {
+        let mut data = WALLET_FILE_KEY_DOMAIN.to_vec();
+        data.extend_from_slice(wallet_secret.as_ref());
+        let key = sha256_hash(&data);
+        data.zeroize();
+        Self(key)
+    }
+
+    /// Provides the key to the application after the wallet has been
+    /// opened, created or its secret has been changed.
+    pub fn unlock(wallet_secret: &Secret) {
+        runtime()
+            .application_events()
+            .sender
+            .try_send(Events::WalletFileKey(Self::new(wallet_secret)))
+            .ok();
+    }
+}
+
+impl PartialEq for WalletFileKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ref() == other.0.as_ref()
+    }
+}
+
+impl std::fmt::Debug for WalletFileKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("WalletFileKey")
+    }
+}
+
+/// Contents of a wallet file.
+pub trait WalletFileData:
+    Clone + Default + Serialize + DeserializeOwned + Send + Sync + 'static
+{
+    /// Extension appended to the wallet file name.
+    const EXTENSION: &'static str;
+    /// Format version; files of a later version are not loaded.
+    const VERSION: u32;
+    /// Name of the contents used in messages.
+    const NAME: &'static str;
+
+    /// Called once the contents have been loaded.
+    fn loaded(&mut self) {}
+}
+
+#[derive(Serialize, Deserialize)]
+struct Payload<T> {
+    version: u32,
+    #[serde(flatten)]
+    data: T,
+}
+
+/// Wallet file contents along with the wallet and the key they belong to.
+#[derive(Clone, Debug)]
+pub struct EncryptedWalletFile<T> {
+    wallet_filename: String,
+    key: WalletFileKey,
+    data: T,
+}
+
+impl<T: WalletFileData> EncryptedWalletFile<T> {
+    pub fn new(wallet_filename: &str, key: WalletFileKey) -> Self {
+        Self {
+            wallet_filename: wallet_filename.to_string(),
+            key,
+            data: T::default(),
+        }
+    }
+
+    pub fn wallet_filename(&self) -> &str {
+        self.wallet_filename.as_str()
+    }
+
+    pub fn has_key(&self, key: &WalletFileKey) -> bool {
+        self.key == *key
+    }
+
+    /// Replaces the encryption key, e.g. after the wallet secret has
+    /// been changed. The file must be stored afterwards.
+    pub fn set_key(&mut self, key: WalletFileKey) {
+        self.key = key;
+    }
+
+    fn filename(wallet_filename: &str) -> String {
+        format!("{wallet_filename}.{}", T::EXTENSION)
+    }
+
+    /// Loads and decrypts the file of the wallet. A missing file results
+    /// in empty contents.
+    pub async fn load(wallet_filename: &str, key: WalletFileKey) -> Result<Self> {
+        let mut file = Self::new(wallet_filename, key);
+        let Some(json) = load_encrypted(&Self::filename(wallet_filename), &file.key).await? else {
+            return Ok(file);
+        };
+
+        let payload: Payload<T> = serde_json::from_slice(json.as_ref())?;
+        if payload.version > T::VERSION {
+            return Err(Error::custom(format!(
+                "Unsupported {} version: {}",
+                T::NAME,
+                payload.version
+            )));
+        }
+
+        file.data = payload.data;
+        file.data.loaded();
+        Ok(file)
+    }
+
+    /// Returns the store of the current contents. Its revision is taken
+    /// right away, so a store created later is never overwritten by it.
+    pub fn store(&self) -> impl Future<Output = Result<()>> + Send + use<T> {
+        let revision = next_store_revision();
+        let filename = Self::filename(&self.wallet_filename);
+        let key = self.key.clone();
+        let payload = Payload {
+            version: T::VERSION,
+            data: self.data.clone(),
+        };
+        async move {
+            let mut json = serde_json::to_vec(&payload)?;
+            let result = store_encrypted(&filename, &key, &json, revision).await;
+            json.zeroize();
+            result
+        }
+    }
+}
+
+impl<T> std::ops::Deref for EncryptedWalletFile<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<T> std::ops::DerefMut for EncryptedWalletFile<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+/// Reads and decrypts a wallet file. Returns `None` if the file does not exist.
+async fn load_encrypted(filename: &str, key: &WalletFileKey) -> Result<Option<Secret>> {
+    let storage = Storage::try_new(filename)?;
+    if !storage.exists().await? {
+        return Ok(None);
+    }
+
+    let data = workflow_store::fs::read_to_string(storage.filename()).await?;
+    let data = Vec::<u8>::from_hex(data.trim())
+        .map_err(|err| Error::custom(format!("Invalid file {filename}: {err}")))?;
+    let data = decrypt_xchacha20poly1305(&data, &key.0)
+        .map_err(|_| Error::custom(format!("Unable to decrypt {filename}")))?;
+    Ok(Some(data))
+}
+
+/// Revision handed out to each store of a wallet file.
+static STORE_REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the revision of a store that is about to be issued. Revisions
+/// must be taken in the order the changes are made.
+fn next_store_revision() -> u64 {
+    STORE_REVISION.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+/// Last revision written to each file. Stores run as separate tasks, so
+/// the lock also keeps two writes of the same file from interleaving.
+fn written_revisions() -> &'static futures::lock::Mutex<AHashMap<String, u64>> {
+    static WRITTEN: OnceLock<futures::lock::Mutex<AHashMap<String, u64>>> = OnceLock::new();
+    WRITTEN.get_or_init(Default::default)
+}
+
+/// Encrypts and writes a wallet file, unless a store with a later
+/// `revision` has already been written.
+async fn store_encrypted(
+    filename: &str,
+    key: &WalletFileKey,
+    data: &[u8],
+    revision: u64,
+) -> Result<()> {
+    let mut written = written_revisions().lock().await;
+    if written.get(filename).is_some_and(|last| *last > revision) {
+        return Ok(());
+    }
+
+    let data = encrypt_xchacha20poly1305(data, &key.0)?;
+    let storage = Storage::try_new(filename)?;
+    storage.ensure_dir().await?;
+    workflow_store::fs::write_string(storage.filename(), &data.to_hex()).await?;
+    written.insert(filename.to_string(), revision);
+    Ok(())
+}
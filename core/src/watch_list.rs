@@ -0,0 +1,178 @@
+//! Watch-only address lists, kept in the encrypted `<wallet>.watch` file
+//! next to the wallet file.
+
+use crate::imports::*;
+use crate::wallet_file::{EncryptedWalletFile, WalletFileData};
+
+/// Maximum number of addresses in a list, and checked at once.
+pub const MAX_WATCHED_ADDRESSES: usize = 100;
+
+/// Parses a list of addresses separated by whitespace, commas or
+/// semicolons, dropping duplicates and rejecting addresses of other networks.
+pub fn parse_addresses(text: &str, network_type: &NetworkType) -> Result<Vec<Address>> {
+    let mut addresses: Vec<Address> = Vec::new();
+    for address in text
+        .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+        .filter(|address| !address.is_empty())
+    {
+        let address = try_user_string_to_address(address, network_type)
+            .map_err(|err| Error::custom(format!("{address}: {err}")))?;
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+
+    if addresses.len() > MAX_WATCHED_ADDRESSES {
+        return Err(Error::custom(i18n_args(
+            "Please enter at most {count} addresses",
+            &[("count", MAX_WATCHED_ADDRESSES.to_string())],
+        )));
+    }
+
+    Ok(addresses)
+}
+
+/// A named list of addresses monitored without any keys.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WatchedAddresses {
+    pub name: String,
+    /// Network the addresses belong to; lists are only offered on this network.
+    pub network: Network,
+    pub addresses: Vec<String>,
+}
+
+impl WatchedAddresses {
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::custom(i18n("Please enter a name")));
+        }
+        if self.addresses.is_empty() {
+            return Err(Error::custom(i18n("Please enter at least one address")));
+        }
+        if self.addresses.len() > MAX_WATCHED_ADDRESSES {
+            return Err(Error::custom(i18n_args(
+                "Please enter at most {count} addresses",
+                &[("count", MAX_WATCHED_ADDRESSES.to_string())],
+            )));
+        }
+        let network_type: NetworkType = self.network.into();
+        for address in self.addresses.iter() {
+            try_user_string_to_address(address, &network_type)
+                .map_err(|err| Error::custom(format!("{address}: {err}")))?;
+        }
+        Ok(())
+    }
+}
+
+/// Watch-only address lists of the wallet.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WatchListData {
+    lists: Vec<WatchedAddresses>,
+}
+
+impl WalletFileData for WatchListData {
+    const EXTENSION: &'static str = "watch";
+    const VERSION: u32 = 1;
+    const NAME: &'static str = "watch list";
+}
+
+pub type WatchList = EncryptedWalletFile<WatchListData>;
+
+impl WatchListData {
+    /// Lists of the given network along with their index.
+    pub fn lists_for_network(
+        &self,
+        network: Network,
+    ) -> impl Iterator<Item = (usize, &WatchedAddresses)> {
+        self.lists
+            .iter()
+            .enumerate()
+            .filter(move |(_, list)| list.network == network)
+    }
+
+    pub fn insert(&mut self, mut list: WatchedAddresses) -> Result<()> {
+        list.name = list.name.trim().to_string();
+        list.validate()?;
+
+        let duplicate = self.lists.iter().any(|existing| {
+            existing.network == list.network && existing.name.eq_ignore_ascii_case(&list.name)
+        });
+        if duplicate {
+            return Err(Error::custom(i18n(
+                "A watch-only address list with this name already exists",
+            )));
+        }
+
+        self.lists.push(list);
+        self.lists.sort_by_key(|list| list.name.to_lowercase());
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<WatchedAddresses> {
+        (index < self.lists.len()).then(|| self.lists.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::{Prefix, Version};
+
+    #[test]
+    fn addresses_are_parsed_and_deduplicated() {
+        let a = Address::new(Prefix::Mainnet, Version::PubKey, &[1u8; 32]).to_string();
+        let b = Address::new(Prefix::Mainnet, Version::PubKey, &[2u8; 32]).to_string();
+        let testnet = Address::new(Prefix::Testnet, Version::PubKey, &[3u8; 32]).to_string();
+
+        let addresses =
+            parse_addresses(&format!("{a}\n {b}, {a};"), &NetworkType::Mainnet).unwrap();
+        assert_eq!(
+            addresses
+                .iter()
+                .map(|address| address.to_string())
+                .collect::<Vec<_>>(),
+            vec![a.clone(), b]
+        );
+        assert!(parse_addresses(&format!("{a} {testnet}"), &NetworkType::Mainnet).is_err());
+        assert!(
+            parse_addresses("", &NetworkType::Mainnet)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn lists_are_validated_and_unique_per_network() {
+        let key = WalletFileKey::new(&Secret::from("secret"));
+        let mut watch_list = WatchList::new("test", key);
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &[1u8; 32]).to_string();
+        let list = WatchedAddresses {
+            name: " Exchange ".to_string(),
+            network: Network::Mainnet,
+            addresses: vec![address],
+        };
+
+        watch_list.insert(list.clone()).unwrap();
+        assert!(watch_list.insert(list.clone()).is_err());
+        assert_eq!(
+            watch_list
+                .lists_for_network(Network::Mainnet)
+                .next()
+                .map(|(_, list)| list.name.as_str()),
+            Some("Exchange")
+        );
+
+        let testnet = WatchedAddresses {
+            network: Network::Testnet10,
+            ..list.clone()
+        };
+        assert!(watch_list.insert(testnet).is_err());
+
+        let empty = WatchedAddresses {
+            name: "Cold storage".to_string(),
+            addresses: vec![],
+            ..list
+        };
+        assert!(watch_list.insert(empty).is_err());
+    }
+}